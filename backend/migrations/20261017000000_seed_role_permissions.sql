@@ -0,0 +1,22 @@
+-- Seed role permissions used by the authorization middleware.
+-- Permissions are "resource:action" strings where action is "read" or "write";
+-- "resource:*" grants both actions and "*" grants everything.
+ALTER TABLE roles ALTER COLUMN id SET DEFAULT gen_random_uuid();
+
+INSERT INTO roles (name, permissions)
+VALUES
+  ('ADMIN', '["*"]'::jsonb),
+  ('MANAGER', '[
+    "vehicles:*", "drivers:*", "assignments:*", "maintenance:*",
+    "logistics:*", "telemetry:*", "financial:read",
+    "settings:read", "users:read", "roles:read"
+  ]'::jsonb),
+  ('DRIVER', '[
+    "vehicles:read", "drivers:read", "assignments:read",
+    "logistics:read", "telemetry:*", "settings:read"
+  ]'::jsonb),
+  ('MECHANIC', '[
+    "vehicles:read", "maintenance:*", "telemetry:read",
+    "assignments:read", "settings:read"
+  ]'::jsonb)
+ON CONFLICT (name) DO NOTHING;
@@ -0,0 +1,11 @@
+-- Trackers re-upload buffered points, so a reading is identified by (vehicle_id, time).
+-- Drop existing duplicates, then enforce uniqueness so batch inserts can skip them.
+DELETE FROM vehicle_telemetry a
+USING vehicle_telemetry b
+WHERE a.vehicle_id = b.vehicle_id
+  AND a.time = b.time
+  AND a.ctid > b.ctid
+  AND a.tableoid = b.tableoid;
+
+DROP INDEX IF EXISTS idx_telemetry_vehicle_time;
+CREATE UNIQUE INDEX idx_telemetry_vehicle_time ON vehicle_telemetry (vehicle_id, time DESC);
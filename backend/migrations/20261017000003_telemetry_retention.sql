@@ -0,0 +1,4 @@
+-- Retention for monthly vehicle_telemetry partitions. 0 keeps data forever.
+ALTER TABLE app_settings
+  ADD COLUMN IF NOT EXISTS telemetry_retention_months INTEGER NOT NULL DEFAULT 12,
+  ADD COLUMN IF NOT EXISTS telemetry_archive_expired BOOLEAN NOT NULL DEFAULT FALSE;
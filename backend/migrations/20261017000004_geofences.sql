@@ -0,0 +1,43 @@
+-- Free-text context for generated alerts (e.g. which geofence was crossed)
+ALTER TABLE alerts ADD COLUMN IF NOT EXISTS message TEXT;
+
+CREATE TYPE geofence_kind AS ENUM ('POLYGON', 'CIRCLE');
+
+CREATE TABLE geofences (
+    id UUID PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    kind geofence_kind NOT NULL,
+    -- Circles are stored as their buffered polygon so every check is a plain ST_Contains
+    area GEOMETRY(POLYGON, 4326) NOT NULL,
+    center GEOMETRY(POINT, 4326),
+    radius_m DOUBLE PRECISION,
+    -- Scope: a single vehicle, every vehicle of a type, or (both NULL) the whole fleet
+    vehicle_id UUID REFERENCES vehicles(id) ON DELETE CASCADE,
+    vehicle_type vehicle_type,
+    alert_on_entry BOOLEAN NOT NULL DEFAULT TRUE,
+    alert_on_exit BOOLEAN NOT NULL DEFAULT TRUE,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT geofences_single_scope CHECK (vehicle_id IS NULL OR vehicle_type IS NULL),
+    CONSTRAINT geofences_circle_shape CHECK (kind <> 'CIRCLE' OR (center IS NOT NULL AND radius_m > 0))
+);
+
+CREATE INDEX idx_geofences_area ON geofences USING GIST (area);
+
+-- Last known inside/outside state per vehicle, used to detect transitions
+CREATE TABLE geofence_vehicle_states (
+    geofence_id UUID NOT NULL REFERENCES geofences(id) ON DELETE CASCADE,
+    vehicle_id UUID NOT NULL REFERENCES vehicles(id) ON DELETE CASCADE,
+    inside BOOLEAN NOT NULL,
+    observed_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (geofence_id, vehicle_id)
+);
+
+UPDATE roles
+SET permissions = permissions || '["geofences:*"]'::jsonb
+WHERE name = 'MANAGER' AND NOT permissions ? 'geofences:*';
+
+UPDATE roles
+SET permissions = permissions || '["geofences:read"]'::jsonb
+WHERE name = 'DRIVER' AND NOT permissions ? 'geofences:read';
@@ -0,0 +1,11 @@
+-- Thresholds for telemetry anomaly alerts. A value of 0 turns the rule off.
+ALTER TABLE app_settings
+  ADD COLUMN IF NOT EXISTS speed_limit_kmh DOUBLE PRECISION NOT NULL DEFAULT 110,
+  ADD COLUMN IF NOT EXISTS fuel_drop_threshold DOUBLE PRECISION NOT NULL DEFAULT 15,
+  ADD COLUMN IF NOT EXISTS idle_alert_minutes INTEGER NOT NULL DEFAULT 20,
+  ADD COLUMN IF NOT EXISTS tracker_stale_minutes INTEGER NOT NULL DEFAULT 30;
+
+-- Anomaly alerts are suppressed while one of the same type is still open for the entity
+CREATE INDEX IF NOT EXISTS idx_alerts_open_by_entity
+  ON alerts (entity_id, type)
+  WHERE is_resolved = FALSE;
@@ -0,0 +1,8 @@
+-- Progress of the odometer sync job. Telemetry up to synced_until has been folded into
+-- vehicles.current_mileage (km); remainder_m carries the distance not yet worth a whole km.
+CREATE TABLE IF NOT EXISTS vehicle_odometer_sync (
+    vehicle_id UUID PRIMARY KEY REFERENCES vehicles(id) ON DELETE CASCADE,
+    synced_until TIMESTAMPTZ NOT NULL,
+    remainder_m DOUBLE PRECISION NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
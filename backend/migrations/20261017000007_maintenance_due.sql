@@ -0,0 +1,5 @@
+-- Odometer reading (km) when the service was done; the next km-based due point counts from it
+ALTER TABLE maintenance_records ADD COLUMN IF NOT EXISTS mileage INTEGER;
+
+CREATE INDEX IF NOT EXISTS idx_maintenance_records_vehicle_type_date
+  ON maintenance_records (vehicle_id, type, date DESC);
@@ -0,0 +1,40 @@
+CREATE TYPE work_order_status AS ENUM ('OPEN', 'IN_PROGRESS', 'AWAITING_PARTS', 'COMPLETED', 'CANCELLED');
+CREATE TYPE work_order_item_kind AS ENUM ('PART', 'LABOR');
+
+CREATE TABLE IF NOT EXISTS work_orders (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    vehicle_id UUID NOT NULL REFERENCES vehicles(id),
+    mechanic_id UUID REFERENCES users(id),
+    type maintenance_type NOT NULL,
+    description TEXT NOT NULL,
+    provider VARCHAR(255),
+    status work_order_status NOT NULL DEFAULT 'OPEN',
+    -- Set when the order is completed
+    maintenance_record_id UUID REFERENCES maintenance_records(id),
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_work_orders_vehicle ON work_orders (vehicle_id);
+CREATE INDEX IF NOT EXISTS idx_work_orders_mechanic ON work_orders (mechanic_id);
+CREATE INDEX IF NOT EXISTS idx_work_orders_open
+  ON work_orders (status)
+  WHERE status NOT IN ('COMPLETED', 'CANCELLED');
+
+CREATE TABLE IF NOT EXISTS work_order_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    work_order_id UUID NOT NULL REFERENCES work_orders(id) ON DELETE CASCADE,
+    kind work_order_item_kind NOT NULL,
+    description TEXT NOT NULL,
+    quantity DECIMAL(10, 2) NOT NULL CHECK (quantity > 0),
+    unit_cost DECIMAL(10, 2) NOT NULL CHECK (unit_cost >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_work_order_items_order ON work_order_items (work_order_id);
+
+UPDATE roles
+SET permissions = permissions || '["work-orders:*"]'::jsonb
+WHERE name IN ('MANAGER', 'MECHANIC') AND NOT permissions ? 'work-orders:*';
@@ -0,0 +1,40 @@
+CREATE TYPE stock_movement_kind AS ENUM ('RECEIPT', 'CONSUMPTION', 'RETURN', 'ADJUSTMENT');
+
+CREATE TABLE IF NOT EXISTS parts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    sku VARCHAR(64) NOT NULL UNIQUE,
+    description TEXT NOT NULL,
+    unit_cost DECIMAL(10, 2) NOT NULL CHECK (unit_cost >= 0),
+    quantity_on_hand DECIMAL(10, 2) NOT NULL DEFAULT 0 CHECK (quantity_on_hand >= 0),
+    reorder_threshold DECIMAL(10, 2) NOT NULL DEFAULT 0 CHECK (reorder_threshold >= 0),
+    -- Empty means the part fits every vehicle type
+    compatible_vehicle_types vehicle_type[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Ledger of every change to quantity_on_hand. quantity is the signed change; parts
+-- leaving the shelf for a vehicle carry its id so their cost can be attributed to it.
+CREATE TABLE IF NOT EXISTS stock_movements (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    part_id UUID NOT NULL REFERENCES parts(id),
+    kind stock_movement_kind NOT NULL,
+    quantity DECIMAL(10, 2) NOT NULL CHECK (quantity <> 0),
+    unit_cost DECIMAL(10, 2) NOT NULL,
+    vehicle_id UUID REFERENCES vehicles(id),
+    work_order_id UUID REFERENCES work_orders(id),
+    maintenance_record_id UUID REFERENCES maintenance_records(id),
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_stock_movements_part ON stock_movements (part_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_stock_movements_vehicle ON stock_movements (vehicle_id) WHERE vehicle_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_stock_movements_work_order ON stock_movements (work_order_id) WHERE work_order_id IS NOT NULL;
+
+-- Work order lines drawn from inventory
+ALTER TABLE work_order_items ADD COLUMN IF NOT EXISTS part_id UUID REFERENCES parts(id);
+
+UPDATE roles
+SET permissions = permissions || '["parts:*"]'::jsonb
+WHERE name IN ('MANAGER', 'MECHANIC') AND NOT permissions ? 'parts:*';
@@ -0,0 +1,3 @@
+-- Days before drivers.license_expiry that a "License Expiring" alert is raised
+ALTER TABLE app_settings
+  ADD COLUMN IF NOT EXISTS license_expiry_warning_days INTEGER NOT NULL DEFAULT 30;
@@ -0,0 +1,23 @@
+CREATE TYPE duty_event_kind AS ENUM ('CLOCK_IN', 'CLOCK_OUT');
+
+-- Explicit on/off duty punches; assignments add the rest of a driver's on-duty time
+CREATE TABLE IF NOT EXISTS duty_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    driver_id UUID NOT NULL REFERENCES drivers(id),
+    kind duty_event_kind NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_duty_events_driver_time ON duty_events (driver_id, occurred_at);
+CREATE INDEX IF NOT EXISTS idx_vehicle_assignments_driver_time ON vehicle_assignments (driver_id, start_time);
+
+ALTER TABLE app_settings
+  ADD COLUMN IF NOT EXISTS hos_max_daily_hours DOUBLE PRECISION NOT NULL DEFAULT 11,
+  ADD COLUMN IF NOT EXISTS hos_max_weekly_hours DOUBLE PRECISION NOT NULL DEFAULT 60,
+  ADD COLUMN IF NOT EXISTS hos_min_rest_hours DOUBLE PRECISION NOT NULL DEFAULT 10;
+
+UPDATE roles
+SET permissions = permissions || '["hos:*"]'::jsonb
+WHERE name IN ('MANAGER', 'DRIVER') AND NOT permissions ? 'hos:*';
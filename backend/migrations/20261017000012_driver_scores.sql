@@ -0,0 +1,21 @@
+-- Safety score of each completed assignment, computed from the vehicle's telemetry
+CREATE TABLE IF NOT EXISTS trip_scores (
+    assignment_id UUID PRIMARY KEY REFERENCES vehicle_assignments(id) ON DELETE CASCADE,
+    driver_id UUID NOT NULL REFERENCES drivers(id),
+    vehicle_id UUID NOT NULL REFERENCES vehicles(id),
+    started_at TIMESTAMPTZ NOT NULL,
+    ended_at TIMESTAMPTZ NOT NULL,
+    readings INTEGER NOT NULL,
+    driving_minutes DOUBLE PRECISION NOT NULL,
+    speeding_events INTEGER NOT NULL,
+    harsh_acceleration_events INTEGER NOT NULL,
+    harsh_braking_events INTEGER NOT NULL,
+    idle_minutes DOUBLE PRECISION NOT NULL,
+    night_minutes DOUBLE PRECISION NOT NULL,
+    -- NULL when the trip has too little telemetry to judge
+    score DOUBLE PRECISION,
+    scored_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_trip_scores_driver ON trip_scores (driver_id, ended_at);
+CREATE INDEX IF NOT EXISTS idx_trip_scores_ended ON trip_scores (ended_at);
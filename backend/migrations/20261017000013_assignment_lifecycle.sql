@@ -0,0 +1,18 @@
+ALTER TABLE vehicle_assignments
+  ADD COLUMN IF NOT EXISTS started_at TIMESTAMPTZ,
+  ADD COLUMN IF NOT EXISTS completed_at TIMESTAMPTZ,
+  ADD COLUMN IF NOT EXISTS cancelled_at TIMESTAMPTZ,
+  ADD COLUMN IF NOT EXISTS cancel_reason TEXT;
+
+-- Best guesses for assignments closed before the lifecycle was recorded
+UPDATE vehicle_assignments SET started_at = start_time
+WHERE status IN ('ACTIVE', 'COMPLETED') AND started_at IS NULL;
+UPDATE vehicle_assignments SET completed_at = updated_at
+WHERE status = 'COMPLETED' AND completed_at IS NULL;
+UPDATE vehicle_assignments SET cancelled_at = updated_at
+WHERE status = 'CANCELLED' AND cancelled_at IS NULL;
+
+-- Scanned by the activation job
+CREATE INDEX IF NOT EXISTS idx_vehicle_assignments_scheduled
+  ON vehicle_assignments (start_time)
+  WHERE status = 'SCHEDULED';
@@ -0,0 +1,8 @@
+-- Assignments dispatched for a transport job
+CREATE TABLE IF NOT EXISTS job_assignments (
+    assignment_id UUID PRIMARY KEY REFERENCES vehicle_assignments(id) ON DELETE CASCADE,
+    job_id UUID NOT NULL REFERENCES transport_jobs(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_assignments_job ON job_assignments (job_id);
@@ -0,0 +1,22 @@
+-- A transport job can run as several assignments, one per leg, in order
+ALTER TABLE job_assignments ADD COLUMN IF NOT EXISTS leg_number INT;
+
+UPDATE job_assignments ja
+SET leg_number = numbered.leg_number
+FROM (
+    SELECT
+        j.assignment_id,
+        ROW_NUMBER() OVER (PARTITION BY j.job_id ORDER BY a.start_time, j.created_at) AS leg_number
+    FROM job_assignments j
+    JOIN vehicle_assignments a ON a.id = j.assignment_id
+) numbered
+WHERE ja.assignment_id = numbered.assignment_id AND ja.leg_number IS NULL;
+
+ALTER TABLE job_assignments ALTER COLUMN leg_number SET NOT NULL;
+ALTER TABLE job_assignments DROP CONSTRAINT IF EXISTS job_assignments_leg_number_check;
+ALTER TABLE job_assignments ADD CONSTRAINT job_assignments_leg_number_check CHECK (leg_number > 0);
+ALTER TABLE job_assignments DROP CONSTRAINT IF EXISTS job_assignments_job_id_leg_number_key;
+ALTER TABLE job_assignments ADD CONSTRAINT job_assignments_job_id_leg_number_key UNIQUE (job_id, leg_number);
+
+-- The unique constraint's index covers lookups by job
+DROP INDEX IF EXISTS idx_job_assignments_job;
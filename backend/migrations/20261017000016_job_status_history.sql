@@ -0,0 +1,20 @@
+ALTER TYPE job_status ADD VALUE IF NOT EXISTS 'CANCELLED';
+
+CREATE TABLE IF NOT EXISTS job_status_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    job_id UUID NOT NULL REFERENCES transport_jobs(id) ON DELETE CASCADE,
+    -- NULL for the status a job was created in
+    from_status job_status,
+    to_status job_status NOT NULL,
+    changed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reason TEXT,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_job_status_history_job ON job_status_history (job_id, changed_at);
+
+-- Jobs created before the history was kept start with their current status
+INSERT INTO job_status_history (job_id, from_status, to_status, changed_at)
+SELECT j.id, NULL, j.status, j.created_at
+FROM transport_jobs j
+WHERE NOT EXISTS (SELECT 1 FROM job_status_history h WHERE h.job_id = j.id);
@@ -0,0 +1,25 @@
+CREATE TYPE pod_file_kind AS ENUM ('SIGNATURE', 'PHOTO');
+
+-- Captured once per job, before it can be marked DELIVERED
+CREATE TABLE IF NOT EXISTS proof_of_delivery (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    job_id UUID NOT NULL UNIQUE REFERENCES transport_jobs(id) ON DELETE CASCADE,
+    recipient_name VARCHAR(255) NOT NULL,
+    location GEOMETRY(POINT, 4326) NOT NULL,
+    notes TEXT,
+    captured_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    captured_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Signature and photos; the bytes live in object storage under storage_key
+CREATE TABLE IF NOT EXISTS proof_of_delivery_files (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    pod_id UUID NOT NULL REFERENCES proof_of_delivery(id) ON DELETE CASCADE,
+    kind pod_file_kind NOT NULL,
+    storage_key TEXT NOT NULL UNIQUE,
+    content_type VARCHAR(100) NOT NULL,
+    size_bytes BIGINT NOT NULL CHECK (size_bytes >= 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_proof_of_delivery_files_pod ON proof_of_delivery_files (pod_id);
@@ -0,0 +1,27 @@
+CREATE TYPE vehicle_document_type AS ENUM ('REGISTRATION', 'INSURANCE', 'INSPECTION', 'PERMIT', 'OTHER');
+
+-- Renewals are added as new documents; the latest expiry of each type is the one in force
+CREATE TABLE IF NOT EXISTS vehicle_documents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    vehicle_id UUID NOT NULL REFERENCES vehicles(id) ON DELETE CASCADE,
+    type vehicle_document_type NOT NULL,
+    document_number VARCHAR(100),
+    issue_date DATE,
+    -- NULL for documents that do not expire
+    expiry_date DATE,
+    notes TEXT,
+    -- Scanned copy in object storage, if one was uploaded
+    file_name VARCHAR(255),
+    content_type VARCHAR(100),
+    size_bytes BIGINT CHECK (size_bytes >= 0),
+    storage_key TEXT UNIQUE,
+    uploaded_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT vehicle_documents_dates_check CHECK (expiry_date IS NULL OR issue_date IS NULL OR expiry_date >= issue_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_vehicle_documents_vehicle ON vehicle_documents (vehicle_id, type, expiry_date);
+
+-- Days before a vehicle document expires that an "... Expiring" alert is raised
+ALTER TABLE app_settings
+  ADD COLUMN IF NOT EXISTS document_expiry_warning_days INTEGER NOT NULL DEFAULT 30;
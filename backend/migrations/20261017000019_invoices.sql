@@ -0,0 +1,57 @@
+-- Tax percentage added to new invoices and days after issue that they are due
+ALTER TABLE app_settings
+  ADD COLUMN IF NOT EXISTS tax_rate DECIMAL(5, 2) NOT NULL DEFAULT 0 CHECK (tax_rate BETWEEN 0 AND 100),
+  ADD COLUMN IF NOT EXISTS payment_terms_days INTEGER NOT NULL DEFAULT 30;
+
+CREATE TYPE invoice_status AS ENUM ('ISSUED', 'PARTIALLY_PAID', 'PAID');
+
+CREATE TABLE IF NOT EXISTS invoices (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Gap-free: the next number is taken while holding a lock on the table
+    sequence_number INTEGER NOT NULL UNIQUE CHECK (sequence_number > 0),
+    invoice_number TEXT GENERATED ALWAYS AS ('INV-' || LPAD(sequence_number::text, 6, '0')) STORED,
+    customer_id UUID NOT NULL REFERENCES customers(id),
+    -- Copied from the customer and settings at issue, so later edits leave the invoice as sent
+    billing_address TEXT NOT NULL,
+    currency TEXT NOT NULL,
+    tax_rate DECIMAL(5, 2) NOT NULL CHECK (tax_rate BETWEEN 0 AND 100),
+    subtotal DECIMAL(12, 2) NOT NULL CHECK (subtotal >= 0),
+    tax_amount DECIMAL(12, 2) NOT NULL CHECK (tax_amount >= 0),
+    total DECIMAL(12, 2) NOT NULL CHECK (total >= 0),
+    status invoice_status NOT NULL DEFAULT 'ISSUED',
+    issue_date DATE NOT NULL,
+    due_date DATE NOT NULL CHECK (due_date >= issue_date),
+    notes TEXT,
+    issued_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_invoices_customer ON invoices (customer_id, issue_date);
+
+-- One line per transport job; a job is billed on one invoice only
+CREATE TABLE IF NOT EXISTS invoice_lines (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    invoice_id UUID NOT NULL REFERENCES invoices(id) ON DELETE CASCADE,
+    job_id UUID NOT NULL UNIQUE REFERENCES transport_jobs(id),
+    description TEXT NOT NULL,
+    amount DECIMAL(12, 2) NOT NULL CHECK (amount >= 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_invoice_lines_invoice ON invoice_lines (invoice_id);
+
+CREATE TABLE IF NOT EXISTS invoice_payments (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    invoice_id UUID NOT NULL REFERENCES invoices(id) ON DELETE CASCADE,
+    amount DECIMAL(12, 2) NOT NULL CHECK (amount > 0),
+    paid_on DATE NOT NULL,
+    method VARCHAR(50),
+    reference VARCHAR(255),
+    recorded_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_invoice_payments_invoice ON invoice_payments (invoice_id);
+
+UPDATE roles
+SET permissions = permissions || '["invoices:*"]'::jsonb
+WHERE name = 'MANAGER' AND NOT permissions ? 'invoices:*';
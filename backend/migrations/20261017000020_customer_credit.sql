@@ -0,0 +1,7 @@
+-- Most a customer may owe across open invoices and unbilled jobs; NULL means no limit
+ALTER TABLE customers
+  ADD COLUMN IF NOT EXISTS credit_limit DECIMAL(12, 2) CHECK (credit_limit >= 0),
+  -- Refuse new jobs over the limit instead of only raising an alert
+  ADD COLUMN IF NOT EXISTS block_over_credit_limit BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX IF NOT EXISTS idx_transport_jobs_customer ON transport_jobs (customer_id, status);
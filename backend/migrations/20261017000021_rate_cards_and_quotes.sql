@@ -0,0 +1,61 @@
+-- Prices quoted for transport jobs. The active card for the requested vehicle type is
+-- used, falling back to the active card without a vehicle type.
+CREATE TABLE IF NOT EXISTS rate_cards (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL,
+    vehicle_type vehicle_type,
+    per_km DECIMAL(10, 4) NOT NULL DEFAULT 0 CHECK (per_km >= 0),
+    per_kg DECIMAL(10, 4) NOT NULL DEFAULT 0 CHECK (per_kg >= 0),
+    -- Least a quote using the card comes to
+    minimum_charge DECIMAL(12, 2) NOT NULL DEFAULT 0 CHECK (minimum_charge >= 0),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- At most one active card per vehicle type, and one for any vehicle type
+CREATE UNIQUE INDEX IF NOT EXISTS idx_rate_cards_active_vehicle_type
+    ON rate_cards (vehicle_type) WHERE is_active AND vehicle_type IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_rate_cards_active_any
+    ON rate_cards ((TRUE)) WHERE is_active AND vehicle_type IS NULL;
+
+-- Percentage added to the distance and weight charges when a shipment of the type,
+-- e.g. HAZARDOUS, is carried
+CREATE TABLE IF NOT EXISTS rate_card_surcharges (
+    rate_card_id UUID NOT NULL REFERENCES rate_cards(id) ON DELETE CASCADE,
+    shipment_type VARCHAR(50) NOT NULL,
+    percent DECIMAL(5, 2) NOT NULL CHECK (percent BETWEEN 0 AND 100),
+    PRIMARY KEY (rate_card_id, shipment_type)
+);
+
+CREATE TABLE IF NOT EXISTS quotes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    customer_id UUID NOT NULL REFERENCES customers(id),
+    rate_card_id UUID REFERENCES rate_cards(id) ON DELETE SET NULL,
+    vehicle_type vehicle_type,
+    origin GEOMETRY(POINT, 4326) NOT NULL,
+    destination GEOMETRY(POINT, 4326) NOT NULL,
+    waypoints GEOMETRY(LINESTRING, 4326),
+    -- Shipments to create with the job, as [{weight, dimensions, type}]
+    shipments JSONB NOT NULL DEFAULT '[]',
+    distance_km DECIMAL(10, 2) NOT NULL CHECK (distance_km >= 0),
+    weight_kg DECIMAL(12, 2) NOT NULL CHECK (weight_kg >= 0),
+    -- Price breakdown as worked out from the rate card at the time
+    distance_charge DECIMAL(12, 2) NOT NULL,
+    weight_charge DECIMAL(12, 2) NOT NULL,
+    surcharge DECIMAL(12, 2) NOT NULL,
+    price DECIMAL(12, 2) NOT NULL CHECK (price >= 0),
+    currency TEXT NOT NULL,
+    valid_until DATE NOT NULL,
+    -- Set once the quote is converted; the job keeps the quoted price
+    job_id UUID UNIQUE REFERENCES transport_jobs(id) ON DELETE SET NULL,
+    converted_at TIMESTAMPTZ,
+    created_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_quotes_customer ON quotes (customer_id, created_at);
+
+UPDATE roles
+SET permissions = permissions || '["rate-cards:*", "quotes:*"]'::jsonb
+WHERE name = 'MANAGER' AND NOT permissions ? 'quotes:*';
@@ -0,0 +1,35 @@
+-- At most one open alert per entity and type for the alerts the system raises through
+-- `create_unless_open`, so deduplication holds under concurrent requests. Alerts created
+-- by hand and geofence crossings may repeat and are not touched. Keep the list in step
+-- with the alert type constants in the services.
+UPDATE alerts a
+SET is_resolved = TRUE, resolved_at = NOW()
+WHERE a.is_resolved = FALSE
+  AND a.type IN (
+    'Speeding', 'Sudden Fuel Drop', 'Excessive Idling', 'Tracker Offline',
+    'Maintenance Due Soon', 'Maintenance Overdue', 'Low Stock', 'Credit Limit Exceeded',
+    'License Expiring', 'License Expired',
+    'Registration Expiring', 'Registration Expired', 'Insurance Expiring', 'Insurance Expired',
+    'Inspection Expiring', 'Inspection Expired', 'Permit Expiring', 'Permit Expired',
+    'Document Expiring', 'Document Expired'
+  )
+  AND EXISTS (
+    SELECT 1 FROM alerts newer
+    WHERE newer.entity_id = a.entity_id
+      AND newer.type = a.type
+      AND newer.is_resolved = FALSE
+      AND (newer.created_at, newer.id) > (a.created_at, a.id)
+  );
+
+DROP INDEX IF EXISTS idx_alerts_open_by_entity;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_alerts_open_by_entity
+  ON alerts (entity_id, type)
+  WHERE is_resolved = FALSE AND type IN (
+    'Speeding', 'Sudden Fuel Drop', 'Excessive Idling', 'Tracker Offline',
+    'Maintenance Due Soon', 'Maintenance Overdue', 'Low Stock', 'Credit Limit Exceeded',
+    'License Expiring', 'License Expired',
+    'Registration Expiring', 'Registration Expired', 'Insurance Expiring', 'Insurance Expired',
+    'Inspection Expiring', 'Inspection Expired', 'Permit Expiring', 'Permit Expired',
+    'Document Expiring', 'Document Expired'
+  );
@@ -0,0 +1,6 @@
+-- Odometer reading (km) when the vehicle was added. Until a preventive service is logged
+-- the km-based maintenance schedule counts from it rather than from 0 km. Existing vehicles
+-- start from their current reading.
+ALTER TABLE vehicles ADD COLUMN IF NOT EXISTS initial_mileage INTEGER;
+UPDATE vehicles SET initial_mileage = current_mileage WHERE initial_mileage IS NULL;
+ALTER TABLE vehicles ALTER COLUMN initial_mileage SET NOT NULL;
@@ -48,7 +48,7 @@ pub async fn create_vehicle(
     service: web::Data<dyn VehicleServiceTrait>,
     item: web::Json<CreateVehicleRequest>,
 ) -> Result<HttpResponse, AppError> {
-    item.validate().map_err(|e| AppError::ValidationError(e))?;
+    item.validate().map_err(AppError::ValidationError)?;
     
     let req = item.into_inner();
     let dto = CreateVehicleDto {
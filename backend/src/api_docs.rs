@@ -2,15 +2,29 @@ use utoipa::{OpenApi, Modify};
 use utoipa::openapi::security::{SecurityScheme, HttpBuilder, HttpAuthScheme};
 use crate::models::postgres::{
     vehicle::{Vehicle, CreateVehicleDto, VehicleType, VehicleStatus, FuelType},
-    user::{User, CreateUserDto, UserRole, Role},
+    user::{User, CreateUserDto, UserRole, Role, UpdateRolePermissionsDto},
     driver::{Driver, DriverWithUser, CreateDriverDto, DriverStatus},
-    assignment::{VehicleAssignment, CreateAssignmentDto, AssignmentStatus},
-    maintenance::{MaintenanceRecord, CreateMaintenanceRecordDto, MaintenanceType, Alert, CreateAlertDto, AlertSeverity},
-    logistics::{Customer, CreateCustomerDto, TransportJob, CreateTransportJobDto, JobStatus, Route, CreateRouteDto, Shipment, CreateShipmentDto},
-    telemetry::{VehicleTelemetry, CreateVehicleTelemetryDto},
+    assignment::{VehicleAssignment, CreateAssignmentDto, AssignmentStatus, CancelAssignmentDto, UpdateAssignmentDto, AssignmentQuery},
+    maintenance::{MaintenanceRecord, CreateMaintenanceRecordDto, MaintenanceType, Alert, CreateAlertDto, AlertSeverity, MaintenanceDue, MaintenanceDueStatus, MaintenanceDueReport},
+    logistics::{Customer, CreateCustomerDto, UpdateCreditLimitDto, TransportJob, CreateTransportJobDto, JobStatus, Route, CreateRouteDto, Shipment, CreateShipmentDto, JobLeg, AddJobLegDto, UpdateJobStatusDto, JobStatusHistory},
+    telemetry::{VehicleTelemetry, CreateVehicleTelemetryDto, BulkTelemetryResult, TelemetryRejection, TelemetryBucket, TelemetryFormat, TelemetryHistoryQuery, TelemetryHistory},
     financial::{MonthlyFinancialSummary, VehicleProfitability},
+    geofence::{Geofence, CreateGeofenceDto, GeofenceKind},
+    odometer::{DailyDistance, DailyDistanceQuery, DistanceSource},
+    work_order::{WorkOrder, WorkOrderItem, WorkOrderDetails, WorkOrderStatus, WorkOrderItemKind, CreateWorkOrderDto, UpdateWorkOrderStatusDto, AssignMechanicDto, CreateWorkOrderItemDto, WorkOrderQuery},
+    part::{Part, CreatePartDto, UpdatePartDto, PartQuery, StockMovement, StockMovementKind, CreateStockMovementDto},
+    driver_score::{TripScore, WeeklyScore, DriverScore, LeaderboardEntry, DriverScoreQuery, LeaderboardQuery},
+    hos::{DutyEvent, DutyEventKind, ClockDto, DutySource, DutyPeriod, DutyShift, DutyDay, HosLogQuery, HosLog},
+    dispatch::{DispatchSuggestion, DispatchSuggestionQuery, AcceptDispatchDto},
+    proof_of_delivery::{ProofOfDelivery, ProofOfDeliveryFile, ProofOfDeliveryDetails, PodFileKind, CreateProofOfDeliveryDto},
+    vehicle_document::{VehicleDocument, VehicleDocumentType, CreateVehicleDocumentDto},
+    invoice::{Invoice, InvoiceLine, InvoicePayment, InvoiceDetails, InvoiceStatus, CreateInvoiceDto, RecordPaymentDto, InvoiceQuery,
+        AgingBuckets, CustomerAging, ArAgingReport, AgingQuery, StatementEntryKind, StatementEntry, CustomerStatement, StatementQuery},
+    quote::{RateCard, RateCardSurcharge, RateCardDto, Quote, QuoteShipmentDto, CreateQuoteDto, QuoteQuery},
 };
-use crate::services::auth_service::{LoginDto, AuthResponse, Claims};
+use crate::models::redis::vehicle_status::VehicleStatusCache;
+use crate::error::{ConflictDetails, ConflictKind};
+use crate::services::auth_service::{LoginDto, AuthResponse, Claims, RefreshTokenDto, LogoutDto};
 
 pub struct SecurityAddon;
 
@@ -35,19 +49,36 @@ impl Modify for SecurityAddon {
     paths(
         crate::routes::auth::register,
         crate::routes::auth::login,
+        crate::routes::auth::refresh,
+        crate::routes::auth::logout,
         crate::routes::auth::me
     ),
     components(
         schemas(
             Vehicle, CreateVehicleDto, VehicleType, VehicleStatus, FuelType,
-            User, CreateUserDto, UserRole, Role,
+            User, CreateUserDto, UserRole, Role, UpdateRolePermissionsDto,
             Driver, DriverWithUser, CreateDriverDto, DriverStatus,
-            VehicleAssignment, CreateAssignmentDto, AssignmentStatus,
+            VehicleAssignment, CreateAssignmentDto, AssignmentStatus, CancelAssignmentDto, UpdateAssignmentDto, AssignmentQuery,
             MaintenanceRecord, CreateMaintenanceRecordDto, MaintenanceType, Alert, CreateAlertDto, AlertSeverity,
-            Customer, CreateCustomerDto, TransportJob, CreateTransportJobDto, JobStatus, Route, CreateRouteDto, Shipment, CreateShipmentDto,
-            VehicleTelemetry, CreateVehicleTelemetryDto,
+            MaintenanceDue, MaintenanceDueStatus, MaintenanceDueReport,
+            Customer, CreateCustomerDto, UpdateCreditLimitDto, TransportJob, CreateTransportJobDto, JobStatus, Route, CreateRouteDto, Shipment, CreateShipmentDto, JobLeg, AddJobLegDto, UpdateJobStatusDto, JobStatusHistory,
+            VehicleTelemetry, CreateVehicleTelemetryDto, BulkTelemetryResult, TelemetryRejection, TelemetryBucket, TelemetryFormat, TelemetryHistoryQuery, TelemetryHistory, VehicleStatusCache,
             MonthlyFinancialSummary, VehicleProfitability,
-            LoginDto, AuthResponse, Claims
+            Geofence, CreateGeofenceDto, GeofenceKind,
+            DailyDistance, DailyDistanceQuery, DistanceSource,
+            WorkOrder, WorkOrderItem, WorkOrderDetails, WorkOrderStatus, WorkOrderItemKind, CreateWorkOrderDto,
+            UpdateWorkOrderStatusDto, AssignMechanicDto, CreateWorkOrderItemDto, WorkOrderQuery,
+            Part, CreatePartDto, UpdatePartDto, PartQuery, StockMovement, StockMovementKind, CreateStockMovementDto,
+            TripScore, WeeklyScore, DriverScore, LeaderboardEntry, DriverScoreQuery, LeaderboardQuery,
+            DutyEvent, DutyEventKind, ClockDto, DutySource, DutyPeriod, DutyShift, DutyDay, HosLogQuery, HosLog,
+            DispatchSuggestion, DispatchSuggestionQuery, AcceptDispatchDto,
+            ProofOfDelivery, ProofOfDeliveryFile, ProofOfDeliveryDetails, PodFileKind, CreateProofOfDeliveryDto,
+            VehicleDocument, VehicleDocumentType, CreateVehicleDocumentDto,
+            Invoice, InvoiceLine, InvoicePayment, InvoiceDetails, InvoiceStatus, CreateInvoiceDto, RecordPaymentDto, InvoiceQuery,
+            AgingBuckets, CustomerAging, ArAgingReport, AgingQuery, StatementEntryKind, StatementEntry, CustomerStatement, StatementQuery,
+            RateCard, RateCardSurcharge, RateCardDto, Quote, QuoteShipmentDto, CreateQuoteDto, QuoteQuery,
+            ConflictDetails, ConflictKind,
+            LoginDto, AuthResponse, Claims, RefreshTokenDto, LogoutDto
         )
     ),
     modifiers(&SecurityAddon),
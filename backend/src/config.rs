@@ -7,6 +7,20 @@ pub struct Config {
     pub database_url: String,
     pub server_address: String,
     pub jwt_secret: String,
+    pub redis_url: String,
+    /// Directory for stored files when no S3 bucket is configured
+    pub storage_path: String,
+    pub s3: Option<S3Config>,
+}
+
+/// S3-compatible object storage, e.g. the MinIO service in docker-compose.
+#[derive(Debug, Deserialize, Clone)]
+pub struct S3Config {
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
 }
 
 impl Config {
@@ -15,11 +29,29 @@ impl Config {
         let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
         let server_address = env::var("SERVER_ADDRESS").unwrap_or_else(|_| "127.0.0.1:8080".to_string());
         let jwt_secret = env::var("JWT_SECRET").unwrap_or_else(|_| "secret".to_string());
+        let redis_url = env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        let storage_path = env::var("STORAGE_PATH").unwrap_or_else(|_| "./storage".to_string());
+
+        // Files go to S3 once a bucket and endpoint are set, otherwise to `storage_path`
+        let s3 = match (env::var("S3_ENDPOINT"), env::var("S3_BUCKET_NAME")) {
+            (Ok(endpoint), Ok(bucket)) => Some(S3Config {
+                endpoint,
+                bucket,
+                region: env::var("AWS_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+                access_key_id: env::var("AWS_ACCESS_KEY_ID").expect("AWS_ACCESS_KEY_ID must be set for S3 storage"),
+                secret_access_key: env::var("AWS_SECRET_ACCESS_KEY")
+                    .expect("AWS_SECRET_ACCESS_KEY must be set for S3 storage"),
+            }),
+            _ => None,
+        };
 
         Config {
             database_url,
             server_address,
             jwt_secret,
+            redis_url,
+            storage_path,
+            s3,
         }
     }
 }
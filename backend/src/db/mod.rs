@@ -10,3 +10,7 @@ pub async fn init_db(config: &Config) -> DbPool {
         .await
         .expect("Failed to connect to Postgres")
 }
+
+pub fn init_redis(config: &Config) -> redis::Client {
+    redis::Client::open(config.redis_url.as_str()).expect("Invalid REDIS_URL")
+}
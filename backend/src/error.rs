@@ -1,5 +1,30 @@
 use actix_web::{HttpResponse, ResponseError};
 use derive_more::Display;
+use serde::Serialize;
+use sqlx::postgres::PgDatabaseError;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ConflictKind {
+    /// Overlaps a time range that is already taken (exclusion constraint)
+    Overlap,
+    /// A record with the same unique value already exists
+    Duplicate,
+    /// Refers to a record that does not exist, or is still referred to
+    Reference,
+    /// The record was changed by another request since it was read
+    Stale,
+}
+
+/// Body of 409 Conflict responses.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ConflictDetails {
+    pub kind: ConflictKind,
+    pub message: String,
+    /// Database constraint that was violated, if any
+    pub constraint: Option<String>,
+}
 
 #[derive(Debug, Display)]
 pub enum AppError {
@@ -12,6 +37,9 @@ pub enum AppError {
     #[display(fmt = "AuthError: {}", _0)]
     AuthError(String),
 
+    #[display(fmt = "Forbidden: {}", _0)]
+    Forbidden(String),
+
     #[display(fmt = "NotFound: {}", _0)]
     NotFound(String),
 
@@ -24,8 +52,62 @@ pub enum AppError {
     #[display(fmt = "SerializationError: {}", _0)]
     SerializationError(String),
 
+    #[display(fmt = "StorageError: {}", _0)]
+    StorageError(String),
+
     #[display(fmt = "ValidationError: {:?}", _0)]
     ValidationError(validator::ValidationErrors),
+
+    #[display(fmt = "Conflict: {}", "_0.message")]
+    Conflict(ConflictDetails),
+}
+
+impl AppError {
+    pub fn conflict(kind: ConflictKind, message: impl Into<String>) -> Self {
+        AppError::Conflict(ConflictDetails { kind, message: message.into(), constraint: None })
+    }
+}
+
+/// Messages for constraints clients are expected to run into; others fall back to the
+/// database's own detail.
+fn constraint_message(constraint: &str) -> Option<&'static str> {
+    match constraint {
+        "vehicle_assignments_vehicle_id_tstzrange_excl" => Some("Vehicle already has an assignment in this period"),
+        "vehicles_vin_key" => Some("A vehicle with this VIN already exists"),
+        "vehicles_license_plate_key" => Some("A vehicle with this license plate already exists"),
+        "users_email_key" => Some("A user with this email already exists"),
+        "drivers_license_number_key" => Some("A driver with this license number already exists"),
+        "roles_name_key" => Some("A role with this name already exists"),
+        "parts_sku_key" => Some("A part with this SKU already exists"),
+        "job_assignments_pkey" => Some("Assignment is already a leg of a transport job"),
+        "job_assignments_job_id_leg_number_key" => Some("The job already has a leg with this number"),
+        "proof_of_delivery_job_id_key" => Some("Proof of delivery was already captured for this job"),
+        "idx_alerts_open_by_entity" => Some("An open alert of this type already exists for this entity"),
+        _ => None,
+    }
+}
+
+/// Constraint violations become `Conflict`; everything else stays a `DatabaseError`.
+impl From<sqlx::Error> for AppError {
+    fn from(error: sqlx::Error) -> Self {
+        let Some(db_error) = error.as_database_error() else {
+            return AppError::DatabaseError(error);
+        };
+        let kind = match db_error.code().as_deref() {
+            Some("23P01") => ConflictKind::Overlap,
+            Some("23505") => ConflictKind::Duplicate,
+            Some("23503") => ConflictKind::Reference,
+            _ => return AppError::DatabaseError(error),
+        };
+        let constraint = db_error.constraint().map(str::to_string);
+        let message = constraint
+            .as_deref()
+            .and_then(constraint_message)
+            .map(str::to_string)
+            .or_else(|| db_error.try_downcast_ref::<PgDatabaseError>().and_then(|e| e.detail()).map(str::to_string))
+            .unwrap_or_else(|| db_error.message().to_string());
+        AppError::Conflict(ConflictDetails { kind, message, constraint })
+    }
 }
 
 impl ResponseError for AppError {
@@ -37,8 +119,10 @@ impl ResponseError for AppError {
             }
             AppError::AuthError(ref message) => HttpResponse::Unauthorized().json(message),
             AppError::BadRequest(ref message) => HttpResponse::BadRequest().json(message),
+            AppError::Forbidden(ref message) => HttpResponse::Forbidden().json(message),
             AppError::NotFound(ref message) => HttpResponse::NotFound().json(message),
             AppError::ValidationError(ref errors) => HttpResponse::BadRequest().json(errors),
+            AppError::Conflict(ref details) => HttpResponse::Conflict().json(details),
             AppError::DatabaseError(ref message) => {
                 // In production, we shouldn't leak DB errors to the client
                 eprintln!("Database error: {:?}", message);
@@ -52,6 +136,10 @@ impl ResponseError for AppError {
                 eprintln!("Serialization error: {:?}", message);
                 HttpResponse::InternalServerError().json("Internal Server Error")
             }
+            AppError::StorageError(ref message) => {
+                eprintln!("Storage error: {:?}", message);
+                HttpResponse::InternalServerError().json("Internal Server Error")
+            }
         }
     }
 }
@@ -0,0 +1,176 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use chrono::Utc;
+
+use crate::error::AppError;
+use crate::services::telemetry_partition_service::TelemetryPartitionServiceTrait;
+use crate::services::anomaly_service::AnomalyServiceTrait;
+use crate::services::odometer_service::OdometerServiceTrait;
+use crate::services::maintenance_service::MaintenanceServiceTrait;
+use crate::services::license_service::LicenseServiceTrait;
+use crate::services::vehicle_document_service::VehicleDocumentServiceTrait;
+use crate::services::driver_score_service::DriverScoreServiceTrait;
+use crate::services::assignment_service::AssignmentServiceTrait;
+use crate::models::postgres::assignment::AssignmentStatus;
+
+const TELEMETRY_PARTITION_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const STALE_TRACKER_INTERVAL: Duration = Duration::from_secs(5 * 60);
+const ODOMETER_SYNC_INTERVAL: Duration = Duration::from_secs(15 * 60);
+const MAINTENANCE_DUE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const LICENSE_EXPIRY_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const DOCUMENT_EXPIRY_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const DRIVER_SCORE_INTERVAL: Duration = Duration::from_secs(30 * 60);
+const ASSIGNMENT_ACTIVATION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Readings younger than this are left for the next odometer run, so stragglers from
+/// batched uploads are not skipped once the cursor has passed them.
+const ODOMETER_SYNC_LAG_MINUTES: i64 = 10;
+
+/// Assignments completed more recently than this are scored on a later run, once
+/// readings uploaded after the trip have arrived.
+const DRIVER_SCORE_LAG_MINUTES: i64 = 15;
+
+/// Runs `task` on startup and then every `period`. Failures are logged and retried on
+/// the next tick; a slow run delays the following one instead of overlapping it.
+pub fn spawn_periodic<F, Fut>(name: &'static str, period: Duration, task: F)
+where
+    F: Fn() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(), AppError>> + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(e) = task().await {
+                eprintln!("Background job '{}' failed: {}", name, e);
+            }
+        }
+    });
+}
+
+/// Keeps monthly `vehicle_telemetry` partitions ahead of time and enforces retention.
+pub fn spawn_telemetry_partition_job(service: Arc<dyn TelemetryPartitionServiceTrait>) {
+    spawn_periodic("telemetry partitions", TELEMETRY_PARTITION_INTERVAL, move || {
+        let service = service.clone();
+        async move {
+            let report = service.run_maintenance(Utc::now().date_naive()).await?;
+            if !report.created.is_empty() || !report.dropped.is_empty() || !report.archived.is_empty() {
+                println!(
+                    "Telemetry partitions: created {:?}, moved {} rows, dropped {:?}, archived {:?}",
+                    report.created, report.moved_rows, report.dropped, report.archived
+                );
+            }
+            Ok(())
+        }
+    });
+}
+
+/// Raises alerts for in-service vehicles whose trackers have stopped reporting.
+pub fn spawn_stale_tracker_job(service: Arc<dyn AnomalyServiceTrait>) {
+    spawn_periodic("stale trackers", STALE_TRACKER_INTERVAL, move || {
+        let service = service.clone();
+        async move {
+            let alerts = service.check_stale_trackers(Utc::now()).await?;
+            if !alerts.is_empty() {
+                println!("Stale trackers: raised {} offline alerts", alerts.len());
+            }
+            Ok(())
+        }
+    });
+}
+
+/// Keeps `vehicles.current_mileage` in step with the distance reported by telemetry.
+pub fn spawn_odometer_sync_job(service: Arc<dyn OdometerServiceTrait>) {
+    spawn_periodic("odometer sync", ODOMETER_SYNC_INTERVAL, move || {
+        let service = service.clone();
+        async move {
+            let until = Utc::now() - chrono::Duration::minutes(ODOMETER_SYNC_LAG_MINUTES);
+            let report = service.sync_odometers(until).await?;
+            if report.vehicles_synced > 0 {
+                println!(
+                    "Odometer sync: updated {} vehicles, added {} km",
+                    report.vehicles_synced, report.kilometers_added
+                );
+            }
+            Ok(())
+        }
+    });
+}
+
+/// Raises maintenance due-soon and overdue alerts from the schedules.
+pub fn spawn_maintenance_due_job(service: Arc<dyn MaintenanceServiceTrait>) {
+    spawn_periodic("maintenance due", MAINTENANCE_DUE_INTERVAL, move || {
+        let service = service.clone();
+        async move {
+            let alerts = service.raise_due_alerts(Utc::now()).await?;
+            if !alerts.is_empty() {
+                println!("Maintenance due: raised {} alerts", alerts.len());
+            }
+            Ok(())
+        }
+    });
+}
+
+/// Warns about driver licenses that are about to expire or already have.
+pub fn spawn_license_expiry_job(service: Arc<dyn LicenseServiceTrait>) {
+    spawn_periodic("license expiry", LICENSE_EXPIRY_INTERVAL, move || {
+        let service = service.clone();
+        async move {
+            let alerts = service.raise_expiry_alerts(Utc::now().date_naive()).await?;
+            if !alerts.is_empty() {
+                println!("License expiry: raised {} alerts", alerts.len());
+            }
+            Ok(())
+        }
+    });
+}
+
+/// Raises alerts for vehicle registrations, insurance and other documents nearing expiry.
+pub fn spawn_document_expiry_job(service: Arc<dyn VehicleDocumentServiceTrait>) {
+    spawn_periodic("document expiry", DOCUMENT_EXPIRY_INTERVAL, move || {
+        let service = service.clone();
+        async move {
+            let alerts = service.raise_expiry_alerts(Utc::now().date_naive()).await?;
+            if !alerts.is_empty() {
+                println!("Document expiry: raised {} alerts", alerts.len());
+            }
+            Ok(())
+        }
+    });
+}
+
+/// Scores the driving behaviour of completed assignments from their telemetry.
+pub fn spawn_driver_score_job(service: Arc<dyn DriverScoreServiceTrait>) {
+    spawn_periodic("driver scores", DRIVER_SCORE_INTERVAL, move || {
+        let service = service.clone();
+        async move {
+            let completed_before = Utc::now() - chrono::Duration::minutes(DRIVER_SCORE_LAG_MINUTES);
+            let scored = service.score_completed_trips(completed_before).await?;
+            if scored > 0 {
+                println!("Driver scores: scored {} trips", scored);
+            }
+            Ok(())
+        }
+    });
+}
+
+/// Starts scheduled assignments once their start time arrives.
+pub fn spawn_assignment_activation_job(service: Arc<dyn AssignmentServiceTrait>) {
+    spawn_periodic("assignment activation", ASSIGNMENT_ACTIVATION_INTERVAL, move || {
+        let service = service.clone();
+        async move {
+            let changed = service.activate_due_assignments(Utc::now()).await?;
+            if !changed.is_empty() {
+                let started = changed.iter().filter(|a| a.status == AssignmentStatus::Active).count();
+                println!(
+                    "Assignment activation: started {}, cancelled {} that never started",
+                    started,
+                    changed.len() - started
+                );
+            }
+            Ok(())
+        }
+    });
+}
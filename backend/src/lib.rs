@@ -8,3 +8,4 @@ pub mod services;
 pub mod error;
 pub mod middleware;
 pub mod api_docs;
+pub mod jobs;
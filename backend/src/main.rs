@@ -1,29 +1,61 @@
-use fleet_management_backend::{config, db, routes};
+use fleet_management_backend::{config, db, jobs, routes};
 use fleet_management_backend::repositories::postgres::vehicle_repo::VehicleRepository;
 use fleet_management_backend::services::vehicle_service::{VehicleService, VehicleServiceTrait};
 use fleet_management_backend::repositories::postgres::driver_repo::DriverRepository;
 use fleet_management_backend::services::driver_service::{DriverService, DriverServiceTrait};
 use fleet_management_backend::repositories::postgres::assignment_repo::AssignmentRepository;
+use fleet_management_backend::repositories::postgres::unit_of_work::UnitOfWork;
 use fleet_management_backend::services::assignment_service::{AssignmentService, AssignmentServiceTrait};
 use fleet_management_backend::repositories::postgres::maintenance_repo::{MaintenanceRecordRepository, MaintenanceScheduleRepository, AlertRepository};
 use fleet_management_backend::services::maintenance_service::{MaintenanceService, MaintenanceServiceTrait};
-use fleet_management_backend::repositories::postgres::logistics_repo::{CustomerRepository, TransportJobRepository, RouteRepository, ShipmentRepository};
+use fleet_management_backend::repositories::postgres::logistics_repo::{CustomerRepository, TransportJobRepository, RouteRepository, ShipmentRepository, JobLegRepository};
 use fleet_management_backend::services::logistics_service::{LogisticsService, LogisticsServiceTrait};
+use fleet_management_backend::repositories::postgres::dispatch_repo::DispatchRepository;
+use fleet_management_backend::services::dispatch_service::{DispatchService, DispatchServiceTrait};
+use fleet_management_backend::repositories::postgres::proof_of_delivery_repo::ProofOfDeliveryRepository;
+use fleet_management_backend::repositories::storage;
+use fleet_management_backend::services::proof_of_delivery_service::{ProofOfDeliveryService, ProofOfDeliveryServiceTrait};
+use fleet_management_backend::repositories::postgres::vehicle_document_repo::VehicleDocumentRepository;
+use fleet_management_backend::services::vehicle_document_service::{VehicleDocumentService, VehicleDocumentServiceTrait};
 use fleet_management_backend::repositories::postgres::telemetry_repo::TelemetryRepository;
+use fleet_management_backend::repositories::redis::vehicle_status_repo::VehicleStatusCacheRepository;
 use fleet_management_backend::services::telemetry_service::{TelemetryService, TelemetryServiceTrait};
 use fleet_management_backend::repositories::postgres::financial_repo::FinancialRepository;
 use fleet_management_backend::services::financial_service::{FinancialService, FinancialServiceTrait};
+use fleet_management_backend::repositories::postgres::invoice_repo::InvoiceRepository;
+use fleet_management_backend::services::invoice_service::{InvoiceService, InvoiceServiceTrait};
+use fleet_management_backend::repositories::postgres::quote_repo::{QuoteRepository, RateCardRepository};
+use fleet_management_backend::services::quote_service::{QuoteService, QuoteServiceTrait};
 use fleet_management_backend::repositories::postgres::user_repo::UserRepository;
 use fleet_management_backend::repositories::postgres::settings_repo::SettingsRepository;
+use fleet_management_backend::repositories::postgres::token_repo::TokenRepository;
 use fleet_management_backend::services::auth_service::{AuthService, AuthServiceTrait};
 use fleet_management_backend::services::settings_service::{SettingsService, SettingsServiceTrait};
 use fleet_management_backend::services::user_service::{UserService, UserServiceTrait};
+use fleet_management_backend::repositories::postgres::role_repo::RoleRepository;
+use fleet_management_backend::repositories::postgres::partition_repo::TelemetryPartitionRepository;
+use fleet_management_backend::repositories::postgres::geofence_repo::GeofenceRepository;
+use fleet_management_backend::services::geofence_service::{GeofenceService, GeofenceServiceTrait};
+use fleet_management_backend::services::telemetry_partition_service::TelemetryPartitionService;
+use fleet_management_backend::services::anomaly_service::{AnomalyService, AnomalyServiceTrait};
+use fleet_management_backend::services::odometer_service::{OdometerService, OdometerServiceTrait};
+use fleet_management_backend::repositories::postgres::odometer_repo::OdometerRepository;
+use fleet_management_backend::repositories::postgres::work_order_repo::WorkOrderRepository;
+use fleet_management_backend::services::work_order_service::{WorkOrderService, WorkOrderServiceTrait};
+use fleet_management_backend::repositories::postgres::part_repo::PartRepository;
+use fleet_management_backend::services::part_service::{PartService, PartServiceTrait};
+use fleet_management_backend::services::license_service::LicenseService;
+use fleet_management_backend::repositories::postgres::hos_repo::HosRepository;
+use fleet_management_backend::services::hos_service::{HosService, HosServiceTrait};
+use fleet_management_backend::repositories::postgres::driver_score_repo::DriverScoreRepository;
+use fleet_management_backend::services::driver_score_service::{DriverScoreService, DriverScoreServiceTrait};
+use fleet_management_backend::services::role_service::{RoleService, RoleServiceTrait};
 use fleet_management_backend::middleware::auth_middleware::{self, Auth};
+use fleet_management_backend::middleware::permission_middleware::Authorize;
 use fleet_management_backend::api_docs::ApiDoc;
 use actix_web::{web, App, HttpServer, HttpResponse, Responder};
 use actix_cors::Cors;
 use dotenv::dotenv;
-use env_logger;
 use std::sync::Arc;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
@@ -42,6 +74,7 @@ async fn main() -> std::io::Result<()> {
 
     let config = config::Config::init();
     let pool = db::init_db(&config).await;
+    let redis_client = db::init_redis(&config);
 
     // Run migrations automatically on startup
     sqlx::migrate!("./migrations")
@@ -56,6 +89,85 @@ async fn main() -> std::io::Result<()> {
             .build()
     );
 
+    // Role permissions are cached inside the service, so share one instance across workers
+    // to make permission edits visible everywhere at once.
+    let role_service: Arc<dyn RoleServiceTrait> = Arc::new(RoleService::new(
+        Arc::new(RoleRepository::new(pool.clone())),
+    ));
+
+    jobs::spawn_telemetry_partition_job(Arc::new(TelemetryPartitionService::new(
+        Arc::new(TelemetryPartitionRepository::new(pool.clone())),
+        Arc::new(SettingsRepository::new(pool.clone())),
+    )));
+
+    let anomaly_service: Arc<dyn AnomalyServiceTrait> = Arc::new(AnomalyService::new(
+        Arc::new(TelemetryRepository::new(pool.clone())),
+        Arc::new(AlertRepository::new(pool.clone())),
+        Arc::new(SettingsRepository::new(pool.clone())),
+    ));
+    jobs::spawn_stale_tracker_job(anomaly_service.clone());
+
+    let odometer_service: Arc<dyn OdometerServiceTrait> = Arc::new(OdometerService::new(
+        Arc::new(OdometerRepository::new(pool.clone())),
+    ));
+    jobs::spawn_odometer_sync_job(odometer_service.clone());
+
+    // Shared with the due-date job, which raises alerts from the same schedules
+    let maintenance_service: Arc<dyn MaintenanceServiceTrait> = Arc::new(MaintenanceService::new(
+        Arc::new(MaintenanceRecordRepository::new(pool.clone())),
+        Arc::new(MaintenanceScheduleRepository::new(pool.clone())),
+        Arc::new(AlertRepository::new(pool.clone())),
+        Arc::new(SettingsRepository::new(pool.clone())),
+    ));
+    jobs::spawn_maintenance_due_job(maintenance_service.clone());
+
+    jobs::spawn_license_expiry_job(Arc::new(LicenseService::new(
+        Arc::new(DriverRepository::new(pool.clone())),
+        Arc::new(AlertRepository::new(pool.clone())),
+        Arc::new(SettingsRepository::new(pool.clone())),
+    )));
+
+    // Shared with the scoring job, which fills in the trip scores it reports
+    let driver_score_service: Arc<dyn DriverScoreServiceTrait> = Arc::new(DriverScoreService::new(
+        Arc::new(DriverScoreRepository::new(pool.clone())),
+        Arc::new(TelemetryRepository::new(pool.clone())),
+        Arc::new(DriverRepository::new(pool.clone())),
+        Arc::new(SettingsRepository::new(pool.clone())),
+    ));
+    jobs::spawn_driver_score_job(driver_score_service.clone());
+
+    // Hours of service, also consulted before every new assignment
+    let hos_service: Arc<dyn HosServiceTrait> = Arc::new(HosService::new(
+        Arc::new(HosRepository::new(pool.clone())),
+        Arc::new(AssignmentRepository::new(pool.clone())),
+        Arc::new(DriverRepository::new(pool.clone())),
+        Arc::new(SettingsRepository::new(pool.clone())),
+    ));
+
+    let storage = storage::init(&config)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+
+    // Vehicle documents, also consulted before every new assignment
+    let document_service: Arc<dyn VehicleDocumentServiceTrait> = Arc::new(VehicleDocumentService::new(
+        Arc::new(VehicleDocumentRepository::new(pool.clone())),
+        Arc::new(VehicleRepository::new(pool.clone())),
+        Arc::new(AlertRepository::new(pool.clone())),
+        Arc::new(SettingsRepository::new(pool.clone())),
+        storage.clone(),
+    ));
+    jobs::spawn_document_expiry_job(document_service.clone());
+
+    // Shared with the activation job, which starts scheduled assignments on time
+    let assignment_service: Arc<dyn AssignmentServiceTrait> = Arc::new(AssignmentService::new(
+        Arc::new(AssignmentRepository::new(pool.clone())),
+        Arc::new(VehicleRepository::new(pool.clone())),
+        Arc::new(DriverRepository::new(pool.clone())),
+        hos_service.clone(),
+        document_service.clone(),
+        Arc::new(UnitOfWork::new(pool.clone())),
+    ));
+    jobs::spawn_assignment_activation_job(assignment_service.clone());
+
     println!("Server running at http://{}", config.server_address);
 
     HttpServer::new(move || {
@@ -68,34 +180,37 @@ async fn main() -> std::io::Result<()> {
         let vehicle_repo = Box::new(VehicleRepository::new(pool.clone()));
         let vehicle_service: Arc<dyn VehicleServiceTrait> = Arc::new(VehicleService::new(vehicle_repo));
         let vehicle_service_data = web::Data::from(vehicle_service);
+        let document_service_data = web::Data::from(document_service.clone());
 
         let driver_repo = Box::new(DriverRepository::new(pool.clone()));
         let driver_service: Arc<dyn DriverServiceTrait> = Arc::new(DriverService::new(driver_repo));
         let driver_service_data = web::Data::from(driver_service);
+        let driver_score_service_data = web::Data::from(driver_score_service.clone());
 
-        // Assignment Service
-        let assignment_repo = Arc::new(AssignmentRepository::new(pool.clone()));
-        let vehicle_repo_for_assignment = Arc::new(VehicleRepository::new(pool.clone()));
-        let driver_repo_for_assignment = Arc::new(DriverRepository::new(pool.clone()));
-        
-        let assignment_service: Arc<dyn AssignmentServiceTrait> = Arc::new(AssignmentService::new(
-            assignment_repo,
-            vehicle_repo_for_assignment,
-            driver_repo_for_assignment,
-        ));
-        let assignment_service_data = web::Data::from(assignment_service);
+        let hos_service_data = web::Data::from(hos_service.clone());
+        let assignment_service_data = web::Data::from(assignment_service.clone());
 
         // Maintenance Service
-        let maintenance_record_repo = Arc::new(MaintenanceRecordRepository::new(pool.clone()));
-        let maintenance_schedule_repo = Arc::new(MaintenanceScheduleRepository::new(pool.clone()));
-        let alert_repo = Arc::new(AlertRepository::new(pool.clone()));
-        
-        let maintenance_service: Arc<dyn MaintenanceServiceTrait> = Arc::new(MaintenanceService::new(
-            maintenance_record_repo,
-            maintenance_schedule_repo,
-            alert_repo,
+        let maintenance_service_data = web::Data::from(maintenance_service.clone());
+
+        // Parts Service
+        let part_service: Arc<dyn PartServiceTrait> = Arc::new(PartService::new(
+            Arc::new(PartRepository::new(pool.clone())),
+            Arc::new(VehicleRepository::new(pool.clone())),
+            Arc::new(AlertRepository::new(pool.clone())),
+        ));
+        let part_service_data = web::Data::from(part_service.clone());
+
+        // Work Order Service
+        let work_order_service: Arc<dyn WorkOrderServiceTrait> = Arc::new(WorkOrderService::new(
+            Arc::new(WorkOrderRepository::new(pool.clone())),
+            Arc::new(VehicleRepository::new(pool.clone())),
+            Arc::new(UserRepository::new(pool.clone())),
+            Arc::new(AlertRepository::new(pool.clone())),
+            part_service,
+            Arc::new(UnitOfWork::new(pool.clone())),
         ));
-        let maintenance_service_data = web::Data::from(maintenance_service);
+        let work_order_service_data = web::Data::from(work_order_service);
 
         // Logistics Service
         let customer_repo = Arc::new(CustomerRepository::new(pool.clone()));
@@ -103,18 +218,79 @@ async fn main() -> std::io::Result<()> {
         let route_repo = Arc::new(RouteRepository::new(pool.clone()));
         let shipment_repo = Arc::new(ShipmentRepository::new(pool.clone()));
 
+        // Proof of delivery, which also holds back jobs from being delivered without it
+        let pod_service = Arc::new(ProofOfDeliveryService::new(
+            job_repo.clone(),
+            Arc::new(ProofOfDeliveryRepository::new(pool.clone())),
+            storage.clone(),
+        ));
+        let pod_service_data = web::Data::from(pod_service.clone() as Arc<dyn ProofOfDeliveryServiceTrait>);
+
+        // Invoices, which alone move jobs to invoiced and paid
+        let invoice_service = Arc::new(InvoiceService::new(
+            Arc::new(InvoiceRepository::new(pool.clone())),
+            customer_repo.clone(),
+            job_repo.clone(),
+            Arc::new(SettingsRepository::new(pool.clone())),
+        ));
+        let invoice_service_data = web::Data::from(invoice_service.clone() as Arc<dyn InvoiceServiceTrait>);
+
         let logistics_service: Arc<dyn LogisticsServiceTrait> = Arc::new(LogisticsService::new(
+            customer_repo.clone(),
+            job_repo.clone(),
+            route_repo,
+            shipment_repo.clone(),
+            Arc::new(JobLegRepository::new(pool.clone())),
+            Arc::new(AssignmentRepository::new(pool.clone())),
+            Arc::new(AlertRepository::new(pool.clone())),
+            Arc::new(UnitOfWork::new(pool.clone())),
+            vec![pod_service, invoice_service],
+        ));
+        let logistics_service_data = web::Data::from(logistics_service);
+
+        // Quotes, converted into jobs in one transaction with the same credit check
+        let quote_service: Arc<dyn QuoteServiceTrait> = Arc::new(QuoteService::new(
+            Arc::new(RateCardRepository::new(pool.clone())),
+            Arc::new(QuoteRepository::new(pool.clone())),
             customer_repo,
+            Arc::new(SettingsRepository::new(pool.clone())),
+            Arc::new(AlertRepository::new(pool.clone())),
+            Arc::new(UnitOfWork::new(pool.clone())),
+        ));
+        let quote_service_data = web::Data::from(quote_service);
+
+        // Dispatch Service
+        let dispatch_service: Arc<dyn DispatchServiceTrait> = Arc::new(DispatchService::new(
             job_repo,
-            route_repo,
             shipment_repo,
+            Arc::new(DispatchRepository::new(pool.clone())),
+            Arc::new(DriverRepository::new(pool.clone())),
+            hos_service.clone(),
+            maintenance_service.clone(),
+            assignment_service.clone(),
         ));
-        let logistics_service_data = web::Data::from(logistics_service);
+        let dispatch_service_data = web::Data::from(dispatch_service);
+
+        // Geofence Service
+        let geofence_service: Arc<dyn GeofenceServiceTrait> = Arc::new(GeofenceService::new(
+            Arc::new(GeofenceRepository::new(pool.clone())),
+            Arc::new(AlertRepository::new(pool.clone())),
+        ));
+        let geofence_service_data = web::Data::from(geofence_service.clone());
 
         // Telemetry Service
         let telemetry_repo = Arc::new(TelemetryRepository::new(pool.clone()));
-        let telemetry_service: Arc<dyn TelemetryServiceTrait> = Arc::new(TelemetryService::new(telemetry_repo));
+        let status_cache = Arc::new(VehicleStatusCacheRepository::new(redis_client.clone()));
+        let telemetry_service: Arc<dyn TelemetryServiceTrait> = Arc::new(TelemetryService::new(
+            telemetry_repo,
+            Arc::new(AssignmentRepository::new(pool.clone())),
+            Arc::new(VehicleRepository::new(pool.clone())),
+            status_cache,
+            geofence_service,
+            anomaly_service.clone(),
+        ));
         let telemetry_service_data = web::Data::from(telemetry_service);
+        let odometer_service_data = web::Data::from(odometer_service.clone());
 
         // Financial Service
         let financial_repo = Arc::new(FinancialRepository::new(pool.clone()));
@@ -123,7 +299,8 @@ async fn main() -> std::io::Result<()> {
 
         // Auth Service
         let user_repo = Box::new(UserRepository::new(pool.clone()));
-        let auth_service: Arc<dyn AuthServiceTrait> = Arc::new(AuthService::new(user_repo, config.jwt_secret.clone()));
+        let token_repo = Arc::new(TokenRepository::new(pool.clone()));
+        let auth_service: Arc<dyn AuthServiceTrait> = Arc::new(AuthService::new(user_repo, token_repo.clone(), config.jwt_secret.clone()));
         let auth_service_data = web::Data::from(auth_service);
 
         // Settings Service
@@ -141,15 +318,27 @@ async fn main() -> std::io::Result<()> {
             .app_data(web::Data::new(pool.clone()))
             .app_data(web::Data::from(user_active_cache.clone()))
             .app_data(vehicle_service_data)
+            .app_data(document_service_data)
             .app_data(driver_service_data)
+            .app_data(driver_score_service_data)
             .app_data(assignment_service_data)
             .app_data(maintenance_service_data)
+            .app_data(work_order_service_data)
+            .app_data(part_service_data)
+            .app_data(hos_service_data)
             .app_data(logistics_service_data)
+            .app_data(dispatch_service_data)
+            .app_data(pod_service_data)
+            .app_data(invoice_service_data)
+            .app_data(quote_service_data)
             .app_data(telemetry_service_data)
+            .app_data(geofence_service_data)
+            .app_data(odometer_service_data)
             .app_data(financial_service_data)
             .app_data(auth_service_data)
             .app_data(settings_service_data)
             .app_data(user_service_data)
+            .app_data(web::Data::from(role_service.clone()))
             .service(
                 SwaggerUi::new("/swagger-ui/{_:.*}")
                     .url("/api-docs/openapi.json", ApiDoc::openapi())
@@ -157,24 +346,35 @@ async fn main() -> std::io::Result<()> {
             .route("/health", web::get().to(health_check))
             .service(
                 web::scope("/api")
-                    .configure(routes::vehicle::config)
-                    .configure(routes::driver::config)
-                    .configure(routes::assignment::config)
-                    .configure(routes::maintenance::config)
-                    .configure(routes::logistics::config)
-                    .configure(routes::telemetry::config)
-                    .configure(routes::financial::config)
                     .configure(routes::auth::config_public)
                     .service(
                         web::scope("")
+                            // Middleware runs in reverse registration order: Auth first, then Authorize
+                            .wrap(Authorize)
                             .wrap(Auth {
                                 jwt_secret: config.jwt_secret.clone(),
                                 pool: pool.clone(),
                                 cache: user_active_cache.clone(),
+                                token_repo: token_repo.clone(),
                             })
                             .configure(routes::auth::config_protected)
+                            .configure(routes::vehicle::config)
+                            .configure(routes::driver::config)
+                            .configure(routes::assignment::config)
+                            .configure(routes::maintenance::config)
+                            .configure(routes::work_orders::config)
+                            .configure(routes::parts::config)
+                            .configure(routes::hos::config)
+                            .configure(routes::logistics::config)
+                            .configure(routes::telemetry::config)
+                            .configure(routes::geofences::config)
+                            .configure(routes::financial::config)
+                            .configure(routes::invoices::config)
+                            .configure(routes::rate_cards::config)
+                            .configure(routes::quotes::config)
                             .configure(routes::settings::config)
                             .configure(routes::users::config)
+                            .configure(routes::roles::config)
                     )
             )
     })
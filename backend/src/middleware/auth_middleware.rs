@@ -6,6 +6,7 @@ use actix_web::{
 use futures_util::future::LocalBoxFuture;
 use jsonwebtoken::{decode, DecodingKey, Validation, Algorithm};
 use crate::services::auth_service::Claims;
+use crate::repositories::postgres::token_repo::TokenRepositoryTrait;
 use std::rc::Rc;
 use sqlx::PgPool;
 use std::sync::Arc;
@@ -21,6 +22,7 @@ pub struct Auth {
     pub jwt_secret: String,
     pub pool: PgPool,
     pub cache: Arc<Cache<Uuid, UserActiveCache>>,
+    pub token_repo: Arc<dyn TokenRepositoryTrait>,
 }
 
 impl<S, B> Transform<S, ServiceRequest> for Auth
@@ -41,6 +43,7 @@ where
             jwt_secret: self.jwt_secret.clone(),
             pool: self.pool.clone(),
             cache: self.cache.clone(),
+            token_repo: self.token_repo.clone(),
         }))
     }
 }
@@ -50,6 +53,7 @@ pub struct AuthMiddleware<S> {
     jwt_secret: String,
     pool: PgPool,
     cache: Arc<Cache<Uuid, UserActiveCache>>,
+    token_repo: Arc<dyn TokenRepositoryTrait>,
 }
 
 impl<S, B> Service<ServiceRequest> for AuthMiddleware<S>
@@ -69,14 +73,14 @@ where
         let jwt_secret = self.jwt_secret.clone();
         let pool = self.pool.clone();
         let cache = self.cache.clone();
+        let token_repo = self.token_repo.clone();
 
         Box::pin(async move {
             let auth_header = req.headers().get("Authorization");
 
             if let Some(auth_header) = auth_header {
                 if let Ok(auth_str) = auth_header.to_str() {
-                    if auth_str.starts_with("Bearer ") {
-                        let token = &auth_str[7..];
+                    if let Some(token) = auth_str.strip_prefix("Bearer ") {
                         let validation = Validation::new(Algorithm::HS256);
                         let key = DecodingKey::from_secret(jwt_secret.as_bytes());
 
@@ -84,6 +88,17 @@ where
                             Ok(token_data) => {
                                 let claims = token_data.claims.clone();
                                 let user_id = claims.user_id;
+
+                                // Tokens ended by logout stay invalid until they expire
+                                match token_repo.is_access_token_revoked(claims.jti).await {
+                                    Ok(false) => {}
+                                    Ok(true) => {
+                                        return Err(actix_web::error::ErrorUnauthorized("Token has been revoked"));
+                                    }
+                                    Err(_) => {
+                                        return Err(actix_web::error::ErrorInternalServerError("Failed to verify token status"));
+                                    }
+                                }
                                 
                                 // Check cache first - assume active if cached
                                 if let Some(_cached) = cache.get(&user_id).await {
@@ -0,0 +1,99 @@
+use std::future::{ready, Ready};
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::Method,
+    web, Error, HttpMessage,
+};
+use futures_util::future::LocalBoxFuture;
+use crate::services::auth_service::Claims;
+use crate::services::role_service::{permission_for, RoleServiceTrait};
+use std::rc::Rc;
+
+/// Resources every authenticated user may access regardless of role.
+const SELF_SERVICE_RESOURCES: &[&str] = &["auth"];
+
+/// Checks the caller's role against the `roles` table before the request reaches a handler.
+///
+/// The required permission is derived from the route: the first path segment below the
+/// wrapped scope is the resource (`/vehicles/{id}` -> `vehicles`) and the HTTP method is the
+/// action (`GET`/`HEAD` -> `read`, anything else -> `write`). Must be wrapped *inside* `Auth`
+/// so `Claims` are already in the request extensions.
+pub struct Authorize;
+
+impl<S, B> Transform<S, ServiceRequest> for Authorize
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = AuthorizeMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuthorizeMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct AuthorizeMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for AuthorizeMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let srv = self.service.clone();
+
+        Box::pin(async move {
+            let role = match req.extensions().get::<Claims>() {
+                Some(claims) => claims.role,
+                None => return Err(actix_web::error::ErrorUnauthorized("Missing credentials")),
+            };
+
+            let resource = req
+                .match_info()
+                .unprocessed()
+                .trim_start_matches('/')
+                .split('/')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+
+            if SELF_SERVICE_RESOURCES.contains(&resource.as_str()) {
+                return srv.call(req).await;
+            }
+
+            let write = !matches!(*req.method(), Method::GET | Method::HEAD);
+            let permission = permission_for(&resource, write);
+
+            let role_service = match req.app_data::<web::Data<dyn RoleServiceTrait>>() {
+                Some(service) => service.clone(),
+                None => return Err(actix_web::error::ErrorInternalServerError("Role service not configured")),
+            };
+
+            match role_service.has_permission(role, &permission).await {
+                Ok(true) => srv.call(req).await,
+                Ok(false) => Err(actix_web::error::ErrorForbidden(format!(
+                    "Role {} lacks permission {}",
+                    role.as_str(),
+                    permission
+                ))),
+                Err(_) => Err(actix_web::error::ErrorInternalServerError("Failed to verify permissions")),
+            }
+        })
+    }
+}
@@ -3,15 +3,31 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Default, ToSchema)]
 #[sqlx(type_name = "assignment_status", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum AssignmentStatus {
+    #[default]
     Scheduled,
     Active,
     Completed,
     Cancelled,
 }
 
+impl AssignmentStatus {
+    pub fn is_closed(self) -> bool {
+        matches!(self, AssignmentStatus::Completed | AssignmentStatus::Cancelled)
+    }
+
+    /// Scheduled → Active → Completed; anything not yet closed can be cancelled.
+    pub fn can_transition_to(self, next: AssignmentStatus) -> bool {
+        use AssignmentStatus::*;
+        matches!(
+            (self, next),
+            (Scheduled, Active) | (Active, Completed) | (Scheduled | Active, Cancelled)
+        )
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone, ToSchema)]
 pub struct VehicleAssignment {
     pub id: Uuid,
@@ -22,6 +38,28 @@ pub struct VehicleAssignment {
     pub status: AssignmentStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// When the assignment actually became active
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub cancel_reason: Option<String>,
+}
+
+impl VehicleAssignment {
+    /// When the driver actually took the vehicle, or is planned to.
+    pub fn effective_start(&self) -> DateTime<Utc> {
+        self.started_at.unwrap_or(self.start_time)
+    }
+
+    /// The earlier of the planned end and the moment the assignment was closed. `None`
+    /// while it is open-ended.
+    pub fn effective_end(&self) -> Option<DateTime<Utc>> {
+        let closed_at = self.completed_at.or(self.cancelled_at);
+        match (self.end_time, closed_at) {
+            (Some(end), Some(closed)) => Some(end.min(closed)),
+            (end, closed) => end.or(closed),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -30,5 +68,36 @@ pub struct CreateAssignmentDto {
     pub driver_id: Uuid,
     pub start_time: DateTime<Utc>,
     pub end_time: Option<DateTime<Utc>>,
+    /// Set by the service: assignments whose start time has passed become Active right
+    /// away, later ones stay Scheduled until then. Completed and Cancelled are rejected.
+    #[serde(default)]
     pub status: AssignmentStatus,
+    /// Transport job the assignment is dispatched for
+    #[serde(default)]
+    pub job_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct CancelAssignmentDto {
+    pub reason: String,
+}
+
+/// Body of `PUT /api/assignments/{id}`.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct UpdateAssignmentDto {
+    pub status: Option<AssignmentStatus>,
+    /// Required when cancelling
+    pub reason: Option<String>,
+}
+
+/// Filters for `GET /api/assignments`, as used by the dispatch calendar.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct AssignmentQuery {
+    /// Assignments still running at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Assignments starting before this time
+    pub to: Option<DateTime<Utc>>,
+    pub vehicle_id: Option<Uuid>,
+    pub driver_id: Option<Uuid>,
+    pub status: Option<AssignmentStatus>,
 }
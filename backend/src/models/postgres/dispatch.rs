@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use utoipa::ToSchema;
+use crate::models::postgres::vehicle::VehicleType;
+use crate::models::postgres::maintenance::MaintenanceDueStatus;
+
+/// An available vehicle with its distance from the job's pickup point.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq)]
+pub struct VehicleCandidate {
+    pub vehicle_id: Uuid,
+    pub license_plate: String,
+    pub vehicle_type: VehicleType,
+    pub specs: Option<Value>,
+    /// `None` when the job has no route or the vehicle has never reported a position
+    pub distance_km: Option<f64>,
+    pub position_at: Option<DateTime<Utc>>,
+}
+
+/// A vehicle and driver pair proposed for a transport job, best first.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct DispatchSuggestion {
+    pub vehicle_id: Uuid,
+    pub license_plate: String,
+    pub vehicle_type: VehicleType,
+    pub driver_id: Uuid,
+    pub driver_name: Option<String>,
+    /// 0 to 100, higher is a better match
+    pub score: f64,
+    pub load_kg: f64,
+    /// From `specs.payload_kg`; `None` if the vehicle has no recorded payload
+    pub capacity_kg: Option<f64>,
+    pub distance_km: Option<f64>,
+    pub position_at: Option<DateTime<Utc>>,
+    /// `None` when no hours-of-service limits are configured
+    pub driver_hours_remaining: Option<f64>,
+    pub maintenance_status: MaintenanceDueStatus,
+    /// Days until the next service; negative once overdue
+    pub maintenance_days_remaining: Option<i64>,
+    /// Points that lowered the score, for the dispatcher to check
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct DispatchSuggestionQuery {
+    /// Number of pairs to return, 10 by default
+    pub limit: Option<usize>,
+}
+
+/// Body of `POST /api/logistics/jobs/{id}/dispatch`.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct AcceptDispatchDto {
+    pub vehicle_id: Uuid,
+    pub driver_id: Uuid,
+    /// Defaults to now, which starts the assignment right away
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+}
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use utoipa::ToSchema;
 
 #[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, ToSchema)]
@@ -27,6 +27,14 @@ pub struct Driver {
     pub deleted_at: Option<DateTime<Utc>>,
 }
 
+impl Driver {
+    /// Whether the license is still valid on `day`. Drivers without a recorded expiry
+    /// are not blocked.
+    pub fn license_valid_on(&self, day: NaiveDate) -> bool {
+        self.license_expiry.is_none_or(|expiry| day <= expiry)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
 pub struct DriverWithUser {
     pub id: Uuid,
@@ -48,3 +56,12 @@ pub struct CreateDriverDto {
     pub license_number: String,
     pub status: DriverStatus,
 }
+
+/// A driver's license expiry date, as checked by the daily expiry job.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq)]
+pub struct LicenseExpiry {
+    pub driver_id: Uuid,
+    pub license_number: String,
+    pub name: Option<String>,
+    pub license_expiry: NaiveDate,
+}
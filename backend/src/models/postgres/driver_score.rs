@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::ToSchema;
+
+/// Driving behaviour over one completed assignment.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq, ToSchema)]
+pub struct TripScore {
+    pub assignment_id: Uuid,
+    pub driver_id: Uuid,
+    pub vehicle_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub readings: i32,
+    pub driving_minutes: f64,
+    /// Times the speed limit in settings was exceeded, counting each stretch once
+    pub speeding_events: i32,
+    pub harsh_acceleration_events: i32,
+    pub harsh_braking_events: i32,
+    pub idle_minutes: f64,
+    /// Minutes driven between 22:00 and 05:00 in the company time zone
+    pub night_minutes: f64,
+    /// 0 to 100, higher is safer. `None` if the trip had too little telemetry.
+    pub score: Option<f64>,
+    pub scored_at: DateTime<Utc>,
+}
+
+/// Trip scores of one driver summed over a week starting on Monday (UTC).
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq, ToSchema)]
+pub struct WeeklyScore {
+    pub week_start: NaiveDate,
+    pub trips: i64,
+    pub driving_minutes: f64,
+    pub speeding_events: i64,
+    pub harsh_acceleration_events: i64,
+    pub harsh_braking_events: i64,
+    pub idle_minutes: f64,
+    pub night_minutes: f64,
+    /// Trip scores weighted by driving time
+    pub score: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct DriverScore {
+    pub driver_id: Uuid,
+    pub since: NaiveDate,
+    /// Weekly scores weighted by driving time
+    pub score: Option<f64>,
+    pub trips: i64,
+    pub weekly: Vec<WeeklyScore>,
+    pub recent_trips: Vec<TripScore>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq, ToSchema)]
+pub struct LeaderboardEntry {
+    pub rank: i64,
+    pub driver_id: Uuid,
+    pub name: Option<String>,
+    pub license_number: String,
+    pub trips: i64,
+    pub driving_minutes: f64,
+    pub score: f64,
+}
+
+/// Query string of `GET /api/drivers/{id}/score`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct DriverScoreQuery {
+    /// Weeks to include, counting the current one. Defaults to 4.
+    pub weeks: Option<i64>,
+}
+
+/// Query string of `GET /api/drivers/leaderboard`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct LeaderboardQuery {
+    /// Weeks to include, counting the current one. Defaults to 4.
+    pub weeks: Option<i64>,
+    /// Defaults to 20
+    pub limit: Option<i64>,
+}
@@ -13,6 +13,9 @@ pub struct MonthlyFinancialSummary {
     pub cost: Decimal,
     #[schema(value_type = String)]
     pub profit: Decimal,
+    /// Invoice payments received in the month
+    #[schema(value_type = String)]
+    pub collected: Decimal,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use utoipa::ToSchema;
+use crate::models::postgres::vehicle::VehicleType;
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, ToSchema)]
+#[sqlx(type_name = "geofence_kind", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GeofenceKind {
+    Polygon,
+    Circle,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct Geofence {
+    pub id: Uuid,
+    pub name: String,
+    pub kind: GeofenceKind,
+    #[schema(value_type = Object)]
+    pub area: Value, // GeoJSON Polygon; the buffered circle for CIRCLE geofences
+    #[schema(value_type = Object)]
+    pub center: Option<Value>, // GeoJSON Point, CIRCLE only
+    pub radius_m: Option<f64>,
+    pub vehicle_id: Option<Uuid>,
+    pub vehicle_type: Option<VehicleType>,
+    pub alert_on_entry: bool,
+    pub alert_on_exit: bool,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Body of both create and update requests.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct CreateGeofenceDto {
+    pub name: String,
+    pub kind: GeofenceKind,
+    /// Required for POLYGON: a GeoJSON Polygon
+    #[schema(value_type = Object)]
+    pub area: Option<Value>,
+    /// Required for CIRCLE: a GeoJSON Point
+    #[schema(value_type = Object)]
+    pub center: Option<Value>,
+    /// Required for CIRCLE
+    pub radius_m: Option<f64>,
+    /// Limit the geofence to one vehicle...
+    pub vehicle_id: Option<Uuid>,
+    /// ...or to every vehicle of a type. Applies to the whole fleet when neither is set.
+    pub vehicle_type: Option<VehicleType>,
+    #[serde(default = "default_true")]
+    pub alert_on_entry: bool,
+    #[serde(default = "default_true")]
+    pub alert_on_exit: bool,
+    #[serde(default = "default_true")]
+    pub is_active: bool,
+}
+
+/// A vehicle crossing a geofence boundary.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq)]
+pub struct GeofenceTransition {
+    pub geofence_id: Uuid,
+    pub name: String,
+    /// True when the vehicle entered, false when it left
+    pub inside: bool,
+    pub alert_on_entry: bool,
+    pub alert_on_exit: bool,
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, ToSchema)]
+#[sqlx(type_name = "duty_event_kind", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DutyEventKind {
+    ClockIn,
+    ClockOut,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq, ToSchema)]
+pub struct DutyEvent {
+    pub id: Uuid,
+    pub driver_id: Uuid,
+    pub kind: DutyEventKind,
+    pub occurred_at: DateTime<Utc>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body of the clock-in and clock-out endpoints.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct ClockDto {
+    /// Defaults to now; may be back-dated but not set in the future
+    #[serde(default)]
+    pub occurred_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DutySource {
+    Clock,
+    Assignment,
+}
+
+/// A stretch of on-duty time, from a clock-in/clock-out pair or an assignment.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct DutyPeriod {
+    pub source: DutySource,
+    pub assignment_id: Option<Uuid>,
+    pub start: DateTime<Utc>,
+    /// `None` while the driver is still clocked in or the assignment is open-ended
+    pub end: Option<DateTime<Utc>>,
+}
+
+/// On-duty time between two rest breaks.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct DutyShift {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub on_duty_hours: f64,
+    /// Off-duty hours since the previous shift, if there was one in the report range
+    pub rest_before_hours: Option<f64>,
+    /// On-duty hours in the 7 days ending with this shift
+    pub weekly_hours: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct DutyDay {
+    pub date: NaiveDate,
+    pub on_duty_hours: f64,
+}
+
+/// Filters for the HOS log. Defaults to the last 7 days; at most 31 days at once.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct HosLogQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+/// Hours-of-service log for one driver. Days are UTC calendar days.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct HosLog {
+    pub driver_id: Uuid,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub max_daily_hours: f64,
+    pub max_weekly_hours: f64,
+    pub min_rest_hours: f64,
+    pub events: Vec<DutyEvent>,
+    pub periods: Vec<DutyPeriod>,
+    pub shifts: Vec<DutyShift>,
+    pub days: Vec<DutyDay>,
+    pub total_hours: f64,
+    /// Shifts that broke the daily or weekly limit
+    pub violations: Vec<String>,
+}
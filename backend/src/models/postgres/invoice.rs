@@ -0,0 +1,241 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[sqlx(type_name = "invoice_status", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InvoiceStatus {
+    Issued,
+    PartiallyPaid,
+    Paid,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct Invoice {
+    pub id: Uuid,
+    /// Sequential, e.g. `INV-000042`
+    pub invoice_number: String,
+    pub customer_id: Uuid,
+    pub customer_name: String,
+    /// As it was when the invoice was issued
+    pub billing_address: String,
+    pub currency: String,
+    /// Percentage applied to the subtotal
+    #[schema(value_type = String)]
+    pub tax_rate: Decimal,
+    #[schema(value_type = String)]
+    pub subtotal: Decimal,
+    #[schema(value_type = String)]
+    pub tax_amount: Decimal,
+    #[schema(value_type = String)]
+    pub total: Decimal,
+    #[schema(value_type = String)]
+    pub amount_paid: Decimal,
+    #[schema(value_type = String)]
+    pub balance_due: Decimal,
+    pub status: InvoiceStatus,
+    pub issue_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub notes: Option<String>,
+    pub issued_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The charge for one transport job.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct InvoiceLine {
+    pub id: Uuid,
+    pub invoice_id: Uuid,
+    pub job_id: Uuid,
+    pub description: String,
+    #[schema(value_type = String)]
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct InvoicePayment {
+    pub id: Uuid,
+    pub invoice_id: Uuid,
+    #[schema(value_type = String)]
+    pub amount: Decimal,
+    pub paid_on: NaiveDate,
+    pub method: Option<String>,
+    pub reference: Option<String>,
+    pub recorded_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Invoice with its lines and payments, as returned by `GET /api/invoices/{id}`.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct InvoiceDetails {
+    #[serde(flatten)]
+    pub invoice: Invoice,
+    pub lines: Vec<InvoiceLine>,
+    pub payments: Vec<InvoicePayment>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct CreateInvoiceDto {
+    pub customer_id: Uuid,
+    /// Delivered jobs of the customer to bill; all of them when omitted
+    pub job_ids: Option<Vec<Uuid>>,
+    /// Defaults to today
+    pub issue_date: Option<NaiveDate>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct RecordPaymentDto {
+    #[schema(value_type = String)]
+    pub amount: Decimal,
+    /// Defaults to today
+    pub paid_on: Option<NaiveDate>,
+    pub method: Option<String>,
+    pub reference: Option<String>,
+}
+
+/// Filters for `GET /api/invoices`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct InvoiceQuery {
+    pub customer_id: Option<Uuid>,
+    pub status: Option<InvoiceStatus>,
+}
+
+/// An invoice ready to be recorded, its amounts already worked out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewInvoice {
+    pub customer_id: Uuid,
+    pub billing_address: String,
+    pub currency: String,
+    pub tax_rate: Decimal,
+    pub subtotal: Decimal,
+    pub tax_amount: Decimal,
+    pub total: Decimal,
+    pub issue_date: NaiveDate,
+    pub due_date: NaiveDate,
+    pub notes: Option<String>,
+    pub issued_by: Option<Uuid>,
+    pub lines: Vec<NewInvoiceLine>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewInvoiceLine {
+    pub job_id: Uuid,
+    pub description: String,
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewInvoicePayment {
+    pub amount: Decimal,
+    pub paid_on: NaiveDate,
+    pub method: Option<String>,
+    pub reference: Option<String>,
+    pub recorded_by: Option<Uuid>,
+}
+
+/// Unpaid balances by days since the invoice date.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, Default, PartialEq, ToSchema)]
+pub struct AgingBuckets {
+    #[schema(value_type = String)]
+    pub days_0_30: Decimal,
+    #[schema(value_type = String)]
+    pub days_31_60: Decimal,
+    #[schema(value_type = String)]
+    pub days_61_90: Decimal,
+    #[schema(value_type = String)]
+    pub days_over_90: Decimal,
+    #[schema(value_type = String)]
+    pub total: Decimal,
+}
+
+impl AgingBuckets {
+    pub fn add(&mut self, other: &AgingBuckets) {
+        self.days_0_30 += other.days_0_30;
+        self.days_31_60 += other.days_31_60;
+        self.days_61_90 += other.days_61_90;
+        self.days_over_90 += other.days_over_90;
+        self.total += other.total;
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct CustomerAging {
+    pub customer_id: Uuid,
+    pub customer_name: String,
+    #[schema(value_type = Option<String>)]
+    pub credit_limit: Option<Decimal>,
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub balances: AgingBuckets,
+}
+
+/// Accounts receivable aging, as returned by `GET /api/invoices/aging`.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct ArAgingReport {
+    pub as_of: NaiveDate,
+    pub currency: String,
+    /// Customers with a balance, largest first
+    pub customers: Vec<CustomerAging>,
+    pub totals: AgingBuckets,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct AgingQuery {
+    /// Defaults to today; payments received later are not counted
+    pub as_of: Option<NaiveDate>,
+    pub customer_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StatementEntryKind {
+    Invoice,
+    Payment,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct StatementEntry {
+    pub date: NaiveDate,
+    pub kind: StatementEntryKind,
+    pub invoice_id: Uuid,
+    pub invoice_number: String,
+    /// Payment reference, if one was given
+    pub reference: Option<String>,
+    /// Positive for invoices, negative for payments
+    #[schema(value_type = String)]
+    pub amount: Decimal,
+    /// Running balance after the entry
+    #[schema(value_type = String)]
+    pub balance: Decimal,
+}
+
+/// Invoices and payments of a customer over a period, as returned by
+/// `GET /api/invoices/statements/{customer_id}`.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct CustomerStatement {
+    pub customer_id: Uuid,
+    pub customer_name: String,
+    pub billing_address: String,
+    pub currency: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    #[schema(value_type = String)]
+    pub opening_balance: Decimal,
+    pub entries: Vec<StatementEntry>,
+    #[schema(value_type = String)]
+    pub closing_balance: Decimal,
+    /// The closing balance by age, as of `to`
+    pub aging: AgingBuckets,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct StatementQuery {
+    /// Defaults to the first day of the month of `to`
+    pub from: Option<NaiveDate>,
+    /// Defaults to today
+    pub to: Option<NaiveDate>,
+}
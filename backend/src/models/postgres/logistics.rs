@@ -5,6 +5,7 @@ use chrono::{DateTime, Utc};
 use serde_json::Value;
 use rust_decimal::Decimal;
 use utoipa::ToSchema;
+use crate::models::postgres::assignment::AssignmentStatus;
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
 pub struct Customer {
@@ -16,6 +17,11 @@ pub struct Customer {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub deleted_at: Option<DateTime<Utc>>,
+    /// Most the customer may owe across open invoices and unbilled jobs; no limit when null
+    #[schema(value_type = Option<String>)]
+    pub credit_limit: Option<Decimal>,
+    /// Refuse new jobs over the limit instead of only raising an alert
+    pub block_over_credit_limit: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -24,6 +30,20 @@ pub struct CreateCustomerDto {
     #[schema(value_type = Object)]
     pub contact_info: Value,
     pub billing_address: String,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub credit_limit: Option<Decimal>,
+    #[serde(default)]
+    pub block_over_credit_limit: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct UpdateCreditLimitDto {
+    /// Clears the limit when null
+    #[schema(value_type = Option<String>)]
+    pub credit_limit: Option<Decimal>,
+    #[serde(default)]
+    pub block_over_credit_limit: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, ToSchema)]
@@ -34,6 +54,23 @@ pub enum JobStatus {
     Delivered,
     Invoiced,
     Paid,
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Pending → InProgress → Delivered → Invoiced → Paid; a job can be cancelled until
+    /// it is delivered.
+    pub fn can_transition_to(self, next: JobStatus) -> bool {
+        use JobStatus::*;
+        matches!(
+            (self, next),
+            (Pending, InProgress)
+                | (InProgress, Delivered)
+                | (Delivered, Invoiced)
+                | (Invoiced, Paid)
+                | (Pending | InProgress, Cancelled)
+        )
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
@@ -50,11 +87,33 @@ pub struct TransportJob {
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CreateTransportJobDto {
     pub customer_id: Uuid,
+    /// Must be `Pending`; later statuses are only reached through the status workflow
     pub status: JobStatus,
     #[schema(value_type = String)]
     pub agreed_price: Decimal,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct UpdateJobStatusDto {
+    pub status: JobStatus,
+    /// Required when cancelling
+    pub reason: Option<String>,
+}
+
+/// One status change of a transport job. The first entry has no `from_status`.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct JobStatusHistory {
+    pub id: Uuid,
+    pub job_id: Uuid,
+    pub from_status: Option<JobStatus>,
+    pub to_status: JobStatus,
+    /// `None` for changes made by the system
+    pub changed_by: Option<Uuid>,
+    pub changed_by_name: Option<String>,
+    pub reason: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct Route {
     pub id: Uuid,
@@ -99,3 +158,25 @@ pub struct CreateShipmentDto {
     pub dimensions: Value,
     pub r#type: String,
 }
+
+/// One leg of a transport job: an assignment with the vehicle and driver running it.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct JobLeg {
+    pub job_id: Uuid,
+    pub leg_number: i32,
+    pub assignment_id: Uuid,
+    pub vehicle_id: Uuid,
+    pub license_plate: String,
+    pub driver_id: Uuid,
+    pub driver_name: Option<String>,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub status: AssignmentStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct AddJobLegDto {
+    pub assignment_id: Uuid,
+    /// Defaults to the leg after the job's last one
+    pub leg_number: Option<i32>,
+}
@@ -20,12 +20,15 @@ pub struct MaintenanceRecord {
     pub id: Uuid,
     pub vehicle_id: Uuid,
     pub r#type: MaintenanceType,
+    /// Excludes parts drawn from inventory, which are costed through stock movements
     #[schema(value_type = String)]
     pub cost: Decimal,
     pub date: DateTime<Utc>,
     pub provider: String,
     pub description: Option<String>,
     pub created_at: DateTime<Utc>,
+    /// Odometer reading in km at the time of service
+    pub mileage: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -37,6 +40,9 @@ pub struct CreateMaintenanceRecordDto {
     pub date: DateTime<Utc>,
     pub provider: String,
     pub description: Option<String>,
+    /// Defaults to the vehicle's current mileage
+    #[serde(default)]
+    pub mileage: Option<i32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
@@ -72,6 +78,7 @@ pub struct Alert {
     pub is_resolved: bool,
     pub created_at: DateTime<Utc>,
     pub resolved_at: Option<DateTime<Utc>>,
+    pub message: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -79,4 +86,55 @@ pub struct CreateAlertDto {
     pub entity_id: Uuid,
     pub r#type: String,
     pub severity: AlertSeverity,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// What the due calculation needs for one scheduled vehicle.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct VehicleServiceBaseline {
+    pub vehicle_id: Uuid,
+    pub license_plate: String,
+    pub vehicle_type: VehicleType,
+    pub current_mileage: i32,
+    /// Odometer reading when the vehicle was added
+    pub initial_mileage: i32,
+    pub vehicle_created_at: DateTime<Utc>,
+    /// Shortest positive intervals across the type's schedules; `None` when no schedule sets one
+    pub interval_km: Option<i32>,
+    pub interval_months: Option<i32>,
+    /// Latest preventive record, if any
+    pub last_service_date: Option<DateTime<Utc>>,
+    pub last_service_mileage: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenanceDueStatus {
+    Ok,
+    DueSoon,
+    Overdue,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct MaintenanceDue {
+    pub vehicle_id: Uuid,
+    pub license_plate: String,
+    pub vehicle_type: VehicleType,
+    pub current_mileage: i32,
+    pub last_service_date: Option<DateTime<Utc>>,
+    pub last_service_mileage: Option<i32>,
+    pub next_due_date: Option<DateTime<Utc>>,
+    /// Unknown when the last service was logged without a mileage
+    pub next_due_km: Option<i32>,
+    /// Negative once overdue
+    pub days_remaining: Option<i64>,
+    pub km_remaining: Option<i32>,
+    pub status: MaintenanceDueStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct MaintenanceDueReport {
+    pub overdue: Vec<MaintenanceDue>,
+    pub due_soon: Vec<MaintenanceDue>,
 }
@@ -7,3 +7,15 @@ pub mod maintenance;
 pub mod telemetry;
 pub mod financial;
 pub mod settings;
+pub mod token;
+pub mod geofence;
+pub mod odometer;
+pub mod work_order;
+pub mod part;
+pub mod hos;
+pub mod driver_score;
+pub mod dispatch;
+pub mod proof_of_delivery;
+pub mod vehicle_document;
+pub mod invoice;
+pub mod quote;
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::ToSchema;
+
+/// Where the odometer sync left off for a vehicle.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq)]
+pub struct OdometerCursor {
+    pub vehicle_id: Uuid,
+    pub synced_until: DateTime<Utc>,
+    pub remainder_m: f64,
+}
+
+/// Distance covered by a vehicle's readings in a window.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq)]
+pub struct DistanceMeasurement {
+    /// Sum of plausible GPS segments
+    pub distance_m: f64,
+    /// Latest `engine_status.odometer` value in the window, in km
+    pub odometer_km: Option<f64>,
+    pub last_time: Option<DateTime<Utc>>,
+}
+
+/// Result of one sync step, applied only if the cursor still reads `previous_until`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OdometerUpdate {
+    pub vehicle_id: Uuid,
+    pub previous_until: DateTime<Utc>,
+    pub synced_until: DateTime<Utc>,
+    pub add_km: i32,
+    pub remainder_m: f64,
+    /// Raises current_mileage to at least this reading
+    pub odometer_km: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct OdometerSyncReport {
+    pub vehicles_synced: usize,
+    pub kilometers_added: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DistanceSource {
+    Gps,
+    Odometer,
+}
+
+/// Per-day totals as measured in the database, before a source is picked.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq)]
+pub struct DailyDistanceRow {
+    pub day: NaiveDate,
+    pub gps_m: f64,
+    /// Sum of increases between consecutive odometer readings, when the tracker reports them
+    pub odometer_km: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct DailyDistance {
+    /// UTC calendar day
+    pub day: NaiveDate,
+    pub distance_km: f64,
+    pub source: DistanceSource,
+}
+
+/// Query string of `GET /api/telemetry/vehicle/{id}/distance`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct DailyDistanceQuery {
+    /// First day, inclusive. Defaults to six days before `to`.
+    pub from: Option<NaiveDate>,
+    /// Last day, inclusive. Defaults to today (UTC).
+    pub to: Option<NaiveDate>,
+}
@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use utoipa::ToSchema;
+use crate::models::postgres::vehicle::VehicleType;
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct Part {
+    pub id: Uuid,
+    pub sku: String,
+    pub description: String,
+    #[schema(value_type = String)]
+    pub unit_cost: Decimal,
+    #[schema(value_type = String)]
+    pub quantity_on_hand: Decimal,
+    /// A low-stock alert is raised once quantity_on_hand drops to this level
+    #[schema(value_type = String)]
+    pub reorder_threshold: Decimal,
+    /// Empty means the part fits every vehicle type
+    pub compatible_vehicle_types: Vec<VehicleType>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Part {
+    pub fn is_low_stock(&self) -> bool {
+        self.quantity_on_hand <= self.reorder_threshold
+    }
+
+    pub fn fits(&self, vehicle_type: VehicleType) -> bool {
+        self.compatible_vehicle_types.is_empty() || self.compatible_vehicle_types.contains(&vehicle_type)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct CreatePartDto {
+    pub sku: String,
+    pub description: String,
+    #[schema(value_type = String)]
+    pub unit_cost: Decimal,
+    #[serde(default)]
+    #[schema(value_type = String)]
+    pub reorder_threshold: Decimal,
+    #[serde(default)]
+    pub compatible_vehicle_types: Vec<VehicleType>,
+}
+
+/// Catalogue fields only; stock levels change through movements.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct UpdatePartDto {
+    pub description: String,
+    #[schema(value_type = String)]
+    pub unit_cost: Decimal,
+    #[schema(value_type = String)]
+    pub reorder_threshold: Decimal,
+    #[serde(default)]
+    pub compatible_vehicle_types: Vec<VehicleType>,
+}
+
+/// Filters for `GET /api/parts`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct PartQuery {
+    /// Parts that fit this vehicle type, including universal ones
+    pub vehicle_type: Option<VehicleType>,
+    #[serde(default)]
+    pub low_stock: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, ToSchema)]
+#[sqlx(type_name = "stock_movement_kind", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StockMovementKind {
+    /// Stock delivered by a supplier
+    Receipt,
+    /// Stock used on a vehicle
+    Consumption,
+    /// Unused stock coming back from a vehicle
+    Return,
+    /// Stocktake correction, in either direction
+    Adjustment,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct StockMovement {
+    pub id: Uuid,
+    pub part_id: Uuid,
+    pub kind: StockMovementKind,
+    /// Signed change to quantity_on_hand
+    #[schema(value_type = String)]
+    pub quantity: Decimal,
+    #[schema(value_type = String)]
+    pub unit_cost: Decimal,
+    pub vehicle_id: Option<Uuid>,
+    pub work_order_id: Option<Uuid>,
+    pub maintenance_record_id: Option<Uuid>,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body of `POST /api/parts/{id}/movements`. `quantity` is how many units moved; only
+/// adjustments may be negative.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct CreateStockMovementDto {
+    pub kind: StockMovementKind,
+    #[schema(value_type = String)]
+    pub quantity: Decimal,
+    /// Purchase price for receipts; becomes the part's unit cost. Defaults to the current one.
+    #[schema(value_type = Option<String>)]
+    pub unit_cost: Option<Decimal>,
+    /// Required for consumptions and returns
+    pub vehicle_id: Option<Uuid>,
+    pub maintenance_record_id: Option<Uuid>,
+    pub note: Option<String>,
+}
+
+/// A validated movement with its signed quantity, ready to be applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewStockMovement {
+    pub part_id: Uuid,
+    pub kind: StockMovementKind,
+    pub quantity: Decimal,
+    pub unit_cost: Option<Decimal>,
+    pub vehicle_id: Option<Uuid>,
+    pub work_order_id: Option<Uuid>,
+    pub maintenance_record_id: Option<Uuid>,
+    pub note: Option<String>,
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, ToSchema)]
+#[sqlx(type_name = "pod_file_kind", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PodFileKind {
+    Signature,
+    Photo,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct ProofOfDelivery {
+    pub id: Uuid,
+    pub job_id: Uuid,
+    pub recipient_name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub notes: Option<String>,
+    pub captured_by: Option<Uuid>,
+    pub captured_by_name: Option<String>,
+    pub captured_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct ProofOfDeliveryFile {
+    pub id: Uuid,
+    pub pod_id: Uuid,
+    pub kind: PodFileKind,
+    /// Internal location of the bytes; clients fetch files through the API
+    #[serde(skip)]
+    pub storage_key: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Proof of delivery with its signature and photos, as returned by
+/// `GET /api/logistics/jobs/{id}/proof-of-delivery`. File bytes are served separately.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct ProofOfDeliveryDetails {
+    #[serde(flatten)]
+    pub proof: ProofOfDelivery,
+    pub files: Vec<ProofOfDeliveryFile>,
+}
+
+/// Images are base64, optionally as `data:` URLs; PNG, JPEG and WebP are accepted.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct CreateProofOfDeliveryDto {
+    pub recipient_name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub notes: Option<String>,
+    pub signature: String,
+    #[serde(default)]
+    pub photos: Vec<String>,
+}
+
+/// A file already written to storage, to be recorded with the proof of delivery.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewProofOfDeliveryFile {
+    pub kind: PodFileKind,
+    pub storage_key: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+}
@@ -0,0 +1,150 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::Value;
+use rust_decimal::Decimal;
+use utoipa::ToSchema;
+use crate::models::postgres::vehicle::VehicleType;
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq, ToSchema)]
+pub struct RateCard {
+    pub id: Uuid,
+    pub name: String,
+    /// Card used for quotes without a vehicle type, or of a type with no card, when null
+    pub vehicle_type: Option<VehicleType>,
+    #[schema(value_type = String)]
+    pub per_km: Decimal,
+    #[schema(value_type = String)]
+    pub per_kg: Decimal,
+    #[schema(value_type = String)]
+    pub minimum_charge: Decimal,
+    pub is_active: bool,
+    #[sqlx(skip)]
+    pub surcharges: Vec<RateCardSurcharge>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Percentage added to the distance and weight charges when a shipment of the type is
+/// carried, e.g. `hazmat`.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq, ToSchema)]
+pub struct RateCardSurcharge {
+    /// Matched against `Shipment.type`, ignoring case
+    pub shipment_type: String,
+    #[schema(value_type = String)]
+    pub percent: Decimal,
+}
+
+/// Body of `POST /api/rate-cards` and `PUT /api/rate-cards/{id}`.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct RateCardDto {
+    pub name: String,
+    pub vehicle_type: Option<VehicleType>,
+    #[schema(value_type = String)]
+    pub per_km: Decimal,
+    #[schema(value_type = String)]
+    pub per_kg: Decimal,
+    #[serde(default)]
+    #[schema(value_type = String)]
+    pub minimum_charge: Decimal,
+    #[serde(default = "default_active")]
+    pub is_active: bool,
+    /// Replaces the card's surcharges
+    #[serde(default)]
+    pub surcharges: Vec<RateCardSurcharge>,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct Quote {
+    pub id: Uuid,
+    pub customer_id: Uuid,
+    pub rate_card_id: Option<Uuid>,
+    pub vehicle_type: Option<VehicleType>,
+    #[schema(value_type = Object)]
+    pub origin: Value, // GeoJSON Point
+    #[schema(value_type = Object)]
+    pub destination: Value, // GeoJSON Point
+    #[schema(value_type = Object)]
+    pub waypoints: Option<Value>, // GeoJSON LineString
+    /// Shipments created with the job, as `[{weight, dimensions, type}]`
+    #[schema(value_type = Object)]
+    pub shipments: Value,
+    #[schema(value_type = String)]
+    pub distance_km: Decimal,
+    #[schema(value_type = String)]
+    pub weight_kg: Decimal,
+    #[schema(value_type = String)]
+    pub distance_charge: Decimal,
+    #[schema(value_type = String)]
+    pub weight_charge: Decimal,
+    #[schema(value_type = String)]
+    pub surcharge: Decimal,
+    /// What the job is charged if the quote is converted; never below the card's minimum
+    #[schema(value_type = String)]
+    pub price: Decimal,
+    pub currency: String,
+    pub valid_until: NaiveDate,
+    /// The job the quote was converted into
+    pub job_id: Option<Uuid>,
+    pub converted_at: Option<DateTime<Utc>>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct QuoteShipmentDto {
+    pub weight: f64,
+    #[schema(value_type = Object)]
+    pub dimensions: Value,
+    pub r#type: String,
+}
+
+/// Body of `POST /api/quotes`.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct CreateQuoteDto {
+    pub customer_id: Uuid,
+    /// Picks the rate card; the card without a vehicle type is used when omitted
+    pub vehicle_type: Option<VehicleType>,
+    #[schema(value_type = Object)]
+    pub origin: Value,
+    #[schema(value_type = Object)]
+    pub destination: Value,
+    #[schema(value_type = Object)]
+    pub waypoints: Option<Value>,
+    #[serde(default)]
+    pub shipments: Vec<QuoteShipmentDto>,
+    /// Defaults to 30 days from today
+    pub valid_until: Option<NaiveDate>,
+}
+
+/// Filters for `GET /api/quotes`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct QuoteQuery {
+    pub customer_id: Option<Uuid>,
+}
+
+/// A priced quote ready to be recorded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewQuote {
+    pub customer_id: Uuid,
+    pub rate_card_id: Uuid,
+    pub vehicle_type: Option<VehicleType>,
+    pub origin: Value,
+    pub destination: Value,
+    pub waypoints: Option<Value>,
+    pub shipments: Value,
+    pub distance_km: Decimal,
+    pub weight_kg: Decimal,
+    pub distance_charge: Decimal,
+    pub weight_charge: Decimal,
+    pub surcharge: Decimal,
+    pub price: Decimal,
+    pub currency: String,
+    pub valid_until: NaiveDate,
+    pub created_by: Option<Uuid>,
+}
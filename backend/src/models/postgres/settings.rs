@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
 use utoipa::ToSchema;
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
@@ -21,10 +22,78 @@ pub struct AppSettings {
     pub notify_sms: bool,
     pub notify_desktop: bool,
     pub notify_weekly_summary: bool,
+    /// Months of telemetry kept before old partitions are removed; 0 keeps everything
+    pub telemetry_retention_months: i32,
+    /// Detach expired partitions into standalone tables instead of dropping them
+    pub telemetry_archive_expired: bool,
+    /// Readings above this speed raise a speeding alert; 0 disables the rule
+    pub speed_limit_kmh: f64,
+    /// Drop in fuel_level percentage points between consecutive readings treated as possible theft
+    pub fuel_drop_threshold: f64,
+    /// Minutes a vehicle may idle with the engine running before an alert is raised
+    pub idle_alert_minutes: i32,
+    /// Minutes without a report before a tracker is considered offline
+    pub tracker_stale_minutes: i32,
+    /// Days before a driver's license expires that an alert is raised
+    pub license_expiry_warning_days: i32,
+    /// On-duty hours allowed between two rest breaks; 0 disables the rule
+    pub hos_max_daily_hours: f64,
+    /// On-duty hours allowed in any 7 consecutive days; 0 disables the rule
+    pub hos_max_weekly_hours: f64,
+    /// Off-duty hours that count as a rest break and start a new duty day
+    pub hos_min_rest_hours: f64,
+    /// Days before a vehicle document expires that an alert is raised
+    pub document_expiry_warning_days: i32,
+    /// Tax percentage added to new invoices
+    #[schema(value_type = String)]
+    pub tax_rate: Decimal,
+    /// Days after issue that an invoice is due
+    pub payment_terms_days: i32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// The column defaults from the migrations with placeholder company details, so tests only
+/// spell out the settings they depend on.
+#[cfg(test)]
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            id: 1,
+            company_name: "Fleet Co".into(),
+            contact_email: "ops@example.com".into(),
+            phone_number: "555-0100".into(),
+            time_zone: "UTC".into(),
+            address: "1 Depot Rd".into(),
+            distance_unit: "km".into(),
+            currency: "USD".into(),
+            date_format: "YYYY-MM-DD".into(),
+            notify_maintenance_alerts: true,
+            notify_license_expiry: true,
+            notify_service_completion: true,
+            notify_payment: true,
+            notify_sms: false,
+            notify_desktop: false,
+            notify_weekly_summary: true,
+            telemetry_retention_months: 12,
+            telemetry_archive_expired: false,
+            speed_limit_kmh: 110.0,
+            fuel_drop_threshold: 15.0,
+            idle_alert_minutes: 20,
+            tracker_stale_minutes: 30,
+            license_expiry_warning_days: 30,
+            hos_max_daily_hours: 11.0,
+            hos_max_weekly_hours: 60.0,
+            hos_min_rest_hours: 10.0,
+            document_expiry_warning_days: 30,
+            tax_rate: Decimal::ZERO,
+            payment_terms_days: 30,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UpdateAppSettingsDto {
     pub company_name: String,
@@ -42,4 +111,32 @@ pub struct UpdateAppSettingsDto {
     pub notify_sms: bool,
     pub notify_desktop: bool,
     pub notify_weekly_summary: bool,
+    /// Left unchanged when omitted
+    #[serde(default)]
+    pub telemetry_retention_months: Option<i32>,
+    #[serde(default)]
+    pub telemetry_archive_expired: Option<bool>,
+    #[serde(default)]
+    pub speed_limit_kmh: Option<f64>,
+    #[serde(default)]
+    pub fuel_drop_threshold: Option<f64>,
+    #[serde(default)]
+    pub idle_alert_minutes: Option<i32>,
+    #[serde(default)]
+    pub tracker_stale_minutes: Option<i32>,
+    #[serde(default)]
+    pub license_expiry_warning_days: Option<i32>,
+    #[serde(default)]
+    pub hos_max_daily_hours: Option<f64>,
+    #[serde(default)]
+    pub hos_max_weekly_hours: Option<f64>,
+    #[serde(default)]
+    pub hos_min_rest_hours: Option<f64>,
+    #[serde(default)]
+    pub document_expiry_warning_days: Option<i32>,
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub tax_rate: Option<Decimal>,
+    #[serde(default)]
+    pub payment_terms_days: Option<i32>,
 }
@@ -28,3 +28,80 @@ pub struct CreateVehicleTelemetryDto {
     #[schema(value_type = Object)]
     pub engine_status: Value,
 }
+
+/// A batch entry that was not stored, identified by its position in the request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
+pub struct TelemetryRejection {
+    pub index: usize,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct BulkTelemetryResult {
+    pub received: usize,
+    pub inserted: usize,
+    /// Valid readings skipped because (vehicle_id, time) was already stored or repeated in the batch
+    pub duplicates: usize,
+    pub rejected: Vec<TelemetryRejection>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TelemetryFormat {
+    Json,
+    Geojson,
+}
+
+/// Query string of `GET /api/telemetry/vehicle/{id}`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct TelemetryHistoryQuery {
+    /// Defaults to 24 hours before `to`
+    pub from: Option<DateTime<Utc>>,
+    /// Defaults to now
+    pub to: Option<DateTime<Utc>>,
+    /// Averaging window such as `30s`, `5m`, `1h` or `1d`; raw points when omitted
+    pub bucket: Option<String>,
+    pub format: Option<TelemetryFormat>,
+}
+
+/// Readings averaged over one time bucket.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct TelemetryBucket {
+    /// Start of the bucket
+    pub time: DateTime<Utc>,
+    pub vehicle_id: Uuid,
+    /// Centroid of the positions in the bucket, as a GeoJSON Point
+    #[schema(value_type = Object)]
+    pub location: Value,
+    pub avg_speed: f64,
+    pub max_speed: f64,
+    pub avg_fuel_level: f64,
+    pub samples: i64,
+}
+
+/// Trip geometry built by PostGIS from the stored positions.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct TelemetryTrack {
+    /// GeoJSON LineString, absent when fewer than two positions were found
+    pub geometry: Option<Value>,
+    pub point_count: i64,
+    pub started_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[serde(untagged)]
+pub enum TelemetryHistory {
+    Points(Vec<VehicleTelemetry>),
+    Buckets(Vec<TelemetryBucket>),
+    /// GeoJSON FeatureCollection
+    #[schema(value_type = Object)]
+    Track(Value),
+}
+
+/// Last report from a vehicle whose tracker has gone quiet.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, PartialEq)]
+pub struct TrackerLastSeen {
+    pub vehicle_id: Uuid,
+    pub last_seen: DateTime<Utc>,
+}
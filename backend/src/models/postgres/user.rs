@@ -5,7 +5,7 @@ use chrono::{DateTime, Utc};
 use serde_json::Value;
 use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq, Hash, ToSchema)]
 #[sqlx(type_name = "user_role", rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum UserRole {
     Admin,
@@ -14,6 +14,18 @@ pub enum UserRole {
     Mechanic,
 }
 
+impl UserRole {
+    /// Name of the matching row in the `roles` table.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserRole::Admin => "ADMIN",
+            UserRole::Manager => "MANAGER",
+            UserRole::Driver => "DRIVER",
+            UserRole::Mechanic => "MECHANIC",
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct User {
     pub id: Uuid,
@@ -28,11 +40,17 @@ pub struct User {
     pub deleted_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, FromRow, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
 pub struct Role {
     pub id: Uuid,
     pub name: String,
-    pub permissions: Value, // JSONB
+    #[schema(value_type = Vec<String>)]
+    pub permissions: Value, // JSONB array of "resource:action" strings
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct UpdateRolePermissionsDto {
+    pub permissions: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq, Hash, ToSchema)]
+#[sqlx(type_name = "vehicle_document_type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum VehicleDocumentType {
+    Registration,
+    Insurance,
+    Inspection,
+    Permit,
+    Other,
+}
+
+impl VehicleDocumentType {
+    /// Vehicles cannot be assigned while one of these is expired.
+    pub fn is_mandatory(self) -> bool {
+        matches!(
+            self,
+            VehicleDocumentType::Registration | VehicleDocumentType::Insurance | VehicleDocumentType::Inspection
+        )
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            VehicleDocumentType::Registration => "Registration",
+            VehicleDocumentType::Insurance => "Insurance",
+            VehicleDocumentType::Inspection => "Inspection",
+            VehicleDocumentType::Permit => "Permit",
+            VehicleDocumentType::Other => "Document",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct VehicleDocument {
+    pub id: Uuid,
+    pub vehicle_id: Uuid,
+    pub r#type: VehicleDocumentType,
+    pub document_number: Option<String>,
+    pub issue_date: Option<NaiveDate>,
+    /// Empty for documents that do not expire
+    pub expiry_date: Option<NaiveDate>,
+    pub notes: Option<String>,
+    /// Name of the uploaded file; empty when no copy was uploaded
+    pub file_name: Option<String>,
+    pub content_type: Option<String>,
+    pub size_bytes: Option<i64>,
+    /// Internal location of the bytes; clients fetch the file through the API
+    #[serde(skip)]
+    pub storage_key: Option<String>,
+    pub uploaded_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl VehicleDocument {
+    pub fn valid_on(&self, date: NaiveDate) -> bool {
+        self.expiry_date.is_none_or(|expiry| expiry >= date)
+    }
+}
+
+/// File content is base64, optionally as a `data:` URL; PDF, PNG, JPEG and WebP are accepted.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct CreateVehicleDocumentDto {
+    pub r#type: VehicleDocumentType,
+    pub document_number: Option<String>,
+    pub issue_date: Option<NaiveDate>,
+    pub expiry_date: Option<NaiveDate>,
+    pub notes: Option<String>,
+    pub file_name: Option<String>,
+    pub file: Option<String>,
+}
+
+/// A document ready to be recorded, its file (if any) already in storage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewVehicleDocument {
+    pub vehicle_id: Uuid,
+    pub r#type: VehicleDocumentType,
+    pub document_number: Option<String>,
+    pub issue_date: Option<NaiveDate>,
+    pub expiry_date: Option<NaiveDate>,
+    pub notes: Option<String>,
+    pub file_name: Option<String>,
+    pub content_type: Option<String>,
+    pub size_bytes: Option<i64>,
+    pub storage_key: Option<String>,
+    pub uploaded_by: Option<Uuid>,
+}
+
+/// The document in force for one vehicle and type, for expiry alerts.
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct DocumentExpiry {
+    pub document_id: Uuid,
+    pub vehicle_id: Uuid,
+    pub license_plate: String,
+    pub r#type: VehicleDocumentType,
+    pub document_number: Option<String>,
+    pub expiry_date: NaiveDate,
+}
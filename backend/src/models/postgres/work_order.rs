@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use utoipa::ToSchema;
+use crate::models::postgres::maintenance::MaintenanceType;
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, ToSchema)]
+#[sqlx(type_name = "work_order_status", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WorkOrderStatus {
+    Open,
+    InProgress,
+    AwaitingParts,
+    Completed,
+    Cancelled,
+}
+
+impl WorkOrderStatus {
+    pub fn is_closed(self) -> bool {
+        matches!(self, WorkOrderStatus::Completed | WorkOrderStatus::Cancelled)
+    }
+
+    /// Open → InProgress ⇄ AwaitingParts → Completed; anything not yet closed can be cancelled.
+    pub fn can_transition_to(self, next: WorkOrderStatus) -> bool {
+        use WorkOrderStatus::*;
+        matches!(
+            (self, next),
+            (Open, InProgress)
+                | (InProgress, AwaitingParts)
+                | (AwaitingParts, InProgress)
+                | (InProgress, Completed)
+                | (Open | InProgress | AwaitingParts, Cancelled)
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, ToSchema)]
+#[sqlx(type_name = "work_order_item_kind", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WorkOrderItemKind {
+    Part,
+    Labor,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct WorkOrder {
+    pub id: Uuid,
+    pub vehicle_id: Uuid,
+    /// User with the MECHANIC role
+    pub mechanic_id: Option<Uuid>,
+    pub r#type: MaintenanceType,
+    pub description: String,
+    /// Outside shop doing the work; in-house when empty
+    pub provider: Option<String>,
+    pub status: WorkOrderStatus,
+    pub maintenance_record_id: Option<Uuid>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone, ToSchema)]
+pub struct WorkOrderItem {
+    pub id: Uuid,
+    pub work_order_id: Uuid,
+    pub kind: WorkOrderItemKind,
+    /// Inventory part the line was drawn from
+    pub part_id: Option<Uuid>,
+    pub description: String,
+    #[schema(value_type = String)]
+    pub quantity: Decimal,
+    #[schema(value_type = String)]
+    pub unit_cost: Decimal,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A work order with its line items, as returned by `GET /api/work-orders/{id}`.
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct WorkOrderDetails {
+    #[serde(flatten)]
+    pub work_order: WorkOrder,
+    pub items: Vec<WorkOrderItem>,
+    #[schema(value_type = String)]
+    pub total_cost: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct CreateWorkOrderDto {
+    pub vehicle_id: Uuid,
+    pub r#type: MaintenanceType,
+    pub description: String,
+    pub mechanic_id: Option<Uuid>,
+    pub provider: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct UpdateWorkOrderStatusDto {
+    pub status: WorkOrderStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct AssignMechanicDto {
+    pub mechanic_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct CreateWorkOrderItemDto {
+    pub kind: WorkOrderItemKind,
+    /// Takes the quantity out of stock at the part's unit cost
+    #[serde(default)]
+    pub part_id: Option<Uuid>,
+    /// Defaults to the part's description for inventory lines
+    #[serde(default)]
+    pub description: String,
+    #[schema(value_type = String)]
+    pub quantity: Decimal,
+    /// Required unless `part_id` is set
+    #[serde(default)]
+    #[schema(value_type = Option<String>)]
+    pub unit_cost: Option<Decimal>,
+}
+
+/// Filters for `GET /api/work-orders`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, ToSchema)]
+pub struct WorkOrderQuery {
+    pub status: Option<WorkOrderStatus>,
+    pub vehicle_id: Option<Uuid>,
+    pub mechanic_id: Option<Uuid>,
+}
@@ -1,9 +1,13 @@
 pub mod postgres;
 pub mod redis;
+pub mod storage;
 
 pub use postgres::vehicle_repo::VehicleRepositoryTrait;
 pub use postgres::driver_repo::DriverRepositoryTrait;
 pub use postgres::user_repo::UserRepositoryTrait;
+pub use postgres::role_repo::RoleRepositoryTrait;
+pub use postgres::token_repo::TokenRepositoryTrait;
+pub use postgres::geofence_repo::GeofenceRepositoryTrait;
 pub use postgres::assignment_repo::AssignmentRepositoryTrait;
 pub use postgres::telemetry_repo::TelemetryRepositoryTrait;
 pub use postgres::logistics_repo::{
@@ -12,3 +16,4 @@ pub use postgres::logistics_repo::{
 pub use postgres::maintenance_repo::{
     MaintenanceRecordRepositoryTrait, MaintenanceScheduleRepositoryTrait, AlertRepositoryTrait
 };
+pub use storage::StorageTrait;
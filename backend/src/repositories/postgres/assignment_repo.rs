@@ -1,6 +1,7 @@
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 use uuid::Uuid;
-use crate::models::postgres::assignment::{VehicleAssignment, CreateAssignmentDto, AssignmentStatus};
+use chrono::{DateTime, Utc};
+use crate::models::postgres::assignment::{VehicleAssignment, CreateAssignmentDto, AssignmentStatus, AssignmentQuery};
 use crate::error::AppError;
 use async_trait::async_trait;
 
@@ -8,10 +9,20 @@ use async_trait::async_trait;
 #[async_trait]
 pub trait AssignmentRepositoryTrait: Send + Sync {
     async fn create(&self, dto: CreateAssignmentDto) -> Result<VehicleAssignment, AppError>;
-    async fn find_all(&self) -> Result<Vec<VehicleAssignment>, AppError>;
+    async fn find_all(&self, query: AssignmentQuery) -> Result<Vec<VehicleAssignment>, AppError>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<VehicleAssignment>, AppError>;
     async fn find_by_driver_id(&self, driver_id: Uuid) -> Result<Vec<VehicleAssignment>, AppError>;
-    async fn update_status(&self, id: Uuid, status: AssignmentStatus) -> Result<VehicleAssignment, AppError>;
+    async fn find_active_by_vehicle_id(&self, vehicle_id: Uuid) -> Result<Option<VehicleAssignment>, AppError>;
+    /// Assignments of the driver that overlap `[from, to)`, leaving out those cancelled
+    /// before they started.
+    async fn find_by_driver_between(&self, driver_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<VehicleAssignment>, AppError>;
+    /// Scheduled assignments whose start time is at or before `now`.
+    async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<VehicleAssignment>, AppError>;
+    /// Moves the assignment from `from` to `to`, recording when it started or completed.
+    /// Returns `None` if it is no longer in `from`.
+    async fn update_status(&self, id: Uuid, from: AssignmentStatus, to: AssignmentStatus) -> Result<Option<VehicleAssignment>, AppError>;
+    /// Returns `None` if the assignment is no longer in `from`.
+    async fn cancel(&self, id: Uuid, from: AssignmentStatus, reason: String) -> Result<Option<VehicleAssignment>, AppError>;
 }
 
 pub struct AssignmentRepository {
@@ -22,18 +33,17 @@ impl AssignmentRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
-}
 
-#[async_trait]
-impl AssignmentRepositoryTrait for AssignmentRepository {
-    async fn create(&self, dto: CreateAssignmentDto) -> Result<VehicleAssignment, AppError> {
+    // The writes below also run inside a unit of work, so they take any executor
+
+    pub(crate) async fn insert<'e>(executor: impl PgExecutor<'e>, dto: CreateAssignmentDto) -> Result<VehicleAssignment, AppError> {
         let id = Uuid::new_v4();
         let assignment = sqlx::query_as::<_, VehicleAssignment>(
             r#"
             INSERT INTO vehicle_assignments (
-                id, vehicle_id, driver_id, start_time, end_time, status, created_at, updated_at
+                id, vehicle_id, driver_id, start_time, end_time, status, started_at, created_at, updated_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, NOW(), NOW())
+            VALUES ($1, $2, $3, $4, $5, $6, CASE WHEN $6 = 'ACTIVE' THEN NOW() END, NOW(), NOW())
             RETURNING *
             "#
         )
@@ -43,20 +53,95 @@ impl AssignmentRepositoryTrait for AssignmentRepository {
         .bind(dto.start_time)
         .bind(dto.end_time)
         .bind(dto.status)
-        .fetch_one(&self.pool)
+        .fetch_one(executor)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(assignment)
+    }
+
+    pub(crate) async fn transition<'e>(
+        executor: impl PgExecutor<'e>,
+        id: Uuid,
+        from: AssignmentStatus,
+        to: AssignmentStatus,
+    ) -> Result<Option<VehicleAssignment>, AppError> {
+        let assignment = sqlx::query_as::<_, VehicleAssignment>(
+            r#"
+            UPDATE vehicle_assignments
+            SET status = $3,
+                started_at = CASE WHEN $3 = 'ACTIVE' THEN NOW() ELSE started_at END,
+                completed_at = CASE WHEN $3 = 'COMPLETED' THEN NOW() ELSE completed_at END,
+                updated_at = NOW()
+            WHERE id = $1 AND status = $2
+            RETURNING *
+            "#
+        )
+        .bind(id)
+        .bind(from)
+        .bind(to)
+        .fetch_optional(executor)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(assignment)
     }
 
-    async fn find_all(&self) -> Result<Vec<VehicleAssignment>, AppError> {
+    pub(crate) async fn cancel_from<'e>(
+        executor: impl PgExecutor<'e>,
+        id: Uuid,
+        from: AssignmentStatus,
+        reason: String,
+    ) -> Result<Option<VehicleAssignment>, AppError> {
+        let assignment = sqlx::query_as::<_, VehicleAssignment>(
+            r#"
+            UPDATE vehicle_assignments
+            SET status = 'CANCELLED',
+                cancelled_at = NOW(),
+                cancel_reason = $3,
+                updated_at = NOW()
+            WHERE id = $1 AND status = $2
+            RETURNING *
+            "#
+        )
+        .bind(id)
+        .bind(from)
+        .bind(reason)
+        .fetch_optional(executor)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(assignment)
+    }
+}
+
+#[async_trait]
+impl AssignmentRepositoryTrait for AssignmentRepository {
+    async fn create(&self, dto: CreateAssignmentDto) -> Result<VehicleAssignment, AppError> {
+        Self::insert(&self.pool, dto).await
+    }
+
+    async fn find_all(&self, query: AssignmentQuery) -> Result<Vec<VehicleAssignment>, AppError> {
         let assignments = sqlx::query_as::<_, VehicleAssignment>(
-            "SELECT * FROM vehicle_assignments"
+            r#"
+            SELECT * FROM vehicle_assignments
+            WHERE ($1::timestamptz IS NULL
+                   OR COALESCE(LEAST(end_time, completed_at, cancelled_at), 'infinity') > $1)
+              AND ($2::timestamptz IS NULL OR start_time < $2)
+              AND ($3::uuid IS NULL OR vehicle_id = $3)
+              AND ($4::uuid IS NULL OR driver_id = $4)
+              AND ($5::assignment_status IS NULL OR status = $5)
+            ORDER BY start_time
+            "#
         )
+        .bind(query.from)
+        .bind(query.to)
+        .bind(query.vehicle_id)
+        .bind(query.driver_id)
+        .bind(query.status)
         .fetch_all(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(assignments)
     }
@@ -68,7 +153,7 @@ impl AssignmentRepositoryTrait for AssignmentRepository {
         .bind(id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(assignment)
     }
@@ -80,26 +165,71 @@ impl AssignmentRepositoryTrait for AssignmentRepository {
         .bind(driver_id)
         .fetch_all(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(assignments)
     }
 
-    async fn update_status(&self, id: Uuid, status: AssignmentStatus) -> Result<VehicleAssignment, AppError> {
+    async fn find_active_by_vehicle_id(&self, vehicle_id: Uuid) -> Result<Option<VehicleAssignment>, AppError> {
         let assignment = sqlx::query_as::<_, VehicleAssignment>(
             r#"
-            UPDATE vehicle_assignments 
-            SET status = $1, updated_at = NOW()
-            WHERE id = $2
-            RETURNING *
+            SELECT * FROM vehicle_assignments
+            WHERE vehicle_id = $1 AND status = 'ACTIVE'
+            ORDER BY start_time DESC
+            LIMIT 1
             "#
         )
-        .bind(status)
-        .bind(id)
-        .fetch_one(&self.pool)
+        .bind(vehicle_id)
+        .fetch_optional(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(assignment)
     }
+
+    async fn find_by_driver_between(&self, driver_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<VehicleAssignment>, AppError> {
+        // Mirrors VehicleAssignment::effective_start and effective_end
+        let assignments = sqlx::query_as::<_, VehicleAssignment>(
+            r#"
+            SELECT * FROM vehicle_assignments
+            WHERE driver_id = $1
+              AND (status <> 'CANCELLED' OR started_at IS NOT NULL)
+              AND COALESCE(started_at, start_time) < $3
+              AND COALESCE(LEAST(end_time, completed_at, cancelled_at), 'infinity') > $2
+            ORDER BY start_time
+            "#
+        )
+        .bind(driver_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(assignments)
+    }
+
+    async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<VehicleAssignment>, AppError> {
+        let assignments = sqlx::query_as::<_, VehicleAssignment>(
+            r#"
+            SELECT * FROM vehicle_assignments
+            WHERE status = 'SCHEDULED' AND start_time <= $1
+            ORDER BY start_time
+            "#
+        )
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(assignments)
+    }
+
+    async fn update_status(&self, id: Uuid, from: AssignmentStatus, to: AssignmentStatus) -> Result<Option<VehicleAssignment>, AppError> {
+        Self::transition(&self.pool, id, from, to).await
+    }
+
+    async fn cancel(&self, id: Uuid, from: AssignmentStatus, reason: String) -> Result<Option<VehicleAssignment>, AppError> {
+        Self::cancel_from(&self.pool, id, from, reason).await
+    }
 }
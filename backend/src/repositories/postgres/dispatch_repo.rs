@@ -0,0 +1,55 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::models::postgres::dispatch::VehicleCandidate;
+use crate::error::AppError;
+use async_trait::async_trait;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait DispatchRepositoryTrait: Send + Sync {
+    /// Available vehicles, with the distance from their latest reported position to the
+    /// origin of the job's route.
+    async fn find_vehicle_candidates(&self, job_id: Uuid) -> Result<Vec<VehicleCandidate>, AppError>;
+}
+
+pub struct DispatchRepository {
+    pool: PgPool,
+}
+
+impl DispatchRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DispatchRepositoryTrait for DispatchRepository {
+    async fn find_vehicle_candidates(&self, job_id: Uuid) -> Result<Vec<VehicleCandidate>, AppError> {
+        let candidates = sqlx::query_as::<_, VehicleCandidate>(
+            r#"
+            SELECT
+                v.id AS vehicle_id,
+                v.license_plate,
+                v.type AS vehicle_type,
+                v.specs,
+                ST_DistanceSphere(t.location, r.origin) / 1000.0 AS distance_km,
+                t.time AS position_at
+            FROM vehicles v
+            LEFT JOIN LATERAL (SELECT origin FROM routes WHERE job_id = $1 LIMIT 1) r ON TRUE
+            LEFT JOIN LATERAL (
+                SELECT location, time FROM vehicle_telemetry
+                WHERE vehicle_id = v.id
+                ORDER BY time DESC
+                LIMIT 1
+            ) t ON TRUE
+            WHERE v.deleted_at IS NULL AND v.status = 'AVAILABLE'
+            "#
+        )
+        .bind(job_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(candidates)
+    }
+}
@@ -1,6 +1,6 @@
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 use uuid::Uuid;
-use crate::models::postgres::driver::{Driver, DriverWithUser, CreateDriverDto, DriverStatus};
+use crate::models::postgres::driver::{Driver, DriverWithUser, CreateDriverDto, DriverStatus, LicenseExpiry};
 use crate::error::AppError;
 use async_trait::async_trait;
 
@@ -14,6 +14,8 @@ pub trait DriverRepositoryTrait: Send + Sync {
     async fn find_by_id_with_user(&self, id: Uuid) -> Result<Option<DriverWithUser>, AppError>;
     async fn update_status(&self, id: Uuid, status: DriverStatus) -> Result<Driver, AppError>;
     async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+    /// Every active driver with a recorded license expiry date.
+    async fn find_license_expiries(&self) -> Result<Vec<LicenseExpiry>, AppError>;
 }
 
 pub struct DriverRepository {
@@ -24,6 +26,25 @@ impl DriverRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// Also used inside a unit of work.
+    pub(crate) async fn set_status<'e>(executor: impl PgExecutor<'e>, id: Uuid, status: DriverStatus) -> Result<Driver, AppError> {
+        let driver = sqlx::query_as::<_, Driver>(
+            r#"
+            UPDATE drivers 
+            SET status = $1, updated_at = NOW()
+            WHERE id = $2 AND deleted_at IS NULL
+            RETURNING *
+            "#
+        )
+        .bind(status)
+        .bind(id)
+        .fetch_one(executor)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(driver)
+    }
 }
 
 #[async_trait]
@@ -45,7 +66,7 @@ impl DriverRepositoryTrait for DriverRepository {
         .bind(dto.status)
         .fetch_one(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(driver)
     }
@@ -56,7 +77,7 @@ impl DriverRepositoryTrait for DriverRepository {
         )
         .fetch_all(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(drivers)
     }
@@ -83,7 +104,7 @@ impl DriverRepositoryTrait for DriverRepository {
         )
         .fetch_all(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(drivers)
     }
@@ -95,7 +116,7 @@ impl DriverRepositoryTrait for DriverRepository {
         .bind(id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(driver)
     }
@@ -123,27 +144,13 @@ impl DriverRepositoryTrait for DriverRepository {
         .bind(id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(driver)
     }
 
     async fn update_status(&self, id: Uuid, status: DriverStatus) -> Result<Driver, AppError> {
-        let driver = sqlx::query_as::<_, Driver>(
-            r#"
-            UPDATE drivers 
-            SET status = $1, updated_at = NOW()
-            WHERE id = $2 AND deleted_at IS NULL
-            RETURNING *
-            "#
-        )
-        .bind(status)
-        .bind(id)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(AppError::DatabaseError)?;
-
-        Ok(driver)
+        Self::set_status(&self.pool, id, status).await
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), AppError> {
@@ -153,7 +160,7 @@ impl DriverRepositoryTrait for DriverRepository {
         .bind(id)
         .execute(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         if result.rows_affected() == 0 {
             return Err(AppError::NotFound(format!("Driver with id {} not found", id)));
@@ -161,4 +168,27 @@ impl DriverRepositoryTrait for DriverRepository {
 
         Ok(())
     }
+
+    async fn find_license_expiries(&self) -> Result<Vec<LicenseExpiry>, AppError> {
+        let expiries = sqlx::query_as::<_, LicenseExpiry>(
+            r#"
+            SELECT
+                d.id AS driver_id,
+                d.license_number,
+                u.name,
+                d.license_expiry
+            FROM drivers d
+            JOIN users u ON d.user_id = u.id
+            WHERE d.license_expiry IS NOT NULL
+              AND d.deleted_at IS NULL
+              AND u.deleted_at IS NULL
+            ORDER BY d.license_expiry
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(expiries)
+    }
 }
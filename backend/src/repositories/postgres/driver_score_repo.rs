@@ -0,0 +1,195 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::models::postgres::assignment::VehicleAssignment;
+use crate::models::postgres::driver_score::{TripScore, WeeklyScore, LeaderboardEntry};
+use crate::error::AppError;
+use async_trait::async_trait;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait DriverScoreRepositoryTrait: Send + Sync {
+    /// Completed assignments closed before `completed_before` that have no trip score yet.
+    async fn find_unscored_trips(&self, completed_before: DateTime<Utc>, limit: i64) -> Result<Vec<VehicleAssignment>, AppError>;
+    /// Keeps the existing score if another worker stored one first.
+    async fn save_trip_score(&self, score: TripScore) -> Result<(), AppError>;
+    async fn find_trip_scores(&self, driver_id: Uuid, since: DateTime<Utc>, limit: i64) -> Result<Vec<TripScore>, AppError>;
+    async fn find_weekly_scores(&self, driver_id: Uuid, since: DateTime<Utc>) -> Result<Vec<WeeklyScore>, AppError>;
+    async fn find_leaderboard(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<LeaderboardEntry>, AppError>;
+    /// Offset of `time_zone` from UTC at `at`, in seconds. `None` for unknown zone names.
+    async fn utc_offset(&self, time_zone: String, at: DateTime<Utc>) -> Result<Option<i32>, AppError>;
+}
+
+pub struct DriverScoreRepository {
+    pool: PgPool,
+}
+
+impl DriverScoreRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DriverScoreRepositoryTrait for DriverScoreRepository {
+    async fn find_unscored_trips(&self, completed_before: DateTime<Utc>, limit: i64) -> Result<Vec<VehicleAssignment>, AppError> {
+        let trips = sqlx::query_as::<_, VehicleAssignment>(
+            r#"
+            SELECT a.* FROM vehicle_assignments a
+            WHERE a.status = 'COMPLETED'
+              AND a.completed_at <= $1
+              AND NOT EXISTS (SELECT 1 FROM trip_scores t WHERE t.assignment_id = a.id)
+            ORDER BY a.completed_at
+            LIMIT $2
+            "#
+        )
+        .bind(completed_before)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(trips)
+    }
+
+    async fn save_trip_score(&self, score: TripScore) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO trip_scores (
+                assignment_id, driver_id, vehicle_id, started_at, ended_at, readings, driving_minutes,
+                speeding_events, harsh_acceleration_events, harsh_braking_events, idle_minutes,
+                night_minutes, score, scored_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+            ON CONFLICT (assignment_id) DO NOTHING
+            "#
+        )
+        .bind(score.assignment_id)
+        .bind(score.driver_id)
+        .bind(score.vehicle_id)
+        .bind(score.started_at)
+        .bind(score.ended_at)
+        .bind(score.readings)
+        .bind(score.driving_minutes)
+        .bind(score.speeding_events)
+        .bind(score.harsh_acceleration_events)
+        .bind(score.harsh_braking_events)
+        .bind(score.idle_minutes)
+        .bind(score.night_minutes)
+        .bind(score.score)
+        .bind(score.scored_at)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    async fn find_trip_scores(&self, driver_id: Uuid, since: DateTime<Utc>, limit: i64) -> Result<Vec<TripScore>, AppError> {
+        let scores = sqlx::query_as::<_, TripScore>(
+            r#"
+            SELECT * FROM trip_scores
+            WHERE driver_id = $1 AND ended_at >= $2
+            ORDER BY ended_at DESC
+            LIMIT $3
+            "#
+        )
+        .bind(driver_id)
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(scores)
+    }
+
+    async fn find_weekly_scores(&self, driver_id: Uuid, since: DateTime<Utc>) -> Result<Vec<WeeklyScore>, AppError> {
+        // Short trips still count for at least a minute so they are not ignored entirely
+        let weeks = sqlx::query_as::<_, WeeklyScore>(
+            r#"
+            SELECT
+                date_trunc('week', ended_at AT TIME ZONE 'UTC')::date AS week_start,
+                COUNT(*) AS trips,
+                SUM(driving_minutes) AS driving_minutes,
+                SUM(speeding_events)::bigint AS speeding_events,
+                SUM(harsh_acceleration_events)::bigint AS harsh_acceleration_events,
+                SUM(harsh_braking_events)::bigint AS harsh_braking_events,
+                SUM(idle_minutes) AS idle_minutes,
+                SUM(night_minutes) AS night_minutes,
+                ROUND((
+                    SUM(score * GREATEST(driving_minutes, 1))
+                    / NULLIF(SUM(GREATEST(driving_minutes, 1)) FILTER (WHERE score IS NOT NULL), 0)
+                )::numeric, 1)::float8 AS score
+            FROM trip_scores
+            WHERE driver_id = $1 AND ended_at >= $2
+            GROUP BY 1
+            ORDER BY 1
+            "#
+        )
+        .bind(driver_id)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(weeks)
+    }
+
+    async fn find_leaderboard(&self, since: DateTime<Utc>, limit: i64) -> Result<Vec<LeaderboardEntry>, AppError> {
+        let entries = sqlx::query_as::<_, LeaderboardEntry>(
+            r#"
+            WITH totals AS (
+                SELECT
+                    t.driver_id,
+                    COUNT(*) AS trips,
+                    SUM(t.driving_minutes) AS driving_minutes,
+                    ROUND((
+                        SUM(t.score * GREATEST(t.driving_minutes, 1)) / SUM(GREATEST(t.driving_minutes, 1))
+                    )::numeric, 1)::float8 AS score
+                FROM trip_scores t
+                JOIN drivers d ON d.id = t.driver_id
+                WHERE t.ended_at >= $1 AND t.score IS NOT NULL AND d.deleted_at IS NULL
+                GROUP BY t.driver_id
+            )
+            SELECT
+                RANK() OVER (ORDER BY totals.score DESC) AS rank,
+                totals.driver_id,
+                u.name,
+                d.license_number,
+                totals.trips,
+                totals.driving_minutes,
+                totals.score
+            FROM totals
+            JOIN drivers d ON d.id = totals.driver_id
+            JOIN users u ON u.id = d.user_id
+            ORDER BY rank, totals.driving_minutes DESC
+            LIMIT $2
+            "#
+        )
+        .bind(since)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(entries)
+    }
+
+    async fn utc_offset(&self, time_zone: String, at: DateTime<Utc>) -> Result<Option<i32>, AppError> {
+        let offset = sqlx::query_scalar::<_, i32>(
+            r#"
+            SELECT EXTRACT(EPOCH FROM ($2 AT TIME ZONE name) - ($2 AT TIME ZONE 'UTC'))::int
+            FROM pg_timezone_names
+            WHERE name = $1
+            "#
+        )
+        .bind(time_zone)
+        .bind(at)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(offset)
+    }
+}
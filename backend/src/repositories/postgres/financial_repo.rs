@@ -2,6 +2,57 @@ use sqlx::PgPool;
 use crate::models::postgres::financial::{MonthlyFinancialSummary, VehicleProfitability};
 use crate::error::AppError;
 
+/// Inventory parts used on vehicles, net of returns. Maintenance record costs exclude
+/// stocked parts, so these add to them rather than overlap.
+const PART_COSTS_CTE: &str = r#"
+    part_costs AS (
+        SELECT vehicle_id, created_at, ROUND(-quantity * unit_cost, 2) AS cost
+        FROM stock_movements
+        WHERE kind IN ('CONSUMPTION', 'RETURN') AND vehicle_id IS NOT NULL
+    )
+"#;
+
+/// Revenue per job that went ahead: the invoiced amount once the job is on an invoice,
+/// dated by the invoice, and otherwise the agreed price as of the job's last update.
+/// Tax is left out since it is passed on rather than earned.
+const JOB_REVENUE_CTE: &str = r#"
+    job_revenue AS (
+        SELECT j.id AS job_id, COALESCE(l.amount, j.agreed_price) AS revenue,
+               COALESCE(i.issue_date, j.updated_at::date) AS earned_on
+        FROM transport_jobs j
+        LEFT JOIN invoice_lines l ON l.job_id = j.id
+        LEFT JOIN invoices i ON i.id = l.invoice_id
+        WHERE j.status <> 'CANCELLED'
+    )
+"#;
+
+/// Revenue of jobs that went ahead, split evenly across the legs that were not
+/// cancelled and credited to the vehicle that ran each leg. The first leg takes the
+/// rounding remainder so the shares add up to the agreed price.
+const LEG_REVENUE_CTE: &str = r#"
+    legs AS (
+        SELECT
+            ja.job_id,
+            a.vehicle_id,
+            COUNT(*) OVER (PARTITION BY ja.job_id) AS leg_count,
+            ROW_NUMBER() OVER (PARTITION BY ja.job_id ORDER BY ja.leg_number) AS position
+        FROM job_assignments ja
+        JOIN vehicle_assignments a ON a.id = ja.assignment_id
+        WHERE a.status <> 'CANCELLED'
+    ),
+    leg_revenue AS (
+        SELECT
+            l.vehicle_id,
+            CASE
+                WHEN l.position = 1
+                THEN j.revenue - ROUND(j.revenue / l.leg_count, 2) * (l.leg_count - 1)
+                ELSE ROUND(j.revenue / l.leg_count, 2)
+            END AS revenue
+        FROM legs l
+        JOIN job_revenue j ON j.job_id = l.job_id
+    )
+"#;
+
 pub struct FinancialRepository {
     pool: PgPool,
 }
@@ -12,72 +63,101 @@ impl FinancialRepository {
     }
 
     pub async fn get_monthly_summary(&self) -> Result<Vec<MonthlyFinancialSummary>, AppError> {
-        let query = r#"
-            WITH monthly_revenue AS (
+        let query = format!(r#"
+            WITH {PART_COSTS_CTE}, {JOB_REVENUE_CTE},
+            monthly_revenue AS (
                 SELECT 
-                    TO_CHAR(updated_at, 'YYYY-MM') as month, 
-                    COALESCE(SUM(agreed_price), 0)::decimal as revenue 
-                FROM transport_jobs 
-                GROUP BY TO_CHAR(updated_at, 'YYYY-MM')
+                    TO_CHAR(earned_on, 'YYYY-MM') as month, 
+                    COALESCE(SUM(revenue), 0)::decimal as revenue 
+                FROM job_revenue 
+                GROUP BY TO_CHAR(earned_on, 'YYYY-MM')
+            ),
+            monthly_collected AS (
+                SELECT TO_CHAR(paid_on, 'YYYY-MM') as month, SUM(amount)::decimal as collected
+                FROM invoice_payments
+                GROUP BY TO_CHAR(paid_on, 'YYYY-MM')
             ),
             monthly_cost AS (
                 SELECT 
-                    TO_CHAR(date, 'YYYY-MM') as month, 
+                    month, 
                     COALESCE(SUM(cost), 0)::decimal as cost 
-                FROM maintenance_records 
-                WHERE date IS NOT NULL
-                GROUP BY TO_CHAR(date, 'YYYY-MM')
+                FROM (
+                    SELECT TO_CHAR(date, 'YYYY-MM') as month, cost
+                    FROM maintenance_records 
+                    WHERE date IS NOT NULL
+                    UNION ALL
+                    SELECT TO_CHAR(created_at, 'YYYY-MM') as month, cost
+                    FROM part_costs
+                ) costs
+                GROUP BY month
             ),
             all_months AS (
                 SELECT month FROM monthly_revenue
                 UNION
                 SELECT month FROM monthly_cost
+                UNION
+                SELECT month FROM monthly_collected
             )
             SELECT 
                 m.month,
                 COALESCE(r.revenue, 0) as revenue,
                 COALESCE(c.cost, 0) as cost,
-                (COALESCE(r.revenue, 0) - COALESCE(c.cost, 0)) as profit
+                (COALESCE(r.revenue, 0) - COALESCE(c.cost, 0)) as profit,
+                COALESCE(p.collected, 0) as collected
             FROM all_months m
             LEFT JOIN monthly_revenue r ON m.month = r.month
             LEFT JOIN monthly_cost c ON m.month = c.month
+            LEFT JOIN monthly_collected p ON m.month = p.month
             ORDER BY m.month DESC
-        "#;
+        "#);
 
-        let summaries = sqlx::query_as::<_, MonthlyFinancialSummary>(query)
+        let summaries = sqlx::query_as::<_, MonthlyFinancialSummary>(&query)
             .fetch_all(&self.pool)
             .await
-            .map_err(AppError::DatabaseError)?;
+            .map_err(AppError::from)?;
 
         Ok(summaries)
     }
 
     pub async fn get_vehicle_profitability(&self) -> Result<Vec<VehicleProfitability>, AppError> {
-        let query = r#"
-            WITH vehicle_costs AS (
+        let query = format!(r#"
+            WITH {PART_COSTS_CTE}, {JOB_REVENUE_CTE}, {LEG_REVENUE_CTE},
+            vehicle_revenue AS (
+                SELECT vehicle_id, COALESCE(SUM(revenue), 0)::decimal AS total_revenue
+                FROM leg_revenue
+                GROUP BY vehicle_id
+            ),
+            vehicle_costs AS (
                 SELECT 
                     vehicle_id, 
                     COALESCE(SUM(cost), 0)::decimal as total_maintenance_cost
-                FROM maintenance_records 
-                WHERE vehicle_id IS NOT NULL
+                FROM (
+                    SELECT vehicle_id, cost
+                    FROM maintenance_records 
+                    WHERE vehicle_id IS NOT NULL
+                    UNION ALL
+                    SELECT vehicle_id, cost
+                    FROM part_costs
+                ) costs
                 GROUP BY vehicle_id
             )
             SELECT 
                 v.id as vehicle_id,
                 v.license_plate as vehicle_plate,
-                0::decimal as revenue,
+                COALESCE(r.total_revenue, 0)::decimal as revenue,
                 COALESCE(c.total_maintenance_cost, 0)::decimal as cost,
-                (0::decimal - COALESCE(c.total_maintenance_cost, 0)::decimal) as profit,
-                RANK() OVER (ORDER BY (0::decimal - COALESCE(c.total_maintenance_cost, 0)::decimal) DESC)::integer as rank
+                (COALESCE(r.total_revenue, 0) - COALESCE(c.total_maintenance_cost, 0))::decimal as profit,
+                RANK() OVER (ORDER BY (COALESCE(r.total_revenue, 0) - COALESCE(c.total_maintenance_cost, 0)) DESC)::integer as rank
             FROM vehicles v
+            LEFT JOIN vehicle_revenue r ON v.id = r.vehicle_id
             LEFT JOIN vehicle_costs c ON v.id = c.vehicle_id
             ORDER BY profit DESC
-        "#;
+        "#);
 
-        let profitability = sqlx::query_as::<_, VehicleProfitability>(query)
+        let profitability = sqlx::query_as::<_, VehicleProfitability>(&query)
             .fetch_all(&self.pool)
             .await
-            .map_err(AppError::DatabaseError)?;
+            .map_err(AppError::from)?;
 
         Ok(profitability)
     }
@@ -0,0 +1,236 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use crate::models::postgres::geofence::{Geofence, CreateGeofenceDto, GeofenceTransition};
+use crate::error::AppError;
+use async_trait::async_trait;
+
+const GEOFENCE_COLUMNS: &str = r#"
+    id,
+    name,
+    kind,
+    ST_AsGeoJSON(area)::jsonb as area,
+    ST_AsGeoJSON(center)::jsonb as center,
+    radius_m,
+    vehicle_id,
+    vehicle_type,
+    alert_on_entry,
+    alert_on_exit,
+    is_active,
+    created_at,
+    updated_at
+"#;
+
+/// Circles are buffered on the geography type so the radius is in meters.
+const AREA_EXPR: &str = r#"
+    CASE WHEN $3 = 'CIRCLE'::geofence_kind
+        THEN ST_Buffer(ST_SetSRID(ST_GeomFromGeoJSON($5::jsonb), 4326)::geography, $6)::geometry
+        ELSE ST_SetSRID(ST_GeomFromGeoJSON($4::jsonb), 4326)
+    END
+"#;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait GeofenceRepositoryTrait: Send + Sync {
+    async fn create(&self, dto: CreateGeofenceDto) -> Result<Geofence, AppError>;
+    async fn find_all(&self) -> Result<Vec<Geofence>, AppError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Geofence>, AppError>;
+    /// Replaces the geofence. Vehicle states are reset, so the next reading sets a new baseline.
+    async fn update(&self, id: Uuid, dto: CreateGeofenceDto) -> Result<Option<Geofence>, AppError>;
+    async fn delete(&self, id: Uuid) -> Result<bool, AppError>;
+    /// Records whether the vehicle is inside each applicable geofence along a batch of
+    /// `(time, location)` points and returns every flip in time order, in one query. The
+    /// first reading for a pair only sets the baseline, and readings older than the stored
+    /// state are ignored.
+    async fn evaluate_track(&self, vehicle_id: Uuid, points: Vec<(DateTime<Utc>, Value)>) -> Result<Vec<GeofenceTransition>, AppError>;
+}
+
+pub struct GeofenceRepository {
+    pool: PgPool,
+}
+
+impl GeofenceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GeofenceRepositoryTrait for GeofenceRepository {
+    async fn create(&self, dto: CreateGeofenceDto) -> Result<Geofence, AppError> {
+        let id = Uuid::new_v4();
+        let geofence = sqlx::query_as::<_, Geofence>(&format!(
+            r#"
+            INSERT INTO geofences (
+                id, name, kind, area, center, radius_m, vehicle_id, vehicle_type,
+                alert_on_entry, alert_on_exit, is_active
+            )
+            VALUES (
+                $1, $2, $3, {},
+                ST_SetSRID(ST_GeomFromGeoJSON($5::jsonb), 4326),
+                $6, $7, $8, $9, $10, $11
+            )
+            RETURNING {}
+            "#,
+            AREA_EXPR, GEOFENCE_COLUMNS
+        ))
+        .bind(id)
+        .bind(dto.name)
+        .bind(dto.kind)
+        .bind(dto.area)
+        .bind(dto.center)
+        .bind(dto.radius_m)
+        .bind(dto.vehicle_id)
+        .bind(dto.vehicle_type)
+        .bind(dto.alert_on_entry)
+        .bind(dto.alert_on_exit)
+        .bind(dto.is_active)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(geofence)
+    }
+
+    async fn find_all(&self) -> Result<Vec<Geofence>, AppError> {
+        let geofences = sqlx::query_as::<_, Geofence>(&format!(
+            "SELECT {} FROM geofences ORDER BY name",
+            GEOFENCE_COLUMNS
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(geofences)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Geofence>, AppError> {
+        let geofence = sqlx::query_as::<_, Geofence>(&format!(
+            "SELECT {} FROM geofences WHERE id = $1",
+            GEOFENCE_COLUMNS
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(geofence)
+    }
+
+    async fn update(&self, id: Uuid, dto: CreateGeofenceDto) -> Result<Option<Geofence>, AppError> {
+        let geofence = sqlx::query_as::<_, Geofence>(&format!(
+            r#"
+            WITH reset AS (
+                DELETE FROM geofence_vehicle_states WHERE geofence_id = $1
+            )
+            UPDATE geofences
+            SET
+                name = $2,
+                kind = $3,
+                area = {},
+                center = ST_SetSRID(ST_GeomFromGeoJSON($5::jsonb), 4326),
+                radius_m = $6,
+                vehicle_id = $7,
+                vehicle_type = $8,
+                alert_on_entry = $9,
+                alert_on_exit = $10,
+                is_active = $11,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            AREA_EXPR, GEOFENCE_COLUMNS
+        ))
+        .bind(id)
+        .bind(dto.name)
+        .bind(dto.kind)
+        .bind(dto.area)
+        .bind(dto.center)
+        .bind(dto.radius_m)
+        .bind(dto.vehicle_id)
+        .bind(dto.vehicle_type)
+        .bind(dto.alert_on_entry)
+        .bind(dto.alert_on_exit)
+        .bind(dto.is_active)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(geofence)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM geofences WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn evaluate_track(&self, vehicle_id: Uuid, points: Vec<(DateTime<Utc>, Value)>) -> Result<Vec<GeofenceTransition>, AppError> {
+        let (times, locations): (Vec<DateTime<Utc>>, Vec<Value>) = points.into_iter().unzip();
+        // Each point is compared with the one before it for the same geofence, the first
+        // with the stored state. Points older than the stored state arrived late and are
+        // skipped. Only the newest point per geofence is written back.
+        let transitions = sqlx::query_as::<_, GeofenceTransition>(
+            r#"
+            WITH points AS (
+                SELECT p.time, p.ord, ST_SetSRID(ST_GeomFromGeoJSON(p.location), 4326) as geom
+                FROM unnest($2::timestamptz[], $3::jsonb[]) WITH ORDINALITY AS p(time, location, ord)
+            ),
+            applicable AS (
+                SELECT g.id, g.name, g.area, g.alert_on_entry, g.alert_on_exit
+                FROM geofences g
+                JOIN vehicles v ON v.id = $1
+                WHERE g.is_active
+                  AND (g.vehicle_id IS NULL OR g.vehicle_id = v.id)
+                  AND (g.vehicle_type IS NULL OR g.vehicle_type = v.type)
+            ),
+            observed AS (
+                SELECT
+                    a.id as geofence_id,
+                    a.name,
+                    a.alert_on_entry,
+                    a.alert_on_exit,
+                    pt.time,
+                    pt.ord,
+                    ST_Contains(a.area, pt.geom) as inside,
+                    s.inside as stored_inside
+                FROM applicable a
+                CROSS JOIN points pt
+                LEFT JOIN geofence_vehicle_states s ON s.geofence_id = a.id AND s.vehicle_id = $1
+                WHERE s.observed_at IS NULL OR s.observed_at <= pt.time
+            ),
+            sequenced AS (
+                SELECT
+                    o.*,
+                    LAG(o.inside, 1, o.stored_inside) OVER (PARTITION BY o.geofence_id ORDER BY o.time, o.ord) as was_inside,
+                    ROW_NUMBER() OVER (PARTITION BY o.geofence_id ORDER BY o.time DESC, o.ord DESC) as newest
+                FROM observed o
+            ),
+            upserted AS (
+                INSERT INTO geofence_vehicle_states (geofence_id, vehicle_id, inside, observed_at)
+                SELECT geofence_id, $1, inside, time FROM sequenced WHERE newest = 1
+                ON CONFLICT (geofence_id, vehicle_id) DO UPDATE
+                    SET inside = EXCLUDED.inside, observed_at = EXCLUDED.observed_at
+                    WHERE geofence_vehicle_states.observed_at <= EXCLUDED.observed_at
+            )
+            SELECT geofence_id, name, inside, alert_on_entry, alert_on_exit
+            FROM sequenced
+            WHERE was_inside IS NOT NULL AND was_inside <> inside
+            ORDER BY time, ord
+            "#
+        )
+        .bind(vehicle_id)
+        .bind(times)
+        .bind(locations)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(transitions)
+    }
+}
@@ -0,0 +1,100 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::models::postgres::hos::{DutyEvent, DutyEventKind};
+use crate::error::AppError;
+use async_trait::async_trait;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait HosRepositoryTrait: Send + Sync {
+    async fn create_event(
+        &self,
+        driver_id: Uuid,
+        kind: DutyEventKind,
+        occurred_at: DateTime<Utc>,
+        note: Option<String>,
+    ) -> Result<DutyEvent, AppError>;
+    async fn find_last_event(&self, driver_id: Uuid) -> Result<Option<DutyEvent>, AppError>;
+    /// Events in `[from, to)`, plus the last one before `from` so a shift that started
+    /// earlier is still visible.
+    async fn find_events(&self, driver_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<DutyEvent>, AppError>;
+}
+
+pub struct HosRepository {
+    pool: PgPool,
+}
+
+impl HosRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl HosRepositoryTrait for HosRepository {
+    async fn create_event(
+        &self,
+        driver_id: Uuid,
+        kind: DutyEventKind,
+        occurred_at: DateTime<Utc>,
+        note: Option<String>,
+    ) -> Result<DutyEvent, AppError> {
+        let event = sqlx::query_as::<_, DutyEvent>(
+            r#"
+            INSERT INTO duty_events (driver_id, kind, occurred_at, note)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#
+        )
+        .bind(driver_id)
+        .bind(kind)
+        .bind(occurred_at)
+        .bind(note)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(event)
+    }
+
+    async fn find_last_event(&self, driver_id: Uuid) -> Result<Option<DutyEvent>, AppError> {
+        let event = sqlx::query_as::<_, DutyEvent>(
+            r#"
+            SELECT * FROM duty_events
+            WHERE driver_id = $1
+            ORDER BY occurred_at DESC, created_at DESC
+            LIMIT 1
+            "#
+        )
+        .bind(driver_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(event)
+    }
+
+    async fn find_events(&self, driver_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<DutyEvent>, AppError> {
+        let events = sqlx::query_as::<_, DutyEvent>(
+            r#"
+            SELECT * FROM duty_events
+            WHERE driver_id = $1
+              AND occurred_at < $3
+              AND occurred_at >= COALESCE(
+                  (SELECT MAX(occurred_at) FROM duty_events WHERE driver_id = $1 AND occurred_at < $2),
+                  $2
+              )
+            ORDER BY occurred_at, created_at
+            "#
+        )
+        .bind(driver_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(events)
+    }
+}
@@ -0,0 +1,436 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::{FromRow, PgExecutor, PgPool};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::invoice::{
+    Invoice, InvoiceLine, InvoicePayment, InvoiceDetails, InvoiceQuery, InvoiceStatus,
+    NewInvoice, NewInvoicePayment, CustomerAging, StatementEntry, StatementEntryKind
+};
+use crate::models::postgres::logistics::{JobStatus, TransportJob};
+
+const INVOICE_SELECT: &str = r#"
+    SELECT i.id, i.invoice_number, i.customer_id, c.name AS customer_name, i.billing_address,
+           i.currency, i.tax_rate, i.subtotal, i.tax_amount, i.total,
+           COALESCE(p.amount_paid, 0) AS amount_paid,
+           i.total - COALESCE(p.amount_paid, 0) AS balance_due,
+           i.status, i.issue_date, i.due_date, i.notes, i.issued_by, i.created_at
+    FROM invoices i
+    JOIN customers c ON c.id = i.customer_id
+    LEFT JOIN (
+        SELECT invoice_id, SUM(amount) AS amount_paid
+        FROM invoice_payments
+        GROUP BY invoice_id
+    ) p ON p.invoice_id = i.id
+"#;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait InvoiceRepositoryTrait: Send + Sync {
+    /// Numbers and records the invoice and moves its jobs from `Delivered` to `Invoiced`
+    /// in one transaction. Returns `None`, recording nothing, if any job is no longer
+    /// delivered.
+    async fn create(&self, invoice: NewInvoice) -> Result<Option<InvoiceDetails>, AppError>;
+    async fn find_all(&self, query: InvoiceQuery) -> Result<Vec<Invoice>, AppError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<InvoiceDetails>, AppError>;
+    /// Delivered jobs of the customer that are not on an invoice yet.
+    async fn find_billable_jobs(&self, customer_id: Uuid) -> Result<Vec<TransportJob>, AppError>;
+    /// Records the payment and updates the invoice status; once it is paid in full its
+    /// jobs move from `Invoiced` to `Paid`. Returns `None`, recording nothing, if the
+    /// payment exceeds the balance due.
+    async fn record_payment(&self, invoice_id: Uuid, payment: NewInvoicePayment) -> Result<Option<InvoiceDetails>, AppError>;
+    /// Balances still owed on `as_of` by customer, bucketed by days since the invoice
+    /// date. Customers with nothing owed are left out.
+    async fn find_aging(&self, as_of: NaiveDate, customer_id: Option<Uuid>) -> Result<Vec<CustomerAging>, AppError>;
+    /// What the customer owed before `date`.
+    async fn find_balance_before(&self, customer_id: Uuid, date: NaiveDate) -> Result<Decimal, AppError>;
+    /// Invoices and payments of the customer dated from `from` to `to`, oldest first. Each
+    /// balance is the running total within the period, without the opening balance.
+    async fn find_statement_entries(&self, customer_id: Uuid, from: NaiveDate, to: NaiveDate) -> Result<Vec<StatementEntry>, AppError>;
+}
+
+#[derive(FromRow)]
+struct StatementRow {
+    date: NaiveDate,
+    kind: String,
+    invoice_id: Uuid,
+    invoice_number: String,
+    reference: Option<String>,
+    amount: Decimal,
+    balance: Decimal,
+}
+
+impl From<StatementRow> for StatementEntry {
+    fn from(row: StatementRow) -> Self {
+        Self {
+            date: row.date,
+            kind: if row.kind == "PAYMENT" { StatementEntryKind::Payment } else { StatementEntryKind::Invoice },
+            invoice_id: row.invoice_id,
+            invoice_number: row.invoice_number,
+            reference: row.reference,
+            amount: row.amount,
+            balance: row.balance,
+        }
+    }
+}
+
+pub struct InvoiceRepository {
+    pool: PgPool,
+}
+
+impl InvoiceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn fetch_invoice<'e>(executor: impl PgExecutor<'e>, id: Uuid) -> Result<Option<Invoice>, AppError> {
+        sqlx::query_as::<_, Invoice>(&format!("{} WHERE i.id = $1", INVOICE_SELECT))
+            .bind(id)
+            .fetch_optional(executor)
+            .await
+            .map_err(AppError::from)
+    }
+
+    async fn fetch_lines<'e>(executor: impl PgExecutor<'e>, invoice_id: Uuid) -> Result<Vec<InvoiceLine>, AppError> {
+        sqlx::query_as::<_, InvoiceLine>(
+            r#"
+            SELECT l.*
+            FROM invoice_lines l
+            JOIN transport_jobs j ON j.id = l.job_id
+            WHERE l.invoice_id = $1
+            ORDER BY j.created_at, l.id
+            "#
+        )
+        .bind(invoice_id)
+        .fetch_all(executor)
+        .await
+        .map_err(AppError::from)
+    }
+
+    async fn fetch_payments<'e>(executor: impl PgExecutor<'e>, invoice_id: Uuid) -> Result<Vec<InvoicePayment>, AppError> {
+        sqlx::query_as::<_, InvoicePayment>(
+            "SELECT * FROM invoice_payments WHERE invoice_id = $1 ORDER BY paid_on, created_at"
+        )
+        .bind(invoice_id)
+        .fetch_all(executor)
+        .await
+        .map_err(AppError::from)
+    }
+
+    /// Moves the jobs between statuses and records the change in their history.
+    /// Returns the jobs that were in `from`.
+    async fn transition_jobs(
+        conn: &mut sqlx::PgConnection,
+        job_ids: &[Uuid],
+        from: JobStatus,
+        to: JobStatus,
+        changed_by: Option<Uuid>,
+        reason: String,
+    ) -> Result<Vec<Uuid>, AppError> {
+        let moved: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            UPDATE transport_jobs
+            SET status = $3, updated_at = NOW()
+            WHERE id = ANY($1) AND status = $2
+            RETURNING id
+            "#
+        )
+        .bind(job_ids)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(AppError::from)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO job_status_history (job_id, from_status, to_status, changed_by, reason)
+            SELECT job_id, $2, $3, $4, $5
+            FROM UNNEST($1::uuid[]) AS job_id
+            "#
+        )
+        .bind(&moved)
+        .bind(from)
+        .bind(to)
+        .bind(changed_by)
+        .bind(reason)
+        .execute(&mut *conn)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(moved)
+    }
+}
+
+#[async_trait]
+impl InvoiceRepositoryTrait for InvoiceRepository {
+    async fn create(&self, invoice: NewInvoice) -> Result<Option<InvoiceDetails>, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        // Serializes numbering so numbers are handed out without gaps
+        sqlx::query("LOCK TABLE invoices IN SHARE ROW EXCLUSIVE MODE")
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        let (id, invoice_number): (Uuid, String) = sqlx::query_as(
+            r#"
+            INSERT INTO invoices (
+                sequence_number, customer_id, billing_address, currency, tax_rate,
+                subtotal, tax_amount, total, issue_date, due_date, notes, issued_by
+            )
+            SELECT COALESCE(MAX(sequence_number), 0) + 1, $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11
+            FROM invoices
+            RETURNING id, invoice_number
+            "#
+        )
+        .bind(invoice.customer_id)
+        .bind(&invoice.billing_address)
+        .bind(&invoice.currency)
+        .bind(invoice.tax_rate)
+        .bind(invoice.subtotal)
+        .bind(invoice.tax_amount)
+        .bind(invoice.total)
+        .bind(invoice.issue_date)
+        .bind(invoice.due_date)
+        .bind(&invoice.notes)
+        .bind(invoice.issued_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        let mut job_ids = Vec::with_capacity(invoice.lines.len());
+        for line in invoice.lines {
+            sqlx::query("INSERT INTO invoice_lines (invoice_id, job_id, description, amount) VALUES ($1, $2, $3, $4)")
+                .bind(id)
+                .bind(line.job_id)
+                .bind(line.description)
+                .bind(line.amount)
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::from)?;
+            job_ids.push(line.job_id);
+        }
+
+        let invoiced = Self::transition_jobs(
+            &mut tx,
+            &job_ids,
+            JobStatus::Delivered,
+            JobStatus::Invoiced,
+            invoice.issued_by,
+            format!("Invoice {}", invoice_number),
+        )
+        .await?;
+        if invoiced.len() != job_ids.len() {
+            return Ok(None);
+        }
+
+        let invoice = Self::fetch_invoice(&mut *tx, id).await?
+            .ok_or_else(|| AppError::InternalServerError("Invoice missing after insert".into()))?;
+        let lines = Self::fetch_lines(&mut *tx, id).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(Some(InvoiceDetails { invoice, lines, payments: Vec::new() }))
+    }
+
+    async fn find_all(&self, query: InvoiceQuery) -> Result<Vec<Invoice>, AppError> {
+        sqlx::query_as::<_, Invoice>(&format!(
+            r#"
+            {}
+            WHERE ($1::uuid IS NULL OR i.customer_id = $1)
+              AND ($2::invoice_status IS NULL OR i.status = $2)
+            ORDER BY i.sequence_number DESC
+            "#,
+            INVOICE_SELECT
+        ))
+        .bind(query.customer_id)
+        .bind(query.status)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<InvoiceDetails>, AppError> {
+        let Some(invoice) = Self::fetch_invoice(&self.pool, id).await? else {
+            return Ok(None);
+        };
+        let lines = Self::fetch_lines(&self.pool, id).await?;
+        let payments = Self::fetch_payments(&self.pool, id).await?;
+        Ok(Some(InvoiceDetails { invoice, lines, payments }))
+    }
+
+    async fn find_billable_jobs(&self, customer_id: Uuid) -> Result<Vec<TransportJob>, AppError> {
+        sqlx::query_as::<_, TransportJob>(
+            r#"
+            SELECT j.*
+            FROM transport_jobs j
+            WHERE j.customer_id = $1
+              AND j.status = 'DELIVERED'
+              AND NOT EXISTS (SELECT 1 FROM invoice_lines l WHERE l.job_id = j.id)
+            ORDER BY j.created_at
+            "#
+        )
+        .bind(customer_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
+
+    async fn record_payment(&self, invoice_id: Uuid, payment: NewInvoicePayment) -> Result<Option<InvoiceDetails>, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let (total, invoice_number): (Decimal, String) = sqlx::query_as(
+            "SELECT total, invoice_number FROM invoices WHERE id = $1 FOR UPDATE"
+        )
+        .bind(invoice_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::from)?
+        .ok_or_else(|| AppError::NotFound("Invoice not found".into()))?;
+
+        let paid: Decimal = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(amount), 0) FROM invoice_payments WHERE invoice_id = $1"
+        )
+        .bind(invoice_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        let paid = paid + payment.amount;
+        if paid > total {
+            return Ok(None);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO invoice_payments (invoice_id, amount, paid_on, method, reference, recorded_by)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#
+        )
+        .bind(invoice_id)
+        .bind(payment.amount)
+        .bind(payment.paid_on)
+        .bind(payment.method)
+        .bind(payment.reference)
+        .bind(payment.recorded_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        let status = if paid == total { InvoiceStatus::Paid } else { InvoiceStatus::PartiallyPaid };
+        sqlx::query("UPDATE invoices SET status = $2 WHERE id = $1")
+            .bind(invoice_id)
+            .bind(status)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        let lines = Self::fetch_lines(&mut *tx, invoice_id).await?;
+        if status == InvoiceStatus::Paid {
+            let job_ids: Vec<Uuid> = lines.iter().map(|line| line.job_id).collect();
+            Self::transition_jobs(
+                &mut tx,
+                &job_ids,
+                JobStatus::Invoiced,
+                JobStatus::Paid,
+                payment.recorded_by,
+                format!("Invoice {} paid", invoice_number),
+            )
+            .await?;
+        }
+
+        let invoice = Self::fetch_invoice(&mut *tx, invoice_id).await?
+            .ok_or_else(|| AppError::InternalServerError("Invoice missing after payment".into()))?;
+        let payments = Self::fetch_payments(&mut *tx, invoice_id).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(Some(InvoiceDetails { invoice, lines, payments }))
+    }
+
+    async fn find_aging(&self, as_of: NaiveDate, customer_id: Option<Uuid>) -> Result<Vec<CustomerAging>, AppError> {
+        sqlx::query_as::<_, CustomerAging>(
+            r#"
+            WITH balances AS (
+                SELECT i.customer_id,
+                       $1::date - i.issue_date AS age,
+                       i.total - COALESCE((
+                           SELECT SUM(p.amount)
+                           FROM invoice_payments p
+                           WHERE p.invoice_id = i.id AND p.paid_on <= $1
+                       ), 0) AS balance
+                FROM invoices i
+                WHERE i.issue_date <= $1
+                  AND ($2::uuid IS NULL OR i.customer_id = $2)
+            )
+            SELECT c.id AS customer_id, c.name AS customer_name, c.credit_limit,
+                   COALESCE(SUM(b.balance) FILTER (WHERE b.age <= 30), 0) AS days_0_30,
+                   COALESCE(SUM(b.balance) FILTER (WHERE b.age BETWEEN 31 AND 60), 0) AS days_31_60,
+                   COALESCE(SUM(b.balance) FILTER (WHERE b.age BETWEEN 61 AND 90), 0) AS days_61_90,
+                   COALESCE(SUM(b.balance) FILTER (WHERE b.age > 90), 0) AS days_over_90,
+                   SUM(b.balance) AS total
+            FROM balances b
+            JOIN customers c ON c.id = b.customer_id
+            WHERE b.balance > 0
+            GROUP BY c.id, c.name, c.credit_limit
+            ORDER BY total DESC, c.name
+            "#
+        )
+        .bind(as_of)
+        .bind(customer_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
+
+    async fn find_balance_before(&self, customer_id: Uuid, date: NaiveDate) -> Result<Decimal, AppError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COALESCE((
+                       SELECT SUM(total) FROM invoices
+                       WHERE customer_id = $1 AND issue_date < $2
+                   ), 0)
+                 - COALESCE((
+                       SELECT SUM(p.amount)
+                       FROM invoice_payments p
+                       JOIN invoices i ON i.id = p.invoice_id
+                       WHERE i.customer_id = $1 AND p.paid_on < $2
+                   ), 0)
+            "#
+        )
+        .bind(customer_id)
+        .bind(date)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
+
+    async fn find_statement_entries(&self, customer_id: Uuid, from: NaiveDate, to: NaiveDate) -> Result<Vec<StatementEntry>, AppError> {
+        let rows = sqlx::query_as::<_, StatementRow>(
+            r#"
+            SELECT date, kind, invoice_id, invoice_number, reference, amount,
+                   SUM(amount) OVER (ORDER BY date, kind, created_at ROWS UNBOUNDED PRECEDING) AS balance
+            FROM (
+                SELECT i.issue_date AS date, 'INVOICE' AS kind, i.id AS invoice_id, i.invoice_number,
+                       NULL::varchar AS reference, i.total AS amount, i.created_at
+                FROM invoices i
+                WHERE i.customer_id = $1 AND i.issue_date BETWEEN $2 AND $3
+                UNION ALL
+                SELECT p.paid_on, 'PAYMENT', i.id, i.invoice_number, p.reference, -p.amount, p.created_at
+                FROM invoice_payments p
+                JOIN invoices i ON i.id = p.invoice_id
+                WHERE i.customer_id = $1 AND p.paid_on BETWEEN $2 AND $3
+            ) entries
+            ORDER BY date, kind, created_at
+            "#
+        )
+        .bind(customer_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(rows.into_iter().map(StatementEntry::from).collect())
+    }
+}
@@ -1,10 +1,12 @@
-use sqlx::PgPool;
+use rust_decimal::Decimal;
+use sqlx::{PgConnection, PgExecutor, PgPool};
 use uuid::Uuid;
 use crate::models::postgres::logistics::{
-    Customer, CreateCustomerDto,
-    TransportJob, CreateTransportJobDto, JobStatus,
+    Customer, CreateCustomerDto, UpdateCreditLimitDto,
+    TransportJob, CreateTransportJobDto, JobStatus, JobStatusHistory,
     Route, CreateRouteDto,
-    Shipment, CreateShipmentDto
+    Shipment, CreateShipmentDto,
+    JobLeg
 };
 use crate::error::AppError;
 use async_trait::async_trait;
@@ -17,6 +19,7 @@ pub trait CustomerRepositoryTrait: Send + Sync {
     async fn find_all(&self) -> Result<Vec<Customer>, AppError>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Customer>, AppError>;
     async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+    async fn update_credit_limit(&self, id: Uuid, dto: UpdateCreditLimitDto) -> Result<Option<Customer>, AppError>;
 }
 
 pub struct CustomerRepository {
@@ -27,6 +30,45 @@ impl CustomerRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// Locks the customer's row until the transaction ends.
+    pub(crate) async fn lock<'e>(executor: impl PgExecutor<'e>, id: Uuid) -> Result<Option<Customer>, AppError> {
+        sqlx::query_as::<_, Customer>("SELECT * FROM customers WHERE id = $1 AND deleted_at IS NULL FOR UPDATE")
+            .bind(id)
+            .fetch_optional(executor)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// What the customer owes or will owe: unpaid invoice balances plus the price of
+    /// jobs that are not cancelled and not invoiced yet.
+    pub(crate) async fn exposure<'e>(executor: impl PgExecutor<'e>, id: Uuid) -> Result<Decimal, AppError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COALESCE((
+                       SELECT SUM(i.total - COALESCE(p.amount_paid, 0))
+                       FROM invoices i
+                       LEFT JOIN (
+                           SELECT invoice_id, SUM(amount) AS amount_paid
+                           FROM invoice_payments
+                           GROUP BY invoice_id
+                       ) p ON p.invoice_id = i.id
+                       WHERE i.customer_id = $1 AND i.status <> 'PAID'
+                   ), 0)
+                 + COALESCE((
+                       SELECT SUM(j.agreed_price)
+                       FROM transport_jobs j
+                       WHERE j.customer_id = $1
+                         AND j.status IN ('PENDING', 'IN_PROGRESS', 'DELIVERED')
+                         AND NOT EXISTS (SELECT 1 FROM invoice_lines l WHERE l.job_id = j.id)
+                   ), 0)
+            "#
+        )
+        .bind(id)
+        .fetch_one(executor)
+        .await
+        .map_err(AppError::from)
+    }
 }
 
 #[async_trait]
@@ -36,9 +78,10 @@ impl CustomerRepositoryTrait for CustomerRepository {
         let customer = sqlx::query_as::<_, Customer>(
             r#"
             INSERT INTO customers (
-                id, name, contact_info, billing_address, created_at, updated_at
+                id, name, contact_info, billing_address, credit_limit, block_over_credit_limit,
+                created_at, updated_at
             )
-            VALUES ($1, $2, $3, $4, NOW(), NOW())
+            VALUES ($1, $2, $3, $4, $5, $6, NOW(), NOW())
             RETURNING *
             "#
         )
@@ -46,9 +89,11 @@ impl CustomerRepositoryTrait for CustomerRepository {
         .bind(dto.name)
         .bind(dto.contact_info)
         .bind(dto.billing_address)
+        .bind(dto.credit_limit)
+        .bind(dto.block_over_credit_limit)
         .fetch_one(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(customer)
     }
@@ -59,7 +104,7 @@ impl CustomerRepositoryTrait for CustomerRepository {
         )
         .fetch_all(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(customers)
     }
@@ -71,7 +116,7 @@ impl CustomerRepositoryTrait for CustomerRepository {
         .bind(id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(customer)
     }
@@ -83,7 +128,7 @@ impl CustomerRepositoryTrait for CustomerRepository {
         .bind(id)
         .execute(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         if result.rows_affected() == 0 {
             return Err(AppError::NotFound(format!("Customer with id {} not found", id)));
@@ -91,16 +136,44 @@ impl CustomerRepositoryTrait for CustomerRepository {
 
         Ok(())
     }
+
+    async fn update_credit_limit(&self, id: Uuid, dto: UpdateCreditLimitDto) -> Result<Option<Customer>, AppError> {
+        sqlx::query_as::<_, Customer>(
+            r#"
+            UPDATE customers
+            SET credit_limit = $2, block_over_credit_limit = $3, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING *
+            "#
+        )
+        .bind(id)
+        .bind(dto.credit_limit)
+        .bind(dto.block_over_credit_limit)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
 }
 
 // --- TransportJob Repository ---
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait TransportJobRepositoryTrait: Send + Sync {
-    async fn create(&self, dto: CreateTransportJobDto) -> Result<TransportJob, AppError>;
+    /// Records the starting status as the job's first history entry.
+    async fn create(&self, dto: CreateTransportJobDto, created_by: Option<Uuid>) -> Result<TransportJob, AppError>;
     async fn find_all(&self) -> Result<Vec<TransportJob>, AppError>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<TransportJob>, AppError>;
-    async fn update_status(&self, id: Uuid, status: JobStatus) -> Result<TransportJob, AppError>;
+    /// Changes the status and records it in the history, in one transaction. Returns
+    /// `None` if the job is no longer in `from`.
+    async fn update_status(
+        &self,
+        id: Uuid,
+        from: JobStatus,
+        to: JobStatus,
+        changed_by: Option<Uuid>,
+        reason: Option<String>,
+    ) -> Result<Option<TransportJob>, AppError>;
+    async fn find_status_history(&self, job_id: Uuid) -> Result<Vec<JobStatusHistory>, AppError>;
 }
 
 pub struct TransportJobRepository {
@@ -111,11 +184,9 @@ impl TransportJobRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
-}
 
-#[async_trait]
-impl TransportJobRepositoryTrait for TransportJobRepository {
-    async fn create(&self, dto: CreateTransportJobDto) -> Result<TransportJob, AppError> {
+    /// Inserts the job and its first history entry on `conn`, which should be in a transaction.
+    pub(crate) async fn insert(conn: &mut PgConnection, dto: CreateTransportJobDto, created_by: Option<Uuid>) -> Result<TransportJob, AppError> {
         let id = Uuid::new_v4();
         let job = sqlx::query_as::<_, TransportJob>(
             r#"
@@ -130,9 +201,47 @@ impl TransportJobRepositoryTrait for TransportJobRepository {
         .bind(dto.customer_id)
         .bind(dto.status)
         .bind(dto.agreed_price)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(AppError::from)?;
+
+        Self::record_status(&mut *conn, job.id, None, job.status, created_by, None).await?;
+        Ok(job)
+    }
+
+    async fn record_status<'e>(
+        executor: impl PgExecutor<'e>,
+        job_id: Uuid,
+        from: Option<JobStatus>,
+        to: JobStatus,
+        changed_by: Option<Uuid>,
+        reason: Option<String>,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO job_status_history (job_id, from_status, to_status, changed_by, reason)
+            VALUES ($1, $2, $3, $4, $5)
+            "#
+        )
+        .bind(job_id)
+        .bind(from)
+        .bind(to)
+        .bind(changed_by)
+        .bind(reason)
+        .execute(executor)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TransportJobRepositoryTrait for TransportJobRepository {
+    async fn create(&self, dto: CreateTransportJobDto, created_by: Option<Uuid>) -> Result<TransportJob, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+        let job = Self::insert(&mut tx, dto, created_by).await?;
+        tx.commit().await.map_err(AppError::from)?;
 
         Ok(job)
     }
@@ -143,7 +252,7 @@ impl TransportJobRepositoryTrait for TransportJobRepository {
         )
         .fetch_all(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(jobs)
     }
@@ -155,27 +264,62 @@ impl TransportJobRepositoryTrait for TransportJobRepository {
         .bind(id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(job)
     }
 
-    async fn update_status(&self, id: Uuid, status: JobStatus) -> Result<TransportJob, AppError> {
+    async fn update_status(
+        &self,
+        id: Uuid,
+        from: JobStatus,
+        to: JobStatus,
+        changed_by: Option<Uuid>,
+        reason: Option<String>,
+    ) -> Result<Option<TransportJob>, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
         let job = sqlx::query_as::<_, TransportJob>(
             r#"
             UPDATE transport_jobs 
-            SET status = $1, updated_at = NOW()
-            WHERE id = $2
+            SET status = $3, updated_at = NOW()
+            WHERE id = $1 AND status = $2
             RETURNING *
             "#
         )
-        .bind(status)
         .bind(id)
-        .fetch_one(&self.pool)
+        .bind(from)
+        .bind(to)
+        .fetch_optional(&mut *tx)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
-        Ok(job)
+        let Some(job) = job else {
+            return Ok(None);
+        };
+        Self::record_status(&mut *tx, id, Some(from), to, changed_by, reason).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(Some(job))
+    }
+
+    async fn find_status_history(&self, job_id: Uuid) -> Result<Vec<JobStatusHistory>, AppError> {
+        let history = sqlx::query_as::<_, JobStatusHistory>(
+            r#"
+            SELECT h.id, h.job_id, h.from_status, h.to_status, h.changed_by,
+                   u.name AS changed_by_name, h.reason, h.changed_at
+            FROM job_status_history h
+            LEFT JOIN users u ON u.id = h.changed_by
+            WHERE h.job_id = $1
+            ORDER BY h.changed_at, h.id
+            "#
+        )
+        .bind(job_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(history)
     }
 }
 
@@ -230,7 +374,7 @@ impl RouteRepositoryTrait for RouteRepository {
         .bind(dto.waypoints)
         .fetch_one(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(route)
     }
@@ -251,7 +395,7 @@ impl RouteRepositoryTrait for RouteRepository {
         .bind(job_id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(route)
     }
@@ -295,7 +439,7 @@ impl ShipmentRepositoryTrait for ShipmentRepository {
         .bind(dto.r#type)
         .fetch_one(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(shipment)
     }
@@ -307,8 +451,109 @@ impl ShipmentRepositoryTrait for ShipmentRepository {
         .bind(job_id)
         .fetch_all(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(shipments)
     }
 }
+
+// --- Job Leg Repository ---
+const JOB_LEG_SELECT: &str = r#"
+    SELECT
+        ja.job_id,
+        ja.leg_number,
+        a.id AS assignment_id,
+        a.vehicle_id,
+        v.license_plate,
+        a.driver_id,
+        u.name AS driver_name,
+        a.start_time,
+        a.end_time,
+        a.status
+    FROM job_assignments ja
+    JOIN vehicle_assignments a ON a.id = ja.assignment_id
+    JOIN vehicles v ON v.id = a.vehicle_id
+    JOIN drivers d ON d.id = a.driver_id
+    LEFT JOIN users u ON u.id = d.user_id
+"#;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait JobLegRepositoryTrait: Send + Sync {
+    async fn create(&self, job_id: Uuid, assignment_id: Uuid, leg_number: Option<i32>) -> Result<JobLeg, AppError>;
+    async fn find_by_job_id(&self, job_id: Uuid) -> Result<Vec<JobLeg>, AppError>;
+    /// Returns `false` if the assignment is not a leg of the job.
+    async fn delete(&self, job_id: Uuid, assignment_id: Uuid) -> Result<bool, AppError>;
+}
+
+pub struct JobLegRepository {
+    pool: PgPool,
+}
+
+impl JobLegRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Also runs inside a unit of work when dispatch creates the assignment. Without a
+    /// leg number the assignment becomes the job's next leg.
+    pub(crate) async fn insert<'e>(
+        executor: impl PgExecutor<'e>,
+        job_id: Uuid,
+        assignment_id: Uuid,
+        leg_number: Option<i32>,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO job_assignments (assignment_id, job_id, leg_number)
+            SELECT $1, $2, COALESCE($3, MAX(leg_number) + 1, 1)
+            FROM job_assignments
+            WHERE job_id = $2
+            "#
+        )
+        .bind(assignment_id)
+        .bind(job_id)
+        .bind(leg_number)
+        .execute(executor)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl JobLegRepositoryTrait for JobLegRepository {
+    async fn create(&self, job_id: Uuid, assignment_id: Uuid, leg_number: Option<i32>) -> Result<JobLeg, AppError> {
+        Self::insert(&self.pool, job_id, assignment_id, leg_number).await?;
+
+        let leg = sqlx::query_as::<_, JobLeg>(&format!("{JOB_LEG_SELECT} WHERE ja.assignment_id = $1"))
+            .bind(assignment_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(leg)
+    }
+
+    async fn find_by_job_id(&self, job_id: Uuid) -> Result<Vec<JobLeg>, AppError> {
+        let legs = sqlx::query_as::<_, JobLeg>(&format!("{JOB_LEG_SELECT} WHERE ja.job_id = $1 ORDER BY ja.leg_number"))
+            .bind(job_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(legs)
+    }
+
+    async fn delete(&self, job_id: Uuid, assignment_id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM job_assignments WHERE job_id = $1 AND assignment_id = $2")
+            .bind(job_id)
+            .bind(assignment_id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
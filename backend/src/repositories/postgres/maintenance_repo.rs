@@ -2,7 +2,7 @@ use sqlx::PgPool;
 use uuid::Uuid;
 use crate::models::postgres::maintenance::{
     MaintenanceRecord, CreateMaintenanceRecordDto,
-    MaintenanceSchedule, CreateMaintenanceScheduleDto,
+    MaintenanceSchedule, CreateMaintenanceScheduleDto, VehicleServiceBaseline,
     Alert, CreateAlertDto
 };
 use crate::models::postgres::vehicle::VehicleType;
@@ -34,9 +34,12 @@ impl MaintenanceRecordRepositoryTrait for MaintenanceRecordRepository {
         let record = sqlx::query_as::<_, MaintenanceRecord>(
             r#"
             INSERT INTO maintenance_records (
-                id, vehicle_id, type, cost, date, provider, description, created_at
+                id, vehicle_id, type, cost, date, provider, description, created_at, mileage
+            )
+            VALUES (
+                $1, $2, $3, $4, $5, $6, $7, NOW(),
+                COALESCE($8, (SELECT current_mileage FROM vehicles WHERE id = $2))
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
             RETURNING *
             "#
         )
@@ -47,9 +50,10 @@ impl MaintenanceRecordRepositoryTrait for MaintenanceRecordRepository {
         .bind(dto.date)
         .bind(dto.provider)
         .bind(dto.description)
+        .bind(dto.mileage)
         .fetch_one(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(record)
     }
@@ -61,7 +65,7 @@ impl MaintenanceRecordRepositoryTrait for MaintenanceRecordRepository {
         .bind(vehicle_id)
         .fetch_all(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(records)
     }
@@ -73,6 +77,8 @@ impl MaintenanceRecordRepositoryTrait for MaintenanceRecordRepository {
 pub trait MaintenanceScheduleRepositoryTrait: Send + Sync {
     async fn create(&self, dto: CreateMaintenanceScheduleDto) -> Result<MaintenanceSchedule, AppError>;
     async fn find_by_vehicle_type(&self, vehicle_type: VehicleType) -> Result<Option<MaintenanceSchedule>, AppError>;
+    /// Every in-service vehicle whose type has a schedule, with its latest preventive record.
+    async fn find_service_baselines(&self) -> Result<Vec<VehicleServiceBaseline>, AppError>;
 }
 
 pub struct MaintenanceScheduleRepository {
@@ -104,7 +110,7 @@ impl MaintenanceScheduleRepositoryTrait for MaintenanceScheduleRepository {
         .bind(dto.interval_months)
         .fetch_one(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(schedule)
     }
@@ -116,10 +122,53 @@ impl MaintenanceScheduleRepositoryTrait for MaintenanceScheduleRepository {
         .bind(vehicle_type)
         .fetch_optional(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(schedule)
     }
+
+    async fn find_service_baselines(&self) -> Result<Vec<VehicleServiceBaseline>, AppError> {
+        // Several schedules for one type are allowed. They share the same starting point, so
+        // the shortest km and month intervals give the earliest due points.
+        let baselines = sqlx::query_as::<_, VehicleServiceBaseline>(
+            r#"
+            SELECT
+                v.id AS vehicle_id,
+                v.license_plate,
+                v.type AS vehicle_type,
+                v.current_mileage,
+                v.initial_mileage,
+                v.created_at AS vehicle_created_at,
+                s.interval_km,
+                s.interval_months,
+                last.date AS last_service_date,
+                last.mileage AS last_service_mileage
+            FROM vehicles v
+            CROSS JOIN LATERAL (
+                SELECT
+                    MIN(interval_km) FILTER (WHERE interval_km > 0) AS interval_km,
+                    MIN(interval_months) FILTER (WHERE interval_months > 0) AS interval_months
+                FROM maintenance_schedules
+                WHERE vehicle_type = v.type
+                HAVING COUNT(*) > 0
+            ) s
+            LEFT JOIN LATERAL (
+                SELECT date, mileage
+                FROM maintenance_records
+                WHERE vehicle_id = v.id AND type = 'PREVENTIVE'
+                ORDER BY date DESC
+                LIMIT 1
+            ) last ON TRUE
+            WHERE v.deleted_at IS NULL AND v.status <> 'OUT_OF_SERVICE'
+            ORDER BY v.license_plate
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(baselines)
+    }
 }
 
 // --- Alert Repository ---
@@ -127,8 +176,14 @@ impl MaintenanceScheduleRepositoryTrait for MaintenanceScheduleRepository {
 #[async_trait]
 pub trait AlertRepositoryTrait: Send + Sync {
     async fn create(&self, dto: CreateAlertDto) -> Result<Alert, AppError>;
+    /// Inserts the alert unless an unresolved one of the same type already exists for the
+    /// entity. Returns `None` when it was suppressed.
+    async fn create_unless_open(&self, dto: CreateAlertDto) -> Result<Option<Alert>, AppError>;
     async fn find_unresolved(&self) -> Result<Vec<Alert>, AppError>;
+    async fn find_unresolved_by_entity(&self, entity_id: Uuid) -> Result<Vec<Alert>, AppError>;
     async fn resolve(&self, id: Uuid) -> Result<Alert, AppError>;
+    /// Resolves every open alert of the given types for an entity. Returns how many were closed.
+    async fn resolve_open(&self, entity_id: Uuid, types: Vec<String>) -> Result<u64, AppError>;
 }
 
 pub struct AlertRepository {
@@ -148,9 +203,9 @@ impl AlertRepositoryTrait for AlertRepository {
         let alert = sqlx::query_as::<_, Alert>(
             r#"
             INSERT INTO alerts (
-                id, entity_id, type, severity, is_resolved, created_at
+                id, entity_id, type, severity, is_resolved, created_at, message
             )
-            VALUES ($1, $2, $3, $4, FALSE, NOW())
+            VALUES ($1, $2, $3, $4, FALSE, NOW(), $5)
             RETURNING *
             "#
         )
@@ -158,9 +213,38 @@ impl AlertRepositoryTrait for AlertRepository {
         .bind(dto.entity_id)
         .bind(dto.r#type)
         .bind(dto.severity)
+        .bind(dto.message)
         .fetch_one(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
+
+        Ok(alert)
+    }
+
+    async fn create_unless_open(&self, dto: CreateAlertDto) -> Result<Option<Alert>, AppError> {
+        let id = Uuid::new_v4();
+        let alert = sqlx::query_as::<_, Alert>(
+            r#"
+            INSERT INTO alerts (
+                id, entity_id, type, severity, is_resolved, created_at, message
+            )
+            SELECT $1, $2, $3, $4, FALSE, NOW(), $5
+            WHERE NOT EXISTS (
+                SELECT 1 FROM alerts WHERE entity_id = $2 AND type = $3 AND is_resolved = FALSE
+            )
+            -- Concurrent inserts of the system-raised types are settled by idx_alerts_open_by_entity
+            ON CONFLICT DO NOTHING
+            RETURNING *
+            "#
+        )
+        .bind(id)
+        .bind(dto.entity_id)
+        .bind(dto.r#type)
+        .bind(dto.severity)
+        .bind(dto.message)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
 
         Ok(alert)
     }
@@ -171,7 +255,19 @@ impl AlertRepositoryTrait for AlertRepository {
         )
         .fetch_all(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
+
+        Ok(alerts)
+    }
+
+    async fn find_unresolved_by_entity(&self, entity_id: Uuid) -> Result<Vec<Alert>, AppError> {
+        let alerts = sqlx::query_as::<_, Alert>(
+            "SELECT * FROM alerts WHERE entity_id = $1 AND is_resolved = FALSE ORDER BY created_at DESC"
+        )
+        .bind(entity_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
 
         Ok(alerts)
     }
@@ -188,8 +284,25 @@ impl AlertRepositoryTrait for AlertRepository {
         .bind(id)
         .fetch_one(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(alert)
     }
+
+    async fn resolve_open(&self, entity_id: Uuid, types: Vec<String>) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE alerts
+            SET is_resolved = TRUE, resolved_at = NOW()
+            WHERE entity_id = $1 AND type = ANY($2) AND is_resolved = FALSE
+            "#
+        )
+        .bind(entity_id)
+        .bind(types)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(result.rows_affected())
+    }
 }
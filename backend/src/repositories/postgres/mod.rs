@@ -7,3 +7,18 @@ pub mod telemetry_repo;
 pub mod user_repo;
 pub mod vehicle_repo;
 pub mod settings_repo;
+pub mod role_repo;
+pub mod token_repo;
+pub mod partition_repo;
+pub mod geofence_repo;
+pub mod odometer_repo;
+pub mod work_order_repo;
+pub mod part_repo;
+pub mod hos_repo;
+pub mod driver_score_repo;
+pub mod unit_of_work;
+pub mod dispatch_repo;
+pub mod proof_of_delivery_repo;
+pub mod vehicle_document_repo;
+pub mod invoice_repo;
+pub mod quote_repo;
@@ -0,0 +1,201 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::models::postgres::odometer::{OdometerCursor, DistanceMeasurement, OdometerUpdate, DailyDistanceRow};
+use crate::error::AppError;
+use async_trait::async_trait;
+
+/// Consecutive readings of a vehicle in `[$2, $3]`, starting from the last reading at or
+/// before `$2` so the first segment is not lost. Each row carries the segment ending at it.
+/// GPS segments are dropped when both ends are stationary (fix jitter) or when they imply
+/// an impossible speed (bad fix).
+const SEGMENTS_CTE: &str = r#"
+    points AS (
+        SELECT
+            time,
+            location,
+            speed,
+            CASE WHEN jsonb_typeof(engine_status->'odometer') = 'number'
+                 THEN (engine_status->>'odometer')::float8 END AS odometer
+        FROM vehicle_telemetry
+        WHERE vehicle_id = $1
+          AND time <= $3
+          AND time >= COALESCE(
+              (SELECT MAX(time) FROM vehicle_telemetry WHERE vehicle_id = $1 AND time <= $2),
+              $2
+          )
+    ),
+    raw_segments AS (
+        SELECT
+            time,
+            odometer,
+            odometer - LAG(odometer) OVER w AS odometer_delta,
+            ST_DistanceSphere(LAG(location) OVER w, location) AS meters,
+            EXTRACT(EPOCH FROM time - LAG(time) OVER w)::float8 AS secs,
+            speed,
+            LAG(speed) OVER w AS prev_speed
+        FROM points
+        WINDOW w AS (ORDER BY time)
+    ),
+    segments AS (
+        SELECT
+            time,
+            odometer,
+            CASE WHEN odometer_delta >= 0 THEN odometer_delta END AS odometer_delta,
+            CASE
+                WHEN meters IS NULL OR secs <= 0 THEN 0
+                WHEN speed < 1 AND prev_speed < 1 THEN 0
+                WHEN meters / secs * 3.6 > 250 THEN 0
+                ELSE meters
+            END AS meters
+        FROM raw_segments
+    )
+"#;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait OdometerRepositoryTrait: Send + Sync {
+    /// Vehicles with readings after their sync cursor, up to `until`. Vehicles never synced
+    /// start from their creation time, when current_mileage was entered by hand.
+    async fn find_pending(&self, until: DateTime<Utc>) -> Result<Vec<OdometerCursor>, AppError>;
+    async fn measure(&self, vehicle_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<DistanceMeasurement, AppError>;
+    /// Moves the cursor and updates current_mileage in one transaction. Returns false
+    /// without changing anything if another worker moved the cursor first.
+    async fn apply(&self, update: OdometerUpdate) -> Result<bool, AppError>;
+    /// Distance per UTC day for days in `[from, to)`.
+    async fn find_daily_distance(&self, vehicle_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<DailyDistanceRow>, AppError>;
+}
+
+pub struct OdometerRepository {
+    pool: PgPool,
+}
+
+impl OdometerRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OdometerRepositoryTrait for OdometerRepository {
+    async fn find_pending(&self, until: DateTime<Utc>) -> Result<Vec<OdometerCursor>, AppError> {
+        let cursors = sqlx::query_as::<_, OdometerCursor>(
+            r#"
+            SELECT
+                v.id AS vehicle_id,
+                COALESCE(s.synced_until, v.created_at) AS synced_until,
+                COALESCE(s.remainder_m, 0) AS remainder_m
+            FROM vehicles v
+            LEFT JOIN vehicle_odometer_sync s ON s.vehicle_id = v.id
+            WHERE v.deleted_at IS NULL
+              AND EXISTS (
+                  SELECT 1 FROM vehicle_telemetry t
+                  WHERE t.vehicle_id = v.id
+                    AND t.time > COALESCE(s.synced_until, v.created_at)
+                    AND t.time <= $1
+              )
+            "#
+        )
+        .bind(until)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(cursors)
+    }
+
+    async fn measure(&self, vehicle_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<DistanceMeasurement, AppError> {
+        let query = format!(
+            r#"
+            WITH {SEGMENTS_CTE}
+            SELECT
+                COALESCE(SUM(meters), 0)::float8 AS distance_m,
+                (SELECT odometer FROM segments WHERE odometer IS NOT NULL ORDER BY time DESC LIMIT 1) AS odometer_km,
+                MAX(time) AS last_time
+            FROM segments
+            "#
+        );
+        let measurement = sqlx::query_as::<_, DistanceMeasurement>(&query)
+            .bind(vehicle_id)
+            .bind(from)
+            .bind(to)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(measurement)
+    }
+
+    async fn apply(&self, update: OdometerUpdate) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        // Claim the cursor first; a concurrent run that already moved it wins
+        let claimed = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO vehicle_odometer_sync (vehicle_id, synced_until, remainder_m, updated_at)
+            VALUES ($1, $3, $4, NOW())
+            ON CONFLICT (vehicle_id) DO UPDATE
+            SET synced_until = EXCLUDED.synced_until,
+                remainder_m = EXCLUDED.remainder_m,
+                updated_at = NOW()
+            WHERE vehicle_odometer_sync.synced_until = $2
+            RETURNING vehicle_id
+            "#
+        )
+        .bind(update.vehicle_id)
+        .bind(update.previous_until)
+        .bind(update.synced_until)
+        .bind(update.remainder_m)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        if claimed.is_none() {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE vehicles
+            SET current_mileage = GREATEST(current_mileage + $2, COALESCE($3, 0)),
+                updated_at = NOW()
+            WHERE id = $1
+            "#
+        )
+        .bind(update.vehicle_id)
+        .bind(update.add_km)
+        .bind(update.odometer_km)
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(true)
+    }
+
+    async fn find_daily_distance(&self, vehicle_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<DailyDistanceRow>, AppError> {
+        // Segments are credited to the day they end in
+        let query = format!(
+            r#"
+            WITH {SEGMENTS_CTE}
+            SELECT
+                (time AT TIME ZONE 'UTC')::date AS day,
+                COALESCE(SUM(meters), 0)::float8 AS gps_m,
+                SUM(odometer_delta)::float8 AS odometer_km
+            FROM segments
+            WHERE time > $2 AND time < $3
+            GROUP BY 1
+            ORDER BY 1
+            "#
+        );
+        let rows = sqlx::query_as::<_, DailyDistanceRow>(&query)
+            .bind(vehicle_id)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(rows)
+    }
+}
@@ -0,0 +1,178 @@
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+use crate::models::postgres::part::{Part, CreatePartDto, UpdatePartDto, PartQuery, StockMovement, NewStockMovement};
+use crate::error::AppError;
+use async_trait::async_trait;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PartRepositoryTrait: Send + Sync {
+    async fn create(&self, dto: CreatePartDto) -> Result<Part, AppError>;
+    async fn find_all(&self, query: PartQuery) -> Result<Vec<Part>, AppError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Part>, AppError>;
+    async fn update(&self, id: Uuid, dto: UpdatePartDto) -> Result<Option<Part>, AppError>;
+    /// Applies the movement and logs it. Returns `None` if it would take stock below zero.
+    async fn record_movement(&self, movement: NewStockMovement) -> Result<Option<Part>, AppError>;
+    async fn find_movements(&self, part_id: Uuid) -> Result<Vec<StockMovement>, AppError>;
+}
+
+/// Changes the stock level and writes the matching ledger row on the caller's connection,
+/// so other repositories can consume stock inside their own transactions. Returns `None`
+/// without writing anything if the part would go below zero.
+pub(crate) async fn apply_movement(conn: &mut PgConnection, movement: &NewStockMovement) -> Result<Option<Part>, AppError> {
+    // Receipts reprice the part at what was last paid for it
+    let part = sqlx::query_as::<_, Part>(
+        r#"
+        UPDATE parts
+        SET quantity_on_hand = quantity_on_hand + $2,
+            unit_cost = CASE WHEN $3 = 'RECEIPT' THEN COALESCE($4, unit_cost) ELSE unit_cost END,
+            updated_at = NOW()
+        WHERE id = $1 AND quantity_on_hand + $2 >= 0
+        RETURNING *
+        "#
+    )
+    .bind(movement.part_id)
+    .bind(movement.quantity)
+    .bind(movement.kind)
+    .bind(movement.unit_cost)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(AppError::from)?;
+
+    let part = match part {
+        Some(part) => part,
+        None => return Ok(None),
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO stock_movements (
+            part_id, kind, quantity, unit_cost, vehicle_id, work_order_id, maintenance_record_id, note
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        "#
+    )
+    .bind(movement.part_id)
+    .bind(movement.kind)
+    .bind(movement.quantity)
+    .bind(movement.unit_cost.unwrap_or(part.unit_cost))
+    .bind(movement.vehicle_id)
+    .bind(movement.work_order_id)
+    .bind(movement.maintenance_record_id)
+    .bind(&movement.note)
+    .execute(&mut *conn)
+    .await
+    .map_err(AppError::from)?;
+
+    Ok(Some(part))
+}
+
+pub struct PartRepository {
+    pool: PgPool,
+}
+
+impl PartRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PartRepositoryTrait for PartRepository {
+    async fn create(&self, dto: CreatePartDto) -> Result<Part, AppError> {
+        let part = sqlx::query_as::<_, Part>(
+            r#"
+            INSERT INTO parts (sku, description, unit_cost, reorder_threshold, compatible_vehicle_types)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#
+        )
+        .bind(dto.sku)
+        .bind(dto.description)
+        .bind(dto.unit_cost)
+        .bind(dto.reorder_threshold)
+        .bind(dto.compatible_vehicle_types)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(part)
+    }
+
+    async fn find_all(&self, query: PartQuery) -> Result<Vec<Part>, AppError> {
+        let parts = sqlx::query_as::<_, Part>(
+            r#"
+            SELECT * FROM parts
+            WHERE ($1::vehicle_type IS NULL
+                   OR cardinality(compatible_vehicle_types) = 0
+                   OR $1 = ANY(compatible_vehicle_types))
+              AND (NOT $2 OR quantity_on_hand <= reorder_threshold)
+            ORDER BY sku
+            "#
+        )
+        .bind(query.vehicle_type)
+        .bind(query.low_stock)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(parts)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Part>, AppError> {
+        let part = sqlx::query_as::<_, Part>("SELECT * FROM parts WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(part)
+    }
+
+    async fn update(&self, id: Uuid, dto: UpdatePartDto) -> Result<Option<Part>, AppError> {
+        let part = sqlx::query_as::<_, Part>(
+            r#"
+            UPDATE parts
+            SET description = $2,
+                unit_cost = $3,
+                reorder_threshold = $4,
+                compatible_vehicle_types = $5,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#
+        )
+        .bind(id)
+        .bind(dto.description)
+        .bind(dto.unit_cost)
+        .bind(dto.reorder_threshold)
+        .bind(dto.compatible_vehicle_types)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(part)
+    }
+
+    async fn record_movement(&self, movement: NewStockMovement) -> Result<Option<Part>, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+        let part = apply_movement(&mut tx, &movement).await?;
+        if part.is_some() {
+            tx.commit().await.map_err(AppError::from)?;
+        }
+        Ok(part)
+    }
+
+    async fn find_movements(&self, part_id: Uuid) -> Result<Vec<StockMovement>, AppError> {
+        let movements = sqlx::query_as::<_, StockMovement>(
+            "SELECT * FROM stock_movements WHERE part_id = $1 ORDER BY created_at DESC"
+        )
+        .bind(part_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(movements)
+    }
+}
+
@@ -0,0 +1,235 @@
+use sqlx::PgPool;
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use crate::error::AppError;
+use async_trait::async_trait;
+
+/// Serializes partition DDL across backend instances.
+const PARTITION_LOCK_KEY: i64 = 0x7465_6c65_6d65_7472;
+
+const PARTITION_PREFIX: &str = "vehicle_telemetry_y";
+
+/// Name of the partition holding `month`, e.g. `vehicle_telemetry_y2026m10`.
+pub fn partition_name(month: NaiveDate) -> String {
+    format!("{}{:04}m{:02}", PARTITION_PREFIX, month.year(), month.month())
+}
+
+/// Inverse of [`partition_name`]; `None` for tables that do not follow the convention.
+pub fn partition_month(name: &str) -> Option<NaiveDate> {
+    let rest = name.strip_prefix(PARTITION_PREFIX)?;
+    let (year, month) = rest.split_once('m')?;
+    NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, 1)
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait TelemetryPartitionRepositoryTrait: Send + Sync {
+    /// First day of each month that has a monthly partition attached.
+    async fn find_partition_months(&self) -> Result<Vec<NaiveDate>, AppError>;
+    /// First day of each month with rows still sitting in the default partition.
+    async fn find_default_partition_months(&self) -> Result<Vec<NaiveDate>, AppError>;
+    /// Creates the partition for `month`, moving matching rows out of the default
+    /// partition. Returns the number of rows moved; a no-op if it already exists.
+    async fn create_partition(&self, month: NaiveDate) -> Result<u64, AppError>;
+    async fn delete_default_rows_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError>;
+    async fn drop_partition(&self, month: NaiveDate) -> Result<(), AppError>;
+    /// Detaches the partition and renames it `archived_<name>`, keeping the data queryable.
+    async fn archive_partition(&self, month: NaiveDate) -> Result<(), AppError>;
+    /// Moves rows for `month` out of the default partition into `archived_<name>`,
+    /// creating that table if needed. Returns the number of rows moved.
+    async fn archive_default_rows(&self, month: NaiveDate) -> Result<u64, AppError>;
+}
+
+pub struct TelemetryPartitionRepository {
+    pool: PgPool,
+}
+
+impl TelemetryPartitionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn lock(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>) -> Result<(), AppError> {
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(PARTITION_LOCK_KEY)
+            .execute(&mut **tx)
+            .await
+            .map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn exists(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, name: &str) -> Result<bool, AppError> {
+        sqlx::query_scalar::<_, bool>("SELECT to_regclass($1) IS NOT NULL")
+            .bind(name)
+            .fetch_one(&mut **tx)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Moves the default partition's rows for `month` into `table`.
+    async fn move_default_rows(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        table: &str,
+        month: NaiveDate,
+    ) -> Result<u64, AppError> {
+        let start = month.with_day(1).unwrap_or(month);
+        let end = start + Months::new(1);
+
+        let result = sqlx::query(&format!(
+            r#"
+            WITH moved AS (
+                DELETE FROM vehicle_telemetry_default
+                WHERE time >= $1 AND time < $2
+                RETURNING time, vehicle_id, location, speed, fuel_level, engine_status
+            )
+            INSERT INTO {} (time, vehicle_id, location, speed, fuel_level, engine_status)
+            SELECT time, vehicle_id, location, speed, fuel_level, engine_status FROM moved
+            "#,
+            table
+        ))
+        .bind(start.and_hms_opt(0, 0, 0).map(|t| t.and_utc()))
+        .bind(end.and_hms_opt(0, 0, 0).map(|t| t.and_utc()))
+        .execute(&mut **tx)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+// Partition names and bounds are derived from `NaiveDate` values only, so formatting
+// them into DDL cannot inject SQL.
+#[async_trait]
+impl TelemetryPartitionRepositoryTrait for TelemetryPartitionRepository {
+    async fn find_partition_months(&self) -> Result<Vec<NaiveDate>, AppError> {
+        let names = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT c.relname::text
+            FROM pg_inherits i
+            JOIN pg_class c ON c.oid = i.inhrelid
+            WHERE i.inhparent = 'vehicle_telemetry'::regclass
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        let mut months: Vec<NaiveDate> = names.iter().filter_map(|n| partition_month(n)).collect();
+        months.sort();
+        Ok(months)
+    }
+
+    async fn find_default_partition_months(&self) -> Result<Vec<NaiveDate>, AppError> {
+        let months = sqlx::query_scalar::<_, NaiveDate>(
+            r#"
+            SELECT DISTINCT date_trunc('month', time AT TIME ZONE 'UTC')::date
+            FROM vehicle_telemetry_default
+            ORDER BY 1
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(months)
+    }
+
+    async fn create_partition(&self, month: NaiveDate) -> Result<u64, AppError> {
+        let name = partition_name(month);
+        let start = month.with_day(1).unwrap_or(month);
+        let end = start + Months::new(1);
+
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+        Self::lock(&mut tx).await?;
+
+        if Self::exists(&mut tx, &name).await? {
+            tx.commit().await.map_err(AppError::from)?;
+            return Ok(0);
+        }
+
+        // A partition cannot be attached while the default partition holds rows in its
+        // range, so build it detached, move the rows across, then attach.
+        sqlx::query(&format!(
+            "CREATE TABLE {} (LIKE vehicle_telemetry INCLUDING DEFAULTS INCLUDING CONSTRAINTS)",
+            name
+        ))
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        let moved = Self::move_default_rows(&mut tx, &name, month).await?;
+
+        sqlx::query(&format!(
+            "ALTER TABLE vehicle_telemetry ATTACH PARTITION {} FOR VALUES FROM ('{} 00:00:00+00') TO ('{} 00:00:00+00')",
+            name, start, end
+        ))
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(moved)
+    }
+
+    async fn delete_default_rows_before(&self, cutoff: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM vehicle_telemetry_default WHERE time < $1")
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn drop_partition(&self, month: NaiveDate) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+        Self::lock(&mut tx).await?;
+
+        sqlx::query(&format!("DROP TABLE IF EXISTS {}", partition_name(month)))
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn archive_partition(&self, month: NaiveDate) -> Result<(), AppError> {
+        let name = partition_name(month);
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+        Self::lock(&mut tx).await?;
+
+        if Self::exists(&mut tx, &name).await? {
+            sqlx::query(&format!("ALTER TABLE vehicle_telemetry DETACH PARTITION {}", name))
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::from)?;
+
+            sqlx::query(&format!("ALTER TABLE {} RENAME TO archived_{}", name, name))
+                .execute(&mut *tx)
+                .await
+                .map_err(AppError::from)?;
+        }
+
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(())
+    }
+
+    async fn archive_default_rows(&self, month: NaiveDate) -> Result<u64, AppError> {
+        let name = format!("archived_{}", partition_name(month));
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+        Self::lock(&mut tx).await?;
+
+        sqlx::query(&format!(
+            "CREATE TABLE IF NOT EXISTS {} (LIKE vehicle_telemetry INCLUDING DEFAULTS INCLUDING CONSTRAINTS)",
+            name
+        ))
+        .execute(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        let moved = Self::move_default_rows(&mut tx, &name, month).await?;
+
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(moved)
+    }
+}
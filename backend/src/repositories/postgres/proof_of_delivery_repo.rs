@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::proof_of_delivery::{
+    ProofOfDelivery, ProofOfDeliveryFile, ProofOfDeliveryDetails, CreateProofOfDeliveryDto, NewProofOfDeliveryFile
+};
+
+const POD_SELECT: &str = r#"
+    SELECT p.id, p.job_id, p.recipient_name,
+           ST_Y(p.location) AS latitude, ST_X(p.location) AS longitude,
+           p.notes, p.captured_by, u.name AS captured_by_name, p.captured_at
+    FROM proof_of_delivery p
+    LEFT JOIN users u ON u.id = p.captured_by
+"#;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ProofOfDeliveryRepositoryTrait: Send + Sync {
+    /// Records the proof and its already stored files in one transaction.
+    async fn create(
+        &self,
+        job_id: Uuid,
+        dto: &CreateProofOfDeliveryDto,
+        captured_by: Option<Uuid>,
+        files: Vec<NewProofOfDeliveryFile>,
+    ) -> Result<ProofOfDeliveryDetails, AppError>;
+    async fn find_by_job_id(&self, job_id: Uuid) -> Result<Option<ProofOfDeliveryDetails>, AppError>;
+    async fn exists_for_job(&self, job_id: Uuid) -> Result<bool, AppError>;
+    async fn find_file(&self, job_id: Uuid, file_id: Uuid) -> Result<Option<ProofOfDeliveryFile>, AppError>;
+}
+
+pub struct ProofOfDeliveryRepository {
+    pool: PgPool,
+}
+
+impl ProofOfDeliveryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn fetch_proof<'e>(executor: impl PgExecutor<'e>, job_id: Uuid) -> Result<Option<ProofOfDelivery>, AppError> {
+        sqlx::query_as::<_, ProofOfDelivery>(&format!("{} WHERE p.job_id = $1", POD_SELECT))
+            .bind(job_id)
+            .fetch_optional(executor)
+            .await
+            .map_err(AppError::from)
+    }
+
+    async fn fetch_files<'e>(executor: impl PgExecutor<'e>, pod_id: Uuid) -> Result<Vec<ProofOfDeliveryFile>, AppError> {
+        sqlx::query_as::<_, ProofOfDeliveryFile>(
+            "SELECT * FROM proof_of_delivery_files WHERE pod_id = $1 ORDER BY kind, created_at, id"
+        )
+        .bind(pod_id)
+        .fetch_all(executor)
+        .await
+        .map_err(AppError::from)
+    }
+}
+
+#[async_trait]
+impl ProofOfDeliveryRepositoryTrait for ProofOfDeliveryRepository {
+    async fn create(
+        &self,
+        job_id: Uuid,
+        dto: &CreateProofOfDeliveryDto,
+        captured_by: Option<Uuid>,
+        files: Vec<NewProofOfDeliveryFile>,
+    ) -> Result<ProofOfDeliveryDetails, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let pod_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO proof_of_delivery (job_id, recipient_name, location, notes, captured_by)
+            VALUES ($1, $2, ST_SetSRID(ST_MakePoint($3, $4), 4326), $5, $6)
+            RETURNING id
+            "#
+        )
+        .bind(job_id)
+        .bind(&dto.recipient_name)
+        .bind(dto.longitude)
+        .bind(dto.latitude)
+        .bind(&dto.notes)
+        .bind(captured_by)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        for file in files {
+            sqlx::query(
+                r#"
+                INSERT INTO proof_of_delivery_files (pod_id, kind, storage_key, content_type, size_bytes)
+                VALUES ($1, $2, $3, $4, $5)
+                "#
+            )
+            .bind(pod_id)
+            .bind(file.kind)
+            .bind(file.storage_key)
+            .bind(file.content_type)
+            .bind(file.size_bytes)
+            .execute(&mut *tx)
+            .await
+            .map_err(AppError::from)?;
+        }
+
+        let proof = Self::fetch_proof(&mut *tx, job_id).await?
+            .ok_or_else(|| AppError::InternalServerError("Proof of delivery missing after insert".into()))?;
+        let files = Self::fetch_files(&mut *tx, pod_id).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(ProofOfDeliveryDetails { proof, files })
+    }
+
+    async fn find_by_job_id(&self, job_id: Uuid) -> Result<Option<ProofOfDeliveryDetails>, AppError> {
+        let Some(proof) = Self::fetch_proof(&self.pool, job_id).await? else {
+            return Ok(None);
+        };
+        let files = Self::fetch_files(&self.pool, proof.id).await?;
+        Ok(Some(ProofOfDeliveryDetails { proof, files }))
+    }
+
+    async fn exists_for_job(&self, job_id: Uuid) -> Result<bool, AppError> {
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM proof_of_delivery WHERE job_id = $1)")
+            .bind(job_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(AppError::from)
+    }
+
+    async fn find_file(&self, job_id: Uuid, file_id: Uuid) -> Result<Option<ProofOfDeliveryFile>, AppError> {
+        sqlx::query_as::<_, ProofOfDeliveryFile>(
+            r#"
+            SELECT f.*
+            FROM proof_of_delivery_files f
+            JOIN proof_of_delivery p ON p.id = f.pod_id
+            WHERE p.job_id = $1 AND f.id = $2
+            "#
+        )
+        .bind(job_id)
+        .bind(file_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
+}
@@ -0,0 +1,358 @@
+use async_trait::async_trait;
+use sqlx::{PgConnection, PgExecutor, PgPool};
+use serde_json::Value;
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::quote::{
+    RateCard, RateCardSurcharge, RateCardDto, Quote, QuoteQuery, NewQuote
+};
+use crate::models::postgres::vehicle::VehicleType;
+
+// --- Rate Card Repository ---
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait RateCardRepositoryTrait: Send + Sync {
+    async fn create(&self, dto: RateCardDto) -> Result<RateCard, AppError>;
+    /// Updates the card and replaces its surcharges.
+    async fn update(&self, id: Uuid, dto: RateCardDto) -> Result<Option<RateCard>, AppError>;
+    async fn find_all(&self) -> Result<Vec<RateCard>, AppError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RateCard>, AppError>;
+    /// The active card for the vehicle type, or else the active card without one.
+    async fn find_for_vehicle_type(&self, vehicle_type: Option<VehicleType>) -> Result<Option<RateCard>, AppError>;
+    /// Quotes priced with the card keep their prices. Returns `false` if there was no card.
+    async fn delete(&self, id: Uuid) -> Result<bool, AppError>;
+}
+
+pub struct RateCardRepository {
+    pool: PgPool,
+}
+
+impl RateCardRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn fetch_surcharges<'e>(executor: impl PgExecutor<'e>, rate_card_id: Uuid) -> Result<Vec<RateCardSurcharge>, AppError> {
+        sqlx::query_as::<_, RateCardSurcharge>(
+            "SELECT shipment_type, percent FROM rate_card_surcharges WHERE rate_card_id = $1 ORDER BY shipment_type"
+        )
+        .bind(rate_card_id)
+        .fetch_all(executor)
+        .await
+        .map_err(AppError::from)
+    }
+
+    async fn replace_surcharges(
+        conn: &mut sqlx::PgConnection,
+        rate_card_id: Uuid,
+        surcharges: Vec<RateCardSurcharge>,
+    ) -> Result<Vec<RateCardSurcharge>, AppError> {
+        sqlx::query("DELETE FROM rate_card_surcharges WHERE rate_card_id = $1")
+            .bind(rate_card_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(AppError::from)?;
+
+        for surcharge in surcharges {
+            sqlx::query("INSERT INTO rate_card_surcharges (rate_card_id, shipment_type, percent) VALUES ($1, $2, $3)")
+                .bind(rate_card_id)
+                .bind(surcharge.shipment_type)
+                .bind(surcharge.percent)
+                .execute(&mut *conn)
+                .await
+                .map_err(AppError::from)?;
+        }
+
+        Self::fetch_surcharges(&mut *conn, rate_card_id).await
+    }
+
+    async fn with_surcharges(&self, card: Option<RateCard>) -> Result<Option<RateCard>, AppError> {
+        let Some(mut card) = card else {
+            return Ok(None);
+        };
+        card.surcharges = Self::fetch_surcharges(&self.pool, card.id).await?;
+        Ok(Some(card))
+    }
+}
+
+#[async_trait]
+impl RateCardRepositoryTrait for RateCardRepository {
+    async fn create(&self, dto: RateCardDto) -> Result<RateCard, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let mut card = sqlx::query_as::<_, RateCard>(
+            r#"
+            INSERT INTO rate_cards (name, vehicle_type, per_km, per_kg, minimum_charge, is_active)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#
+        )
+        .bind(dto.name)
+        .bind(dto.vehicle_type)
+        .bind(dto.per_km)
+        .bind(dto.per_kg)
+        .bind(dto.minimum_charge)
+        .bind(dto.is_active)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        card.surcharges = Self::replace_surcharges(&mut tx, card.id, dto.surcharges).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(card)
+    }
+
+    async fn update(&self, id: Uuid, dto: RateCardDto) -> Result<Option<RateCard>, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let card = sqlx::query_as::<_, RateCard>(
+            r#"
+            UPDATE rate_cards
+            SET name = $2, vehicle_type = $3, per_km = $4, per_kg = $5, minimum_charge = $6,
+                is_active = $7, updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#
+        )
+        .bind(id)
+        .bind(dto.name)
+        .bind(dto.vehicle_type)
+        .bind(dto.per_km)
+        .bind(dto.per_kg)
+        .bind(dto.minimum_charge)
+        .bind(dto.is_active)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        let Some(mut card) = card else {
+            return Ok(None);
+        };
+        card.surcharges = Self::replace_surcharges(&mut tx, card.id, dto.surcharges).await?;
+        tx.commit().await.map_err(AppError::from)?;
+
+        Ok(Some(card))
+    }
+
+    async fn find_all(&self) -> Result<Vec<RateCard>, AppError> {
+        let mut cards = sqlx::query_as::<_, RateCard>(
+            "SELECT * FROM rate_cards ORDER BY is_active DESC, vehicle_type NULLS LAST, name"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        for card in &mut cards {
+            card.surcharges = Self::fetch_surcharges(&self.pool, card.id).await?;
+        }
+        Ok(cards)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<RateCard>, AppError> {
+        let card = sqlx::query_as::<_, RateCard>("SELECT * FROM rate_cards WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        self.with_surcharges(card).await
+    }
+
+    async fn find_for_vehicle_type(&self, vehicle_type: Option<VehicleType>) -> Result<Option<RateCard>, AppError> {
+        let card = sqlx::query_as::<_, RateCard>(
+            r#"
+            SELECT * FROM rate_cards
+            WHERE is_active AND (vehicle_type = $1 OR vehicle_type IS NULL)
+            ORDER BY vehicle_type NULLS LAST
+            LIMIT 1
+            "#
+        )
+        .bind(vehicle_type)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        self.with_surcharges(card).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM rate_cards WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+// --- Quote Repository ---
+const QUOTE_SELECT: &str = r#"
+    SELECT
+        id, customer_id, rate_card_id, vehicle_type,
+        ST_AsGeoJSON(origin)::jsonb AS origin,
+        ST_AsGeoJSON(destination)::jsonb AS destination,
+        ST_AsGeoJSON(waypoints)::jsonb AS waypoints,
+        shipments, distance_km, weight_kg, distance_charge, weight_charge, surcharge, price,
+        currency, valid_until, job_id, converted_at, created_by, created_at
+    FROM quotes
+"#;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait QuoteRepositoryTrait: Send + Sync {
+    /// Length in kilometers of the path from the origin through the waypoints to the
+    /// destination, measured on the spheroid.
+    async fn route_distance_km(&self, origin: Value, destination: Value, waypoints: Option<Value>) -> Result<f64, AppError>;
+    async fn create(&self, quote: NewQuote) -> Result<Quote, AppError>;
+    async fn find_all(&self, query: QuoteQuery) -> Result<Vec<Quote>, AppError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Quote>, AppError>;
+}
+
+pub struct QuoteRepository {
+    pool: PgPool,
+}
+
+impl QuoteRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn fetch_quote<'e>(executor: impl PgExecutor<'e>, id: Uuid) -> Result<Option<Quote>, AppError> {
+        sqlx::query_as::<_, Quote>(&format!("{} WHERE id = $1", QUOTE_SELECT))
+            .bind(id)
+            .fetch_optional(executor)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Locks the quote's row until the transaction ends.
+    pub(crate) async fn lock<'e>(executor: impl PgExecutor<'e>, id: Uuid) -> Result<Option<Quote>, AppError> {
+        sqlx::query_as::<_, Quote>(&format!("{} WHERE id = $1 FOR UPDATE", QUOTE_SELECT))
+            .bind(id)
+            .fetch_optional(executor)
+            .await
+            .map_err(AppError::from)
+    }
+
+    /// Links the quote to the job and copies the quote's route and shipments onto it, on
+    /// `conn`, which should be in a transaction.
+    pub(crate) async fn attach(conn: &mut PgConnection, id: Uuid, job_id: Uuid) -> Result<(), AppError> {
+        sqlx::query("UPDATE quotes SET job_id = $2, converted_at = NOW() WHERE id = $1")
+            .bind(id)
+            .bind(job_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(AppError::from)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO routes (id, job_id, origin, destination, waypoints)
+            SELECT gen_random_uuid(), $2, origin, destination, waypoints
+            FROM quotes
+            WHERE id = $1
+            "#
+        )
+        .bind(id)
+        .bind(job_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(AppError::from)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO shipments (id, job_id, weight, dimensions, type)
+            SELECT gen_random_uuid(), $2, s.weight, s.dimensions, s.type
+            FROM quotes q
+            CROSS JOIN LATERAL jsonb_to_recordset(q.shipments) AS s(weight FLOAT, dimensions JSONB, type VARCHAR(50))
+            WHERE q.id = $1
+            "#
+        )
+        .bind(id)
+        .bind(job_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl QuoteRepositoryTrait for QuoteRepository {
+    async fn route_distance_km(&self, origin: Value, destination: Value, waypoints: Option<Value>) -> Result<f64, AppError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT ST_Length(ST_MakeLine(array_remove(ARRAY[
+                       ST_SetSRID(ST_GeomFromGeoJSON($1::jsonb), 4326),
+                       CASE WHEN $3::jsonb IS NULL THEN NULL ELSE ST_SetSRID(ST_GeomFromGeoJSON($3::jsonb), 4326) END,
+                       ST_SetSRID(ST_GeomFromGeoJSON($2::jsonb), 4326)
+                   ], NULL))::geography) / 1000.0
+            "#
+        )
+        .bind(origin)
+        .bind(destination)
+        .bind(waypoints)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
+
+    async fn create(&self, quote: NewQuote) -> Result<Quote, AppError> {
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO quotes (
+                customer_id, rate_card_id, vehicle_type, origin, destination, waypoints, shipments,
+                distance_km, weight_kg, distance_charge, weight_charge, surcharge, price,
+                currency, valid_until, created_by
+            )
+            VALUES (
+                $1, $2, $3,
+                ST_SetSRID(ST_GeomFromGeoJSON($4::jsonb), 4326),
+                ST_SetSRID(ST_GeomFromGeoJSON($5::jsonb), 4326),
+                CASE WHEN $6::jsonb IS NULL THEN NULL ELSE ST_SetSRID(ST_GeomFromGeoJSON($6::jsonb), 4326) END,
+                $7, $8, $9, $10, $11, $12, $13, $14, $15, $16
+            )
+            RETURNING id
+            "#
+        )
+        .bind(quote.customer_id)
+        .bind(quote.rate_card_id)
+        .bind(quote.vehicle_type)
+        .bind(quote.origin)
+        .bind(quote.destination)
+        .bind(quote.waypoints)
+        .bind(quote.shipments)
+        .bind(quote.distance_km)
+        .bind(quote.weight_kg)
+        .bind(quote.distance_charge)
+        .bind(quote.weight_charge)
+        .bind(quote.surcharge)
+        .bind(quote.price)
+        .bind(quote.currency)
+        .bind(quote.valid_until)
+        .bind(quote.created_by)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Self::fetch_quote(&self.pool, id).await?
+            .ok_or_else(|| AppError::InternalServerError("Quote missing after insert".into()))
+    }
+
+    async fn find_all(&self, query: QuoteQuery) -> Result<Vec<Quote>, AppError> {
+        sqlx::query_as::<_, Quote>(&format!(
+            "{} WHERE ($1::uuid IS NULL OR customer_id = $1) ORDER BY created_at DESC",
+            QUOTE_SELECT
+        ))
+        .bind(query.customer_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Quote>, AppError> {
+        Self::fetch_quote(&self.pool, id).await
+    }
+}
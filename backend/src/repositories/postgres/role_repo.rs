@@ -0,0 +1,67 @@
+use sqlx::PgPool;
+use serde_json::Value;
+use crate::models::postgres::user::{Role, UserRole};
+use crate::error::AppError;
+use async_trait::async_trait;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait RoleRepositoryTrait: Send + Sync {
+    async fn find_all(&self) -> Result<Vec<Role>, AppError>;
+    async fn find_by_role(&self, role: UserRole) -> Result<Option<Role>, AppError>;
+    async fn update_permissions(&self, role: UserRole, permissions: Value) -> Result<Role, AppError>;
+}
+
+pub struct RoleRepository {
+    pool: PgPool,
+}
+
+impl RoleRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RoleRepositoryTrait for RoleRepository {
+    async fn find_all(&self) -> Result<Vec<Role>, AppError> {
+        let roles = sqlx::query_as::<_, Role>(
+            "SELECT * FROM roles ORDER BY name"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(roles)
+    }
+
+    async fn find_by_role(&self, role: UserRole) -> Result<Option<Role>, AppError> {
+        let role = sqlx::query_as::<_, Role>(
+            "SELECT * FROM roles WHERE name = $1"
+        )
+        .bind(role.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(role)
+    }
+
+    async fn update_permissions(&self, role: UserRole, permissions: Value) -> Result<Role, AppError> {
+        let role = sqlx::query_as::<_, Role>(
+            r#"
+            INSERT INTO roles (id, name, permissions)
+            VALUES (gen_random_uuid(), $1, $2)
+            ON CONFLICT (name) DO UPDATE SET permissions = EXCLUDED.permissions
+            RETURNING *
+            "#
+        )
+        .bind(role.as_str())
+        .bind(permissions)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(role)
+    }
+}
@@ -28,7 +28,7 @@ impl SettingsRepositoryTrait for SettingsRepository {
         )
         .fetch_one(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(settings)
     }
@@ -53,6 +53,19 @@ impl SettingsRepositoryTrait for SettingsRepository {
               notify_sms = $13,
               notify_desktop = $14,
               notify_weekly_summary = $15,
+              telemetry_retention_months = COALESCE($16, telemetry_retention_months),
+              telemetry_archive_expired = COALESCE($17, telemetry_archive_expired),
+              speed_limit_kmh = COALESCE($18, speed_limit_kmh),
+              fuel_drop_threshold = COALESCE($19, fuel_drop_threshold),
+              idle_alert_minutes = COALESCE($20, idle_alert_minutes),
+              tracker_stale_minutes = COALESCE($21, tracker_stale_minutes),
+              license_expiry_warning_days = COALESCE($22, license_expiry_warning_days),
+              hos_max_daily_hours = COALESCE($23, hos_max_daily_hours),
+              hos_max_weekly_hours = COALESCE($24, hos_max_weekly_hours),
+              hos_min_rest_hours = COALESCE($25, hos_min_rest_hours),
+              document_expiry_warning_days = COALESCE($26, document_expiry_warning_days),
+              tax_rate = COALESCE($27, tax_rate),
+              payment_terms_days = COALESCE($28, payment_terms_days),
               updated_at = NOW()
             WHERE id = 1
             RETURNING *
@@ -73,9 +86,22 @@ impl SettingsRepositoryTrait for SettingsRepository {
         .bind(dto.notify_sms)
         .bind(dto.notify_desktop)
         .bind(dto.notify_weekly_summary)
+        .bind(dto.telemetry_retention_months)
+        .bind(dto.telemetry_archive_expired)
+        .bind(dto.speed_limit_kmh)
+        .bind(dto.fuel_drop_threshold)
+        .bind(dto.idle_alert_minutes)
+        .bind(dto.tracker_stale_minutes)
+        .bind(dto.license_expiry_warning_days)
+        .bind(dto.hos_max_daily_hours)
+        .bind(dto.hos_max_weekly_hours)
+        .bind(dto.hos_min_rest_hours)
+        .bind(dto.document_expiry_warning_days)
+        .bind(dto.tax_rate)
+        .bind(dto.payment_terms_days)
         .fetch_one(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(settings)
     }
@@ -1,6 +1,7 @@
 use sqlx::PgPool;
 use uuid::Uuid;
-use crate::models::postgres::telemetry::{VehicleTelemetry, CreateVehicleTelemetryDto};
+use chrono::{DateTime, Utc};
+use crate::models::postgres::telemetry::{VehicleTelemetry, CreateVehicleTelemetryDto, TelemetryBucket, TelemetryTrack, TrackerLastSeen};
 use crate::error::AppError;
 use async_trait::async_trait;
 
@@ -9,6 +10,21 @@ use async_trait::async_trait;
 pub trait TelemetryRepositoryTrait: Send + Sync {
     async fn create(&self, dto: CreateVehicleTelemetryDto) -> Result<VehicleTelemetry, AppError>;
     async fn find_latest_by_vehicle_id(&self, vehicle_id: Uuid) -> Result<Option<VehicleTelemetry>, AppError>;
+    /// Inserts all readings in one statement, skipping any (vehicle_id, time) already stored.
+    /// Returns only the rows that were inserted.
+    async fn create_many(&self, dtos: Vec<CreateVehicleTelemetryDto>) -> Result<Vec<VehicleTelemetry>, AppError>;
+    /// Readings in `[from, to)`, oldest first, capped at `limit` rows.
+    async fn find_by_vehicle_in_range(&self, vehicle_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>, limit: i64) -> Result<Vec<VehicleTelemetry>, AppError>;
+    async fn find_bucketed_by_vehicle(&self, vehicle_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>, bucket_secs: i64) -> Result<Vec<TelemetryBucket>, AppError>;
+    /// Builds the trip LineString in PostGIS, from raw positions or bucket centroids.
+    async fn find_track(&self, vehicle_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>, bucket_secs: Option<i64>) -> Result<TelemetryTrack, AppError>;
+    /// The most recent reading strictly before `before`.
+    async fn find_latest_before(&self, vehicle_id: Uuid, before: DateTime<Utc>) -> Result<Option<VehicleTelemetry>, AppError>;
+    /// Readings in `[from, to]` plus the last one before `from`, newest first, so callers
+    /// can tell whether a state held for the whole window.
+    async fn find_covering_range(&self, vehicle_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>, limit: i64) -> Result<Vec<VehicleTelemetry>, AppError>;
+    /// In-service vehicles that have reported before but not since `cutoff`.
+    async fn find_silent_vehicles(&self, cutoff: DateTime<Utc>) -> Result<Vec<TrackerLastSeen>, AppError>;
 }
 
 pub struct TelemetryRepository {
@@ -54,7 +70,7 @@ impl TelemetryRepositoryTrait for TelemetryRepository {
         .bind(dto.engine_status)
         .fetch_one(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(telemetry)
     }
@@ -78,8 +94,248 @@ impl TelemetryRepositoryTrait for TelemetryRepository {
         .bind(vehicle_id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(telemetry)
     }
+
+    async fn create_many(&self, dtos: Vec<CreateVehicleTelemetryDto>) -> Result<Vec<VehicleTelemetry>, AppError> {
+        if dtos.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut times = Vec::with_capacity(dtos.len());
+        let mut vehicle_ids = Vec::with_capacity(dtos.len());
+        let mut locations = Vec::with_capacity(dtos.len());
+        let mut speeds = Vec::with_capacity(dtos.len());
+        let mut fuel_levels = Vec::with_capacity(dtos.len());
+        let mut engine_statuses = Vec::with_capacity(dtos.len());
+        for dto in dtos {
+            times.push(dto.time);
+            vehicle_ids.push(dto.vehicle_id);
+            locations.push(dto.location);
+            speeds.push(dto.speed);
+            fuel_levels.push(dto.fuel_level);
+            engine_statuses.push(dto.engine_status);
+        }
+
+        let telemetry = sqlx::query_as::<_, VehicleTelemetry>(
+            r#"
+            INSERT INTO vehicle_telemetry (
+                time, vehicle_id, location, speed, fuel_level, engine_status
+            )
+            SELECT
+                t.time,
+                t.vehicle_id,
+                ST_SetSRID(ST_GeomFromGeoJSON(t.location), 4326),
+                t.speed,
+                t.fuel_level,
+                t.engine_status
+            FROM UNNEST(
+                $1::timestamptz[],
+                $2::uuid[],
+                $3::jsonb[],
+                $4::float8[],
+                $5::float8[],
+                $6::jsonb[]
+            ) AS t(time, vehicle_id, location, speed, fuel_level, engine_status)
+            ON CONFLICT (vehicle_id, time) DO NOTHING
+            RETURNING
+                time,
+                vehicle_id,
+                ST_AsGeoJSON(location)::jsonb as location,
+                speed,
+                fuel_level,
+                engine_status
+            "#
+        )
+        .bind(times)
+        .bind(vehicle_ids)
+        .bind(locations)
+        .bind(speeds)
+        .bind(fuel_levels)
+        .bind(engine_statuses)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(telemetry)
+    }
+
+    async fn find_by_vehicle_in_range(&self, vehicle_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>, limit: i64) -> Result<Vec<VehicleTelemetry>, AppError> {
+        let telemetry = sqlx::query_as::<_, VehicleTelemetry>(
+            r#"
+            SELECT
+                time,
+                vehicle_id,
+                ST_AsGeoJSON(location)::jsonb as location,
+                speed,
+                fuel_level,
+                engine_status
+            FROM vehicle_telemetry
+            WHERE vehicle_id = $1 AND time >= $2 AND time < $3
+            ORDER BY time ASC
+            LIMIT $4
+            "#
+        )
+        .bind(vehicle_id)
+        .bind(from)
+        .bind(to)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(telemetry)
+    }
+
+    async fn find_bucketed_by_vehicle(&self, vehicle_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>, bucket_secs: i64) -> Result<Vec<TelemetryBucket>, AppError> {
+        let buckets = sqlx::query_as::<_, TelemetryBucket>(
+            r#"
+            SELECT
+                date_bin(make_interval(secs => $4), time, TIMESTAMPTZ 'epoch') as time,
+                vehicle_id,
+                ST_AsGeoJSON(ST_Centroid(ST_Collect(location)))::jsonb as location,
+                AVG(speed) as avg_speed,
+                MAX(speed) as max_speed,
+                AVG(fuel_level) as avg_fuel_level,
+                COUNT(*) as samples
+            FROM vehicle_telemetry
+            WHERE vehicle_id = $1 AND time >= $2 AND time < $3
+            GROUP BY 1, vehicle_id
+            ORDER BY 1 ASC
+            "#
+        )
+        .bind(vehicle_id)
+        .bind(from)
+        .bind(to)
+        .bind(bucket_secs as f64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(buckets)
+    }
+
+    async fn find_track(&self, vehicle_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>, bucket_secs: Option<i64>) -> Result<TelemetryTrack, AppError> {
+        // Without a bucket every reading is its own point
+        let track = sqlx::query_as::<_, TelemetryTrack>(
+            r#"
+            WITH points AS (
+                SELECT
+                    CASE WHEN $4::float8 IS NULL THEN time
+                         ELSE date_bin(make_interval(secs => $4), time, TIMESTAMPTZ 'epoch')
+                    END as time,
+                    location
+                FROM vehicle_telemetry
+                WHERE vehicle_id = $1 AND time >= $2 AND time < $3
+            ),
+            bucketed AS (
+                SELECT time, ST_Centroid(ST_Collect(location)) as location
+                FROM points
+                GROUP BY time
+            )
+            SELECT
+                CASE WHEN COUNT(*) >= 2
+                     THEN ST_AsGeoJSON(ST_MakeLine(location ORDER BY time))::jsonb
+                END as geometry,
+                COUNT(*) as point_count,
+                MIN(time) as started_at,
+                MAX(time) as ended_at
+            FROM bucketed
+            "#
+        )
+        .bind(vehicle_id)
+        .bind(from)
+        .bind(to)
+        .bind(bucket_secs.map(|secs| secs as f64))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(track)
+    }
+
+    async fn find_latest_before(&self, vehicle_id: Uuid, before: DateTime<Utc>) -> Result<Option<VehicleTelemetry>, AppError> {
+        let telemetry = sqlx::query_as::<_, VehicleTelemetry>(
+            r#"
+            SELECT
+                time,
+                vehicle_id,
+                ST_AsGeoJSON(location)::jsonb as location,
+                speed,
+                fuel_level,
+                engine_status
+            FROM vehicle_telemetry
+            WHERE vehicle_id = $1 AND time < $2
+            ORDER BY time DESC
+            LIMIT 1
+            "#
+        )
+        .bind(vehicle_id)
+        .bind(before)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(telemetry)
+    }
+
+    async fn find_covering_range(&self, vehicle_id: Uuid, from: DateTime<Utc>, to: DateTime<Utc>, limit: i64) -> Result<Vec<VehicleTelemetry>, AppError> {
+        let telemetry = sqlx::query_as::<_, VehicleTelemetry>(
+            r#"
+            SELECT
+                time,
+                vehicle_id,
+                ST_AsGeoJSON(location)::jsonb as location,
+                speed,
+                fuel_level,
+                engine_status
+            FROM vehicle_telemetry
+            WHERE vehicle_id = $1
+              AND time <= $3
+              AND time >= COALESCE(
+                  (SELECT MAX(time) FROM vehicle_telemetry WHERE vehicle_id = $1 AND time < $2),
+                  $2
+              )
+            ORDER BY time DESC
+            LIMIT $4
+            "#
+        )
+        .bind(vehicle_id)
+        .bind(from)
+        .bind(to)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(telemetry)
+    }
+
+    async fn find_silent_vehicles(&self, cutoff: DateTime<Utc>) -> Result<Vec<TrackerLastSeen>, AppError> {
+        // One index probe per vehicle instead of aggregating the whole telemetry table
+        let silent = sqlx::query_as::<_, TrackerLastSeen>(
+            r#"
+            SELECT v.id AS vehicle_id, latest.time AS last_seen
+            FROM vehicles v
+            CROSS JOIN LATERAL (
+                SELECT time FROM vehicle_telemetry
+                WHERE vehicle_id = v.id
+                ORDER BY time DESC
+                LIMIT 1
+            ) latest
+            WHERE v.deleted_at IS NULL
+              AND v.status IN ('AVAILABLE', 'ASSIGNED')
+              AND latest.time < $1
+            ORDER BY latest.time
+            "#
+        )
+        .bind(cutoff)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(silent)
+    }
 }
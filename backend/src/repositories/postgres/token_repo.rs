@@ -0,0 +1,122 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use crate::models::postgres::token::RefreshToken;
+use crate::error::AppError;
+use async_trait::async_trait;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait TokenRepositoryTrait: Send + Sync {
+    async fn create_refresh_token(&self, user_id: Uuid, token_hash: &str, expires_at: DateTime<Utc>) -> Result<RefreshToken, AppError>;
+    async fn find_refresh_token(&self, token_hash: &str) -> Result<Option<RefreshToken>, AppError>;
+    /// Marks a refresh token as used. Returns false if it had already been revoked.
+    async fn revoke_refresh_token(&self, id: Uuid) -> Result<bool, AppError>;
+    async fn revoke_user_refresh_tokens(&self, user_id: Uuid) -> Result<(), AppError>;
+    async fn revoke_access_token(&self, jti: Uuid, expires_at: DateTime<Utc>) -> Result<(), AppError>;
+    async fn is_access_token_revoked(&self, jti: Uuid) -> Result<bool, AppError>;
+}
+
+pub struct TokenRepository {
+    pool: PgPool,
+}
+
+impl TokenRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TokenRepositoryTrait for TokenRepository {
+    async fn create_refresh_token(&self, user_id: Uuid, token_hash: &str, expires_at: DateTime<Utc>) -> Result<RefreshToken, AppError> {
+        let id = Uuid::new_v4();
+        let token = sqlx::query_as::<_, RefreshToken>(
+            r#"
+            INSERT INTO refresh_tokens (id, user_id, token_hash, expires_at, created_at)
+            VALUES ($1, $2, $3, $4, NOW())
+            RETURNING *
+            "#
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(token_hash)
+        .bind(expires_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(token)
+    }
+
+    async fn find_refresh_token(&self, token_hash: &str) -> Result<Option<RefreshToken>, AppError> {
+        let token = sqlx::query_as::<_, RefreshToken>(
+            "SELECT * FROM refresh_tokens WHERE token_hash = $1"
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(token)
+    }
+
+    async fn revoke_refresh_token(&self, id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = NOW() WHERE id = $1 AND revoked_at IS NULL"
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn revoke_user_refresh_tokens(&self, user_id: Uuid) -> Result<(), AppError> {
+        sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL"
+        )
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    async fn revoke_access_token(&self, jti: Uuid, expires_at: DateTime<Utc>) -> Result<(), AppError> {
+        // Opportunistically drop entries for tokens that have expired on their own
+        sqlx::query("DELETE FROM revoked_tokens WHERE expires_at < NOW()")
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO revoked_tokens (jti, expires_at, revoked_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (jti) DO NOTHING
+            "#
+        )
+        .bind(jti)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    async fn is_access_token_revoked(&self, jti: Uuid) -> Result<bool, AppError> {
+        let revoked = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM revoked_tokens WHERE jti = $1)"
+        )
+        .bind(jti)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(revoked)
+    }
+}
@@ -0,0 +1,195 @@
+use sqlx::{PgPool, Postgres, Transaction};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use crate::models::postgres::assignment::{VehicleAssignment, CreateAssignmentDto, AssignmentStatus};
+use crate::models::postgres::vehicle::{Vehicle, VehicleStatus};
+use crate::models::postgres::driver::{Driver, DriverStatus};
+use crate::models::postgres::logistics::{Customer, CreateTransportJobDto, TransportJob};
+use crate::models::postgres::quote::Quote;
+use crate::models::postgres::work_order::{WorkOrder, WorkOrderStatus, CreateWorkOrderDto};
+use crate::repositories::postgres::assignment_repo::AssignmentRepository;
+use crate::repositories::postgres::vehicle_repo::VehicleRepository;
+use crate::repositories::postgres::driver_repo::DriverRepository;
+use crate::repositories::postgres::logistics_repo::{CustomerRepository, JobLegRepository, TransportJobRepository};
+use crate::repositories::postgres::quote_repo::QuoteRepository;
+use crate::repositories::postgres::work_order_repo::WorkOrderRepository;
+use crate::error::AppError;
+use async_trait::async_trait;
+
+/// Starts transactions for writes that have to succeed or fail together across
+/// repositories.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait UnitOfWorkTrait: Send + Sync {
+    async fn begin(&self) -> Result<Box<dyn RepositoryTransactionTrait>, AppError>;
+}
+
+/// Repository writes sharing one transaction. Dropping it without `commit` rolls all of
+/// them back.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait RepositoryTransactionTrait: Send {
+    async fn create_assignment(&mut self, dto: CreateAssignmentDto) -> Result<VehicleAssignment, AppError>;
+    /// Returns `None` if the assignment is no longer in `from`.
+    async fn update_assignment_status(&mut self, id: Uuid, from: AssignmentStatus, to: AssignmentStatus) -> Result<Option<VehicleAssignment>, AppError>;
+    /// Returns `None` if the assignment is no longer in `from`.
+    async fn cancel_assignment(&mut self, id: Uuid, from: AssignmentStatus, reason: String) -> Result<Option<VehicleAssignment>, AppError>;
+    /// Holds the driver's row until the transaction ends, so concurrent bookings of the
+    /// same driver are checked one after the other.
+    async fn lock_driver(&mut self, driver_id: Uuid) -> Result<(), AppError>;
+    /// Scheduled or active assignments of the driver overlapping `[start, end)`. A missing
+    /// end runs indefinitely, as in the vehicle exclusion constraint.
+    async fn find_driver_overlaps(&mut self, driver_id: Uuid, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<VehicleAssignment>, AppError>;
+    /// Adds the assignment to the job as its next leg.
+    async fn link_job(&mut self, job_id: Uuid, assignment_id: Uuid) -> Result<(), AppError>;
+    async fn update_vehicle_status(&mut self, id: Uuid, status: VehicleStatus) -> Result<Vehicle, AppError>;
+    /// Holds the vehicle's row until the transaction ends, so work orders opening and
+    /// closing on the same vehicle settle its status one after the other.
+    async fn lock_vehicle(&mut self, id: Uuid) -> Result<Option<Vehicle>, AppError>;
+    async fn create_work_order(&mut self, dto: CreateWorkOrderDto) -> Result<WorkOrder, AppError>;
+    /// Returns `None` if the order is no longer in `from`.
+    async fn update_work_order_status(&mut self, id: Uuid, from: WorkOrderStatus, to: WorkOrderStatus) -> Result<Option<WorkOrder>, AppError>;
+    /// Closes an in-progress order and logs it as a maintenance record. Returns `None` if
+    /// the order was not in progress.
+    async fn complete_work_order(&mut self, id: Uuid) -> Result<Option<WorkOrder>, AppError>;
+    /// Orders for the vehicle that are neither completed nor cancelled.
+    async fn count_open_work_orders(&mut self, vehicle_id: Uuid) -> Result<i64, AppError>;
+    async fn update_driver_status(&mut self, id: Uuid, status: DriverStatus) -> Result<Driver, AppError>;
+    /// Holds the customer's row until the transaction ends, so concurrent jobs for the
+    /// same customer are credit-checked one after the other.
+    async fn lock_customer(&mut self, customer_id: Uuid) -> Result<Option<Customer>, AppError>;
+    /// What the customer owes or will owe, as seen by this transaction.
+    async fn credit_exposure(&mut self, customer_id: Uuid) -> Result<Decimal, AppError>;
+    /// Records the starting status as the job's first history entry.
+    async fn create_job(&mut self, dto: CreateTransportJobDto, created_by: Option<Uuid>) -> Result<TransportJob, AppError>;
+    /// Holds the quote's row until the transaction ends, so it is converted only once.
+    async fn lock_quote(&mut self, id: Uuid) -> Result<Option<Quote>, AppError>;
+    /// Links the quote to the job and gives the job the quote's route and shipments.
+    async fn attach_quote_job(&mut self, quote_id: Uuid, job_id: Uuid) -> Result<(), AppError>;
+    async fn commit(self: Box<Self>) -> Result<(), AppError>;
+}
+
+pub struct UnitOfWork {
+    pool: PgPool,
+}
+
+impl UnitOfWork {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkTrait for UnitOfWork {
+    async fn begin(&self) -> Result<Box<dyn RepositoryTransactionTrait>, AppError> {
+        let tx = self.pool.begin().await.map_err(AppError::from)?;
+        Ok(Box::new(RepositoryTransaction { tx }))
+    }
+}
+
+pub struct RepositoryTransaction {
+    tx: Transaction<'static, Postgres>,
+}
+
+#[async_trait]
+impl RepositoryTransactionTrait for RepositoryTransaction {
+    async fn create_assignment(&mut self, dto: CreateAssignmentDto) -> Result<VehicleAssignment, AppError> {
+        AssignmentRepository::insert(&mut *self.tx, dto).await
+    }
+
+    async fn update_assignment_status(&mut self, id: Uuid, from: AssignmentStatus, to: AssignmentStatus) -> Result<Option<VehicleAssignment>, AppError> {
+        AssignmentRepository::transition(&mut *self.tx, id, from, to).await
+    }
+
+    async fn cancel_assignment(&mut self, id: Uuid, from: AssignmentStatus, reason: String) -> Result<Option<VehicleAssignment>, AppError> {
+        AssignmentRepository::cancel_from(&mut *self.tx, id, from, reason).await
+    }
+
+    async fn lock_driver(&mut self, driver_id: Uuid) -> Result<(), AppError> {
+        sqlx::query("SELECT id FROM drivers WHERE id = $1 FOR UPDATE")
+            .bind(driver_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(())
+    }
+
+    async fn find_driver_overlaps(&mut self, driver_id: Uuid, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<VehicleAssignment>, AppError> {
+        let assignments = sqlx::query_as::<_, VehicleAssignment>(
+            r#"
+            SELECT * FROM vehicle_assignments
+            WHERE driver_id = $1
+              AND status IN ('SCHEDULED', 'ACTIVE')
+              AND start_time < COALESCE($3, 'infinity')
+              AND COALESCE(end_time, 'infinity') > $2
+            ORDER BY start_time
+            "#
+        )
+        .bind(driver_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&mut *self.tx)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(assignments)
+    }
+
+    async fn link_job(&mut self, job_id: Uuid, assignment_id: Uuid) -> Result<(), AppError> {
+        JobLegRepository::insert(&mut *self.tx, job_id, assignment_id, None).await
+    }
+
+    async fn update_vehicle_status(&mut self, id: Uuid, status: VehicleStatus) -> Result<Vehicle, AppError> {
+        VehicleRepository::set_status(&mut *self.tx, id, status).await
+    }
+
+    async fn lock_vehicle(&mut self, id: Uuid) -> Result<Option<Vehicle>, AppError> {
+        VehicleRepository::lock(&mut *self.tx, id).await
+    }
+
+    async fn create_work_order(&mut self, dto: CreateWorkOrderDto) -> Result<WorkOrder, AppError> {
+        WorkOrderRepository::insert(&mut *self.tx, dto).await
+    }
+
+    async fn update_work_order_status(&mut self, id: Uuid, from: WorkOrderStatus, to: WorkOrderStatus) -> Result<Option<WorkOrder>, AppError> {
+        WorkOrderRepository::transition(&mut *self.tx, id, from, to).await
+    }
+
+    async fn complete_work_order(&mut self, id: Uuid) -> Result<Option<WorkOrder>, AppError> {
+        WorkOrderRepository::complete(&mut self.tx, id).await
+    }
+
+    async fn count_open_work_orders(&mut self, vehicle_id: Uuid) -> Result<i64, AppError> {
+        WorkOrderRepository::count_open(&mut *self.tx, vehicle_id).await
+    }
+
+    async fn update_driver_status(&mut self, id: Uuid, status: DriverStatus) -> Result<Driver, AppError> {
+        DriverRepository::set_status(&mut *self.tx, id, status).await
+    }
+
+    async fn lock_customer(&mut self, customer_id: Uuid) -> Result<Option<Customer>, AppError> {
+        CustomerRepository::lock(&mut *self.tx, customer_id).await
+    }
+
+    async fn credit_exposure(&mut self, customer_id: Uuid) -> Result<Decimal, AppError> {
+        CustomerRepository::exposure(&mut *self.tx, customer_id).await
+    }
+
+    async fn create_job(&mut self, dto: CreateTransportJobDto, created_by: Option<Uuid>) -> Result<TransportJob, AppError> {
+        TransportJobRepository::insert(&mut self.tx, dto, created_by).await
+    }
+
+    async fn lock_quote(&mut self, id: Uuid) -> Result<Option<Quote>, AppError> {
+        QuoteRepository::lock(&mut *self.tx, id).await
+    }
+
+    async fn attach_quote_job(&mut self, quote_id: Uuid, job_id: Uuid) -> Result<(), AppError> {
+        QuoteRepository::attach(&mut self.tx, quote_id, job_id).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), AppError> {
+        self.tx.commit().await.map_err(AppError::from)
+    }
+}
@@ -46,7 +46,7 @@ impl UserRepositoryTrait for UserRepository {
         .bind(dto.is_active)
         .fetch_one(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(user)
     }
@@ -57,7 +57,7 @@ impl UserRepositoryTrait for UserRepository {
         )
         .fetch_all(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(users)
     }
@@ -69,7 +69,7 @@ impl UserRepositoryTrait for UserRepository {
         .bind(id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(user)
     }
@@ -81,7 +81,7 @@ impl UserRepositoryTrait for UserRepository {
         .bind(email)
         .fetch_optional(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(user)
     }
@@ -102,7 +102,7 @@ impl UserRepositoryTrait for UserRepository {
         .bind(is_active)
         .fetch_optional(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         user.ok_or(AppError::NotFound(format!("User with id {} not found", id)))
     }
@@ -114,7 +114,7 @@ impl UserRepositoryTrait for UserRepository {
         .bind(id)
         .execute(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         if result.rows_affected() == 0 {
             return Err(AppError::NotFound(format!("User with id {} not found", id)));
@@ -0,0 +1,125 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::vehicle_document::{VehicleDocument, NewVehicleDocument, DocumentExpiry};
+
+/// Latest document of each type per vehicle: the furthest expiry, with documents that
+/// never expire ahead of all others.
+const CURRENT_DOCUMENTS: &str = r#"
+    SELECT DISTINCT ON (vehicle_id, type) *
+    FROM vehicle_documents
+    ORDER BY vehicle_id, type, expiry_date DESC NULLS FIRST, created_at DESC
+"#;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait VehicleDocumentRepositoryTrait: Send + Sync {
+    async fn create(&self, document: NewVehicleDocument) -> Result<VehicleDocument, AppError>;
+    async fn find_by_vehicle(&self, vehicle_id: Uuid) -> Result<Vec<VehicleDocument>, AppError>;
+    async fn find_by_id(&self, vehicle_id: Uuid, id: Uuid) -> Result<Option<VehicleDocument>, AppError>;
+    /// Returns the removed document, so its file can be deleted too.
+    async fn delete(&self, vehicle_id: Uuid, id: Uuid) -> Result<Option<VehicleDocument>, AppError>;
+    /// The document in force for each type the vehicle has.
+    async fn find_current(&self, vehicle_id: Uuid) -> Result<Vec<VehicleDocument>, AppError>;
+    /// Documents in force that have an expiry date, across vehicles still in the fleet.
+    async fn find_current_expiries(&self) -> Result<Vec<DocumentExpiry>, AppError>;
+}
+
+pub struct VehicleDocumentRepository {
+    pool: PgPool,
+}
+
+impl VehicleDocumentRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl VehicleDocumentRepositoryTrait for VehicleDocumentRepository {
+    async fn create(&self, document: NewVehicleDocument) -> Result<VehicleDocument, AppError> {
+        sqlx::query_as::<_, VehicleDocument>(
+            r#"
+            INSERT INTO vehicle_documents (
+                vehicle_id, type, document_number, issue_date, expiry_date, notes,
+                file_name, content_type, size_bytes, storage_key, uploaded_by
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING *
+            "#
+        )
+        .bind(document.vehicle_id)
+        .bind(document.r#type)
+        .bind(document.document_number)
+        .bind(document.issue_date)
+        .bind(document.expiry_date)
+        .bind(document.notes)
+        .bind(document.file_name)
+        .bind(document.content_type)
+        .bind(document.size_bytes)
+        .bind(document.storage_key)
+        .bind(document.uploaded_by)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
+
+    async fn find_by_vehicle(&self, vehicle_id: Uuid) -> Result<Vec<VehicleDocument>, AppError> {
+        sqlx::query_as::<_, VehicleDocument>(
+            "SELECT * FROM vehicle_documents WHERE vehicle_id = $1 ORDER BY type, expiry_date DESC NULLS FIRST, created_at DESC"
+        )
+        .bind(vehicle_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
+
+    async fn find_by_id(&self, vehicle_id: Uuid, id: Uuid) -> Result<Option<VehicleDocument>, AppError> {
+        sqlx::query_as::<_, VehicleDocument>("SELECT * FROM vehicle_documents WHERE vehicle_id = $1 AND id = $2")
+            .bind(vehicle_id)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)
+    }
+
+    async fn delete(&self, vehicle_id: Uuid, id: Uuid) -> Result<Option<VehicleDocument>, AppError> {
+        sqlx::query_as::<_, VehicleDocument>(
+            "DELETE FROM vehicle_documents WHERE vehicle_id = $1 AND id = $2 RETURNING *"
+        )
+        .bind(vehicle_id)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
+
+    async fn find_current(&self, vehicle_id: Uuid) -> Result<Vec<VehicleDocument>, AppError> {
+        sqlx::query_as::<_, VehicleDocument>(&format!(
+            "SELECT * FROM ({}) d WHERE d.vehicle_id = $1 ORDER BY d.type",
+            CURRENT_DOCUMENTS
+        ))
+        .bind(vehicle_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
+
+    async fn find_current_expiries(&self) -> Result<Vec<DocumentExpiry>, AppError> {
+        sqlx::query_as::<_, DocumentExpiry>(&format!(
+            r#"
+            SELECT d.id AS document_id, d.vehicle_id, v.license_plate, d.type,
+                   d.document_number, d.expiry_date
+            FROM ({}) d
+            JOIN vehicles v ON v.id = d.vehicle_id
+            WHERE d.expiry_date IS NOT NULL AND v.deleted_at IS NULL
+            ORDER BY d.expiry_date, v.license_plate
+            "#,
+            CURRENT_DOCUMENTS
+        ))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)
+    }
+}
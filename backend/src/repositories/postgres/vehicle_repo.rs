@@ -1,4 +1,4 @@
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool};
 use uuid::Uuid;
 use crate::models::postgres::vehicle::{Vehicle, CreateVehicleDto, VehicleStatus};
 use crate::error::AppError;
@@ -12,6 +12,8 @@ pub trait VehicleRepositoryTrait: Send + Sync {
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Vehicle>, AppError>;
     async fn update_status(&self, id: Uuid, status: VehicleStatus) -> Result<Vehicle, AppError>;
     async fn delete(&self, id: Uuid) -> Result<(), AppError>;
+    /// Returns the subset of `ids` that belong to non-deleted vehicles.
+    async fn find_existing_ids(&self, ids: Vec<Uuid>) -> Result<Vec<Uuid>, AppError>;
 }
 
 pub struct VehicleRepository {
@@ -22,6 +24,38 @@ impl VehicleRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// Holds the vehicle's row until the transaction ends.
+    pub(crate) async fn lock<'e>(executor: impl PgExecutor<'e>, id: Uuid) -> Result<Option<Vehicle>, AppError> {
+        let vehicle = sqlx::query_as::<_, Vehicle>(
+            "SELECT * FROM vehicles WHERE id = $1 AND deleted_at IS NULL FOR UPDATE"
+        )
+        .bind(id)
+        .fetch_optional(executor)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(vehicle)
+    }
+
+    /// Also used inside a unit of work.
+    pub(crate) async fn set_status<'e>(executor: impl PgExecutor<'e>, id: Uuid, status: VehicleStatus) -> Result<Vehicle, AppError> {
+        let vehicle = sqlx::query_as::<_, Vehicle>(
+            r#"
+            UPDATE vehicles 
+            SET status = $1, updated_at = NOW()
+            WHERE id = $2 AND deleted_at IS NULL
+            RETURNING *
+            "#
+        )
+        .bind(status)
+        .bind(id)
+        .fetch_one(executor)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(vehicle)
+    }
 }
 
 #[async_trait]
@@ -32,9 +66,9 @@ impl VehicleRepositoryTrait for VehicleRepository {
             r#"
             INSERT INTO vehicles (
                 id, make, model, year, vin, license_plate, type, status, 
-                current_mileage, fuel_type, specs, created_at, updated_at
+                current_mileage, initial_mileage, fuel_type, specs, created_at, updated_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, NOW(), NOW())
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $9, $10, $11, NOW(), NOW())
             RETURNING *
             "#
         )
@@ -51,7 +85,7 @@ impl VehicleRepositoryTrait for VehicleRepository {
         .bind(dto.specs)
         .fetch_one(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(vehicle)
     }
@@ -62,7 +96,7 @@ impl VehicleRepositoryTrait for VehicleRepository {
         )
         .fetch_all(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(vehicles)
     }
@@ -74,27 +108,13 @@ impl VehicleRepositoryTrait for VehicleRepository {
         .bind(id)
         .fetch_optional(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         Ok(vehicle)
     }
 
     async fn update_status(&self, id: Uuid, status: VehicleStatus) -> Result<Vehicle, AppError> {
-        let vehicle = sqlx::query_as::<_, Vehicle>(
-            r#"
-            UPDATE vehicles 
-            SET status = $1, updated_at = NOW()
-            WHERE id = $2 AND deleted_at IS NULL
-            RETURNING *
-            "#
-        )
-        .bind(status)
-        .bind(id)
-        .fetch_one(&self.pool)
-        .await
-        .map_err(AppError::DatabaseError)?;
-
-        Ok(vehicle)
+        Self::set_status(&self.pool, id, status).await
     }
 
     async fn delete(&self, id: Uuid) -> Result<(), AppError> {
@@ -105,7 +125,7 @@ impl VehicleRepositoryTrait for VehicleRepository {
         .bind(id)
         .execute(&self.pool)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(AppError::from)?;
 
         if result.rows_affected() == 0 {
             return Err(AppError::NotFound(format!("Vehicle with id {} not found", id)));
@@ -113,4 +133,16 @@ impl VehicleRepositoryTrait for VehicleRepository {
 
         Ok(())
     }
+
+    async fn find_existing_ids(&self, ids: Vec<Uuid>) -> Result<Vec<Uuid>, AppError> {
+        let existing = sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM vehicles WHERE id = ANY($1) AND deleted_at IS NULL"
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(existing)
+    }
 }
@@ -0,0 +1,339 @@
+use sqlx::{PgConnection, PgExecutor, PgPool};
+use uuid::Uuid;
+use crate::models::postgres::work_order::{
+    WorkOrder, WorkOrderItem, WorkOrderStatus, CreateWorkOrderDto, CreateWorkOrderItemDto, WorkOrderQuery
+};
+use crate::models::postgres::part::{NewStockMovement, StockMovementKind};
+use crate::repositories::postgres::part_repo::apply_movement;
+use crate::error::AppError;
+use async_trait::async_trait;
+
+/// Provider recorded on the maintenance record when the order has none.
+pub const IN_HOUSE_PROVIDER: &str = "In-house";
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait WorkOrderRepositoryTrait: Send + Sync {
+    async fn find_all(&self, query: WorkOrderQuery) -> Result<Vec<WorkOrder>, AppError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<WorkOrder>, AppError>;
+    async fn assign_mechanic(&self, id: Uuid, mechanic_id: Uuid) -> Result<Option<WorkOrder>, AppError>;
+
+    /// Adds a line, taking inventory parts out of stock in the same transaction. Returns
+    /// `None` if there is not enough stock; fails if the order has been closed.
+    async fn add_item(&self, work_order_id: Uuid, dto: CreateWorkOrderItemDto) -> Result<Option<WorkOrderItem>, AppError>;
+    async fn find_items(&self, work_order_id: Uuid) -> Result<Vec<WorkOrderItem>, AppError>;
+    /// Removes a line and puts its inventory part back in stock. Returns the removed line;
+    /// fails if the order has been closed.
+    async fn delete_item(&self, work_order_id: Uuid, item_id: Uuid) -> Result<Option<WorkOrderItem>, AppError>;
+}
+
+pub struct WorkOrderRepository {
+    pool: PgPool,
+}
+
+impl WorkOrderRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    // Opening and closing orders moves the vehicle in and out of maintenance, so these
+    // run inside a unit of work.
+
+    pub(crate) async fn insert<'e>(executor: impl PgExecutor<'e>, dto: CreateWorkOrderDto) -> Result<WorkOrder, AppError> {
+        let work_order = sqlx::query_as::<_, WorkOrder>(
+            r#"
+            INSERT INTO work_orders (vehicle_id, mechanic_id, type, description, provider)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#
+        )
+        .bind(dto.vehicle_id)
+        .bind(dto.mechanic_id)
+        .bind(dto.r#type)
+        .bind(dto.description)
+        .bind(dto.provider)
+        .fetch_one(executor)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(work_order)
+    }
+
+    /// Locks the order's row until the transaction ends and returns its vehicle. Lines
+    /// cannot change once the order is closed, so that is rechecked under the lock.
+    async fn lock_open(conn: &mut PgConnection, id: Uuid) -> Result<Uuid, AppError> {
+        let (vehicle_id, status) = sqlx::query_as::<_, (Uuid, WorkOrderStatus)>(
+            "SELECT vehicle_id, status FROM work_orders WHERE id = $1 FOR UPDATE"
+        )
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(AppError::from)?
+        .ok_or(AppError::NotFound(format!("Work order {} not found", id)))?;
+
+        if status.is_closed() {
+            return Err(AppError::BadRequest(format!("Work order {} is already closed", id)));
+        }
+        Ok(vehicle_id)
+    }
+
+    /// Moves the order from `from` to `to`. Returns `None` if its status is no longer `from`.
+    pub(crate) async fn transition<'e>(executor: impl PgExecutor<'e>, id: Uuid, from: WorkOrderStatus, to: WorkOrderStatus) -> Result<Option<WorkOrder>, AppError> {
+        let work_order = sqlx::query_as::<_, WorkOrder>(
+            r#"
+            UPDATE work_orders
+            SET status = $3,
+                started_at = CASE WHEN $3 = 'IN_PROGRESS' THEN COALESCE(started_at, NOW()) ELSE started_at END,
+                completed_at = CASE WHEN $3 IN ('COMPLETED', 'CANCELLED') THEN NOW() ELSE completed_at END,
+                updated_at = NOW()
+            WHERE id = $1 AND status = $2
+            RETURNING *
+            "#
+        )
+        .bind(id)
+        .bind(from)
+        .bind(to)
+        .fetch_optional(executor)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(work_order)
+    }
+
+    /// Closes an in-progress order and logs it as a maintenance record. The record's cost
+    /// covers lines not drawn from inventory; stocked parts are costed through their
+    /// movements, which get linked to the record. Returns `None` if the order was not in
+    /// progress.
+    pub(crate) async fn complete(conn: &mut PgConnection, id: Uuid) -> Result<Option<WorkOrder>, AppError> {
+        // Lock the order so a concurrent completion cannot log a second record
+        let in_progress = sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM work_orders WHERE id = $1 AND status = 'IN_PROGRESS' FOR UPDATE"
+        )
+        .bind(id)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(AppError::from)?;
+
+        if in_progress.is_none() {
+            return Ok(None);
+        }
+
+        let record_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO maintenance_records (
+                id, vehicle_id, type, cost, date, provider, description, created_at, mileage
+            )
+            SELECT
+                gen_random_uuid(),
+                w.vehicle_id,
+                w.type,
+                COALESCE((
+                    SELECT SUM(i.quantity * i.unit_cost) FROM work_order_items i
+                    WHERE i.work_order_id = w.id AND i.part_id IS NULL
+                ), 0),
+                NOW(),
+                COALESCE(NULLIF(w.provider, ''), $2),
+                w.description,
+                NOW(),
+                v.current_mileage
+            FROM work_orders w
+            JOIN vehicles v ON v.id = w.vehicle_id
+            WHERE w.id = $1
+            RETURNING id
+            "#
+        )
+        .bind(id)
+        .bind(IN_HOUSE_PROVIDER)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(AppError::from)?;
+
+        sqlx::query("UPDATE stock_movements SET maintenance_record_id = $2 WHERE work_order_id = $1")
+            .bind(id)
+            .bind(record_id)
+            .execute(&mut *conn)
+            .await
+            .map_err(AppError::from)?;
+
+        let work_order = sqlx::query_as::<_, WorkOrder>(
+            r#"
+            UPDATE work_orders
+            SET status = 'COMPLETED',
+                maintenance_record_id = $2,
+                completed_at = NOW(),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#
+        )
+        .bind(id)
+        .bind(record_id)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(Some(work_order))
+    }
+
+    /// Orders for the vehicle that are neither completed nor cancelled.
+    pub(crate) async fn count_open<'e>(executor: impl PgExecutor<'e>, vehicle_id: Uuid) -> Result<i64, AppError> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*) FROM work_orders
+            WHERE vehicle_id = $1 AND status NOT IN ('COMPLETED', 'CANCELLED')
+            "#
+        )
+        .bind(vehicle_id)
+        .fetch_one(executor)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(count)
+    }
+}
+
+#[async_trait]
+impl WorkOrderRepositoryTrait for WorkOrderRepository {
+    async fn find_all(&self, query: WorkOrderQuery) -> Result<Vec<WorkOrder>, AppError> {
+        let work_orders = sqlx::query_as::<_, WorkOrder>(
+            r#"
+            SELECT * FROM work_orders
+            WHERE ($1::work_order_status IS NULL OR status = $1)
+              AND ($2::uuid IS NULL OR vehicle_id = $2)
+              AND ($3::uuid IS NULL OR mechanic_id = $3)
+            ORDER BY created_at DESC
+            "#
+        )
+        .bind(query.status)
+        .bind(query.vehicle_id)
+        .bind(query.mechanic_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(work_orders)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<WorkOrder>, AppError> {
+        let work_order = sqlx::query_as::<_, WorkOrder>("SELECT * FROM work_orders WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::from)?;
+
+        Ok(work_order)
+    }
+
+    async fn assign_mechanic(&self, id: Uuid, mechanic_id: Uuid) -> Result<Option<WorkOrder>, AppError> {
+        let work_order = sqlx::query_as::<_, WorkOrder>(
+            r#"
+            UPDATE work_orders
+            SET mechanic_id = $2, updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#
+        )
+        .bind(id)
+        .bind(mechanic_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(work_order)
+    }
+
+    async fn add_item(&self, work_order_id: Uuid, dto: CreateWorkOrderItemDto) -> Result<Option<WorkOrderItem>, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+
+        let vehicle_id = Self::lock_open(&mut tx, work_order_id).await?;
+
+        let unit_cost = match dto.part_id {
+            Some(part_id) => {
+                let consumption = NewStockMovement {
+                    part_id,
+                    kind: StockMovementKind::Consumption,
+                    quantity: -dto.quantity,
+                    unit_cost: None,
+                    vehicle_id: Some(vehicle_id),
+                    work_order_id: Some(work_order_id),
+                    maintenance_record_id: None,
+                    note: None,
+                };
+                match apply_movement(&mut tx, &consumption).await? {
+                    Some(part) => part.unit_cost,
+                    None => return Ok(None),
+                }
+            }
+            None => dto.unit_cost.unwrap_or_default(),
+        };
+
+        let item = sqlx::query_as::<_, WorkOrderItem>(
+            r#"
+            INSERT INTO work_order_items (work_order_id, kind, part_id, description, quantity, unit_cost)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING *
+            "#
+        )
+        .bind(work_order_id)
+        .bind(dto.kind)
+        .bind(dto.part_id)
+        .bind(dto.description)
+        .bind(dto.quantity)
+        .bind(unit_cost)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(Some(item))
+    }
+
+    async fn find_items(&self, work_order_id: Uuid) -> Result<Vec<WorkOrderItem>, AppError> {
+        let items = sqlx::query_as::<_, WorkOrderItem>(
+            "SELECT * FROM work_order_items WHERE work_order_id = $1 ORDER BY created_at"
+        )
+        .bind(work_order_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(AppError::from)?;
+
+        Ok(items)
+    }
+
+    async fn delete_item(&self, work_order_id: Uuid, item_id: Uuid) -> Result<Option<WorkOrderItem>, AppError> {
+        let mut tx = self.pool.begin().await.map_err(AppError::from)?;
+        let vehicle_id = Self::lock_open(&mut tx, work_order_id).await?;
+
+        let item = sqlx::query_as::<_, WorkOrderItem>(
+            "DELETE FROM work_order_items WHERE id = $1 AND work_order_id = $2 RETURNING *"
+        )
+        .bind(item_id)
+        .bind(work_order_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(AppError::from)?;
+
+        let item = match item {
+            Some(item) => item,
+            None => return Ok(None),
+        };
+
+        if let Some(part_id) = item.part_id {
+            // Returned at the cost it was taken out at, so the vehicle's parts cost nets out
+            let restock = NewStockMovement {
+                part_id,
+                kind: StockMovementKind::Return,
+                quantity: item.quantity,
+                unit_cost: Some(item.unit_cost),
+                vehicle_id: Some(vehicle_id),
+                work_order_id: Some(work_order_id),
+                maintenance_record_id: None,
+                note: None,
+            };
+            apply_movement(&mut tx, &restock).await?;
+        }
+
+        tx.commit().await.map_err(AppError::from)?;
+        Ok(Some(item))
+    }
+}
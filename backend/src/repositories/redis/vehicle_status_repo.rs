@@ -1,8 +1,20 @@
 use redis::AsyncCommands;
 use crate::models::redis::vehicle_status::VehicleStatusCache;
 use crate::error::AppError;
+use async_trait::async_trait;
 use uuid::Uuid;
 
+/// Set of vehicle ids with a cached status, so the whole fleet can be read without `KEYS`.
+const INDEX_KEY: &str = "vehicle_status:index";
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait VehicleStatusCacheRepositoryTrait: Send + Sync {
+    async fn set(&self, status: &VehicleStatusCache) -> Result<(), AppError>;
+    async fn get(&self, vehicle_id: Uuid) -> Result<Option<VehicleStatusCache>, AppError>;
+    async fn get_all(&self) -> Result<Vec<VehicleStatusCache>, AppError>;
+}
+
 pub struct VehicleStatusCacheRepository {
     client: redis::Client,
 }
@@ -12,18 +24,30 @@ impl VehicleStatusCacheRepository {
         Self { client }
     }
 
-    pub async fn set(&self, status: &VehicleStatusCache) -> Result<(), AppError> {
+    fn key(vehicle_id: Uuid) -> String {
+        format!("vehicle_status:{}", vehicle_id)
+    }
+}
+
+#[async_trait]
+impl VehicleStatusCacheRepositoryTrait for VehicleStatusCacheRepository {
+    async fn set(&self, status: &VehicleStatusCache) -> Result<(), AppError> {
         let mut conn = self.client.get_async_connection().await.map_err(|e| AppError::RedisError(e.to_string()))?;
-        let key = format!("vehicle_status:{}", status.vehicle_id);
+        let key = Self::key(status.vehicle_id);
         let json = serde_json::to_string(status).map_err(|e| AppError::SerializationError(e.to_string()))?;
-        
-        conn.set::<_, _, ()>(key, json).await.map_err(|e| AppError::RedisError(e.to_string()))?;
+
+        redis::pipe()
+            .set(key, json).ignore()
+            .sadd(INDEX_KEY, status.vehicle_id.to_string()).ignore()
+            .query_async::<_, ()>(&mut conn)
+            .await
+            .map_err(|e| AppError::RedisError(e.to_string()))?;
         Ok(())
     }
 
-    pub async fn get(&self, vehicle_id: Uuid) -> Result<Option<VehicleStatusCache>, AppError> {
+    async fn get(&self, vehicle_id: Uuid) -> Result<Option<VehicleStatusCache>, AppError> {
         let mut conn = self.client.get_async_connection().await.map_err(|e| AppError::RedisError(e.to_string()))?;
-        let key = format!("vehicle_status:{}", vehicle_id);
+        let key = Self::key(vehicle_id);
         
         let json: Option<String> = conn.get(key).await.map_err(|e| AppError::RedisError(e.to_string()))?;
         
@@ -35,4 +59,27 @@ impl VehicleStatusCacheRepository {
             None => Ok(None),
         }
     }
+
+    async fn get_all(&self) -> Result<Vec<VehicleStatusCache>, AppError> {
+        let mut conn = self.client.get_async_connection().await.map_err(|e| AppError::RedisError(e.to_string()))?;
+
+        let ids: Vec<String> = conn.smembers(INDEX_KEY).await.map_err(|e| AppError::RedisError(e.to_string()))?;
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let keys: Vec<String> = ids.iter().map(|id| format!("vehicle_status:{}", id)).collect();
+        // `AsyncCommands::get` sends GET for a one-element list, so issue MGET explicitly
+        let values: Vec<Option<String>> = redis::cmd("MGET")
+            .arg(&keys)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::RedisError(e.to_string()))?;
+
+        values
+            .into_iter()
+            .flatten()
+            .map(|s| serde_json::from_str(&s).map_err(|e| AppError::SerializationError(e.to_string())))
+            .collect()
+    }
 }
@@ -0,0 +1,71 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use async_trait::async_trait;
+use crate::error::AppError;
+use super::{validate_key, StorageTrait};
+
+/// Files under a root directory, one file per key. Meant for development and tests.
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, AppError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl StorageTrait for LocalStorage {
+    async fn put(&self, key: &str, _content_type: &str, data: Vec<u8>) -> Result<(), AppError> {
+        let path = self.path(key)?;
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir).await.map_err(|e| AppError::StorageError(e.to_string()))?;
+        }
+        tokio::fs::write(&path, data).await.map_err(|e| AppError::StorageError(e.to_string()))
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        match tokio::fs::read(self.path(key)?).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(AppError::StorageError(e.to_string())),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(AppError::StorageError(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_local_storage_round_trip() {
+        let root = std::env::temp_dir().join(format!("fleet-storage-{}", Uuid::new_v4()));
+        let storage = LocalStorage::new(&root);
+
+        storage.put("pod/job-1/signature", "image/png", vec![1, 2, 3]).await.unwrap();
+        assert_eq!(storage.get("pod/job-1/signature").await.unwrap(), Some(vec![1, 2, 3]));
+
+        storage.delete("pod/job-1/signature").await.unwrap();
+        assert_eq!(storage.get("pod/job-1/signature").await.unwrap(), None);
+        // Deleting twice is not an error, so cleanup can be retried
+        storage.delete("pod/job-1/signature").await.unwrap();
+        assert!(storage.get("../outside").await.is_err());
+
+        tokio::fs::remove_dir_all(&root).await.unwrap();
+    }
+}
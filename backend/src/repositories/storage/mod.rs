@@ -0,0 +1,62 @@
+pub mod local_storage;
+pub mod s3_storage;
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use crate::config::Config;
+use crate::error::AppError;
+
+pub use local_storage::LocalStorage;
+pub use s3_storage::S3Storage;
+
+/// Binary files such as signatures, photos and scanned documents, addressed by a
+/// `/`-separated key like `pod/{job_id}/{file_id}`. Content types are kept by the caller
+/// alongside the key.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait StorageTrait: Send + Sync {
+    async fn put(&self, key: &str, content_type: &str, data: Vec<u8>) -> Result<(), AppError>;
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError>;
+    async fn delete(&self, key: &str) -> Result<(), AppError>;
+}
+
+/// S3 when a bucket is configured, otherwise the local filesystem. Fails on an unusable
+/// S3 configuration rather than on the first upload.
+pub fn init(config: &Config) -> Result<Arc<dyn StorageTrait>, AppError> {
+    Ok(match &config.s3 {
+        Some(s3) => Arc::new(S3Storage::new(s3.clone())?),
+        None => Arc::new(LocalStorage::new(&config.storage_path)),
+    })
+}
+
+/// Keys come from ids we generate, but are checked anyway so none can escape the
+/// storage root or bucket.
+pub(crate) fn validate_key(key: &str) -> Result<(), AppError> {
+    let valid = !key.is_empty()
+        && key.split('/').all(|segment| {
+            !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::StorageError(format!("Invalid storage key: {}", key)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_key() {
+        assert!(validate_key("pod/6d1f/signature.png").is_ok());
+        assert!(validate_key("").is_err());
+        assert!(validate_key("/etc/passwd").is_err());
+        assert!(validate_key("pod/../secrets").is_err());
+        assert!(validate_key("pod//x").is_err());
+        assert!(validate_key("pod/a b").is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use std::time::Duration;
+use async_trait::async_trait;
+use object_store::aws::{AmazonS3, AmazonS3Builder};
+use object_store::path::Path;
+use object_store::{Attribute, Attributes, ClientOptions, ObjectStore, PutOptions, PutPayload};
+use crate::config::S3Config;
+use crate::error::AppError;
+use super::{validate_key, StorageTrait};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// S3-compatible object storage (AWS S3, MinIO) with path-style URLs. Signing, TLS and
+/// retries of transient failures are handled by `object_store`.
+pub struct S3Storage {
+    store: AmazonS3,
+}
+
+impl S3Storage {
+    /// Fails when the endpoint is not an `http://` or `https://` URL or the client
+    /// cannot be built from the config.
+    pub fn new(config: S3Config) -> Result<Self, AppError> {
+        let allow_http = if config.endpoint.starts_with("https://") {
+            false
+        } else if config.endpoint.starts_with("http://") {
+            true
+        } else {
+            return Err(AppError::StorageError(format!(
+                "S3_ENDPOINT must be an http:// or https:// URL, got {}",
+                config.endpoint
+            )));
+        };
+
+        let store = AmazonS3Builder::new()
+            .with_endpoint(config.endpoint.trim_end_matches('/'))
+            .with_bucket_name(config.bucket)
+            .with_region(config.region)
+            .with_access_key_id(config.access_key_id)
+            .with_secret_access_key(config.secret_access_key)
+            .with_allow_http(allow_http)
+            .with_client_options(ClientOptions::new().with_timeout(REQUEST_TIMEOUT))
+            .build()
+            .map_err(|e| AppError::StorageError(format!("Invalid S3 configuration: {}", e)))?;
+        Ok(Self { store })
+    }
+
+    fn path(key: &str) -> Result<Path, AppError> {
+        validate_key(key)?;
+        Path::parse(key).map_err(|e| AppError::StorageError(format!("Invalid storage key {}: {}", key, e)))
+    }
+}
+
+fn storage_error(method: &str, key: &str, error: object_store::Error) -> AppError {
+    AppError::StorageError(format!("S3 {} {} failed: {}", method, key, error))
+}
+
+#[async_trait]
+impl StorageTrait for S3Storage {
+    async fn put(&self, key: &str, content_type: &str, data: Vec<u8>) -> Result<(), AppError> {
+        let mut attributes = Attributes::new();
+        attributes.insert(Attribute::ContentType, content_type.to_string().into());
+        let options = PutOptions { attributes, ..Default::default() };
+
+        self.store
+            .put_opts(&Self::path(key)?, PutPayload::from(data), options)
+            .await
+            .map_err(|e| storage_error("PUT", key, e))?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, AppError> {
+        let result = match self.store.get(&Self::path(key)?).await {
+            Ok(result) => result,
+            Err(object_store::Error::NotFound { .. }) => return Ok(None),
+            Err(e) => return Err(storage_error("GET", key, e)),
+        };
+        let data = result.bytes().await.map_err(|e| storage_error("GET", key, e))?;
+        Ok(Some(data.to_vec()))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), AppError> {
+        match self.store.delete(&Self::path(key)?).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(storage_error("DELETE", key, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(endpoint: &str) -> S3Config {
+        S3Config {
+            endpoint: endpoint.into(),
+            bucket: "fleet-documents".into(),
+            region: "us-east-1".into(),
+            access_key_id: "AKIDEXAMPLE".into(),
+            secret_access_key: "secret".into(),
+        }
+    }
+
+    #[test]
+    fn test_new_accepts_http_and_https_endpoints() {
+        assert!(S3Storage::new(config("http://minio:9000")).is_ok());
+        assert!(S3Storage::new(config("https://s3.eu-central-1.amazonaws.com/")).is_ok());
+    }
+
+    #[test]
+    fn test_new_rejects_invalid_endpoint() {
+        let result = S3Storage::new(config("minio:9000"));
+        assert!(matches!(result, Err(AppError::StorageError(_))));
+    }
+}
@@ -1,11 +1,16 @@
 use actix_web::{web, HttpResponse, Responder};
 use uuid::Uuid;
-use crate::models::postgres::assignment::{CreateAssignmentDto, AssignmentStatus};
+use crate::models::postgres::assignment::{
+    CreateAssignmentDto, AssignmentStatus, CancelAssignmentDto, UpdateAssignmentDto, AssignmentQuery,
+};
 use crate::services::assignment_service::AssignmentServiceTrait;
 use crate::error::AppError;
 
-pub async fn list_assignments(service: web::Data<dyn AssignmentServiceTrait>) -> Result<impl Responder, AppError> {
-    let assignments = service.list_assignments().await?;
+pub async fn list_assignments(
+    service: web::Data<dyn AssignmentServiceTrait>,
+    query: web::Query<AssignmentQuery>,
+) -> Result<impl Responder, AppError> {
+    let assignments = service.list_assignments(query.into_inner()).await?;
     Ok(HttpResponse::Ok().json(assignments))
 }
 
@@ -26,21 +31,34 @@ pub async fn create_assignment(
     Ok(HttpResponse::Created().json(assignment))
 }
 
-#[derive(serde::Deserialize)]
-pub struct UpdateAssignmentDto {
-    pub status: Option<AssignmentStatus>,
-    pub end_time: Option<String>,
+pub async fn update_assignment(
+    service: web::Data<dyn AssignmentServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<UpdateAssignmentDto>,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+    let dto = dto.into_inner();
+    let assignment = match dto.status {
+        Some(AssignmentStatus::Active) => service.start_assignment(id).await?,
+        Some(AssignmentStatus::Completed) => service.complete_assignment(id).await?,
+        Some(AssignmentStatus::Cancelled) => {
+            let reason = dto.reason.unwrap_or_default();
+            service.cancel_assignment(id, CancelAssignmentDto { reason }).await?
+        }
+        Some(AssignmentStatus::Scheduled) => {
+            return Err(AppError::BadRequest("Assignments cannot be moved back to Scheduled".into()));
+        }
+        None => return Err(AppError::BadRequest("status is required".into())),
+    };
+    Ok(HttpResponse::Ok().json(assignment))
 }
 
-pub async fn update_assignment(
+pub async fn start_assignment(
     service: web::Data<dyn AssignmentServiceTrait>,
     path: web::Path<Uuid>,
-    _dto: web::Json<UpdateAssignmentDto>,
 ) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
-    // For now, just complete the assignment
-    // TODO: Implement proper update logic for different statuses
-    let assignment = service.complete_assignment(id).await?;
+    let assignment = service.start_assignment(id).await?;
     Ok(HttpResponse::Ok().json(assignment))
 }
 
@@ -53,6 +71,16 @@ pub async fn complete_assignment(
     Ok(HttpResponse::Ok().json(assignment))
 }
 
+pub async fn cancel_assignment(
+    service: web::Data<dyn AssignmentServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<CancelAssignmentDto>,
+) -> Result<impl Responder, AppError> {
+    let id = path.into_inner();
+    let assignment = service.cancel_assignment(id, dto.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(assignment))
+}
+
 pub async fn get_driver_assignments(
     service: web::Data<dyn AssignmentServiceTrait>,
     path: web::Path<Uuid>,
@@ -69,7 +97,9 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("", web::post().to(create_assignment))
             .route("/{id}", web::get().to(get_assignment))
             .route("/{id}", web::put().to(update_assignment))
+            .route("/{id}/start", web::patch().to(start_assignment))
             .route("/{id}/complete", web::patch().to(complete_assignment))
+            .route("/{id}/cancel", web::patch().to(cancel_assignment))
             .route("/driver/{driver_id}", web::get().to(get_driver_assignments))
     );
 }
@@ -1,6 +1,7 @@
 use actix_web::{web, HttpResponse, Responder, HttpMessage, HttpRequest};
-use crate::services::auth_service::{AuthServiceTrait, LoginDto, Claims};
+use crate::services::auth_service::{AuthServiceTrait, LoginDto, Claims, RefreshTokenDto, LogoutDto};
 use crate::models::postgres::user::CreateUserDto;
+use crate::error::AppError;
 
 #[utoipa::path(
     post,
@@ -42,6 +43,50 @@ pub async fn login(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/refresh",
+    request_body = RefreshTokenDto,
+    responses(
+        (status = 200, description = "Tokens rotated successfully", body = AuthResponse),
+        (status = 401, description = "Refresh token invalid, expired or revoked"),
+        (status = 500, description = "Internal Server Error")
+    )
+)]
+pub async fn refresh(
+    service: web::Data<dyn AuthServiceTrait>,
+    dto: web::Json<RefreshTokenDto>,
+) -> Result<impl Responder, AppError> {
+    let response = service.refresh(dto.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/logout",
+    request_body = LogoutDto,
+    responses(
+        (status = 204, description = "Logged out; the access token and refresh token(s) are revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal Server Error")
+    ),
+    security(
+        ("jwt" = [])
+    )
+)]
+pub async fn logout(
+    req: HttpRequest,
+    service: web::Data<dyn AuthServiceTrait>,
+    dto: Option<web::Json<LogoutDto>>,
+) -> Result<impl Responder, AppError> {
+    let claims = req.extensions().get::<Claims>().cloned()
+        .ok_or(AppError::AuthError("Unauthorized".into()))?;
+
+    let dto = dto.map(|d| d.into_inner()).unwrap_or_default();
+    service.logout(claims, dto).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
 #[utoipa::path(
     get,
     path = "/api/auth/me",
@@ -61,17 +106,18 @@ pub async fn me(req: HttpRequest) -> impl Responder {
     }
 }
 
+// Registered as plain resources rather than a `/auth` scope so that requests for
+// the protected `/auth/*` routes fall through to the authenticated scope.
 pub fn config_public(cfg: &mut web::ServiceConfig) {
-    cfg.service(
-        web::scope("/auth")
-            .route("/register", web::post().to(register))
-            .route("/login", web::post().to(login))
-    );
+    cfg.service(web::resource("/auth/register").route(web::post().to(register)))
+        .service(web::resource("/auth/login").route(web::post().to(login)))
+        .service(web::resource("/auth/refresh").route(web::post().to(refresh)));
 }
 
 pub fn config_protected(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/auth")
             .route("/me", web::get().to(me))
+            .route("/logout", web::post().to(logout))
     );
 }
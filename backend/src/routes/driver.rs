@@ -1,40 +1,31 @@
 use actix_web::{web, HttpResponse, Responder};
 use crate::models::postgres::driver::{CreateDriverDto, DriverStatus};
+use crate::models::postgres::driver_score::{DriverScoreQuery, LeaderboardQuery};
 use crate::services::driver_service::DriverServiceTrait;
+use crate::services::driver_score_service::DriverScoreServiceTrait;
+use crate::error::AppError;
 use uuid::Uuid;
 
-pub async fn get_drivers(service: web::Data<dyn DriverServiceTrait>) -> impl Responder {
-    let result = service.list_drivers().await;
-
-    match result {
-        Ok(drivers) => HttpResponse::Ok().json(drivers),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+pub async fn get_drivers(service: web::Data<dyn DriverServiceTrait>) -> Result<impl Responder, AppError> {
+    let drivers = service.list_drivers().await?;
+    Ok(HttpResponse::Ok().json(drivers))
 }
 
 pub async fn get_driver_by_id(
     service: web::Data<dyn DriverServiceTrait>,
     path: web::Path<Uuid>,
-) -> impl Responder {
+) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
-    let result = service.get_driver(id).await;
-
-    match result {
-        Ok(driver) => HttpResponse::Ok().json(driver),
-        Err(e) => HttpResponse::NotFound().body(e.to_string()),
-    }
+    let driver = service.get_driver(id).await?;
+    Ok(HttpResponse::Ok().json(driver))
 }
 
 pub async fn create_driver(
     service: web::Data<dyn DriverServiceTrait>,
     item: web::Json<CreateDriverDto>,
-) -> impl Responder {
-    let result = service.create_driver(item.into_inner()).await;
-
-    match result {
-        Ok(driver) => HttpResponse::Created().json(driver),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    let driver = service.create_driver(item.into_inner()).await?;
+    Ok(HttpResponse::Created().json(driver))
 }
 
 #[derive(serde::Deserialize)]
@@ -47,32 +38,41 @@ pub async fn update_driver(
     service: web::Data<dyn DriverServiceTrait>,
     path: web::Path<Uuid>,
     item: web::Json<UpdateDriverDto>,
-) -> impl Responder {
+) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
-    
+
     // For now, we only support status updates
-    if let Some(status) = item.status {
-        let result = service.update_driver_status(id, status).await;
-        match result {
-            Ok(driver) => HttpResponse::Ok().json(driver),
-            Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-        }
-    } else {
-        HttpResponse::BadRequest().body("No updates provided")
-    }
+    let Some(status) = item.status else {
+        return Err(AppError::BadRequest("No updates provided".into()));
+    };
+    let driver = service.update_driver_status(id, status).await?;
+    Ok(HttpResponse::Ok().json(driver))
 }
 
 pub async fn delete_driver(
     service: web::Data<dyn DriverServiceTrait>,
     path: web::Path<Uuid>,
-) -> impl Responder {
+) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
-    let result = service.delete_driver(id).await;
+    service.delete_driver(id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn get_driver_score(
+    service: web::Data<dyn DriverScoreServiceTrait>,
+    path: web::Path<Uuid>,
+    query: web::Query<DriverScoreQuery>,
+) -> Result<impl Responder, AppError> {
+    let score = service.get_driver_score(path.into_inner(), query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(score))
+}
 
-    match result {
-        Ok(_) => HttpResponse::NoContent().finish(),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+pub async fn get_leaderboard(
+    service: web::Data<dyn DriverScoreServiceTrait>,
+    query: web::Query<LeaderboardQuery>,
+) -> Result<impl Responder, AppError> {
+    let leaderboard = service.get_leaderboard(query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(leaderboard))
 }
 
 pub fn config(cfg: &mut web::ServiceConfig) {
@@ -80,8 +80,10 @@ pub fn config(cfg: &mut web::ServiceConfig) {
         web::scope("/drivers")
             .route("", web::get().to(get_drivers))
             .route("", web::post().to(create_driver))
+            .route("/leaderboard", web::get().to(get_leaderboard))
             .route("/{id}", web::get().to(get_driver_by_id))
             .route("/{id}", web::put().to(update_driver))
             .route("/{id}", web::delete().to(delete_driver))
+            .route("/{id}/score", web::get().to(get_driver_score))
     );
 }
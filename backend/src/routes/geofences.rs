@@ -0,0 +1,54 @@
+use actix_web::{web, HttpResponse, Responder};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::geofence::CreateGeofenceDto;
+use crate::services::geofence_service::GeofenceServiceTrait;
+
+pub async fn create_geofence(
+    service: web::Data<dyn GeofenceServiceTrait>,
+    dto: web::Json<CreateGeofenceDto>,
+) -> Result<impl Responder, AppError> {
+    let geofence = service.create_geofence(dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(geofence))
+}
+
+pub async fn list_geofences(service: web::Data<dyn GeofenceServiceTrait>) -> Result<impl Responder, AppError> {
+    let geofences = service.list_geofences().await?;
+    Ok(HttpResponse::Ok().json(geofences))
+}
+
+pub async fn get_geofence(
+    service: web::Data<dyn GeofenceServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let geofence = service.get_geofence(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(geofence))
+}
+
+pub async fn update_geofence(
+    service: web::Data<dyn GeofenceServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<CreateGeofenceDto>,
+) -> Result<impl Responder, AppError> {
+    let geofence = service.update_geofence(path.into_inner(), dto.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(geofence))
+}
+
+pub async fn delete_geofence(
+    service: web::Data<dyn GeofenceServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    service.delete_geofence(path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/geofences")
+            .route("", web::post().to(create_geofence))
+            .route("", web::get().to(list_geofences))
+            .route("/{id}", web::get().to(get_geofence))
+            .route("/{id}", web::put().to(update_geofence))
+            .route("/{id}", web::delete().to(delete_geofence))
+    );
+}
@@ -0,0 +1,48 @@
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::hos::{ClockDto, HosLogQuery};
+use crate::routes::current_claims;
+use crate::services::hos_service::HosServiceTrait;
+
+pub async fn clock_in(
+    req: HttpRequest,
+    service: web::Data<dyn HosServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<ClockDto>,
+) -> Result<impl Responder, AppError> {
+    let caller = current_claims(&req)?;
+    let event = service.clock_in(path.into_inner(), dto.into_inner(), &caller).await?;
+    Ok(HttpResponse::Created().json(event))
+}
+
+pub async fn clock_out(
+    req: HttpRequest,
+    service: web::Data<dyn HosServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<ClockDto>,
+) -> Result<impl Responder, AppError> {
+    let caller = current_claims(&req)?;
+    let event = service.clock_out(path.into_inner(), dto.into_inner(), &caller).await?;
+    Ok(HttpResponse::Created().json(event))
+}
+
+pub async fn get_log(
+    req: HttpRequest,
+    service: web::Data<dyn HosServiceTrait>,
+    path: web::Path<Uuid>,
+    query: web::Query<HosLogQuery>,
+) -> Result<impl Responder, AppError> {
+    let caller = current_claims(&req)?;
+    let log = service.get_log(path.into_inner(), query.into_inner(), &caller).await?;
+    Ok(HttpResponse::Ok().json(log))
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/hos")
+            .route("/drivers/{driver_id}/clock-in", web::post().to(clock_in))
+            .route("/drivers/{driver_id}/clock-out", web::post().to(clock_out))
+            .route("/drivers/{driver_id}/log", web::get().to(get_log))
+    );
+}
@@ -0,0 +1,84 @@
+use actix_web::{http::header, web, HttpRequest, HttpResponse, Responder};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::invoice::{
+    AgingQuery, CreateInvoiceDto, InvoiceQuery, RecordPaymentDto, StatementQuery
+};
+use crate::routes::current_user;
+use crate::services::invoice_service::InvoiceServiceTrait;
+
+pub async fn list_invoices(
+    service: web::Data<dyn InvoiceServiceTrait>,
+    query: web::Query<InvoiceQuery>,
+) -> Result<impl Responder, AppError> {
+    let invoices = service.list(query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(invoices))
+}
+
+pub async fn create_invoice(
+    req: HttpRequest,
+    service: web::Data<dyn InvoiceServiceTrait>,
+    dto: web::Json<CreateInvoiceDto>,
+) -> Result<impl Responder, AppError> {
+    let invoice = service.create(dto.into_inner(), current_user(&req)).await?;
+    Ok(HttpResponse::Created().json(invoice))
+}
+
+pub async fn get_invoice(
+    service: web::Data<dyn InvoiceServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let invoice = service.get(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(invoice))
+}
+
+pub async fn get_invoice_pdf(
+    service: web::Data<dyn InvoiceServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let (invoice_number, pdf) = service.render_pdf(path.into_inner()).await?;
+    Ok(HttpResponse::Ok()
+        .content_type("application/pdf")
+        .insert_header((header::CONTENT_DISPOSITION, format!("inline; filename=\"{}.pdf\"", invoice_number)))
+        .body(pdf))
+}
+
+pub async fn record_payment(
+    req: HttpRequest,
+    service: web::Data<dyn InvoiceServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<RecordPaymentDto>,
+) -> Result<impl Responder, AppError> {
+    let invoice = service.record_payment(path.into_inner(), dto.into_inner(), current_user(&req)).await?;
+    Ok(HttpResponse::Created().json(invoice))
+}
+
+pub async fn get_aging_report(
+    service: web::Data<dyn InvoiceServiceTrait>,
+    query: web::Query<AgingQuery>,
+) -> Result<impl Responder, AppError> {
+    let report = service.aging_report(query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(report))
+}
+
+pub async fn get_customer_statement(
+    service: web::Data<dyn InvoiceServiceTrait>,
+    path: web::Path<Uuid>,
+    query: web::Query<StatementQuery>,
+) -> Result<impl Responder, AppError> {
+    let statement = service.customer_statement(path.into_inner(), query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(statement))
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/invoices")
+            .route("", web::get().to(list_invoices))
+            .route("", web::post().to(create_invoice))
+            .route("/aging", web::get().to(get_aging_report))
+            .route("/statements/{customer_id}", web::get().to(get_customer_statement))
+            .route("/{id}", web::get().to(get_invoice))
+            .route("/{id}/pdf", web::get().to(get_invoice_pdf))
+            .route("/{id}/payments", web::post().to(record_payment))
+    );
+}
@@ -1,132 +1,205 @@
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use uuid::Uuid;
 use crate::models::postgres::logistics::{
-    CreateCustomerDto, CreateTransportJobDto, CreateRouteDto, CreateShipmentDto, JobStatus
+    CreateCustomerDto, UpdateCreditLimitDto, CreateTransportJobDto, CreateRouteDto, CreateShipmentDto, UpdateJobStatusDto, AddJobLegDto
 };
+use crate::models::postgres::dispatch::{AcceptDispatchDto, DispatchSuggestionQuery};
+use crate::models::postgres::proof_of_delivery::CreateProofOfDeliveryDto;
 use crate::services::logistics_service::LogisticsServiceTrait;
+use crate::services::dispatch_service::DispatchServiceTrait;
+use crate::services::proof_of_delivery_service::ProofOfDeliveryServiceTrait;
+use crate::routes::current_user;
+use crate::error::AppError;
+
+/// Room for a base64 signature and the maximum number of photos.
+const POD_PAYLOAD_LIMIT: usize = 64 * 1024 * 1024;
 
 // Customers
 pub async fn create_customer(
     service: web::Data<dyn LogisticsServiceTrait>,
     dto: web::Json<CreateCustomerDto>,
-) -> impl Responder {
-    match service.create_customer(dto.into_inner()).await {
-        Ok(customer) => HttpResponse::Created().json(customer),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    let customer = service.create_customer(dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(customer))
 }
 
-pub async fn list_customers(service: web::Data<dyn LogisticsServiceTrait>) -> impl Responder {
-    match service.list_customers().await {
-        Ok(customers) => HttpResponse::Ok().json(customers),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+pub async fn list_customers(service: web::Data<dyn LogisticsServiceTrait>) -> Result<impl Responder, AppError> {
+    let customers = service.list_customers().await?;
+    Ok(HttpResponse::Ok().json(customers))
 }
 
 pub async fn get_customer(
     service: web::Data<dyn LogisticsServiceTrait>,
     path: web::Path<Uuid>,
-) -> impl Responder {
-    match service.get_customer(path.into_inner()).await {
-        Ok(customer) => HttpResponse::Ok().json(customer),
-        Err(e) => HttpResponse::NotFound().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    let customer = service.get_customer(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(customer))
 }
 
 pub async fn delete_customer(
     service: web::Data<dyn LogisticsServiceTrait>,
     path: web::Path<Uuid>,
-) -> impl Responder {
-    match service.delete_customer(path.into_inner()).await {
-        Ok(_) => HttpResponse::NoContent().finish(),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    service.delete_customer(path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn update_customer_credit_limit(
+    service: web::Data<dyn LogisticsServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<UpdateCreditLimitDto>,
+) -> Result<impl Responder, AppError> {
+    let customer = service.update_customer_credit_limit(path.into_inner(), dto.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(customer))
 }
 
 // Jobs
 pub async fn create_job(
+    req: HttpRequest,
     service: web::Data<dyn LogisticsServiceTrait>,
     dto: web::Json<CreateTransportJobDto>,
-) -> impl Responder {
-    match service.create_job(dto.into_inner()).await {
-        Ok(job) => HttpResponse::Created().json(job),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    let job = service.create_job(dto.into_inner(), current_user(&req)).await?;
+    Ok(HttpResponse::Created().json(job))
 }
 
-pub async fn list_jobs(service: web::Data<dyn LogisticsServiceTrait>) -> impl Responder {
-    match service.list_jobs().await {
-        Ok(jobs) => HttpResponse::Ok().json(jobs),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+pub async fn list_jobs(service: web::Data<dyn LogisticsServiceTrait>) -> Result<impl Responder, AppError> {
+    let jobs = service.list_jobs().await?;
+    Ok(HttpResponse::Ok().json(jobs))
 }
 
 pub async fn get_job(
     service: web::Data<dyn LogisticsServiceTrait>,
     path: web::Path<Uuid>,
-) -> impl Responder {
-    match service.get_job(path.into_inner()).await {
-        Ok(job) => HttpResponse::Ok().json(job),
-        Err(e) => HttpResponse::NotFound().body(e.to_string()),
-    }
-}
-
-#[derive(serde::Deserialize)]
-pub struct UpdateJobStatusDto {
-    status: JobStatus,
+) -> Result<impl Responder, AppError> {
+    let job = service.get_job(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(job))
 }
 
 pub async fn update_job_status(
+    req: HttpRequest,
     service: web::Data<dyn LogisticsServiceTrait>,
     path: web::Path<Uuid>,
     dto: web::Json<UpdateJobStatusDto>,
-) -> impl Responder {
-    match service.update_job_status(path.into_inner(), dto.status.clone()).await {
-        Ok(job) => HttpResponse::Ok().json(job),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    let job = service.update_job_status(path.into_inner(), dto.into_inner(), current_user(&req)).await?;
+    Ok(HttpResponse::Ok().json(job))
+}
+
+pub async fn get_job_status_history(
+    service: web::Data<dyn LogisticsServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let history = service.get_job_status_history(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(history))
+}
+
+// Job legs
+pub async fn get_job_legs(
+    service: web::Data<dyn LogisticsServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let legs = service.get_job_legs(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(legs))
+}
+
+pub async fn add_job_leg(
+    service: web::Data<dyn LogisticsServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<AddJobLegDto>,
+) -> Result<impl Responder, AppError> {
+    let leg = service.add_job_leg(path.into_inner(), dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(leg))
+}
+
+pub async fn remove_job_leg(
+    service: web::Data<dyn LogisticsServiceTrait>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<impl Responder, AppError> {
+    let (job_id, assignment_id) = path.into_inner();
+    service.remove_job_leg(job_id, assignment_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+// Dispatch
+pub async fn suggest_dispatch(
+    service: web::Data<dyn DispatchServiceTrait>,
+    path: web::Path<Uuid>,
+    query: web::Query<DispatchSuggestionQuery>,
+) -> Result<impl Responder, AppError> {
+    let suggestions = service.suggest(path.into_inner(), query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(suggestions))
+}
+
+pub async fn accept_dispatch(
+    service: web::Data<dyn DispatchServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<AcceptDispatchDto>,
+) -> Result<impl Responder, AppError> {
+    let assignment = service.accept(path.into_inner(), dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(assignment))
+}
+
+// Proof of delivery
+pub async fn capture_proof_of_delivery(
+    req: HttpRequest,
+    service: web::Data<dyn ProofOfDeliveryServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<CreateProofOfDeliveryDto>,
+) -> Result<impl Responder, AppError> {
+    let proof = service.capture(path.into_inner(), dto.into_inner(), current_user(&req)).await?;
+    Ok(HttpResponse::Created().json(proof))
+}
+
+pub async fn get_proof_of_delivery(
+    service: web::Data<dyn ProofOfDeliveryServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let proof = service.get(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(proof))
+}
+
+pub async fn get_proof_of_delivery_file(
+    service: web::Data<dyn ProofOfDeliveryServiceTrait>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<impl Responder, AppError> {
+    let (job_id, file_id) = path.into_inner();
+    let (content_type, data) = service.get_file(job_id, file_id).await?;
+    Ok(HttpResponse::Ok().content_type(content_type).body(data))
 }
 
 // Routes
 pub async fn create_route(
     service: web::Data<dyn LogisticsServiceTrait>,
     dto: web::Json<CreateRouteDto>,
-) -> impl Responder {
-    match service.create_route(dto.into_inner()).await {
-        Ok(route) => HttpResponse::Created().json(route),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    let route = service.create_route(dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(route))
 }
 
 pub async fn get_job_route(
     service: web::Data<dyn LogisticsServiceTrait>,
     path: web::Path<Uuid>,
-) -> impl Responder {
-    match service.get_job_route(path.into_inner()).await {
-        Ok(route) => HttpResponse::Ok().json(route),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    let route = service.get_job_route(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(route))
 }
 
 // Shipments
 pub async fn create_shipment(
     service: web::Data<dyn LogisticsServiceTrait>,
     dto: web::Json<CreateShipmentDto>,
-) -> impl Responder {
-    match service.create_shipment(dto.into_inner()).await {
-        Ok(shipment) => HttpResponse::Created().json(shipment),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    let shipment = service.create_shipment(dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(shipment))
 }
 
 pub async fn get_job_shipments(
     service: web::Data<dyn LogisticsServiceTrait>,
     path: web::Path<Uuid>,
-) -> impl Responder {
-    match service.get_job_shipments(path.into_inner()).await {
-        Ok(shipments) => HttpResponse::Ok().json(shipments),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    let shipments = service.get_job_shipments(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(shipments))
 }
 
 pub fn config(cfg: &mut web::ServiceConfig) {
@@ -138,6 +211,7 @@ pub fn config(cfg: &mut web::ServiceConfig) {
                     .route("", web::get().to(list_customers))
                     .route("/{id}", web::get().to(get_customer))
                     .route("/{id}", web::delete().to(delete_customer))
+                    .route("/{id}/credit-limit", web::put().to(update_customer_credit_limit))
             )
             .service(
                 web::scope("/jobs")
@@ -145,6 +219,19 @@ pub fn config(cfg: &mut web::ServiceConfig) {
                     .route("", web::get().to(list_jobs))
                     .route("/{id}", web::get().to(get_job))
                     .route("/{id}/status", web::patch().to(update_job_status))
+                    .route("/{id}/history", web::get().to(get_job_status_history))
+                    .route("/{id}/legs", web::get().to(get_job_legs))
+                    .route("/{id}/legs", web::post().to(add_job_leg))
+                    .route("/{id}/legs/{assignment_id}", web::delete().to(remove_job_leg))
+                    .route("/{id}/dispatch-suggestions", web::get().to(suggest_dispatch))
+                    .route("/{id}/dispatch", web::post().to(accept_dispatch))
+                    .service(
+                        web::resource("/{id}/proof-of-delivery")
+                            .app_data(web::JsonConfig::default().limit(POD_PAYLOAD_LIMIT))
+                            .route(web::post().to(capture_proof_of_delivery))
+                            .route(web::get().to(get_proof_of_delivery))
+                    )
+                    .route("/{id}/proof-of-delivery/files/{file_id}", web::get().to(get_proof_of_delivery_file))
             )
             .service(
                 web::scope("/routes")
@@ -1,5 +1,7 @@
 use actix_web::{web, HttpResponse, Responder};
+use chrono::Utc;
 use uuid::Uuid;
+use crate::error::AppError;
 use crate::models::postgres::maintenance::{CreateMaintenanceRecordDto, CreateMaintenanceScheduleDto, CreateAlertDto};
 use crate::models::postgres::vehicle::VehicleType;
 use crate::services::maintenance_service::MaintenanceServiceTrait;
@@ -8,82 +10,69 @@ use crate::services::maintenance_service::MaintenanceServiceTrait;
 pub async fn create_record(
     service: web::Data<dyn MaintenanceServiceTrait>,
     dto: web::Json<CreateMaintenanceRecordDto>,
-) -> impl Responder {
-    let result = service.create_record(dto.into_inner()).await;
-    match result {
-        Ok(record) => HttpResponse::Created().json(record),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    let record = service.create_record(dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(record))
 }
 
 pub async fn get_vehicle_records(
     service: web::Data<dyn MaintenanceServiceTrait>,
     path: web::Path<Uuid>,
-) -> impl Responder {
+) -> Result<impl Responder, AppError> {
     let vehicle_id = path.into_inner();
-    let result = service.get_vehicle_records(vehicle_id).await;
-    match result {
-        Ok(records) => HttpResponse::Ok().json(records),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+    let records = service.get_vehicle_records(vehicle_id).await?;
+    Ok(HttpResponse::Ok().json(records))
 }
 
 // Schedules
 pub async fn create_schedule(
     service: web::Data<dyn MaintenanceServiceTrait>,
     dto: web::Json<CreateMaintenanceScheduleDto>,
-) -> impl Responder {
-    let result = service.create_schedule(dto.into_inner()).await;
-    match result {
-        Ok(schedule) => HttpResponse::Created().json(schedule),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    let schedule = service.create_schedule(dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(schedule))
 }
 
 pub async fn get_schedule(
     service: web::Data<dyn MaintenanceServiceTrait>,
     path: web::Path<VehicleType>,
-) -> impl Responder {
+) -> Result<impl Responder, AppError> {
     let vehicle_type = path.into_inner();
-    let result = service.get_schedule(vehicle_type).await;
-    match result {
-        Ok(schedule) => HttpResponse::Ok().json(schedule),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+    let schedule = service.get_schedule(vehicle_type).await?;
+    Ok(HttpResponse::Ok().json(schedule))
 }
 
 // Alerts
 pub async fn create_alert(
     service: web::Data<dyn MaintenanceServiceTrait>,
     dto: web::Json<CreateAlertDto>,
-) -> impl Responder {
-    let result = service.create_alert(dto.into_inner()).await;
-    match result {
-        Ok(alert) => HttpResponse::Created().json(alert),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    let alert = service.create_alert(dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(alert))
 }
 
 pub async fn get_unresolved_alerts(
     service: web::Data<dyn MaintenanceServiceTrait>,
-) -> impl Responder {
-    let result = service.get_unresolved_alerts().await;
-    match result {
-        Ok(alerts) => HttpResponse::Ok().json(alerts),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    let alerts = service.get_unresolved_alerts().await?;
+    Ok(HttpResponse::Ok().json(alerts))
 }
 
 pub async fn resolve_alert(
     service: web::Data<dyn MaintenanceServiceTrait>,
     path: web::Path<Uuid>,
-) -> impl Responder {
+) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
-    let result = service.resolve_alert(id).await;
-    match result {
-        Ok(alert) => HttpResponse::Ok().json(alert),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+    let alert = service.resolve_alert(id).await?;
+    Ok(HttpResponse::Ok().json(alert))
+}
+
+// Due dates
+pub async fn get_due(
+    service: web::Data<dyn MaintenanceServiceTrait>,
+) -> Result<impl Responder, AppError> {
+    let report = service.get_due(Utc::now()).await?;
+    Ok(HttpResponse::Ok().json(report))
 }
 
 pub fn config(cfg: &mut web::ServiceConfig) {
@@ -96,5 +85,6 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/alerts", web::post().to(create_alert))
             .route("/alerts", web::get().to(get_unresolved_alerts))
             .route("/alerts/{id}/resolve", web::patch().to(resolve_alert))
+            .route("/due", web::get().to(get_due))
     );
 }
@@ -1,3 +1,8 @@
+use actix_web::{HttpMessage, HttpRequest};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::services::auth_service::Claims;
+
 pub mod vehicle;
 pub mod driver;
 pub mod assignment;
@@ -8,3 +13,24 @@ pub mod financial;
 pub mod auth;
 pub mod settings;
 pub mod users;
+pub mod roles;
+pub mod geofences;
+pub mod work_orders;
+pub mod parts;
+pub mod hos;
+pub mod invoices;
+pub mod rate_cards;
+pub mod quotes;
+
+/// User making the request, for audit trails.
+pub(crate) fn current_user(req: &HttpRequest) -> Option<Uuid> {
+    req.extensions().get::<Claims>().map(|claims| claims.user_id)
+}
+
+/// Claims of the caller, for handlers that restrict what a role may see.
+pub(crate) fn current_claims(req: &HttpRequest) -> Result<Claims, AppError> {
+    req.extensions()
+        .get::<Claims>()
+        .cloned()
+        .ok_or_else(|| AppError::AuthError("Missing credentials".into()))
+}
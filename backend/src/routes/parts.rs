@@ -0,0 +1,67 @@
+use actix_web::{web, HttpResponse, Responder};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::part::{CreatePartDto, UpdatePartDto, PartQuery, CreateStockMovementDto};
+use crate::services::part_service::PartServiceTrait;
+
+pub async fn create_part(
+    service: web::Data<dyn PartServiceTrait>,
+    dto: web::Json<CreatePartDto>,
+) -> Result<impl Responder, AppError> {
+    let part = service.create_part(dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(part))
+}
+
+pub async fn list_parts(
+    service: web::Data<dyn PartServiceTrait>,
+    query: web::Query<PartQuery>,
+) -> Result<impl Responder, AppError> {
+    let parts = service.list_parts(query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(parts))
+}
+
+pub async fn get_part(
+    service: web::Data<dyn PartServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let part = service.get_part(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(part))
+}
+
+pub async fn update_part(
+    service: web::Data<dyn PartServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<UpdatePartDto>,
+) -> Result<impl Responder, AppError> {
+    let part = service.update_part(path.into_inner(), dto.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(part))
+}
+
+pub async fn record_movement(
+    service: web::Data<dyn PartServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<CreateStockMovementDto>,
+) -> Result<impl Responder, AppError> {
+    let part = service.record_movement(path.into_inner(), dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(part))
+}
+
+pub async fn get_movements(
+    service: web::Data<dyn PartServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let movements = service.get_movements(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(movements))
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/parts")
+            .route("", web::post().to(create_part))
+            .route("", web::get().to(list_parts))
+            .route("/{id}", web::get().to(get_part))
+            .route("/{id}", web::put().to(update_part))
+            .route("/{id}/movements", web::post().to(record_movement))
+            .route("/{id}/movements", web::get().to(get_movements))
+    );
+}
@@ -0,0 +1,50 @@
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::quote::{CreateQuoteDto, QuoteQuery};
+use crate::routes::current_user;
+use crate::services::quote_service::QuoteServiceTrait;
+
+pub async fn list_quotes(
+    service: web::Data<dyn QuoteServiceTrait>,
+    query: web::Query<QuoteQuery>,
+) -> Result<impl Responder, AppError> {
+    let quotes = service.list_quotes(query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(quotes))
+}
+
+pub async fn create_quote(
+    req: HttpRequest,
+    service: web::Data<dyn QuoteServiceTrait>,
+    dto: web::Json<CreateQuoteDto>,
+) -> Result<impl Responder, AppError> {
+    let quote = service.create_quote(dto.into_inner(), current_user(&req)).await?;
+    Ok(HttpResponse::Created().json(quote))
+}
+
+pub async fn get_quote(
+    service: web::Data<dyn QuoteServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let quote = service.get_quote(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(quote))
+}
+
+pub async fn convert_quote(
+    req: HttpRequest,
+    service: web::Data<dyn QuoteServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let job = service.convert_quote(path.into_inner(), current_user(&req)).await?;
+    Ok(HttpResponse::Created().json(job))
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/quotes")
+            .route("", web::get().to(list_quotes))
+            .route("", web::post().to(create_quote))
+            .route("/{id}", web::get().to(get_quote))
+            .route("/{id}/convert", web::post().to(convert_quote))
+    );
+}
@@ -0,0 +1,54 @@
+use actix_web::{web, HttpResponse, Responder};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::quote::RateCardDto;
+use crate::services::quote_service::QuoteServiceTrait;
+
+pub async fn list_rate_cards(service: web::Data<dyn QuoteServiceTrait>) -> Result<impl Responder, AppError> {
+    let cards = service.list_rate_cards().await?;
+    Ok(HttpResponse::Ok().json(cards))
+}
+
+pub async fn create_rate_card(
+    service: web::Data<dyn QuoteServiceTrait>,
+    dto: web::Json<RateCardDto>,
+) -> Result<impl Responder, AppError> {
+    let card = service.create_rate_card(dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(card))
+}
+
+pub async fn get_rate_card(
+    service: web::Data<dyn QuoteServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let card = service.get_rate_card(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(card))
+}
+
+pub async fn update_rate_card(
+    service: web::Data<dyn QuoteServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<RateCardDto>,
+) -> Result<impl Responder, AppError> {
+    let card = service.update_rate_card(path.into_inner(), dto.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(card))
+}
+
+pub async fn delete_rate_card(
+    service: web::Data<dyn QuoteServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    service.delete_rate_card(path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/rate-cards")
+            .route("", web::get().to(list_rate_cards))
+            .route("", web::post().to(create_rate_card))
+            .route("/{id}", web::get().to(get_rate_card))
+            .route("/{id}", web::put().to(update_rate_card))
+            .route("/{id}", web::delete().to(delete_rate_card))
+    );
+}
@@ -0,0 +1,37 @@
+use actix_web::{web, HttpResponse, Responder};
+use crate::models::postgres::user::{UpdateRolePermissionsDto, UserRole};
+use crate::services::role_service::RoleServiceTrait;
+use crate::error::AppError;
+
+pub async fn list_roles(service: web::Data<dyn RoleServiceTrait>) -> Result<impl Responder, AppError> {
+    let roles = service.list_roles().await?;
+    Ok(HttpResponse::Ok().json(roles))
+}
+
+pub async fn get_role(
+    service: web::Data<dyn RoleServiceTrait>,
+    path: web::Path<UserRole>,
+) -> Result<impl Responder, AppError> {
+    let role = service.get_role(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(role))
+}
+
+pub async fn update_role_permissions(
+    service: web::Data<dyn RoleServiceTrait>,
+    path: web::Path<UserRole>,
+    dto: web::Json<UpdateRolePermissionsDto>,
+) -> Result<impl Responder, AppError> {
+    let role = service
+        .update_permissions(path.into_inner(), dto.into_inner().permissions)
+        .await?;
+    Ok(HttpResponse::Ok().json(role))
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/roles")
+            .route("", web::get().to(list_roles))
+            .route("/{role}", web::get().to(get_role))
+            .route("/{role}", web::put().to(update_role_permissions))
+    );
+}
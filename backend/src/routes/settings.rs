@@ -1,6 +1,7 @@
 use actix_web::{web, HttpResponse, Responder};
 use crate::models::postgres::settings::UpdateAppSettingsDto;
 use crate::services::settings_service::SettingsServiceTrait;
+use crate::error::AppError;
 
 pub async fn get_settings(service: web::Data<dyn SettingsServiceTrait>) -> impl Responder {
     match service.get_settings().await {
@@ -15,6 +16,7 @@ pub async fn update_settings(
 ) -> impl Responder {
     match service.update_settings(dto.into_inner()).await {
         Ok(settings) => HttpResponse::Ok().json(settings),
+        Err(AppError::BadRequest(message)) => HttpResponse::BadRequest().body(message),
         Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
     }
 }
@@ -1,16 +1,53 @@
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, Responder};
 use uuid::Uuid;
-use crate::models::postgres::telemetry::CreateVehicleTelemetryDto;
+use crate::error::AppError;
+use crate::models::postgres::telemetry::{CreateVehicleTelemetryDto, TelemetryHistoryQuery};
+use crate::models::postgres::odometer::DailyDistanceQuery;
 use crate::services::telemetry_service::TelemetryServiceTrait;
+use crate::services::odometer_service::OdometerServiceTrait;
+
+/// Batches of a few thousand points exceed actix's default 256KB body limit.
+const BATCH_PAYLOAD_LIMIT: usize = 8 * 1024 * 1024;
 
 pub async fn create_telemetry(
     service: web::Data<dyn TelemetryServiceTrait>,
     dto: web::Json<CreateVehicleTelemetryDto>,
-) -> impl Responder {
-    match service.create_telemetry(dto.into_inner()).await {
-        Ok(telemetry) => HttpResponse::Created().json(telemetry),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+) -> Result<impl Responder, AppError> {
+    let telemetry = service.create_telemetry(dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(telemetry))
+}
+
+/// Splits a batch body into one entry per reading. A JSON array is the default;
+/// `application/x-ndjson` bodies carry one reading per non-empty line.
+fn parse_batch(body: &[u8], ndjson: bool) -> Result<Vec<Result<CreateVehicleTelemetryDto, String>>, AppError> {
+    if ndjson {
+        let text = std::str::from_utf8(body)
+            .map_err(|_| AppError::BadRequest("Body is not valid UTF-8".into()))?;
+        return Ok(text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(|e| e.to_string()))
+            .collect());
     }
+
+    let items: Vec<serde_json::Value> = serde_json::from_slice(body)
+        .map_err(|e| AppError::BadRequest(format!("Expected a JSON array of readings: {}", e)))?;
+    Ok(items
+        .into_iter()
+        .map(|item| serde_json::from_value(item).map_err(|e| e.to_string()))
+        .collect())
+}
+
+pub async fn create_telemetry_batch(
+    req: HttpRequest,
+    service: web::Data<dyn TelemetryServiceTrait>,
+    body: web::Bytes,
+) -> Result<impl Responder, AppError> {
+    let ndjson = matches!(req.content_type(), "application/x-ndjson" | "application/ndjson");
+    let entries = parse_batch(&body, ndjson)?;
+
+    let result = service.create_telemetry_batch(entries).await?;
+    Ok(HttpResponse::Ok().json(result))
 }
 
 pub async fn get_latest_telemetry(
@@ -23,10 +60,45 @@ pub async fn get_latest_telemetry(
     }
 }
 
+pub async fn get_telemetry_history(
+    service: web::Data<dyn TelemetryServiceTrait>,
+    path: web::Path<Uuid>,
+    query: web::Query<TelemetryHistoryQuery>,
+) -> Result<impl Responder, AppError> {
+    let history = service.get_history(path.into_inner(), query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(history))
+}
+
+pub async fn get_daily_distance(
+    service: web::Data<dyn OdometerServiceTrait>,
+    path: web::Path<Uuid>,
+    query: web::Query<DailyDistanceQuery>,
+) -> Result<impl Responder, AppError> {
+    let days = service.get_daily_distance(path.into_inner(), query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(days))
+}
+
+pub async fn get_live_positions(
+    service: web::Data<dyn TelemetryServiceTrait>,
+) -> impl Responder {
+    match service.get_live_positions().await {
+        Ok(positions) => HttpResponse::Ok().json(positions),
+        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+    }
+}
+
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/telemetry")
             .route("", web::post().to(create_telemetry))
+            .service(
+                web::resource("/batch")
+                    .app_data(web::PayloadConfig::new(BATCH_PAYLOAD_LIMIT))
+                    .route(web::post().to(create_telemetry_batch))
+            )
+            .route("/live", web::get().to(get_live_positions))
+            .route("/vehicle/{id}", web::get().to(get_telemetry_history))
             .route("/vehicle/{id}/latest", web::get().to(get_latest_telemetry))
+            .route("/vehicle/{id}/distance", web::get().to(get_daily_distance))
     );
 }
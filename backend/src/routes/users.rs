@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use utoipa::ToSchema;
 use crate::services::user_service::UserServiceTrait;
+use crate::error::AppError;
 use crate::models::postgres::user::{CreateUserDto, UserRole};
 use crate::middleware::auth_middleware::{UserActiveCache, invalidate_user_cache};
 use moka::future::Cache;
@@ -32,42 +33,36 @@ pub struct UpdateUserRequest {
     pub is_active: bool,
 }
 
-pub async fn list_users(service: web::Data<dyn UserServiceTrait>) -> impl Responder {
-    match service.list_users().await {
-        Ok(users) => {
-            let public_users: Vec<PublicUser> = users
-                .into_iter()
-                .map(|u| PublicUser {
-                    id: u.id,
-                    email: u.email,
-                    name: u.name,
-                    role: format!("{:?}", u.role),
-                    is_active: u.is_active,
-                })
-                .collect();
-            HttpResponse::Ok().json(public_users)
-        }
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+pub async fn list_users(service: web::Data<dyn UserServiceTrait>) -> Result<impl Responder, AppError> {
+    let users = service.list_users().await?;
+    let public_users: Vec<PublicUser> = users
+        .into_iter()
+        .map(|u| PublicUser {
+            id: u.id,
+            email: u.email,
+            name: u.name,
+            role: format!("{:?}", u.role),
+            is_active: u.is_active,
+        })
+        .collect();
+    Ok(HttpResponse::Ok().json(public_users))
 }
 
-pub async fn get_user(service: web::Data<dyn UserServiceTrait>, path: web::Path<Uuid>) -> impl Responder {
-    match service.get_user(path.into_inner()).await {
-        Ok(user) => HttpResponse::Ok().json(PublicUser {
-            id: user.id,
-            email: user.email,
-            name: user.name,
-            role: format!("{:?}", user.role),
-            is_active: user.is_active,
-        }),
-        Err(e) => HttpResponse::NotFound().body(e.to_string()),
-    }
+pub async fn get_user(service: web::Data<dyn UserServiceTrait>, path: web::Path<Uuid>) -> Result<impl Responder, AppError> {
+    let user = service.get_user(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(PublicUser {
+        id: user.id,
+        email: user.email,
+        name: user.name,
+        role: format!("{:?}", user.role),
+        is_active: user.is_active,
+    }))
 }
 
 pub async fn create_user(
     service: web::Data<dyn UserServiceTrait>,
     dto: web::Json<CreateUserRequest>,
-) -> impl Responder {
+) -> Result<impl Responder, AppError> {
     let dto = dto.into_inner();
     let create_dto = CreateUserDto {
         email: dto.email,
@@ -77,16 +72,14 @@ pub async fn create_user(
         is_active: dto.is_active,
     };
 
-    match service.create_user(create_dto).await {
-        Ok(user) => HttpResponse::Ok().json(PublicUser {
-            id: user.id,
-            email: user.email,
-            name: user.name,
-            role: format!("{:?}", user.role),
-            is_active: user.is_active,
-        }),
-        Err(e) => HttpResponse::BadRequest().body(e.to_string()),
-    }
+    let user = service.create_user(create_dto).await?;
+    Ok(HttpResponse::Ok().json(PublicUser {
+        id: user.id,
+        email: user.email,
+        name: user.name,
+        role: format!("{:?}", user.role),
+        is_active: user.is_active,
+    }))
 }
 
 pub async fn update_user(
@@ -94,26 +87,22 @@ pub async fn update_user(
     cache: web::Data<Arc<Cache<Uuid, UserActiveCache>>>,
     path: web::Path<Uuid>,
     dto: web::Json<UpdateUserRequest>,
-) -> impl Responder {
+) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
     let dto = dto.into_inner();
-    
-    match service.update_user(id, dto.role, dto.is_active).await {
-        Ok(user) => {
-            // If user is being deactivated, invalidate their cache
-            if !dto.is_active {
-                invalidate_user_cache(&cache, id).await;
-            }
-            HttpResponse::Ok().json(PublicUser {
-                id: user.id,
-                email: user.email,
-                name: user.name,
-                role: format!("{:?}", user.role),
-                is_active: user.is_active,
-            })
-        }
-        Err(e) => HttpResponse::BadRequest().body(e.to_string()),
+
+    let user = service.update_user(id, dto.role, dto.is_active).await?;
+    // If user is being deactivated, invalidate their cache
+    if !dto.is_active {
+        invalidate_user_cache(&cache, id).await;
     }
+    Ok(HttpResponse::Ok().json(PublicUser {
+        id: user.id,
+        email: user.email,
+        name: user.name,
+        role: format!("{:?}", user.role),
+        is_active: user.is_active,
+    }))
 }
 
 pub fn config(cfg: &mut web::ServiceConfig) {
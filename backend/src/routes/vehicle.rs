@@ -1,40 +1,35 @@
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{http::header, web, HttpRequest, HttpResponse, Responder};
 use crate::models::postgres::vehicle::{CreateVehicleDto, VehicleStatus};
+use crate::models::postgres::vehicle_document::CreateVehicleDocumentDto;
 use crate::services::vehicle_service::VehicleServiceTrait;
+use crate::services::vehicle_document_service::VehicleDocumentServiceTrait;
+use crate::routes::current_user;
+use crate::error::AppError;
 use uuid::Uuid;
 
-pub async fn get_vehicles(service: web::Data<dyn VehicleServiceTrait>) -> impl Responder {
-    let result = service.list_vehicles().await;
+/// Room for a base64-encoded document of the maximum size.
+const DOCUMENT_PAYLOAD_LIMIT: usize = 16 * 1024 * 1024;
 
-    match result {
-        Ok(vehicles) => HttpResponse::Ok().json(vehicles),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+pub async fn get_vehicles(service: web::Data<dyn VehicleServiceTrait>) -> Result<impl Responder, AppError> {
+    let vehicles = service.list_vehicles().await?;
+    Ok(HttpResponse::Ok().json(vehicles))
 }
 
 pub async fn get_vehicle_by_id(
     service: web::Data<dyn VehicleServiceTrait>,
     path: web::Path<Uuid>,
-) -> impl Responder {
+) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
-    let result = service.get_vehicle(id).await;
-
-    match result {
-        Ok(vehicle) => HttpResponse::Ok().json(vehicle),
-        Err(e) => HttpResponse::NotFound().body(e.to_string()),
-    }
+    let vehicle = service.get_vehicle(id).await?;
+    Ok(HttpResponse::Ok().json(vehicle))
 }
 
 pub async fn create_vehicle(
     service: web::Data<dyn VehicleServiceTrait>,
     item: web::Json<CreateVehicleDto>,
-) -> impl Responder {
-    let result = service.create_vehicle(item.into_inner()).await;
-
-    match result {
-        Ok(vehicle) => HttpResponse::Created().json(vehicle),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-    }
+) -> Result<impl Responder, AppError> {
+    let vehicle = service.create_vehicle(item.into_inner()).await?;
+    Ok(HttpResponse::Created().json(vehicle))
 }
 
 #[derive(serde::Deserialize)]
@@ -46,32 +41,77 @@ pub async fn update_vehicle(
     service: web::Data<dyn VehicleServiceTrait>,
     path: web::Path<Uuid>,
     item: web::Json<UpdateVehicleDto>,
-) -> impl Responder {
+) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
-    
+
     // For now, we only support status updates
-    if let Some(status) = item.status {
-        let result = service.update_vehicle_status(id, status).await;
-        match result {
-            Ok(vehicle) => HttpResponse::Ok().json(vehicle),
-            Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-        }
-    } else {
-        HttpResponse::BadRequest().body("No updates provided")
-    }
+    let Some(status) = item.status else {
+        return Err(AppError::BadRequest("No updates provided".into()));
+    };
+    let vehicle = service.update_vehicle_status(id, status).await?;
+    Ok(HttpResponse::Ok().json(vehicle))
 }
 
 pub async fn delete_vehicle(
     service: web::Data<dyn VehicleServiceTrait>,
     path: web::Path<Uuid>,
-) -> impl Responder {
+) -> Result<impl Responder, AppError> {
     let id = path.into_inner();
-    let result = service.delete_vehicle(id).await;
+    service.delete_vehicle(id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
 
-    match result {
-        Ok(_) => HttpResponse::NoContent().finish(),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+// Documents
+pub async fn list_documents(
+    service: web::Data<dyn VehicleDocumentServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let documents = service.list(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(documents))
+}
+
+pub async fn create_document(
+    req: HttpRequest,
+    service: web::Data<dyn VehicleDocumentServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<CreateVehicleDocumentDto>,
+) -> Result<impl Responder, AppError> {
+    let document = service.create(path.into_inner(), dto.into_inner(), current_user(&req)).await?;
+    Ok(HttpResponse::Created().json(document))
+}
+
+pub async fn get_document(
+    service: web::Data<dyn VehicleDocumentServiceTrait>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<impl Responder, AppError> {
+    let (vehicle_id, document_id) = path.into_inner();
+    let document = service.get(vehicle_id, document_id).await?;
+    Ok(HttpResponse::Ok().json(document))
+}
+
+pub async fn delete_document(
+    service: web::Data<dyn VehicleDocumentServiceTrait>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<impl Responder, AppError> {
+    let (vehicle_id, document_id) = path.into_inner();
+    service.delete(vehicle_id, document_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub async fn get_document_file(
+    service: web::Data<dyn VehicleDocumentServiceTrait>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<impl Responder, AppError> {
+    let (vehicle_id, document_id) = path.into_inner();
+    let (document, data) = service.get_file(vehicle_id, document_id).await?;
+    let mut response = HttpResponse::Ok();
+    if let Some(content_type) = document.content_type {
+        response.content_type(content_type);
+    }
+    if let Some(file_name) = document.file_name {
+        response.insert_header((header::CONTENT_DISPOSITION, format!("inline; filename=\"{}\"", file_name)));
     }
+    Ok(response.body(data))
 }
 
 pub fn config(cfg: &mut web::ServiceConfig) {
@@ -82,5 +122,14 @@ pub fn config(cfg: &mut web::ServiceConfig) {
             .route("/{id}", web::get().to(get_vehicle_by_id))
             .route("/{id}", web::put().to(update_vehicle))
             .route("/{id}", web::delete().to(delete_vehicle))
+            .service(
+                web::resource("/{id}/documents")
+                    .app_data(web::JsonConfig::default().limit(DOCUMENT_PAYLOAD_LIMIT))
+                    .route(web::get().to(list_documents))
+                    .route(web::post().to(create_document))
+            )
+            .route("/{id}/documents/{document_id}", web::get().to(get_document))
+            .route("/{id}/documents/{document_id}", web::delete().to(delete_document))
+            .route("/{id}/documents/{document_id}/file", web::get().to(get_document_file))
     );
 }
@@ -0,0 +1,80 @@
+use actix_web::{web, HttpResponse, Responder};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::work_order::{
+    CreateWorkOrderDto, UpdateWorkOrderStatusDto, AssignMechanicDto, CreateWorkOrderItemDto, WorkOrderQuery
+};
+use crate::services::work_order_service::WorkOrderServiceTrait;
+
+pub async fn create_work_order(
+    service: web::Data<dyn WorkOrderServiceTrait>,
+    dto: web::Json<CreateWorkOrderDto>,
+) -> Result<impl Responder, AppError> {
+    let work_order = service.create_work_order(dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(work_order))
+}
+
+pub async fn list_work_orders(
+    service: web::Data<dyn WorkOrderServiceTrait>,
+    query: web::Query<WorkOrderQuery>,
+) -> Result<impl Responder, AppError> {
+    let work_orders = service.list_work_orders(query.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(work_orders))
+}
+
+pub async fn get_work_order(
+    service: web::Data<dyn WorkOrderServiceTrait>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, AppError> {
+    let details = service.get_work_order(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(details))
+}
+
+pub async fn update_status(
+    service: web::Data<dyn WorkOrderServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<UpdateWorkOrderStatusDto>,
+) -> Result<impl Responder, AppError> {
+    let work_order = service.update_status(path.into_inner(), dto.into_inner().status).await?;
+    Ok(HttpResponse::Ok().json(work_order))
+}
+
+pub async fn assign_mechanic(
+    service: web::Data<dyn WorkOrderServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<AssignMechanicDto>,
+) -> Result<impl Responder, AppError> {
+    let work_order = service.assign_mechanic(path.into_inner(), dto.into_inner().mechanic_id).await?;
+    Ok(HttpResponse::Ok().json(work_order))
+}
+
+pub async fn add_item(
+    service: web::Data<dyn WorkOrderServiceTrait>,
+    path: web::Path<Uuid>,
+    dto: web::Json<CreateWorkOrderItemDto>,
+) -> Result<impl Responder, AppError> {
+    let item = service.add_item(path.into_inner(), dto.into_inner()).await?;
+    Ok(HttpResponse::Created().json(item))
+}
+
+pub async fn remove_item(
+    service: web::Data<dyn WorkOrderServiceTrait>,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<impl Responder, AppError> {
+    let (id, item_id) = path.into_inner();
+    service.remove_item(id, item_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/work-orders")
+            .route("", web::post().to(create_work_order))
+            .route("", web::get().to(list_work_orders))
+            .route("/{id}", web::get().to(get_work_order))
+            .route("/{id}/status", web::patch().to(update_status))
+            .route("/{id}/mechanic", web::put().to(assign_mechanic))
+            .route("/{id}/items", web::post().to(add_item))
+            .route("/{id}/items/{item_id}", web::delete().to(remove_item))
+    );
+}
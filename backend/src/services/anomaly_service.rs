@@ -0,0 +1,412 @@
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::postgres::maintenance::{Alert, AlertSeverity, CreateAlertDto};
+use crate::models::postgres::settings::AppSettings;
+use crate::models::postgres::telemetry::VehicleTelemetry;
+use crate::repositories::postgres::maintenance_repo::AlertRepositoryTrait;
+use crate::repositories::postgres::settings_repo::SettingsRepositoryTrait;
+use crate::repositories::postgres::telemetry_repo::TelemetryRepositoryTrait;
+
+pub const SPEEDING_ALERT: &str = "Speeding";
+pub const FUEL_DROP_ALERT: &str = "Sudden Fuel Drop";
+pub const IDLING_ALERT: &str = "Excessive Idling";
+pub const TRACKER_OFFLINE_ALERT: &str = "Tracker Offline";
+
+/// Below this speed a vehicle with its engine running counts as idling.
+pub(crate) const IDLE_SPEED_KMH: f64 = 2.0;
+
+/// Fuel drops are only compared between readings this close together; over longer
+/// gaps normal consumption can exceed the threshold.
+const FUEL_DROP_WINDOW_MINUTES: i64 = 30;
+
+/// Speeding above the limit by this factor is reported as high severity.
+const SEVERE_SPEEDING_FACTOR: f64 = 1.25;
+
+/// Cap on rows read when working out how long a vehicle has been idling.
+const MAX_IDLE_LOOKBACK_POINTS: i64 = 10_000;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait AnomalyServiceTrait: Send + Sync {
+    /// Runs the per-reading rules (speeding, fuel drop, idling) over newly stored readings.
+    /// Returns the alerts raised; rules with an unresolved alert for the vehicle are skipped.
+    async fn check_readings(&self, readings: &[VehicleTelemetry]) -> Result<Vec<Alert>, AppError>;
+    /// Raises an alert for every in-service vehicle that has stopped reporting.
+    async fn check_stale_trackers(&self, now: DateTime<Utc>) -> Result<Vec<Alert>, AppError>;
+}
+
+pub struct AnomalyService {
+    telemetry_repo: Arc<dyn TelemetryRepositoryTrait>,
+    alert_repo: Arc<dyn AlertRepositoryTrait>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait>,
+}
+
+impl AnomalyService {
+    pub fn new(
+        telemetry_repo: Arc<dyn TelemetryRepositoryTrait>,
+        alert_repo: Arc<dyn AlertRepositoryTrait>,
+        settings_repo: Arc<dyn SettingsRepositoryTrait>,
+    ) -> Self {
+        Self { telemetry_repo, alert_repo, settings_repo }
+    }
+}
+
+/// Reads the engine state from `engine_status`. Explicit `ignition`, `running` or
+/// `engine_on` flags win; otherwise a positive `rpm` means the engine is on.
+fn engine_running(engine_status: &Value) -> bool {
+    let flag = ["ignition", "running", "engine_on"]
+        .iter()
+        .find_map(|key| engine_status.get(key)?.as_bool());
+    match flag {
+        Some(on) => on,
+        None => engine_status
+            .get("rpm")
+            .and_then(Value::as_f64)
+            .is_some_and(|rpm| rpm > 0.0),
+    }
+}
+
+pub(crate) fn is_idle(telemetry: &VehicleTelemetry) -> bool {
+    telemetry.speed < IDLE_SPEED_KMH && engine_running(&telemetry.engine_status)
+}
+
+struct Anomaly {
+    alert_type: &'static str,
+    severity: AlertSeverity,
+    message: String,
+}
+
+fn speeding(settings: &AppSettings, reading: &VehicleTelemetry) -> Option<Anomaly> {
+    let limit = settings.speed_limit_kmh;
+    if limit <= 0.0 || reading.speed <= limit {
+        return None;
+    }
+    let severity = if reading.speed >= limit * SEVERE_SPEEDING_FACTOR {
+        AlertSeverity::High
+    } else {
+        AlertSeverity::Medium
+    };
+    Some(Anomaly {
+        alert_type: SPEEDING_ALERT,
+        severity,
+        message: format!("Reported {:.0} km/h against a limit of {:.0} km/h", reading.speed, limit),
+    })
+}
+
+fn fuel_drop(settings: &AppSettings, previous: &VehicleTelemetry, reading: &VehicleTelemetry) -> Option<Anomaly> {
+    let threshold = settings.fuel_drop_threshold;
+    let drop = previous.fuel_level - reading.fuel_level;
+    if threshold <= 0.0
+        || drop < threshold
+        || reading.time - previous.time > Duration::minutes(FUEL_DROP_WINDOW_MINUTES)
+    {
+        return None;
+    }
+    Some(Anomaly {
+        alert_type: FUEL_DROP_ALERT,
+        severity: AlertSeverity::High,
+        message: format!(
+            "Fuel level fell from {:.1}% to {:.1}% in {} minutes",
+            previous.fuel_level,
+            reading.fuel_level,
+            (reading.time - previous.time).num_minutes()
+        ),
+    })
+}
+
+impl AnomalyService {
+    /// Finds when the idle spell ending at `reading` began by walking back through stored
+    /// readings. Stops at the edge of the alert window since anything older is irrelevant.
+    async fn idle_started(&self, reading: &VehicleTelemetry, window: Duration) -> Result<DateTime<Utc>, AppError> {
+        let history = self.telemetry_repo
+            .find_covering_range(reading.vehicle_id, reading.time - window, reading.time, MAX_IDLE_LOOKBACK_POINTS)
+            .await?;
+
+        let mut since = reading.time;
+        for earlier in history.iter().filter(|t| t.time <= reading.time) {
+            if !is_idle(earlier) {
+                break;
+            }
+            since = earlier.time;
+        }
+        Ok(since)
+    }
+
+    async fn check_vehicle(
+        &self,
+        settings: &AppSettings,
+        vehicle_id: Uuid,
+        readings: Vec<&VehicleTelemetry>,
+    ) -> Result<Vec<Alert>, AppError> {
+        let first = match readings.first() {
+            Some(first) => first,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut open: HashSet<String> = self.alert_repo
+            .find_unresolved_by_entity(vehicle_id)
+            .await?
+            .into_iter()
+            .map(|a| a.r#type)
+            .collect();
+
+        let idle_window = Duration::minutes(settings.idle_alert_minutes.into());
+        let mut previous = self.telemetry_repo.find_latest_before(vehicle_id, first.time).await?;
+        let mut idle_since: Option<DateTime<Utc>> = None;
+        let mut alerts = Vec::new();
+
+        for reading in readings {
+            let mut anomalies = Vec::new();
+            anomalies.extend(speeding(settings, reading));
+            if let Some(previous) = &previous {
+                anomalies.extend(fuel_drop(settings, previous, reading));
+            }
+
+            if !is_idle(reading) {
+                idle_since = None;
+            } else if settings.idle_alert_minutes > 0 && !open.contains(IDLING_ALERT) {
+                let since = match (&previous, idle_since) {
+                    (Some(p), Some(since)) if is_idle(p) => since,
+                    // The spell started before this batch, so look it up
+                    (Some(p), None) if is_idle(p) => self.idle_started(reading, idle_window).await?,
+                    _ => reading.time,
+                };
+                idle_since = Some(since);
+
+                if reading.time - since >= idle_window {
+                    anomalies.push(Anomaly {
+                        alert_type: IDLING_ALERT,
+                        severity: AlertSeverity::Low,
+                        message: format!("Idling with the engine running since {}", since.to_rfc3339()),
+                    });
+                }
+            }
+
+            for anomaly in anomalies {
+                if !open.insert(anomaly.alert_type.to_string()) {
+                    continue;
+                }
+                let created = self.alert_repo.create_unless_open(CreateAlertDto {
+                    entity_id: vehicle_id,
+                    r#type: anomaly.alert_type.to_string(),
+                    severity: anomaly.severity,
+                    message: Some(anomaly.message),
+                }).await?;
+                alerts.extend(created);
+            }
+
+            previous = Some(reading.clone());
+        }
+
+        Ok(alerts)
+    }
+}
+
+#[async_trait]
+impl AnomalyServiceTrait for AnomalyService {
+    async fn check_readings(&self, readings: &[VehicleTelemetry]) -> Result<Vec<Alert>, AppError> {
+        if readings.is_empty() {
+            return Ok(Vec::new());
+        }
+        let settings = self.settings_repo.get().await?;
+
+        // Rules compare consecutive readings, so evaluate each vehicle in time order
+        let mut by_vehicle: BTreeMap<Uuid, Vec<&VehicleTelemetry>> = BTreeMap::new();
+        for reading in readings {
+            by_vehicle.entry(reading.vehicle_id).or_default().push(reading);
+        }
+
+        let mut alerts = Vec::new();
+        for (vehicle_id, mut vehicle_readings) in by_vehicle {
+            vehicle_readings.sort_by_key(|t| t.time);
+            alerts.extend(self.check_vehicle(&settings, vehicle_id, vehicle_readings).await?);
+        }
+        Ok(alerts)
+    }
+
+    async fn check_stale_trackers(&self, now: DateTime<Utc>) -> Result<Vec<Alert>, AppError> {
+        let settings = self.settings_repo.get().await?;
+        if settings.tracker_stale_minutes <= 0 {
+            return Ok(Vec::new());
+        }
+
+        let cutoff = now - Duration::minutes(settings.tracker_stale_minutes.into());
+        let mut alerts = Vec::new();
+        for tracker in self.telemetry_repo.find_silent_vehicles(cutoff).await? {
+            let created = self.alert_repo.create_unless_open(CreateAlertDto {
+                entity_id: tracker.vehicle_id,
+                r#type: TRACKER_OFFLINE_ALERT.to_string(),
+                severity: AlertSeverity::Medium,
+                message: Some(format!("No telemetry received since {}", tracker.last_seen.to_rfc3339())),
+            }).await?;
+            alerts.extend(created);
+        }
+        Ok(alerts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::postgres::telemetry::TrackerLastSeen;
+    use crate::repositories::postgres::maintenance_repo::MockAlertRepositoryTrait;
+    use crate::repositories::postgres::settings_repo::MockSettingsRepositoryTrait;
+    use crate::repositories::postgres::telemetry_repo::MockTelemetryRepositoryTrait;
+    use serde_json::json;
+
+    fn settings_repo() -> MockSettingsRepositoryTrait {
+        let mut repo = MockSettingsRepositoryTrait::new();
+        repo.expect_get().returning(|| Ok(AppSettings::default()));
+        repo
+    }
+
+    fn reading(vehicle_id: Uuid, time: DateTime<Utc>, speed: f64, fuel_level: f64, rpm: i64) -> VehicleTelemetry {
+        VehicleTelemetry {
+            time,
+            vehicle_id,
+            location: json!({"type": "Point", "coordinates": [-74.0, 40.7]}),
+            speed,
+            fuel_level,
+            engine_status: json!({"rpm": rpm}),
+        }
+    }
+
+    fn alert_from(dto: CreateAlertDto) -> Alert {
+        Alert {
+            id: Uuid::new_v4(),
+            entity_id: dto.entity_id,
+            r#type: dto.r#type,
+            severity: dto.severity,
+            is_resolved: false,
+            created_at: Utc::now(),
+            resolved_at: None,
+            message: dto.message,
+        }
+    }
+
+    #[test]
+    fn test_engine_running_prefers_explicit_flags() {
+        assert!(engine_running(&json!({"rpm": 800})));
+        assert!(!engine_running(&json!({"rpm": 0})));
+        assert!(!engine_running(&json!({"ignition": false, "rpm": 800})));
+        assert!(engine_running(&json!({"running": true})));
+        assert!(!engine_running(&Value::Null));
+    }
+
+    #[tokio::test]
+    async fn test_speeding_and_fuel_drop_raise_alerts_once() {
+        let vehicle_id = Uuid::new_v4();
+        let start = Utc::now() - Duration::minutes(10);
+
+        let mut telemetry_repo = MockTelemetryRepositoryTrait::new();
+        telemetry_repo
+            .expect_find_latest_before()
+            .returning(move |id, _| Ok(Some(reading(id, start - Duration::minutes(1), 90.0, 80.0, 2000))));
+
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo.expect_find_unresolved_by_entity().returning(|_| Ok(vec![]));
+        alert_repo
+            .expect_create_unless_open()
+            .times(2)
+            .returning(|dto| Ok(Some(alert_from(dto))));
+
+        let service = AnomalyService::new(Arc::new(telemetry_repo), Arc::new(alert_repo), Arc::new(settings_repo()));
+        let readings = vec![
+            reading(vehicle_id, start, 120.0, 60.0, 2500),
+            reading(vehicle_id, start + Duration::minutes(1), 150.0, 59.0, 2500),
+        ];
+
+        let alerts = service.check_readings(&readings).await.unwrap();
+        let types: Vec<&str> = alerts.iter().map(|a| a.r#type.as_str()).collect();
+        assert_eq!(types, vec![SPEEDING_ALERT, FUEL_DROP_ALERT]);
+        assert!(matches!(alerts[1].severity, AlertSeverity::High));
+    }
+
+    #[tokio::test]
+    async fn test_open_alert_suppresses_rule() {
+        let vehicle_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        let mut telemetry_repo = MockTelemetryRepositoryTrait::new();
+        telemetry_repo.expect_find_latest_before().returning(|_, _| Ok(None));
+
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo.expect_find_unresolved_by_entity().returning(move |id| {
+            Ok(vec![alert_from(CreateAlertDto {
+                entity_id: id,
+                r#type: SPEEDING_ALERT.into(),
+                severity: AlertSeverity::Medium,
+                message: None,
+            })])
+        });
+        alert_repo.expect_create_unless_open().never();
+
+        let service = AnomalyService::new(Arc::new(telemetry_repo), Arc::new(alert_repo), Arc::new(settings_repo()));
+        let alerts = service.check_readings(&[reading(vehicle_id, now, 140.0, 50.0, 2500)]).await.unwrap();
+        assert!(alerts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_idling_looks_up_spell_that_started_earlier() {
+        let vehicle_id = Uuid::new_v4();
+        let now = Utc::now();
+
+        let mut telemetry_repo = MockTelemetryRepositoryTrait::new();
+        telemetry_repo
+            .expect_find_latest_before()
+            .returning(move |id, _| Ok(Some(reading(id, now - Duration::minutes(1), 0.0, 50.0, 700))));
+        telemetry_repo
+            .expect_find_covering_range()
+            .times(1)
+            .returning(move |id, _, _, _| {
+                Ok(vec![
+                    reading(id, now, 0.0, 50.0, 700),
+                    reading(id, now - Duration::minutes(1), 0.0, 50.0, 700),
+                    reading(id, now - Duration::minutes(25), 0.0, 50.0, 700),
+                    reading(id, now - Duration::minutes(40), 45.0, 51.0, 2000),
+                ])
+            });
+
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo.expect_find_unresolved_by_entity().returning(|_| Ok(vec![]));
+        alert_repo
+            .expect_create_unless_open()
+            .withf(|dto| dto.r#type == IDLING_ALERT)
+            .times(1)
+            .returning(|dto| Ok(Some(alert_from(dto))));
+
+        let service = AnomalyService::new(Arc::new(telemetry_repo), Arc::new(alert_repo), Arc::new(settings_repo()));
+        let alerts = service.check_readings(&[reading(vehicle_id, now, 0.0, 50.0, 700)]).await.unwrap();
+        assert_eq!(alerts.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stale_trackers_raise_offline_alerts() {
+        let now = Utc::now();
+        let silent = TrackerLastSeen { vehicle_id: Uuid::new_v4(), last_seen: now - Duration::hours(2) };
+        let silent_id = silent.vehicle_id;
+
+        let mut telemetry_repo = MockTelemetryRepositoryTrait::new();
+        telemetry_repo
+            .expect_find_silent_vehicles()
+            .withf(move |cutoff| *cutoff == now - Duration::minutes(30))
+            .returning(move |_| Ok(vec![silent.clone()]));
+
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo
+            .expect_create_unless_open()
+            .withf(move |dto| dto.entity_id == silent_id && dto.r#type == TRACKER_OFFLINE_ALERT)
+            .times(1)
+            .returning(|dto| Ok(Some(alert_from(dto))));
+
+        let service = AnomalyService::new(Arc::new(telemetry_repo), Arc::new(alert_repo), Arc::new(settings_repo()));
+        let alerts = service.check_stale_trackers(now).await.unwrap();
+        assert_eq!(alerts.len(), 1);
+    }
+}
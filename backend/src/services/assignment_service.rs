@@ -1,28 +1,44 @@
 use std::sync::Arc;
 use uuid::Uuid;
 use async_trait::async_trait;
-use crate::error::AppError;
-use crate::models::postgres::assignment::{VehicleAssignment, CreateAssignmentDto, AssignmentStatus};
+use chrono::{DateTime, Utc};
+use crate::error::{AppError, ConflictKind};
+use crate::models::postgres::assignment::{VehicleAssignment, CreateAssignmentDto, AssignmentStatus, CancelAssignmentDto, AssignmentQuery};
 use crate::models::postgres::vehicle::VehicleStatus;
 use crate::models::postgres::driver::DriverStatus;
 use crate::repositories::postgres::assignment_repo::AssignmentRepositoryTrait;
 use crate::repositories::postgres::vehicle_repo::VehicleRepositoryTrait;
 use crate::repositories::postgres::driver_repo::DriverRepositoryTrait;
+use crate::repositories::postgres::unit_of_work::{UnitOfWorkTrait, RepositoryTransactionTrait};
+use crate::services::hos_service::HosServiceTrait;
+use crate::services::vehicle_document_service::VehicleDocumentServiceTrait;
+
+/// Recorded on scheduled assignments the activation job gives up on.
+pub const NOT_STARTED_REASON: &str = "Not started before its planned end time";
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait AssignmentServiceTrait: Send + Sync {
     async fn create_assignment(&self, dto: CreateAssignmentDto) -> Result<VehicleAssignment, AppError>;
+    async fn start_assignment(&self, id: Uuid) -> Result<VehicleAssignment, AppError>;
     async fn complete_assignment(&self, id: Uuid) -> Result<VehicleAssignment, AppError>;
+    async fn cancel_assignment(&self, id: Uuid, dto: CancelAssignmentDto) -> Result<VehicleAssignment, AppError>;
     async fn get_assignment(&self, id: Uuid) -> Result<VehicleAssignment, AppError>;
-    async fn list_assignments(&self) -> Result<Vec<VehicleAssignment>, AppError>;
+    async fn list_assignments(&self, query: AssignmentQuery) -> Result<Vec<VehicleAssignment>, AppError>;
     async fn get_assignments_by_driver(&self, driver_id: Uuid) -> Result<Vec<VehicleAssignment>, AppError>;
+    /// Starts scheduled assignments whose start time has come, once their vehicle and
+    /// driver are free. Those still waiting at their planned end are cancelled. Returns
+    /// the assignments that changed.
+    async fn activate_due_assignments(&self, now: DateTime<Utc>) -> Result<Vec<VehicleAssignment>, AppError>;
 }
 
 pub struct AssignmentService {
     assignment_repo: Arc<dyn AssignmentRepositoryTrait>,
     vehicle_repo: Arc<dyn VehicleRepositoryTrait>,
     driver_repo: Arc<dyn DriverRepositoryTrait>,
+    hos_service: Arc<dyn HosServiceTrait>,
+    document_service: Arc<dyn VehicleDocumentServiceTrait>,
+    unit_of_work: Arc<dyn UnitOfWorkTrait>,
 }
 
 impl AssignmentService {
@@ -30,77 +46,251 @@ impl AssignmentService {
         assignment_repo: Arc<dyn AssignmentRepositoryTrait>,
         vehicle_repo: Arc<dyn VehicleRepositoryTrait>,
         driver_repo: Arc<dyn DriverRepositoryTrait>,
+        hos_service: Arc<dyn HosServiceTrait>,
+        document_service: Arc<dyn VehicleDocumentServiceTrait>,
+        unit_of_work: Arc<dyn UnitOfWorkTrait>,
     ) -> Self {
         Self {
             assignment_repo,
             vehicle_repo,
             driver_repo,
+            hos_service,
+            document_service,
+            unit_of_work,
+        }
+    }
+
+    async fn find_assignment(&self, id: Uuid) -> Result<VehicleAssignment, AppError> {
+        self.assignment_repo.find_by_id(id).await?
+            .ok_or(AppError::NotFound("Assignment not found".into()))
+    }
+
+    fn check_transition(current: &VehicleAssignment, next: AssignmentStatus) -> Result<(), AppError> {
+        if !current.status.can_transition_to(next) {
+            return Err(AppError::BadRequest(format!(
+                "Cannot move assignment from {:?} to {:?}",
+                current.status, next
+            )));
         }
+        Ok(())
+    }
+
+    fn changed_concurrently(id: Uuid) -> AppError {
+        AppError::conflict(
+            ConflictKind::Stale,
+            format!("Assignment {} was changed by another request; reload and try again", id),
+        )
+    }
+
+    /// Why the assignment's vehicle and driver cannot be taken right now, if anything.
+    async fn unavailable_reason(&self, vehicle_id: Uuid, driver_id: Uuid) -> Result<Option<&'static str>, AppError> {
+        let vehicle = self.vehicle_repo.find_by_id(vehicle_id).await?
+            .ok_or(AppError::NotFound("Vehicle not found".into()))?;
+        if vehicle.status != VehicleStatus::Available {
+            return Ok(Some("Vehicle is not available"));
+        }
+
+        let driver = self.driver_repo.find_by_id(driver_id).await?
+            .ok_or(AppError::NotFound("Driver not found".into()))?;
+        if driver.status != DriverStatus::Available {
+            return Ok(Some("Driver is not available"));
+        }
+
+        Ok(None)
+    }
+
+    async fn take_resources(tx: &mut dyn RepositoryTransactionTrait, assignment: &VehicleAssignment) -> Result<(), AppError> {
+        tx.update_vehicle_status(assignment.vehicle_id, VehicleStatus::Assigned).await?;
+        tx.update_driver_status(assignment.driver_id, DriverStatus::OnDuty).await?;
+        Ok(())
+    }
+
+    async fn release_resources(tx: &mut dyn RepositoryTransactionTrait, assignment: &VehicleAssignment) -> Result<(), AppError> {
+        tx.update_vehicle_status(assignment.vehicle_id, VehicleStatus::Available).await?;
+        tx.update_driver_status(assignment.driver_id, DriverStatus::Available).await?;
+        Ok(())
+    }
+
+    /// Moves a scheduled assignment to Active and takes its vehicle and driver, all or
+    /// nothing. `None` if it was started or cancelled in the meantime.
+    async fn activate(&self, id: Uuid) -> Result<Option<VehicleAssignment>, AppError> {
+        let mut tx = self.unit_of_work.begin().await?;
+        let Some(started) = tx.update_assignment_status(id, AssignmentStatus::Scheduled, AssignmentStatus::Active).await? else {
+            return Ok(None);
+        };
+        Self::take_resources(tx.as_mut(), &started).await?;
+        tx.commit().await?;
+        Ok(Some(started))
     }
 }
 
 #[async_trait]
 impl AssignmentServiceTrait for AssignmentService {
-    async fn create_assignment(&self, dto: CreateAssignmentDto) -> Result<VehicleAssignment, AppError> {
-        // 1. Check if vehicle exists and is available
+    async fn create_assignment(&self, mut dto: CreateAssignmentDto) -> Result<VehicleAssignment, AppError> {
+        let now = Utc::now();
+        if dto.status.is_closed() {
+            return Err(AppError::BadRequest("New assignments are either Scheduled or Active".into()));
+        }
+        if let Some(end) = dto.end_time {
+            if end <= dto.start_time {
+                return Err(AppError::BadRequest("end_time must be after start_time".into()));
+            }
+            if end <= now {
+                return Err(AppError::BadRequest("Assignment would already be over".into()));
+            }
+        }
+        // Assignments that have already begun take the vehicle and driver right away;
+        // later ones are started by the activation job
+        let starts_now = dto.start_time <= now;
+        dto.status = if starts_now { AssignmentStatus::Active } else { AssignmentStatus::Scheduled };
+
+        // 1. Check if vehicle exists and can be assigned
         let vehicle = self.vehicle_repo.find_by_id(dto.vehicle_id).await?
             .ok_or(AppError::NotFound("Vehicle not found".into()))?;
-        
-        if vehicle.status != VehicleStatus::Available {
+
+        if starts_now && vehicle.status != VehicleStatus::Available {
             return Err(AppError::BadRequest("Vehicle is not available".into()));
         }
+        if vehicle.status == VehicleStatus::OutOfService {
+            return Err(AppError::BadRequest("Vehicle is out of service".into()));
+        }
 
-        // 2. Check if driver exists and is available
+        // 2. Check if driver exists and can be assigned
         let driver = self.driver_repo.find_by_id(dto.driver_id).await?
             .ok_or(AppError::NotFound("Driver not found".into()))?;
 
-        if driver.status != DriverStatus::Available {
+        if starts_now && driver.status != DriverStatus::Available {
             return Err(AppError::BadRequest("Driver is not available".into()));
         }
 
-        // 3. Create assignment
-        let assignment = self.assignment_repo.create(dto).await?;
-
-        // 4. Update vehicle status
-        self.vehicle_repo.update_status(vehicle.id, VehicleStatus::Assigned).await?;
+        // The license has to stay valid until the last day of the assignment
+        let today = now.date_naive();
+        let last_day = dto.end_time.unwrap_or(dto.start_time).max(dto.start_time).date_naive().max(today);
+        if !driver.license_valid_on(last_day) {
+            let expiry = driver.license_expiry.unwrap_or(today);
+            return Err(AppError::BadRequest(if expiry < today {
+                format!("Driver's license expired on {}", expiry)
+            } else {
+                format!("Driver's license expires on {}, before the assignment ends", expiry)
+            }));
+        }
+        // Likewise the vehicle's registration, insurance and inspection
+        self.document_service.check_assignment(vehicle.id, today, last_day).await?;
+
+        // Hours-of-service limits, counting the driver's other assignments and clock-ins
+        self.hos_service.check_assignment(driver.id, dto.start_time, dto.end_time).await?;
+
+        // 3. Create assignment; the vehicle exclusion constraint turns overlaps into a
+        // Conflict, and the driver's row lock keeps their check race-free
+        let mut tx = self.unit_of_work.begin().await?;
+        tx.lock_driver(driver.id).await?;
+        if let Some(existing) = tx.find_driver_overlaps(driver.id, dto.start_time, dto.end_time).await?.first() {
+            return Err(AppError::conflict(
+                ConflictKind::Overlap,
+                format!("Driver already has assignment {} in this period", existing.id),
+            ));
+        }
+        let job_id = dto.job_id;
+        let assignment = tx.create_assignment(dto).await?;
+        if let Some(job_id) = job_id {
+            tx.link_job(job_id, assignment.id).await?;
+        }
 
-        // 5. Update driver status
-        self.driver_repo.update_status(driver.id, DriverStatus::OnDuty).await?;
+        // 4. Update vehicle and driver status once the assignment is running
+        if starts_now {
+            Self::take_resources(tx.as_mut(), &assignment).await?;
+        }
+        tx.commit().await?;
 
         Ok(assignment)
     }
 
+    async fn start_assignment(&self, id: Uuid) -> Result<VehicleAssignment, AppError> {
+        let assignment = self.find_assignment(id).await?;
+        Self::check_transition(&assignment, AssignmentStatus::Active)?;
+        if let Some(reason) = self.unavailable_reason(assignment.vehicle_id, assignment.driver_id).await? {
+            return Err(AppError::BadRequest(reason.into()));
+        }
+
+        self.activate(id).await?.ok_or_else(|| Self::changed_concurrently(id))
+    }
+
     async fn complete_assignment(&self, id: Uuid) -> Result<VehicleAssignment, AppError> {
-        let assignment = self.assignment_repo.find_by_id(id).await?
-            .ok_or(AppError::NotFound("Assignment not found".into()))?;
+        let assignment = self.find_assignment(id).await?;
+        Self::check_transition(&assignment, AssignmentStatus::Completed)?;
 
-        if assignment.status == AssignmentStatus::Completed {
-            return Err(AppError::BadRequest("Assignment is already completed".into()));
-        }
+        let mut tx = self.unit_of_work.begin().await?;
+        let completed = tx
+            .update_assignment_status(id, AssignmentStatus::Active, AssignmentStatus::Completed).await?
+            .ok_or_else(|| Self::changed_concurrently(id))?;
+        Self::release_resources(tx.as_mut(), &completed).await?;
+        tx.commit().await?;
 
-        let updated_assignment = self.assignment_repo.update_status(id, AssignmentStatus::Completed).await?;
+        Ok(completed)
+    }
+
+    async fn cancel_assignment(&self, id: Uuid, dto: CancelAssignmentDto) -> Result<VehicleAssignment, AppError> {
+        let reason = dto.reason.trim().to_string();
+        if reason.is_empty() {
+            return Err(AppError::BadRequest("A reason is required to cancel an assignment".into()));
+        }
 
-        // Update vehicle status to Available
-        self.vehicle_repo.update_status(assignment.vehicle_id, VehicleStatus::Available).await?;
+        let assignment = self.find_assignment(id).await?;
+        Self::check_transition(&assignment, AssignmentStatus::Cancelled)?;
 
-        // Update driver status to Available
-        self.driver_repo.update_status(assignment.driver_id, DriverStatus::Available).await?;
+        let mut tx = self.unit_of_work.begin().await?;
+        let cancelled = tx
+            .cancel_assignment(id, assignment.status, reason).await?
+            .ok_or_else(|| Self::changed_concurrently(id))?;
+        // Scheduled assignments never took the vehicle or driver
+        if assignment.status == AssignmentStatus::Active {
+            Self::release_resources(tx.as_mut(), &cancelled).await?;
+        }
+        tx.commit().await?;
 
-        Ok(updated_assignment)
+        Ok(cancelled)
     }
 
     async fn get_assignment(&self, id: Uuid) -> Result<VehicleAssignment, AppError> {
-        self.assignment_repo.find_by_id(id).await?
-            .ok_or(AppError::NotFound("Assignment not found".into()))
+        self.find_assignment(id).await
     }
 
-    async fn list_assignments(&self) -> Result<Vec<VehicleAssignment>, AppError> {
-        self.assignment_repo.find_all().await
+    async fn list_assignments(&self, query: AssignmentQuery) -> Result<Vec<VehicleAssignment>, AppError> {
+        if let (Some(from), Some(to)) = (query.from, query.to) {
+            if to <= from {
+                return Err(AppError::BadRequest("'to' must be after 'from'".into()));
+            }
+        }
+        self.assignment_repo.find_all(query).await
     }
 
     async fn get_assignments_by_driver(&self, driver_id: Uuid) -> Result<Vec<VehicleAssignment>, AppError> {
         self.assignment_repo.find_by_driver_id(driver_id).await
     }
+
+    async fn activate_due_assignments(&self, now: DateTime<Utc>) -> Result<Vec<VehicleAssignment>, AppError> {
+        let mut changed = Vec::new();
+        for assignment in self.assignment_repo.find_due(now).await? {
+            // Skipped assignments were started or cancelled by hand in the meantime
+            if assignment.end_time.is_some_and(|end| end <= now) {
+                if let Some(cancelled) = self.assignment_repo
+                    .cancel(assignment.id, AssignmentStatus::Scheduled, NOT_STARTED_REASON.into()).await?
+                {
+                    changed.push(cancelled);
+                }
+                continue;
+            }
+
+            // The previous assignment may still be running; try again on the next run
+            if self.unavailable_reason(assignment.vehicle_id, assignment.driver_id).await?.is_some() {
+                continue;
+            }
+            if let Some(started) = self.activate(assignment.id).await? {
+                changed.push(started);
+            }
+        }
+        Ok(changed)
+    }
 }
 
 #[cfg(test)]
@@ -109,14 +299,22 @@ mod tests {
     use crate::repositories::postgres::assignment_repo::MockAssignmentRepositoryTrait;
     use crate::repositories::postgres::vehicle_repo::MockVehicleRepositoryTrait;
     use crate::repositories::postgres::driver_repo::MockDriverRepositoryTrait;
+    use crate::repositories::postgres::unit_of_work::{MockUnitOfWorkTrait, MockRepositoryTransactionTrait};
+    use crate::services::hos_service::MockHosServiceTrait;
+    use crate::services::vehicle_document_service::MockVehicleDocumentServiceTrait;
     use crate::models::postgres::vehicle::{Vehicle, VehicleType, FuelType};
     use crate::models::postgres::driver::Driver;
     use chrono::Utc;
     use mockall::predicate::*;
 
+    fn documents_in_order() -> Arc<MockVehicleDocumentServiceTrait> {
+        let mut document_service = MockVehicleDocumentServiceTrait::new();
+        document_service.expect_check_assignment().returning(|_, _, _| Ok(()));
+        Arc::new(document_service)
+    }
+
     #[tokio::test]
     async fn test_create_assignment_success() {
-        let mut mock_assignment_repo = MockAssignmentRepositoryTrait::new();
         let mut mock_vehicle_repo = MockVehicleRepositoryTrait::new();
         let mut mock_driver_repo = MockDriverRepositoryTrait::new();
 
@@ -146,6 +344,9 @@ mod tests {
             user_id: Uuid::new_v4(),
             license_number: "LIC123".into(),
             status: DriverStatus::Available,
+            phone: None,
+            wage_rate: None,
+            license_expiry: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
             deleted_at: None,
@@ -157,6 +358,7 @@ mod tests {
             start_time: Utc::now(),
             end_time: None,
             status: AssignmentStatus::Scheduled,
+            job_id: None,
         };
 
         let assignment = VehicleAssignment {
@@ -165,9 +367,13 @@ mod tests {
             driver_id,
             start_time: dto.start_time,
             end_time: None,
-            status: AssignmentStatus::Scheduled,
+            status: AssignmentStatus::Active,
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            started_at: Some(Utc::now()),
+            completed_at: None,
+            cancelled_at: None,
+            cancel_reason: None,
         };
 
         // Clone for closures
@@ -187,30 +393,508 @@ mod tests {
             .times(1)
             .returning(move |_| Ok(Some(driver_clone.clone())));
 
-        mock_assignment_repo
-            .expect_create()
+        let mut tx = MockRepositoryTransactionTrait::new();
+        tx.expect_lock_driver().with(eq(driver_id)).times(1).returning(|_| Ok(()));
+        tx.expect_find_driver_overlaps().times(1).returning(|_, _, _| Ok(vec![]));
+
+        // Already started, so it becomes Active right away
+        tx.expect_create_assignment()
+            .withf(|dto| dto.status == AssignmentStatus::Active)
             .times(1)
             .returning(move |_| Ok(assignment_clone.clone()));
 
-        mock_vehicle_repo
-            .expect_update_status()
+        tx.expect_update_vehicle_status()
             .with(eq(vehicle_id), eq(VehicleStatus::Assigned))
             .times(1)
             .returning(move |_, _| Ok(vehicle.clone()));
 
-        mock_driver_repo
-            .expect_update_status()
+        tx.expect_update_driver_status()
             .with(eq(driver_id), eq(DriverStatus::OnDuty))
             .times(1)
             .returning(move |_, _| Ok(driver.clone()));
 
+        tx.expect_commit().times(1).returning(|| Ok(()));
+
+        let mut hos_service = MockHosServiceTrait::new();
+        hos_service
+            .expect_check_assignment()
+            .withf(move |id, _, _| *id == driver_id)
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+
         let service = AssignmentService::new(
-            Arc::new(mock_assignment_repo),
+            Arc::new(MockAssignmentRepositoryTrait::new()),
             Arc::new(mock_vehicle_repo),
             Arc::new(mock_driver_repo),
+            Arc::new(hos_service),
+            documents_in_order(),
+            unit_of_work(tx),
         );
 
         let result = service.create_assignment(dto).await;
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_create_assignment_rejects_license_expiring_before_end() {
+        let mut mock_vehicle_repo = MockVehicleRepositoryTrait::new();
+        let mut mock_driver_repo = MockDriverRepositoryTrait::new();
+
+        let today = Utc::now().date_naive();
+        let vehicle_id = Uuid::new_v4();
+        let driver_id = Uuid::new_v4();
+
+        mock_vehicle_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(Vehicle {
+                id,
+                make: "Toyota".into(),
+                model: "Corolla".into(),
+                year: 2020,
+                vin: "VIN123".into(),
+                license_plate: "ABC-123".into(),
+                r#type: VehicleType::Sedan,
+                status: VehicleStatus::Available,
+                current_mileage: 10000,
+                fuel_type: FuelType::Gasoline,
+                specs: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                deleted_at: None,
+            })));
+
+        mock_driver_repo
+            .expect_find_by_id()
+            .returning(move |id| Ok(Some(Driver {
+                id,
+                user_id: Uuid::new_v4(),
+                license_number: "LIC123".into(),
+                status: DriverStatus::Available,
+                phone: None,
+                wage_rate: None,
+                license_expiry: Some(today + chrono::Duration::days(2)),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                deleted_at: None,
+            })));
+
+        // Nothing may be created or updated
+        let service = AssignmentService::new(
+            Arc::new(MockAssignmentRepositoryTrait::new()),
+            Arc::new(mock_vehicle_repo),
+            Arc::new(mock_driver_repo),
+            Arc::new(MockHosServiceTrait::new()),
+            documents_in_order(),
+            Arc::new(MockUnitOfWorkTrait::new()),
+        );
+
+        let result = service.create_assignment(CreateAssignmentDto {
+            vehicle_id,
+            driver_id,
+            start_time: Utc::now(),
+            end_time: Some(Utc::now() + chrono::Duration::days(5)),
+            status: AssignmentStatus::Scheduled,
+            job_id: None,
+        }).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_assignment_rejects_hours_of_service_breach() {
+        let mut mock_vehicle_repo = MockVehicleRepositoryTrait::new();
+        let mut mock_driver_repo = MockDriverRepositoryTrait::new();
+
+        mock_vehicle_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(Vehicle {
+                id,
+                make: "Toyota".into(),
+                model: "Corolla".into(),
+                year: 2020,
+                vin: "VIN123".into(),
+                license_plate: "ABC-123".into(),
+                r#type: VehicleType::Sedan,
+                status: VehicleStatus::Available,
+                current_mileage: 10000,
+                fuel_type: FuelType::Gasoline,
+                specs: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                deleted_at: None,
+            })));
+
+        mock_driver_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(Driver {
+                id,
+                user_id: Uuid::new_v4(),
+                license_number: "LIC123".into(),
+                status: DriverStatus::Available,
+                phone: None,
+                wage_rate: None,
+                license_expiry: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                deleted_at: None,
+            })));
+
+        let mut hos_service = MockHosServiceTrait::new();
+        hos_service
+            .expect_check_assignment()
+            .returning(|_, _, _| Err(AppError::BadRequest("weekly limit".into())));
+
+        // Nothing may be created or updated
+        let service = AssignmentService::new(
+            Arc::new(MockAssignmentRepositoryTrait::new()),
+            Arc::new(mock_vehicle_repo),
+            Arc::new(mock_driver_repo),
+            Arc::new(hos_service),
+            documents_in_order(),
+            Arc::new(MockUnitOfWorkTrait::new()),
+        );
+
+        let result = service.create_assignment(CreateAssignmentDto {
+            vehicle_id: Uuid::new_v4(),
+            driver_id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: Some(Utc::now() + chrono::Duration::hours(8)),
+            status: AssignmentStatus::Scheduled,
+            job_id: None,
+        }).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_assignment_rejects_expired_vehicle_documents() {
+        let mut mock_vehicle_repo = MockVehicleRepositoryTrait::new();
+        mock_vehicle_repo.expect_find_by_id().returning(|id| Ok(Some(vehicle(id, VehicleStatus::Available))));
+        let mut mock_driver_repo = MockDriverRepositoryTrait::new();
+        mock_driver_repo.expect_find_by_id().returning(|id| Ok(Some(driver(id, DriverStatus::Available))));
+
+        let vehicle_id = Uuid::new_v4();
+        let end_time = Utc::now() + chrono::Duration::days(3);
+        let mut document_service = MockVehicleDocumentServiceTrait::new();
+        document_service
+            .expect_check_assignment()
+            .withf(move |id, _, last_day| *id == vehicle_id && *last_day == end_time.date_naive())
+            .times(1)
+            .returning(|_, _, _| Err(AppError::BadRequest("Vehicle's insurance expired on 2026-10-01".into())));
+
+        // Rejected before hours of service are checked or anything is written
+        let service = AssignmentService::new(
+            Arc::new(MockAssignmentRepositoryTrait::new()),
+            Arc::new(mock_vehicle_repo),
+            Arc::new(mock_driver_repo),
+            Arc::new(MockHosServiceTrait::new()),
+            Arc::new(document_service),
+            Arc::new(MockUnitOfWorkTrait::new()),
+        );
+
+        let result = service.create_assignment(CreateAssignmentDto {
+            vehicle_id,
+            driver_id: Uuid::new_v4(),
+            start_time: Utc::now() + chrono::Duration::hours(1),
+            end_time: Some(end_time),
+            status: AssignmentStatus::Scheduled,
+            job_id: None,
+        }).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("insurance")));
+    }
+
+    fn vehicle(id: Uuid, status: VehicleStatus) -> Vehicle {
+        Vehicle {
+            id,
+            make: "Toyota".into(),
+            model: "Corolla".into(),
+            year: 2020,
+            vin: "VIN123".into(),
+            license_plate: "ABC-123".into(),
+            r#type: VehicleType::Sedan,
+            status,
+            current_mileage: 10000,
+            fuel_type: FuelType::Gasoline,
+            specs: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn driver(id: Uuid, status: DriverStatus) -> Driver {
+        Driver {
+            id,
+            user_id: Uuid::new_v4(),
+            license_number: "LIC123".into(),
+            status,
+            phone: None,
+            wage_rate: None,
+            license_expiry: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn unit_of_work(tx: MockRepositoryTransactionTrait) -> Arc<MockUnitOfWorkTrait> {
+        let mut unit_of_work = MockUnitOfWorkTrait::new();
+        unit_of_work.expect_begin().times(1).return_once(move || Ok(Box::new(tx)));
+        Arc::new(unit_of_work)
+    }
+
+    fn scheduled(start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> VehicleAssignment {
+        VehicleAssignment {
+            id: Uuid::new_v4(),
+            vehicle_id: Uuid::new_v4(),
+            driver_id: Uuid::new_v4(),
+            start_time: start,
+            end_time: end,
+            status: AssignmentStatus::Scheduled,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            started_at: None,
+            completed_at: None,
+            cancelled_at: None,
+            cancel_reason: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_future_assignment_is_scheduled_without_taking_resources() {
+        let mut mock_vehicle_repo = MockVehicleRepositoryTrait::new();
+        let mut mock_driver_repo = MockDriverRepositoryTrait::new();
+
+        // Both are busy today, which does not matter for next week
+        mock_vehicle_repo.expect_find_by_id().returning(|id| Ok(Some(vehicle(id, VehicleStatus::Assigned))));
+        mock_driver_repo.expect_find_by_id().returning(|id| Ok(Some(driver(id, DriverStatus::OnDuty))));
+
+        let start = Utc::now() + chrono::Duration::days(7);
+        let end = start + chrono::Duration::hours(8);
+        let mut tx = MockRepositoryTransactionTrait::new();
+        tx.expect_lock_driver().times(1).returning(|_| Ok(()));
+        tx.expect_find_driver_overlaps()
+            .with(always(), eq(start), eq(Some(end)))
+            .times(1)
+            .returning(|_, _, _| Ok(vec![]));
+        tx.expect_create_assignment()
+            .withf(|dto| dto.status == AssignmentStatus::Scheduled)
+            .times(1)
+            .returning(move |_| Ok(scheduled(start, Some(end))));
+        tx.expect_update_vehicle_status().never();
+        tx.expect_update_driver_status().never();
+        tx.expect_commit().times(1).returning(|| Ok(()));
+
+        let mut hos_service = MockHosServiceTrait::new();
+        hos_service.expect_check_assignment().times(1).returning(|_, _, _| Ok(()));
+
+        let service = AssignmentService::new(
+            Arc::new(MockAssignmentRepositoryTrait::new()),
+            Arc::new(mock_vehicle_repo),
+            Arc::new(mock_driver_repo),
+            Arc::new(hos_service),
+            documents_in_order(),
+            unit_of_work(tx),
+        );
+
+        let result = service.create_assignment(CreateAssignmentDto {
+            vehicle_id: Uuid::new_v4(),
+            driver_id: Uuid::new_v4(),
+            start_time: start,
+            end_time: Some(end),
+            status: AssignmentStatus::Active,
+            job_id: None,
+        }).await.unwrap();
+        assert_eq!(result.status, AssignmentStatus::Scheduled);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_active_assignment_releases_vehicle_and_driver() {
+        let mut mock_assignment_repo = MockAssignmentRepositoryTrait::new();
+        let mut tx = MockRepositoryTransactionTrait::new();
+
+        let mut active = scheduled(Utc::now() - chrono::Duration::hours(1), None);
+        active.status = AssignmentStatus::Active;
+        active.started_at = Some(active.start_time);
+        let (id, vehicle_id, driver_id) = (active.id, active.vehicle_id, active.driver_id);
+
+        let found = active.clone();
+        mock_assignment_repo.expect_find_by_id().returning(move |_| Ok(Some(found.clone())));
+        tx.expect_cancel_assignment()
+            .with(eq(id), eq(AssignmentStatus::Active), eq("Vehicle broke down".to_string()))
+            .times(1)
+            .returning(move |_, _, reason| {
+                let mut cancelled = active.clone();
+                cancelled.status = AssignmentStatus::Cancelled;
+                cancelled.cancelled_at = Some(Utc::now());
+                cancelled.cancel_reason = Some(reason);
+                Ok(Some(cancelled))
+            });
+        tx.expect_update_vehicle_status()
+            .with(eq(vehicle_id), eq(VehicleStatus::Available))
+            .times(1)
+            .returning(|id, status| Ok(vehicle(id, status)));
+        tx.expect_update_driver_status()
+            .with(eq(driver_id), eq(DriverStatus::Available))
+            .times(1)
+            .returning(|id, status| Ok(driver(id, status)));
+        tx.expect_commit().times(1).returning(|| Ok(()));
+
+        let service = AssignmentService::new(
+            Arc::new(mock_assignment_repo),
+            Arc::new(MockVehicleRepositoryTrait::new()),
+            Arc::new(MockDriverRepositoryTrait::new()),
+            Arc::new(MockHosServiceTrait::new()),
+            documents_in_order(),
+            unit_of_work(tx),
+        );
+
+        let blank = service.cancel_assignment(id, CancelAssignmentDto { reason: "  ".into() }).await;
+        assert!(matches!(blank, Err(AppError::BadRequest(_))));
+
+        let result = service
+            .cancel_assignment(id, CancelAssignmentDto { reason: " Vehicle broke down ".into() })
+            .await
+            .unwrap();
+        assert_eq!(result.status, AssignmentStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_complete_lost_race_is_a_conflict() {
+        let mut mock_assignment_repo = MockAssignmentRepositoryTrait::new();
+        let mut tx = MockRepositoryTransactionTrait::new();
+
+        let mut active = scheduled(Utc::now() - chrono::Duration::hours(1), None);
+        active.status = AssignmentStatus::Active;
+        let id = active.id;
+
+        mock_assignment_repo.expect_find_by_id().returning(move |_| Ok(Some(active.clone())));
+        // Completed or cancelled by another request in the meantime
+        tx.expect_update_assignment_status().times(1).returning(|_, _, _| Ok(None));
+        tx.expect_update_vehicle_status().never();
+        tx.expect_commit().never();
+
+        let service = AssignmentService::new(
+            Arc::new(mock_assignment_repo),
+            Arc::new(MockVehicleRepositoryTrait::new()),
+            Arc::new(MockDriverRepositoryTrait::new()),
+            Arc::new(MockHosServiceTrait::new()),
+            documents_in_order(),
+            unit_of_work(tx),
+        );
+
+        let result = service.complete_assignment(id).await;
+        assert!(matches!(result, Err(AppError::Conflict(details)) if details.kind == ConflictKind::Stale));
+    }
+
+    #[tokio::test]
+    async fn test_activate_due_assignments_starts_free_ones_and_cancels_expired() {
+        let now = Utc::now();
+        let ready = scheduled(now - chrono::Duration::minutes(1), Some(now + chrono::Duration::hours(8)));
+        let waiting = scheduled(now - chrono::Duration::minutes(5), None);
+        let expired = scheduled(now - chrono::Duration::hours(9), Some(now - chrono::Duration::hours(1)));
+        let (ready_id, expired_id) = (ready.id, expired.id);
+        let busy_vehicle = waiting.vehicle_id;
+
+        let mut mock_assignment_repo = MockAssignmentRepositoryTrait::new();
+        let due = vec![expired.clone(), waiting, ready.clone()];
+        mock_assignment_repo.expect_find_due().times(1).returning(move |_| Ok(due.clone()));
+        mock_assignment_repo
+            .expect_cancel()
+            .with(eq(expired_id), eq(AssignmentStatus::Scheduled), eq(NOT_STARTED_REASON.to_string()))
+            .times(1)
+            .returning(move |_, _, reason| {
+                let mut cancelled = expired.clone();
+                cancelled.status = AssignmentStatus::Cancelled;
+                cancelled.cancel_reason = Some(reason);
+                Ok(Some(cancelled))
+            });
+        // Only the assignment whose vehicle is free gets started
+        let mut tx = MockRepositoryTransactionTrait::new();
+        tx.expect_update_assignment_status()
+            .with(eq(ready_id), eq(AssignmentStatus::Scheduled), eq(AssignmentStatus::Active))
+            .times(1)
+            .returning(move |_, _, _| {
+                let mut started = ready.clone();
+                started.status = AssignmentStatus::Active;
+                started.started_at = Some(Utc::now());
+                Ok(Some(started))
+            });
+
+        let mut mock_vehicle_repo = MockVehicleRepositoryTrait::new();
+        mock_vehicle_repo.expect_find_by_id().returning(move |id| {
+            let status = if id == busy_vehicle { VehicleStatus::Assigned } else { VehicleStatus::Available };
+            Ok(Some(vehicle(id, status)))
+        });
+        tx.expect_update_vehicle_status()
+            .with(always(), eq(VehicleStatus::Assigned))
+            .times(1)
+            .returning(|id, status| Ok(vehicle(id, status)));
+
+        let mut mock_driver_repo = MockDriverRepositoryTrait::new();
+        mock_driver_repo.expect_find_by_id().returning(|id| Ok(Some(driver(id, DriverStatus::Available))));
+        tx.expect_update_driver_status()
+            .with(always(), eq(DriverStatus::OnDuty))
+            .times(1)
+            .returning(|id, status| Ok(driver(id, status)));
+        tx.expect_commit().times(1).returning(|| Ok(()));
+
+        let service = AssignmentService::new(
+            Arc::new(mock_assignment_repo),
+            Arc::new(mock_vehicle_repo),
+            Arc::new(mock_driver_repo),
+            Arc::new(MockHosServiceTrait::new()),
+            documents_in_order(),
+            unit_of_work(tx),
+        );
+
+        let changed = service.activate_due_assignments(now).await.unwrap();
+        let statuses: Vec<_> = changed.iter().map(|a| (a.id, a.status)).collect();
+        assert_eq!(statuses, vec![(expired_id, AssignmentStatus::Cancelled), (ready_id, AssignmentStatus::Active)]);
+    }
+
+    #[tokio::test]
+    async fn test_create_assignment_rejects_overlapping_driver_booking() {
+        let mut mock_vehicle_repo = MockVehicleRepositoryTrait::new();
+        let mut mock_driver_repo = MockDriverRepositoryTrait::new();
+        mock_vehicle_repo.expect_find_by_id().returning(|id| Ok(Some(vehicle(id, VehicleStatus::Available))));
+        mock_driver_repo.expect_find_by_id().returning(|id| Ok(Some(driver(id, DriverStatus::Available))));
+
+        let mut hos_service = MockHosServiceTrait::new();
+        hos_service.expect_check_assignment().returning(|_, _, _| Ok(()));
+
+        let start = Utc::now() + chrono::Duration::days(1);
+        let existing = scheduled(start - chrono::Duration::hours(2), Some(start + chrono::Duration::hours(2)));
+        let existing_id = existing.id;
+
+        // Dropped without commit, so nothing is written
+        let mut tx = MockRepositoryTransactionTrait::new();
+        tx.expect_lock_driver().times(1).returning(|_| Ok(()));
+        tx.expect_find_driver_overlaps().times(1).returning(move |_, _, _| Ok(vec![existing.clone()]));
+        tx.expect_create_assignment().never();
+        tx.expect_commit().never();
+
+        let service = AssignmentService::new(
+            Arc::new(MockAssignmentRepositoryTrait::new()),
+            Arc::new(mock_vehicle_repo),
+            Arc::new(mock_driver_repo),
+            Arc::new(hos_service),
+            documents_in_order(),
+            unit_of_work(tx),
+        );
+
+        let result = service.create_assignment(CreateAssignmentDto {
+            vehicle_id: Uuid::new_v4(),
+            driver_id: Uuid::new_v4(),
+            start_time: start,
+            end_time: Some(start + chrono::Duration::hours(8)),
+            status: AssignmentStatus::Scheduled,
+            job_id: None,
+        }).await;
+        match result {
+            Err(AppError::Conflict(details)) => {
+                assert_eq!(details.kind, ConflictKind::Overlap);
+                assert!(details.message.contains(&existing_id.to_string()));
+            }
+            other => panic!("expected a conflict, got {:?}", other),
+        }
+    }
 }
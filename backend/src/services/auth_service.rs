@@ -1,19 +1,26 @@
+use std::sync::Arc;
 use argon2::{
     password_hash::{
-        rand_core::OsRng,
+        rand_core::{OsRng, RngCore},
         PasswordHash, PasswordHasher, PasswordVerifier, SaltString
     },
     Argon2
 };
 use jsonwebtoken::{encode, EncodingKey, Header};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
-use chrono::{Utc, Duration};
+use chrono::{DateTime, Utc, Duration};
 use utoipa::ToSchema;
 
 use crate::error::AppError;
 use crate::models::postgres::user::{User, CreateUserDto, UserRole};
 use crate::repositories::postgres::user_repo::UserRepositoryTrait;
+use crate::repositories::postgres::token_repo::TokenRepositoryTrait;
+
+/// Access tokens are short-lived; clients renew them with a refresh token.
+const ACCESS_TOKEN_TTL_MINUTES: i64 = 15;
+const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
 
 #[derive(Debug, Serialize, Deserialize, ToSchema, Clone)]
 pub struct Claims {
@@ -22,11 +29,13 @@ pub struct Claims {
     pub role: UserRole,
     pub is_active: bool,
     pub exp: usize,
+    pub jti: Uuid,        // Token ID, used for revocation
 }
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct AuthResponse {
     pub token: String,
+    pub refresh_token: String,
     pub user: User,
 }
 
@@ -36,48 +45,92 @@ pub struct LoginDto {
     pub password: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct RefreshTokenDto {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct LogoutDto {
+    /// Session to end. When omitted, every refresh token of the user is revoked.
+    pub refresh_token: Option<String>,
+}
+
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait AuthServiceTrait: Send + Sync {
     async fn register(&self, dto: CreateUserDto) -> Result<AuthResponse, AppError>;
     async fn login(&self, dto: LoginDto) -> Result<AuthResponse, AppError>;
+    async fn refresh(&self, dto: RefreshTokenDto) -> Result<AuthResponse, AppError>;
+    async fn logout(&self, claims: Claims, dto: LogoutDto) -> Result<(), AppError>;
 }
 
 pub struct AuthService {
     user_repo: Box<dyn UserRepositoryTrait>,
+    token_repo: Arc<dyn TokenRepositoryTrait>,
     jwt_secret: String,
 }
 
+fn hash_refresh_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
 impl AuthService {
-    pub fn new(user_repo: Box<dyn UserRepositoryTrait>, jwt_secret: String) -> Self {
-        Self { user_repo, jwt_secret }
+    pub fn new(
+        user_repo: Box<dyn UserRepositoryTrait>,
+        token_repo: Arc<dyn TokenRepositoryTrait>,
+        jwt_secret: String,
+    ) -> Self {
+        Self { user_repo, token_repo, jwt_secret }
     }
 
     fn generate_token(&self, user: &User) -> Result<String, AppError> {
         let expiration = Utc::now()
-            .checked_add_signed(Duration::hours(24))
+            .checked_add_signed(Duration::minutes(ACCESS_TOKEN_TTL_MINUTES))
             .expect("valid timestamp")
             .timestamp();
 
         let claims = Claims {
             sub: user.id,
             user_id: user.id,
-            role: user.role.clone(),
+            role: user.role,
             is_active: user.is_active,
             exp: expiration as usize,
+            jti: Uuid::new_v4(),
         };
 
         encode(&Header::default(), &claims, &EncodingKey::from_secret(self.jwt_secret.as_bytes()))
             .map_err(|e| AppError::InternalServerError(e.to_string()))
     }
+
+    /// Issues a new opaque refresh token. Only its hash is stored server-side.
+    async fn generate_refresh_token(&self, user: &User) -> Result<String, AppError> {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        let token = hex::encode(bytes);
+
+        let expires_at = Utc::now() + Duration::days(REFRESH_TOKEN_TTL_DAYS);
+        self.token_repo
+            .create_refresh_token(user.id, &hash_refresh_token(&token), expires_at)
+            .await?;
+
+        Ok(token)
+    }
+
+    async fn issue_tokens(&self, user: User) -> Result<AuthResponse, AppError> {
+        let token = self.generate_token(&user)?;
+        let refresh_token = self.generate_refresh_token(&user).await?;
+
+        Ok(AuthResponse { token, refresh_token, user })
+    }
 }
 
 #[async_trait]
 impl AuthServiceTrait for AuthService {
     async fn register(&self, mut dto: CreateUserDto) -> Result<AuthResponse, AppError> {
         // Check if user exists
-        if let Some(_) = self.user_repo.find_by_email(&dto.email).await? {
+        if self.user_repo.find_by_email(&dto.email).await?.is_some() {
             return Err(AppError::BadRequest("User with this email already exists".into()));
         }
 
@@ -93,10 +146,7 @@ impl AuthServiceTrait for AuthService {
         // Create user
         let user = self.user_repo.create(dto).await?;
 
-        // Generate token
-        let token = self.generate_token(&user)?;
-
-        Ok(AuthResponse { token, user })
+        self.issue_tokens(user).await
     }
 
     async fn login(&self, dto: LoginDto) -> Result<AuthResponse, AppError> {
@@ -110,10 +160,51 @@ impl AuthServiceTrait for AuthService {
         Argon2::default().verify_password(dto.password.as_bytes(), &parsed_hash)
             .map_err(|_| AppError::AuthError("Invalid email or password".into()))?;
 
-        // Generate token
-        let token = self.generate_token(&user)?;
+        self.issue_tokens(user).await
+    }
+
+    async fn refresh(&self, dto: RefreshTokenDto) -> Result<AuthResponse, AppError> {
+        let stored = self.token_repo.find_refresh_token(&hash_refresh_token(&dto.refresh_token)).await?
+            .ok_or(AppError::AuthError("Invalid refresh token".into()))?;
+
+        // Refresh tokens are single-use. Seeing a revoked one again means it was copied,
+        // so end every session of that user.
+        if stored.revoked_at.is_some() || !self.token_repo.revoke_refresh_token(stored.id).await? {
+            self.token_repo.revoke_user_refresh_tokens(stored.user_id).await?;
+            return Err(AppError::AuthError("Refresh token has been revoked".into()));
+        }
 
-        Ok(AuthResponse { token, user })
+        if stored.expires_at <= Utc::now() {
+            return Err(AppError::AuthError("Refresh token has expired".into()));
+        }
+
+        let user = self.user_repo.find_by_id(stored.user_id).await?
+            .ok_or(AppError::AuthError("User not found".into()))?;
+
+        if !user.is_active {
+            return Err(AppError::AuthError("User account is inactive".into()));
+        }
+
+        self.issue_tokens(user).await
+    }
+
+    async fn logout(&self, claims: Claims, dto: LogoutDto) -> Result<(), AppError> {
+        let expires_at = DateTime::<Utc>::from_timestamp(claims.exp as i64, 0)
+            .unwrap_or_else(|| Utc::now() + Duration::minutes(ACCESS_TOKEN_TTL_MINUTES));
+        self.token_repo.revoke_access_token(claims.jti, expires_at).await?;
+
+        match dto.refresh_token {
+            Some(refresh_token) => {
+                let stored = self.token_repo.find_refresh_token(&hash_refresh_token(&refresh_token)).await?;
+                // Never let one user end another user's session
+                if let Some(stored) = stored.filter(|t| t.user_id == claims.user_id) {
+                    self.token_repo.revoke_refresh_token(stored.id).await?;
+                }
+            }
+            None => self.token_repo.revoke_user_refresh_tokens(claims.user_id).await?,
+        }
+
+        Ok(())
     }
 }
 
@@ -121,8 +212,35 @@ impl AuthServiceTrait for AuthService {
 mod tests {
     use super::*;
     use crate::repositories::postgres::user_repo::MockUserRepositoryTrait;
+    use crate::repositories::postgres::token_repo::MockTokenRepositoryTrait;
+    use crate::models::postgres::token::RefreshToken;
     use mockall::predicate::*;
 
+    fn sample_user(id: Uuid) -> User {
+        User {
+            id,
+            email: "test@example.com".to_string(),
+            password_hash: "hash".to_string(),
+            role: UserRole::Driver,
+            name: None,
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn stored_token(user_id: Uuid, revoked: bool) -> RefreshToken {
+        RefreshToken {
+            id: Uuid::new_v4(),
+            user_id,
+            token_hash: hash_refresh_token("old-token"),
+            expires_at: Utc::now() + Duration::days(1),
+            revoked_at: if revoked { Some(Utc::now()) } else { None },
+            created_at: Utc::now(),
+        }
+    }
+
     #[tokio::test]
     async fn test_register_success() {
         let mut mock_repo = MockUserRepositoryTrait::new();
@@ -139,17 +257,31 @@ mod tests {
                 email: dto.email,
                 password_hash: dto.password_hash,
                 role: dto.role,
+                name: dto.name,
                 is_active: true,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
                 deleted_at: None,
             }));
 
-        let service = AuthService::new(Box::new(mock_repo), "secret".to_string());
+        let mut mock_tokens = MockTokenRepositoryTrait::new();
+        mock_tokens.expect_create_refresh_token()
+            .times(1)
+            .returning(|user_id, hash, expires_at| Ok(RefreshToken {
+                id: Uuid::new_v4(),
+                user_id,
+                token_hash: hash.to_string(),
+                expires_at,
+                revoked_at: None,
+                created_at: Utc::now(),
+            }));
+
+        let service = AuthService::new(Box::new(mock_repo), Arc::new(mock_tokens), "secret".to_string());
         let dto = CreateUserDto {
             email: email.to_string(),
             password_hash: password.to_string(),
             role: UserRole::Admin,
+            name: None,
             is_active: true,
         };
 
@@ -169,21 +301,107 @@ mod tests {
                 email: "test@example.com".to_string(),
                 password_hash: "hash".to_string(),
                 role: UserRole::Admin,
+                name: None,
                 is_active: true,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
                 deleted_at: None,
             })));
 
-        let service = AuthService::new(Box::new(mock_repo), "secret".to_string());
+        let service = AuthService::new(Box::new(mock_repo), Arc::new(MockTokenRepositoryTrait::new()), "secret".to_string());
         let dto = CreateUserDto {
             email: email.to_string(),
             password_hash: "password".to_string(),
             role: UserRole::Admin,
+            name: None,
             is_active: true,
         };
 
         let result = service.register(dto).await;
         assert!(matches!(result, Err(AppError::BadRequest(_))));
     }
+
+    #[tokio::test]
+    async fn test_refresh_rotates_token() {
+        let user_id = Uuid::new_v4();
+        let stored = stored_token(user_id, false);
+        let stored_id = stored.id;
+
+        let mut mock_repo = MockUserRepositoryTrait::new();
+        mock_repo.expect_find_by_id()
+            .with(eq(user_id))
+            .returning(|id| Ok(Some(sample_user(id))));
+
+        let mut mock_tokens = MockTokenRepositoryTrait::new();
+        mock_tokens.expect_find_refresh_token()
+            .with(eq(hash_refresh_token("old-token")))
+            .returning(move |_| Ok(Some(stored.clone())));
+        mock_tokens.expect_revoke_refresh_token()
+            .with(eq(stored_id))
+            .times(1)
+            .returning(|_| Ok(true));
+        mock_tokens.expect_create_refresh_token()
+            .times(1)
+            .returning(|user_id, hash, expires_at| Ok(RefreshToken {
+                id: Uuid::new_v4(),
+                user_id,
+                token_hash: hash.to_string(),
+                expires_at,
+                revoked_at: None,
+                created_at: Utc::now(),
+            }));
+
+        let service = AuthService::new(Box::new(mock_repo), Arc::new(mock_tokens), "secret".to_string());
+        let response = service.refresh(RefreshTokenDto { refresh_token: "old-token".into() }).await.unwrap();
+
+        assert_eq!(response.user.id, user_id);
+        assert_ne!(response.refresh_token, "old-token");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_reuse_revokes_all_sessions() {
+        let user_id = Uuid::new_v4();
+        let stored = stored_token(user_id, true);
+
+        let mut mock_tokens = MockTokenRepositoryTrait::new();
+        mock_tokens.expect_find_refresh_token()
+            .returning(move |_| Ok(Some(stored.clone())));
+        mock_tokens.expect_revoke_user_refresh_tokens()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = AuthService::new(Box::new(MockUserRepositoryTrait::new()), Arc::new(mock_tokens), "secret".to_string());
+        let result = service.refresh(RefreshTokenDto { refresh_token: "old-token".into() }).await;
+
+        assert!(matches!(result, Err(AppError::AuthError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_logout_revokes_access_and_refresh_tokens() {
+        let user_id = Uuid::new_v4();
+        let jti = Uuid::new_v4();
+
+        let mut mock_tokens = MockTokenRepositoryTrait::new();
+        mock_tokens.expect_revoke_access_token()
+            .withf(move |id, _| *id == jti)
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_tokens.expect_revoke_user_refresh_tokens()
+            .with(eq(user_id))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = AuthService::new(Box::new(MockUserRepositoryTrait::new()), Arc::new(mock_tokens), "secret".to_string());
+        let claims = Claims {
+            sub: user_id,
+            user_id,
+            role: UserRole::Driver,
+            is_active: true,
+            exp: (Utc::now() + Duration::minutes(5)).timestamp() as usize,
+            jti,
+        };
+
+        assert!(service.logout(claims, LogoutDto::default()).await.is_ok());
+    }
 }
@@ -0,0 +1,524 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::postgres::assignment::{CreateAssignmentDto, VehicleAssignment};
+use crate::models::postgres::dispatch::{AcceptDispatchDto, DispatchSuggestion, DispatchSuggestionQuery, VehicleCandidate};
+use crate::models::postgres::driver::DriverStatus;
+use crate::models::postgres::logistics::{JobStatus, Shipment, TransportJob};
+use crate::models::postgres::maintenance::MaintenanceDueStatus;
+use crate::models::postgres::vehicle::VehicleType;
+use crate::repositories::postgres::dispatch_repo::DispatchRepositoryTrait;
+use crate::repositories::postgres::driver_repo::DriverRepositoryTrait;
+use crate::repositories::postgres::logistics_repo::{ShipmentRepositoryTrait, TransportJobRepositoryTrait};
+use crate::services::assignment_service::AssignmentServiceTrait;
+use crate::services::hos_service::HosServiceTrait;
+use crate::services::maintenance_service::MaintenanceServiceTrait;
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 50;
+
+/// Distance to the pickup at which the proximity part of the score halves.
+const HALF_SCORE_KM: f64 = 50.0;
+
+/// Hours left on the driver's clock from which the hours part of the score is full.
+const FULL_SHIFT_HOURS: f64 = 8.0;
+
+/// Share of each factor in the score.
+const CAPACITY_WEIGHT: f64 = 0.3;
+const DISTANCE_WEIGHT: f64 = 0.3;
+const TYPE_WEIGHT: f64 = 0.15;
+const HOURS_WEIGHT: f64 = 0.15;
+const MAINTENANCE_WEIGHT: f64 = 0.1;
+
+/// Shipment types that need a truck or van rather than a car.
+const HEAVY_LOADS: [&str; 5] = ["freight", "pallet", "container", "liquid", "hazmat"];
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait DispatchServiceTrait: Send + Sync {
+    /// Available vehicle and driver pairs that can take the job, best first.
+    async fn suggest(&self, job_id: Uuid, query: DispatchSuggestionQuery) -> Result<Vec<DispatchSuggestion>, AppError>;
+    /// Assigns the chosen pair to the job, with the usual assignment checks.
+    async fn accept(&self, job_id: Uuid, dto: AcceptDispatchDto) -> Result<VehicleAssignment, AppError>;
+}
+
+pub struct DispatchService {
+    job_repo: Arc<dyn TransportJobRepositoryTrait>,
+    shipment_repo: Arc<dyn ShipmentRepositoryTrait>,
+    dispatch_repo: Arc<dyn DispatchRepositoryTrait>,
+    driver_repo: Arc<dyn DriverRepositoryTrait>,
+    hos_service: Arc<dyn HosServiceTrait>,
+    maintenance_service: Arc<dyn MaintenanceServiceTrait>,
+    assignment_service: Arc<dyn AssignmentServiceTrait>,
+}
+
+impl DispatchService {
+    pub fn new(
+        job_repo: Arc<dyn TransportJobRepositoryTrait>,
+        shipment_repo: Arc<dyn ShipmentRepositoryTrait>,
+        dispatch_repo: Arc<dyn DispatchRepositoryTrait>,
+        driver_repo: Arc<dyn DriverRepositoryTrait>,
+        hos_service: Arc<dyn HosServiceTrait>,
+        maintenance_service: Arc<dyn MaintenanceServiceTrait>,
+        assignment_service: Arc<dyn AssignmentServiceTrait>,
+    ) -> Self {
+        Self {
+            job_repo,
+            shipment_repo,
+            dispatch_repo,
+            driver_repo,
+            hos_service,
+            maintenance_service,
+            assignment_service,
+        }
+    }
+
+    async fn open_job(&self, job_id: Uuid) -> Result<TransportJob, AppError> {
+        let job = self.job_repo.find_by_id(job_id).await?
+            .ok_or(AppError::NotFound("Transport job not found".into()))?;
+        if !matches!(job.status, JobStatus::Pending | JobStatus::InProgress) {
+            return Err(AppError::BadRequest(format!("Job is already {:?}", job.status)));
+        }
+        Ok(job)
+    }
+}
+
+/// What the job's shipments add up to.
+#[derive(Debug, Clone, PartialEq)]
+struct Load {
+    weight_kg: f64,
+    /// Each shipment's length, width and height in cm, largest first
+    items: Vec<[f64; 3]>,
+    heavy: bool,
+}
+
+fn load_of(shipments: &[Shipment]) -> Load {
+    Load {
+        weight_kg: shipments.iter().map(|s| s.weight.max(0.0)).sum(),
+        items: shipments.iter().filter_map(|s| shipment_dimensions(&s.dimensions)).collect(),
+        heavy: shipments.iter().any(|s| HEAVY_LOADS.contains(&s.r#type.to_lowercase().as_str())),
+    }
+}
+
+/// Reads `{length, width, height}` (or `{l, w, h}`) in cm. `None` unless all three are set.
+fn shipment_dimensions(dimensions: &Value) -> Option<[f64; 3]> {
+    let side = |long: &str, short: &str| dimensions.get(long).or_else(|| dimensions.get(short)).and_then(Value::as_f64);
+    let mut sides = [side("length", "l")?, side("width", "w")?, side("height", "h")?];
+    sides.sort_by(|a, b| b.total_cmp(a));
+    Some(sides)
+}
+
+/// Vehicle capacity from its specs: `payload_kg`, plus `cargo_length_cm`,
+/// `cargo_width_cm` and `cargo_height_cm` for the cargo space.
+fn payload_kg(specs: Option<&Value>) -> Option<f64> {
+    specs?.get("payload_kg")?.as_f64()
+}
+
+fn cargo_space(specs: Option<&Value>) -> Option<[f64; 3]> {
+    let specs = specs?;
+    let side = |key: &str| specs.get(key).and_then(Value::as_f64);
+    let mut sides = [side("cargo_length_cm")?, side("cargo_width_cm")?, side("cargo_height_cm")?];
+    sides.sort_by(|a, b| b.total_cmp(a));
+    Some(sides)
+}
+
+/// How well the vehicle type suits the load, 0 to 1. `None` if it cannot carry it.
+fn type_fit(vehicle_type: VehicleType, heavy: bool) -> Option<f64> {
+    match (vehicle_type, heavy) {
+        (VehicleType::Truck, true) => Some(1.0),
+        (VehicleType::Van, true) => Some(0.5),
+        (VehicleType::Sedan, true) => None,
+        (VehicleType::Van, false) => Some(1.0),
+        (VehicleType::Sedan, false) => Some(0.7),
+        (VehicleType::Truck, false) => Some(0.6),
+    }
+}
+
+/// The vehicle's part of the score, with the notes that go with it. `None` if the
+/// vehicle cannot take the load or is overdue for service.
+fn score_vehicle(
+    candidate: &VehicleCandidate,
+    load: &Load,
+    maintenance: MaintenanceDueStatus,
+    notes: &mut Vec<String>,
+) -> Option<f64> {
+    if maintenance == MaintenanceDueStatus::Overdue {
+        return None;
+    }
+    let type_score = type_fit(candidate.vehicle_type, load.heavy)?;
+    if type_score < 1.0 {
+        notes.push(format!("{:?} is not the usual vehicle for this load", candidate.vehicle_type));
+    }
+
+    // A vehicle the load fills well beats one that drives around mostly empty
+    let capacity_score = match payload_kg(candidate.specs.as_ref()) {
+        Some(capacity) if load.weight_kg > capacity => return None,
+        Some(capacity) if capacity > 0.0 => 0.5 + 0.5 * load.weight_kg / capacity,
+        _ => {
+            notes.push("No payload recorded for the vehicle".into());
+            0.5
+        }
+    };
+    match cargo_space(candidate.specs.as_ref()) {
+        Some(space) if load.items.iter().any(|item| item.iter().zip(space).any(|(side, room)| *side > room)) => {
+            return None;
+        }
+        None if !load.items.is_empty() => notes.push("No cargo dimensions recorded for the vehicle".into()),
+        _ => {}
+    }
+
+    let distance_score = match candidate.distance_km {
+        Some(km) => 1.0 / (1.0 + km.max(0.0) / HALF_SCORE_KM),
+        None => {
+            notes.push("Distance to pickup unknown".into());
+            0.5
+        }
+    };
+    let maintenance_score = if maintenance == MaintenanceDueStatus::DueSoon {
+        notes.push("Service due soon".into());
+        0.5
+    } else {
+        1.0
+    };
+
+    Some(
+        CAPACITY_WEIGHT * capacity_score
+            + DISTANCE_WEIGHT * distance_score
+            + TYPE_WEIGHT * type_score
+            + MAINTENANCE_WEIGHT * maintenance_score,
+    )
+}
+
+/// The driver's part of the score. `None` once they are out of hours.
+fn score_driver(hours_remaining: Option<f64>, notes: &mut Vec<String>) -> Option<f64> {
+    match hours_remaining {
+        None => Some(HOURS_WEIGHT),
+        Some(hours) if hours <= 0.0 => None,
+        Some(hours) => {
+            if hours < FULL_SHIFT_HOURS {
+                notes.push(format!("Driver has {:.1} hours left", hours));
+            }
+            Some(HOURS_WEIGHT * (hours / FULL_SHIFT_HOURS).min(1.0))
+        }
+    }
+}
+
+fn to_score(raw: f64) -> f64 {
+    (raw * 1000.0).round() / 10.0
+}
+
+#[async_trait]
+impl DispatchServiceTrait for DispatchService {
+    async fn suggest(&self, job_id: Uuid, query: DispatchSuggestionQuery) -> Result<Vec<DispatchSuggestion>, AppError> {
+        let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+        if limit == 0 || limit > MAX_LIMIT {
+            return Err(AppError::BadRequest(format!("limit must be between 1 and {}", MAX_LIMIT)));
+        }
+        self.open_job(job_id).await?;
+
+        let now = Utc::now();
+        let today = now.date_naive();
+        let load = load_of(&self.shipment_repo.find_by_job_id(job_id).await?);
+        let candidates = self.dispatch_repo.find_vehicle_candidates(job_id).await?;
+        let due = self.maintenance_service.get_due(now).await?;
+        let due: HashMap<Uuid, _> = due
+            .overdue
+            .into_iter()
+            .chain(due.due_soon)
+            .map(|d| (d.vehicle_id, (d.status, d.days_remaining)))
+            .collect();
+
+        let mut vehicles = Vec::new();
+        for candidate in candidates {
+            let (maintenance_status, days_remaining) = due
+                .get(&candidate.vehicle_id)
+                .copied()
+                .unwrap_or((MaintenanceDueStatus::Ok, None));
+            let mut notes = Vec::new();
+            if let Some(score) = score_vehicle(&candidate, &load, maintenance_status, &mut notes) {
+                vehicles.push((candidate, maintenance_status, days_remaining, score, notes));
+            }
+        }
+        if vehicles.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut drivers = Vec::new();
+        for driver in self.driver_repo.find_all_with_user().await? {
+            if driver.status != DriverStatus::Available || driver.license_expiry.is_some_and(|expiry| expiry < today) {
+                continue;
+            }
+            let hours_remaining = self.hos_service.hours_remaining(driver.id, now).await?;
+            let mut notes = Vec::new();
+            if let Some(score) = score_driver(hours_remaining, &mut notes) {
+                drivers.push((driver, hours_remaining, score, notes));
+            }
+        }
+
+        let mut suggestions: Vec<DispatchSuggestion> = vehicles
+            .iter()
+            .flat_map(|(vehicle, maintenance_status, days_remaining, vehicle_score, vehicle_notes)| {
+                drivers.iter().map(move |(driver, hours_remaining, driver_score, driver_notes)| DispatchSuggestion {
+                    vehicle_id: vehicle.vehicle_id,
+                    license_plate: vehicle.license_plate.clone(),
+                    vehicle_type: vehicle.vehicle_type,
+                    driver_id: driver.id,
+                    driver_name: driver.name.clone(),
+                    score: to_score(vehicle_score + driver_score),
+                    load_kg: load.weight_kg,
+                    capacity_kg: payload_kg(vehicle.specs.as_ref()),
+                    distance_km: vehicle.distance_km,
+                    position_at: vehicle.position_at,
+                    driver_hours_remaining: *hours_remaining,
+                    maintenance_status: *maintenance_status,
+                    maintenance_days_remaining: *days_remaining,
+                    notes: vehicle_notes.iter().chain(driver_notes).cloned().collect(),
+                })
+            })
+            .collect();
+        suggestions.sort_by(|a, b| {
+            b.score.total_cmp(&a.score).then_with(|| {
+                a.distance_km.unwrap_or(f64::INFINITY).total_cmp(&b.distance_km.unwrap_or(f64::INFINITY))
+            })
+        });
+        suggestions.truncate(limit);
+
+        Ok(suggestions)
+    }
+
+    async fn accept(&self, job_id: Uuid, dto: AcceptDispatchDto) -> Result<VehicleAssignment, AppError> {
+        self.open_job(job_id).await?;
+
+        self.assignment_service.create_assignment(CreateAssignmentDto {
+            vehicle_id: dto.vehicle_id,
+            driver_id: dto.driver_id,
+            start_time: dto.start_time.unwrap_or_else(Utc::now),
+            end_time: dto.end_time,
+            status: Default::default(),
+            job_id: Some(job_id),
+        }).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, NaiveDate};
+    use rust_decimal::Decimal;
+    use serde_json::json;
+    use crate::models::postgres::assignment::AssignmentStatus;
+    use crate::models::postgres::driver::DriverWithUser;
+    use crate::models::postgres::maintenance::{MaintenanceDue, MaintenanceDueReport};
+    use crate::repositories::postgres::dispatch_repo::MockDispatchRepositoryTrait;
+    use crate::repositories::postgres::driver_repo::MockDriverRepositoryTrait;
+    use crate::repositories::postgres::logistics_repo::{MockShipmentRepositoryTrait, MockTransportJobRepositoryTrait};
+    use crate::services::assignment_service::MockAssignmentServiceTrait;
+    use crate::services::hos_service::MockHosServiceTrait;
+    use crate::services::maintenance_service::MockMaintenanceServiceTrait;
+
+    fn job(id: Uuid, status: JobStatus) -> TransportJob {
+        TransportJob {
+            id,
+            customer_id: Uuid::new_v4(),
+            status,
+            agreed_price: Decimal::new(120000, 2),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn shipment(job_id: Uuid, weight: f64, r#type: &str) -> Shipment {
+        Shipment {
+            id: Uuid::new_v4(),
+            job_id,
+            weight,
+            dimensions: json!({"length": 120, "width": 80, "height": 100}),
+            r#type: r#type.into(),
+        }
+    }
+
+    fn candidate(plate: &str, vehicle_type: VehicleType, payload_kg: f64, distance_km: Option<f64>) -> VehicleCandidate {
+        VehicleCandidate {
+            vehicle_id: Uuid::new_v4(),
+            license_plate: plate.into(),
+            vehicle_type,
+            specs: Some(json!({
+                "payload_kg": payload_kg,
+                "cargo_length_cm": 400,
+                "cargo_width_cm": 200,
+                "cargo_height_cm": 200,
+            })),
+            distance_km,
+            position_at: distance_km.map(|_| Utc::now()),
+        }
+    }
+
+    fn driver(name: &str, license_expiry: Option<NaiveDate>) -> DriverWithUser {
+        DriverWithUser {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            license_number: "D1234567".into(),
+            status: DriverStatus::Available,
+            email: format!("{}@example.com", name.to_lowercase()),
+            name: Some(name.into()),
+            phone: None,
+            wage_rate: None,
+            license_expiry,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn due(vehicle: &VehicleCandidate, status: MaintenanceDueStatus) -> MaintenanceDue {
+        MaintenanceDue {
+            vehicle_id: vehicle.vehicle_id,
+            license_plate: vehicle.license_plate.clone(),
+            vehicle_type: vehicle.vehicle_type,
+            current_mileage: 120_000,
+            last_service_date: None,
+            last_service_mileage: None,
+            next_due_date: None,
+            next_due_km: None,
+            days_remaining: Some(if status == MaintenanceDueStatus::Overdue { -3 } else { 5 }),
+            km_remaining: None,
+            status,
+        }
+    }
+
+    fn job_repo(job_id: Uuid, status: JobStatus) -> MockTransportJobRepositoryTrait {
+        let mut job_repo = MockTransportJobRepositoryTrait::new();
+        job_repo.expect_find_by_id().returning(move |id| Ok((id == job_id).then(|| job(id, status))));
+        job_repo
+    }
+
+    #[test]
+    fn test_score_vehicle_rejects_loads_that_do_not_fit() {
+        let job_id = Uuid::new_v4();
+        let load = load_of(&[shipment(job_id, 900.0, "Pallet"), shipment(job_id, 400.0, "Package")]);
+        assert_eq!(load.weight_kg, 1300.0);
+        assert!(load.heavy);
+        let mut notes = Vec::new();
+
+        let truck = candidate("TRK-1", VehicleType::Truck, 2000.0, Some(10.0));
+        assert!(score_vehicle(&truck, &load, MaintenanceDueStatus::Ok, &mut notes).is_some());
+
+        let small = candidate("VAN-1", VehicleType::Van, 1000.0, Some(10.0));
+        assert!(score_vehicle(&small, &load, MaintenanceDueStatus::Ok, &mut notes).is_none());
+
+        let sedan = candidate("CAR-1", VehicleType::Sedan, 5000.0, Some(10.0));
+        assert!(score_vehicle(&sedan, &load, MaintenanceDueStatus::Ok, &mut notes).is_none());
+
+        assert!(score_vehicle(&truck, &load, MaintenanceDueStatus::Overdue, &mut notes).is_none());
+
+        // A pallet taller than the cargo space does not fit whatever the payload
+        let mut low = truck.clone();
+        low.specs = Some(json!({"payload_kg": 2000, "cargo_length_cm": 400, "cargo_width_cm": 200, "cargo_height_cm": 90}));
+        let tall = load_of(&[Shipment { dimensions: json!({"l": 300, "w": 250, "h": 100}), ..shipment(job_id, 10.0, "Pallet") }]);
+        assert!(score_vehicle(&low, &tall, MaintenanceDueStatus::Ok, &mut notes).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_suggest_ranks_pairs_and_skips_unfit_drivers() {
+        let job_id = Uuid::new_v4();
+        let near = candidate("TRK-NEAR", VehicleType::Truck, 2000.0, Some(5.0));
+        let far = candidate("TRK-FAR", VehicleType::Truck, 2000.0, Some(400.0));
+        let overdue = candidate("TRK-OVERDUE", VehicleType::Truck, 2000.0, Some(1.0));
+        let rested = driver("Rested", None);
+        let tired = driver("Tired", None);
+        let out_of_hours = driver("Out", None);
+        let expired = driver("Expired", Some(NaiveDate::from_ymd_opt(2020, 1, 1).unwrap()));
+        let hours: HashMap<Uuid, f64> =
+            HashMap::from([(rested.id, 11.0), (tired.id, 2.0), (out_of_hours.id, 0.0), (expired.id, 11.0)]);
+
+        let mut shipment_repo = MockShipmentRepositoryTrait::new();
+        shipment_repo.expect_find_by_job_id().returning(|job_id| Ok(vec![shipment(job_id, 1500.0, "Freight")]));
+        let mut dispatch_repo = MockDispatchRepositoryTrait::new();
+        let candidates = vec![far.clone(), overdue.clone(), near.clone()];
+        dispatch_repo.expect_find_vehicle_candidates().returning(move |_| Ok(candidates.clone()));
+        let mut maintenance_service = MockMaintenanceServiceTrait::new();
+        let report = MaintenanceDueReport {
+            overdue: vec![due(&overdue, MaintenanceDueStatus::Overdue)],
+            due_soon: vec![due(&far, MaintenanceDueStatus::DueSoon)],
+        };
+        maintenance_service.expect_get_due().returning(move |_| Ok(report.clone()));
+        let mut driver_repo = MockDriverRepositoryTrait::new();
+        let drivers = vec![tired.clone(), out_of_hours, expired, rested.clone()];
+        driver_repo.expect_find_all_with_user().returning(move || Ok(drivers.clone()));
+        let mut hos_service = MockHosServiceTrait::new();
+        hos_service.expect_hours_remaining().returning(move |id, _| Ok(hours.get(&id).copied()));
+
+        let service = DispatchService::new(
+            Arc::new(job_repo(job_id, JobStatus::Pending)),
+            Arc::new(shipment_repo),
+            Arc::new(dispatch_repo),
+            Arc::new(driver_repo),
+            Arc::new(hos_service),
+            Arc::new(maintenance_service),
+            Arc::new(MockAssignmentServiceTrait::new()),
+        );
+        let suggestions = service.suggest(job_id, DispatchSuggestionQuery::default()).await.unwrap();
+
+        let pairs: Vec<_> = suggestions.iter().map(|s| (s.license_plate.as_str(), s.driver_id)).collect();
+        assert_eq!(pairs, vec![
+            ("TRK-NEAR", rested.id),
+            ("TRK-NEAR", tired.id),
+            ("TRK-FAR", rested.id),
+            ("TRK-FAR", tired.id),
+        ]);
+        assert!(suggestions.windows(2).all(|w| w[0].score >= w[1].score));
+        assert_eq!(suggestions[0].load_kg, 1500.0);
+        assert_eq!(suggestions[2].maintenance_status, MaintenanceDueStatus::DueSoon);
+        assert!(suggestions[1].notes.iter().any(|n| n.contains("2.0 hours")));
+
+        let limited = service.suggest(job_id, DispatchSuggestionQuery { limit: Some(1) }).await.unwrap();
+        assert_eq!(limited.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_accept_links_assignment_to_job() {
+        let job_id = Uuid::new_v4();
+        let (vehicle_id, driver_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let start: DateTime<Utc> = Utc::now() + chrono::Duration::hours(2);
+
+        let mut assignment_service = MockAssignmentServiceTrait::new();
+        assignment_service
+            .expect_create_assignment()
+            .withf(move |dto| dto.job_id == Some(job_id) && dto.vehicle_id == vehicle_id && dto.start_time == start)
+            .times(1)
+            .returning(|dto| Ok(VehicleAssignment {
+                id: Uuid::new_v4(),
+                vehicle_id: dto.vehicle_id,
+                driver_id: dto.driver_id,
+                start_time: dto.start_time,
+                end_time: dto.end_time,
+                status: AssignmentStatus::Scheduled,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                started_at: None,
+                completed_at: None,
+                cancelled_at: None,
+                cancel_reason: None,
+            }));
+
+        let service = DispatchService::new(
+            Arc::new(job_repo(job_id, JobStatus::Pending)),
+            Arc::new(MockShipmentRepositoryTrait::new()),
+            Arc::new(MockDispatchRepositoryTrait::new()),
+            Arc::new(MockDriverRepositoryTrait::new()),
+            Arc::new(MockHosServiceTrait::new()),
+            Arc::new(MockMaintenanceServiceTrait::new()),
+            Arc::new(assignment_service),
+        );
+        let dto = AcceptDispatchDto { vehicle_id, driver_id, start_time: Some(start), end_time: None };
+        let assignment = service.accept(job_id, dto.clone()).await.unwrap();
+        assert_eq!(assignment.status, AssignmentStatus::Scheduled);
+
+        let result = service.accept(Uuid::new_v4(), dto).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+}
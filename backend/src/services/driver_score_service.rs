@@ -0,0 +1,416 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration, NaiveTime, Timelike, Utc};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::postgres::assignment::VehicleAssignment;
+use crate::models::postgres::driver_score::{
+    DriverScore, DriverScoreQuery, LeaderboardEntry, LeaderboardQuery, TripScore,
+};
+use crate::models::postgres::telemetry::VehicleTelemetry;
+use crate::repositories::postgres::driver_repo::DriverRepositoryTrait;
+use crate::repositories::postgres::driver_score_repo::DriverScoreRepositoryTrait;
+use crate::repositories::postgres::settings_repo::SettingsRepositoryTrait;
+use crate::repositories::postgres::telemetry_repo::TelemetryRepositoryTrait;
+use crate::services::anomaly_service::{is_idle, IDLE_SPEED_KMH};
+
+const SCORING_BATCH: i64 = 100;
+
+/// Cap on readings loaded for a single trip.
+const MAX_TRIP_READINGS: i64 = 50_000;
+
+/// Readings further apart than this are a gap in coverage, not time spent driving or idling.
+const MAX_SEGMENT_GAP_SECS: i64 = 5 * 60;
+
+/// Speed changes are only judged between readings this close together.
+const HARSH_EVENT_MAX_GAP_SECS: i64 = 10;
+
+/// About 0.28 g.
+const HARSH_ACCELERATION_KMH_PER_SEC: f64 = 10.0;
+
+/// About 0.37 g.
+const HARSH_BRAKING_KMH_PER_SEC: f64 = 13.0;
+
+/// Local hours counted as night driving: from 22:00 until 05:00.
+const NIGHT_START_HOUR: u32 = 22;
+const NIGHT_END_HOUR: u32 = 5;
+
+/// Points lost per event, per hour of driving.
+const SPEEDING_PENALTY: f64 = 5.0;
+const HARSH_ACCELERATION_PENALTY: f64 = 3.0;
+const HARSH_BRAKING_PENALTY: f64 = 4.0;
+
+/// Points lost if the whole trip were spent idling, or driving at night.
+const IDLE_PENALTY: f64 = 20.0;
+const NIGHT_PENALTY: f64 = 10.0;
+
+const DEFAULT_WEEKS: i64 = 4;
+const MAX_WEEKS: i64 = 52;
+const DEFAULT_LEADERBOARD_SIZE: i64 = 20;
+const RECENT_TRIPS: i64 = 20;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait DriverScoreServiceTrait: Send + Sync {
+    /// Scores assignments completed before `completed_before` that have not been scored
+    /// yet. Returns how many were scored.
+    async fn score_completed_trips(&self, completed_before: DateTime<Utc>) -> Result<usize, AppError>;
+    async fn get_driver_score(&self, driver_id: Uuid, query: DriverScoreQuery) -> Result<DriverScore, AppError>;
+    async fn get_leaderboard(&self, query: LeaderboardQuery) -> Result<Vec<LeaderboardEntry>, AppError>;
+}
+
+pub struct DriverScoreService {
+    score_repo: Arc<dyn DriverScoreRepositoryTrait>,
+    telemetry_repo: Arc<dyn TelemetryRepositoryTrait>,
+    driver_repo: Arc<dyn DriverRepositoryTrait>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait>,
+}
+
+impl DriverScoreService {
+    pub fn new(
+        score_repo: Arc<dyn DriverScoreRepositoryTrait>,
+        telemetry_repo: Arc<dyn TelemetryRepositoryTrait>,
+        driver_repo: Arc<dyn DriverRepositoryTrait>,
+        settings_repo: Arc<dyn SettingsRepositoryTrait>,
+    ) -> Self {
+        Self { score_repo, telemetry_repo, driver_repo, settings_repo }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct TripMetrics {
+    readings: i32,
+    driving_minutes: f64,
+    speeding_events: i32,
+    harsh_acceleration_events: i32,
+    harsh_braking_events: i32,
+    idle_minutes: f64,
+    night_minutes: f64,
+}
+
+fn is_night(time: DateTime<Utc>, utc_offset_secs: i32) -> bool {
+    let hour = (time + Duration::seconds(utc_offset_secs.into())).hour();
+    !(NIGHT_END_HOUR..NIGHT_START_HOUR).contains(&hour)
+}
+
+/// Measures a trip from its readings in time order. Time between two readings is
+/// attributed to the state at the first one. A `speed_limit_kmh` of 0 counts no speeding.
+fn trip_metrics(readings: &[VehicleTelemetry], speed_limit_kmh: f64, utc_offset_secs: i32) -> TripMetrics {
+    let speeding = |reading: &VehicleTelemetry| speed_limit_kmh > 0.0 && reading.speed > speed_limit_kmh;
+    let mut metrics = TripMetrics {
+        readings: readings.len() as i32,
+        speeding_events: readings.first().is_some_and(speeding) as i32,
+        ..TripMetrics::default()
+    };
+
+    for pair in readings.windows(2) {
+        let (previous, reading) = (&pair[0], &pair[1]);
+        let secs = (reading.time - previous.time).num_seconds();
+        if speeding(reading) && !speeding(previous) {
+            metrics.speeding_events += 1;
+        }
+        if secs <= 0 || secs > MAX_SEGMENT_GAP_SECS {
+            continue;
+        }
+
+        if secs <= HARSH_EVENT_MAX_GAP_SECS {
+            let rate = (reading.speed - previous.speed) / secs as f64;
+            if rate >= HARSH_ACCELERATION_KMH_PER_SEC {
+                metrics.harsh_acceleration_events += 1;
+            } else if rate <= -HARSH_BRAKING_KMH_PER_SEC {
+                metrics.harsh_braking_events += 1;
+            }
+        }
+
+        let minutes = secs as f64 / 60.0;
+        if previous.speed >= IDLE_SPEED_KMH {
+            metrics.driving_minutes += minutes;
+            if is_night(previous.time, utc_offset_secs) {
+                metrics.night_minutes += minutes;
+            }
+        } else if is_idle(previous) && is_idle(reading) {
+            metrics.idle_minutes += minutes;
+        }
+    }
+    metrics
+}
+
+/// Starts at 100 and deducts for events per hour driven and for the share of the trip
+/// spent idling or driving at night. `None` without enough telemetry to judge.
+fn score(metrics: &TripMetrics) -> Option<f64> {
+    let active_minutes = metrics.driving_minutes + metrics.idle_minutes;
+    if metrics.readings < 2 || active_minutes <= 0.0 {
+        return None;
+    }
+    let hours = (metrics.driving_minutes / 60.0).max(1.0);
+    let events = SPEEDING_PENALTY * f64::from(metrics.speeding_events)
+        + HARSH_ACCELERATION_PENALTY * f64::from(metrics.harsh_acceleration_events)
+        + HARSH_BRAKING_PENALTY * f64::from(metrics.harsh_braking_events);
+    let idle_share = metrics.idle_minutes / active_minutes;
+    let night_share = if metrics.driving_minutes > 0.0 { metrics.night_minutes / metrics.driving_minutes } else { 0.0 };
+
+    let score = 100.0 - events / hours - IDLE_PENALTY * idle_share - NIGHT_PENALTY * night_share;
+    Some((score.clamp(0.0, 100.0) * 10.0).round() / 10.0)
+}
+
+fn round_minutes(minutes: f64) -> f64 {
+    (minutes * 10.0).round() / 10.0
+}
+
+/// Start of the week `weeks - 1` weeks before the current one.
+fn weeks_since(now: DateTime<Utc>, weeks: Option<i64>) -> Result<DateTime<Utc>, AppError> {
+    let weeks = weeks.unwrap_or(DEFAULT_WEEKS);
+    if !(1..=MAX_WEEKS).contains(&weeks) {
+        return Err(AppError::BadRequest(format!("weeks must be between 1 and {}", MAX_WEEKS)));
+    }
+    let today = now.date_naive();
+    let monday = today - Duration::days(today.weekday().num_days_from_monday().into());
+    Ok((monday - Duration::weeks(weeks - 1)).and_time(NaiveTime::MIN).and_utc())
+}
+
+impl DriverScoreService {
+    async fn score_trip(&self, trip: VehicleAssignment, speed_limit_kmh: f64, time_zone: &str) -> Result<(), AppError> {
+        // The trip ends at its planned end or when it was completed, whichever came first
+        let started_at = trip.effective_start();
+        let ended_at = trip.effective_end().unwrap_or(trip.updated_at).max(started_at);
+        let readings = self.telemetry_repo
+            .find_by_vehicle_in_range(trip.vehicle_id, started_at, ended_at, MAX_TRIP_READINGS)
+            .await?;
+        let utc_offset = self.score_repo.utc_offset(time_zone.to_string(), started_at).await?.unwrap_or(0);
+        let metrics = trip_metrics(&readings, speed_limit_kmh, utc_offset);
+
+        self.score_repo.save_trip_score(TripScore {
+            assignment_id: trip.id,
+            driver_id: trip.driver_id,
+            vehicle_id: trip.vehicle_id,
+            started_at,
+            ended_at,
+            readings: metrics.readings,
+            driving_minutes: round_minutes(metrics.driving_minutes),
+            speeding_events: metrics.speeding_events,
+            harsh_acceleration_events: metrics.harsh_acceleration_events,
+            harsh_braking_events: metrics.harsh_braking_events,
+            idle_minutes: round_minutes(metrics.idle_minutes),
+            night_minutes: round_minutes(metrics.night_minutes),
+            score: score(&metrics),
+            scored_at: Utc::now(),
+        }).await
+    }
+}
+
+#[async_trait]
+impl DriverScoreServiceTrait for DriverScoreService {
+    async fn score_completed_trips(&self, completed_before: DateTime<Utc>) -> Result<usize, AppError> {
+        let settings = self.settings_repo.get().await?;
+
+        let mut scored = 0;
+        loop {
+            let trips = self.score_repo.find_unscored_trips(completed_before, SCORING_BATCH).await?;
+            let batch = trips.len();
+            for trip in trips {
+                self.score_trip(trip, settings.speed_limit_kmh, &settings.time_zone).await?;
+            }
+            scored += batch;
+            if (batch as i64) < SCORING_BATCH {
+                return Ok(scored);
+            }
+        }
+    }
+
+    async fn get_driver_score(&self, driver_id: Uuid, query: DriverScoreQuery) -> Result<DriverScore, AppError> {
+        self.driver_repo.find_by_id(driver_id).await?
+            .ok_or_else(|| AppError::NotFound("Driver not found".into()))?;
+
+        let since = weeks_since(Utc::now(), query.weeks)?;
+        let weekly = self.score_repo.find_weekly_scores(driver_id, since).await?;
+        let recent_trips = self.score_repo.find_trip_scores(driver_id, since, RECENT_TRIPS).await?;
+
+        let (weighted, weight) = weekly
+            .iter()
+            .filter_map(|week| Some((week.score?, week.driving_minutes.max(1.0))))
+            .fold((0.0, 0.0), |(sum, total), (score, weight)| (sum + score * weight, total + weight));
+
+        Ok(DriverScore {
+            driver_id,
+            since: since.date_naive(),
+            score: (weight > 0.0).then(|| (weighted / weight * 10.0).round() / 10.0),
+            trips: weekly.iter().map(|week| week.trips).sum(),
+            weekly,
+            recent_trips,
+        })
+    }
+
+    async fn get_leaderboard(&self, query: LeaderboardQuery) -> Result<Vec<LeaderboardEntry>, AppError> {
+        let limit = query.limit.unwrap_or(DEFAULT_LEADERBOARD_SIZE);
+        if limit < 1 {
+            return Err(AppError::BadRequest("limit must be positive".into()));
+        }
+        let since = weeks_since(Utc::now(), query.weeks)?;
+        self.score_repo.find_leaderboard(since, limit).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+    use crate::models::postgres::assignment::AssignmentStatus;
+    use crate::models::postgres::driver_score::WeeklyScore;
+    use crate::models::postgres::driver::{Driver, DriverStatus};
+    use crate::models::postgres::settings::AppSettings;
+    use crate::repositories::postgres::driver_repo::MockDriverRepositoryTrait;
+    use crate::repositories::postgres::driver_score_repo::MockDriverScoreRepositoryTrait;
+    use crate::repositories::postgres::settings_repo::MockSettingsRepositoryTrait;
+    use crate::repositories::postgres::telemetry_repo::MockTelemetryRepositoryTrait;
+
+    fn reading(time: DateTime<Utc>, speed: f64) -> VehicleTelemetry {
+        VehicleTelemetry {
+            time,
+            vehicle_id: Uuid::nil(),
+            location: json!({ "type": "Point", "coordinates": [0.0, 0.0] }),
+            speed,
+            fuel_level: 50.0,
+            engine_status: json!({ "ignition": true }),
+        }
+    }
+
+    fn at(hour: u32, minute: u32, second: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 14, hour, minute, second).unwrap()
+    }
+
+    #[test]
+    fn test_trip_metrics_counts_events() {
+        let readings = vec![
+            reading(at(12, 0, 0), 0.0),
+            reading(at(12, 1, 0), 0.0),  // a minute idling
+            reading(at(12, 1, 5), 60.0), // +12 km/h per second
+            reading(at(12, 2, 0), 120.0),
+            reading(at(12, 3, 0), 125.0), // still the same speeding stretch
+            reading(at(12, 3, 5), 50.0), // -15 km/h per second
+            reading(at(12, 4, 0), 90.0),
+            reading(at(12, 5, 0), 115.0), // second stretch over the limit
+        ];
+        let metrics = trip_metrics(&readings, 110.0, 0);
+
+        assert_eq!(metrics.readings, 8);
+        assert_eq!(metrics.speeding_events, 2);
+        assert_eq!(metrics.harsh_acceleration_events, 1);
+        assert_eq!(metrics.harsh_braking_events, 1);
+        assert_eq!(round_minutes(metrics.idle_minutes), 1.0);
+        assert_eq!(round_minutes(metrics.driving_minutes), 3.9);
+        assert_eq!(metrics.night_minutes, 0.0);
+
+        // 2 speeding, 1 acceleration, 1 braking within the first hour, plus idling
+        let expected = 100.0 - (2.0 * 5.0 + 3.0 + 4.0) - 20.0 * (1.0 / (1.0 + 3.9166666));
+        assert!((score(&metrics).unwrap() - expected).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_night_driving_uses_company_time_zone() {
+        let readings = vec![reading(at(19, 0, 0), 50.0), reading(at(19, 1, 0), 50.0)];
+        assert_eq!(trip_metrics(&readings, 0.0, 0).night_minutes, 0.0);
+        // 19:00 UTC is 22:00 at UTC+3
+        assert_eq!(trip_metrics(&readings, 0.0, 3 * 3600).night_minutes, 1.0);
+    }
+
+    #[test]
+    fn test_trip_without_telemetry_has_no_score() {
+        assert_eq!(score(&trip_metrics(&[], 110.0, 0)), None);
+        assert_eq!(score(&trip_metrics(&[reading(at(12, 0, 0), 40.0)], 110.0, 0)), None);
+    }
+
+    #[tokio::test]
+    async fn test_score_completed_trips_stores_scores() {
+        let trip = VehicleAssignment {
+            id: Uuid::new_v4(),
+            vehicle_id: Uuid::new_v4(),
+            driver_id: Uuid::new_v4(),
+            start_time: at(12, 0, 0),
+            end_time: Some(at(14, 0, 0)),
+            status: AssignmentStatus::Completed,
+            created_at: at(8, 0, 0),
+            updated_at: at(13, 0, 0),
+            started_at: Some(at(12, 0, 0)),
+            // Closed early
+            completed_at: Some(at(13, 0, 0)),
+            cancelled_at: None,
+            cancel_reason: None,
+        };
+        let trip_id = trip.id;
+
+        let mut score_repo = MockDriverScoreRepositoryTrait::new();
+        score_repo.expect_find_unscored_trips().times(1).returning(move |_, _| Ok(vec![trip.clone()]));
+        score_repo.expect_utc_offset().returning(|_, _| Ok(None));
+        score_repo
+            .expect_save_trip_score()
+            .withf(move |score| score.assignment_id == trip_id && score.ended_at == at(13, 0, 0) && score.score == Some(100.0))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let mut telemetry_repo = MockTelemetryRepositoryTrait::new();
+        telemetry_repo
+            .expect_find_by_vehicle_in_range()
+            .withf(|_, from, to, _| *from == at(12, 0, 0) && *to == at(13, 0, 0))
+            .returning(|_, _, _, _| Ok((0..30).map(|i| reading(at(12, i, 0), 50.0)).collect()));
+
+        let mut settings_repo = MockSettingsRepositoryTrait::new();
+        settings_repo.expect_get().returning(|| Ok(AppSettings::default()));
+
+        let service = DriverScoreService::new(
+            Arc::new(score_repo),
+            Arc::new(telemetry_repo),
+            Arc::new(MockDriverRepositoryTrait::new()),
+            Arc::new(settings_repo),
+        );
+        assert_eq!(service.score_completed_trips(at(16, 0, 0)).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_driver_score_weights_weeks_by_driving_time() {
+        let week = |score: Option<f64>, driving_minutes: f64| WeeklyScore {
+            week_start: at(0, 0, 0).date_naive(),
+            trips: 2,
+            driving_minutes,
+            speeding_events: 0,
+            harsh_acceleration_events: 0,
+            harsh_braking_events: 0,
+            idle_minutes: 0.0,
+            night_minutes: 0.0,
+            score,
+        };
+
+        let mut driver_repo = MockDriverRepositoryTrait::new();
+        driver_repo.expect_find_by_id().returning(|id| Ok(Some(Driver {
+            id,
+            user_id: Uuid::new_v4(),
+            license_number: "D1234567".into(),
+            status: DriverStatus::Available,
+            phone: None,
+            wage_rate: None,
+            license_expiry: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        })));
+        let mut score_repo = MockDriverScoreRepositoryTrait::new();
+        score_repo
+            .expect_find_weekly_scores()
+            .returning(move |_, _| Ok(vec![week(Some(90.0), 300.0), week(Some(60.0), 100.0), week(None, 0.0)]));
+        score_repo.expect_find_trip_scores().returning(|_, _, _| Ok(Vec::new()));
+
+        let service = DriverScoreService::new(
+            Arc::new(score_repo),
+            Arc::new(MockTelemetryRepositoryTrait::new()),
+            Arc::new(driver_repo),
+            Arc::new(MockSettingsRepositoryTrait::new()),
+        );
+        let score = service.get_driver_score(Uuid::new_v4(), DriverScoreQuery::default()).await.unwrap();
+        assert_eq!(score.score, Some(82.5));
+        assert_eq!(score.trips, 6);
+
+        let result = service.get_driver_score(Uuid::new_v4(), DriverScoreQuery { weeks: Some(0) }).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+}
@@ -79,6 +79,9 @@ mod tests {
             user_id,
             license_number: "LIC-12345".to_string(),
             status: DriverStatus::Available,
+            phone: None,
+            wage_rate: None,
+            license_expiry: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
             deleted_at: None,
@@ -102,18 +105,22 @@ mod tests {
     async fn test_get_driver_found() {
         let mut mock_repo = MockDriverRepositoryTrait::new();
         let driver_id = Uuid::new_v4();
-        let driver = Driver {
+        let driver = DriverWithUser {
             id: driver_id,
             user_id: Uuid::new_v4(),
             license_number: "LIC-12345".to_string(),
             status: DriverStatus::Available,
+            email: "driver@example.com".to_string(),
+            name: None,
+            phone: None,
+            wage_rate: None,
+            license_expiry: None,
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
-            deleted_at: None,
         };
 
         mock_repo
-            .expect_find_by_id()
+            .expect_find_by_id_with_user()
             .with(eq(driver_id))
             .times(1)
             .return_once(move |_| Ok(Some(driver)));
@@ -131,7 +138,7 @@ mod tests {
         let driver_id = Uuid::new_v4();
 
         mock_repo
-            .expect_find_by_id()
+            .expect_find_by_id_with_user()
             .with(eq(driver_id))
             .times(1)
             .return_once(move |_| Ok(None));
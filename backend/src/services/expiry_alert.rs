@@ -0,0 +1,58 @@
+use chrono::NaiveDate;
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::maintenance::{Alert, AlertSeverity, CreateAlertDto};
+use crate::repositories::postgres::maintenance_repo::AlertRepositoryTrait;
+
+/// Something that lapses on a date and is tracked by an "expiring" and an "expired" alert,
+/// such as a driver's license or a vehicle document.
+pub struct Expiry {
+    /// Entity the alerts are raised on
+    pub entity_id: Uuid,
+    /// Names the item in alert messages, e.g. "License D-123"
+    pub label: String,
+    pub expiry_date: NaiveDate,
+    pub expiring_alert: String,
+    pub expired_alert: String,
+    pub expired_severity: AlertSeverity,
+}
+
+/// Raises the expired alert once `expiry_date` has passed and the expiring alert from
+/// `warn_until` on, resolving the other one. Outside the window, after a renewal or a
+/// settings change, both are resolved. With `notify` off nothing new is raised, but alerts
+/// that no longer apply are still resolved. Returns the alert raised, if it was not open yet.
+pub async fn sync_expiry_alert(
+    alert_repo: &dyn AlertRepositoryTrait,
+    expiry: Expiry,
+    today: NaiveDate,
+    warn_until: NaiveDate,
+    notify: bool,
+) -> Result<Option<Alert>, AppError> {
+    let Expiry { entity_id, label, expiry_date, expiring_alert, expired_alert, expired_severity } = expiry;
+
+    let (alert_type, severity, message, stale) = if expiry_date < today {
+        (expired_alert, expired_severity, format!("{} expired on {}", label, expiry_date), expiring_alert)
+    } else if expiry_date <= warn_until {
+        (
+            expiring_alert,
+            AlertSeverity::Medium,
+            format!("{} expires on {} ({} days)", label, expiry_date, (expiry_date - today).num_days()),
+            expired_alert,
+        )
+    } else {
+        alert_repo.resolve_open(entity_id, vec![expiring_alert, expired_alert]).await?;
+        return Ok(None);
+    };
+
+    // Expired supersedes expiring, and a renewal into the window supersedes expired
+    alert_repo.resolve_open(entity_id, vec![stale]).await?;
+    if !notify {
+        return Ok(None);
+    }
+    alert_repo.create_unless_open(CreateAlertDto {
+        entity_id,
+        r#type: alert_type,
+        severity,
+        message: Some(message),
+    }).await
+}
@@ -0,0 +1,94 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use crate::error::AppError;
+
+pub const IMAGE_TYPES: &[&str] = &["image/png", "image/jpeg", "image/webp"];
+pub const DOCUMENT_TYPES: &[&str] = &["application/pdf", "image/png", "image/jpeg", "image/webp"];
+
+/// Content type told by a file's leading bytes, for the formats we accept.
+pub fn sniff_content_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else if data.starts_with(b"%PDF-") {
+        Some("application/pdf")
+    } else {
+        None
+    }
+}
+
+fn format_name(content_type: &str) -> &str {
+    match content_type {
+        "image/png" => "PNG",
+        "image/jpeg" => "JPEG",
+        "image/webp" => "WebP",
+        "application/pdf" => "PDF",
+        other => other,
+    }
+}
+
+/// Decodes a base64 upload, with or without a `data:` URL prefix, and returns it with its
+/// sniffed content type. `field` names the input in error messages.
+pub fn decode_base64_file(
+    field: &str,
+    encoded: &str,
+    max_bytes: usize,
+    allowed: &[&str],
+) -> Result<(Vec<u8>, &'static str), AppError> {
+    let encoded = match encoded.split_once(";base64,") {
+        Some((prefix, data)) if prefix.starts_with("data:") => data,
+        _ => encoded,
+    };
+    let data = STANDARD
+        .decode(encoded.trim())
+        .map_err(|_| AppError::BadRequest(format!("{} is not valid base64", field)))?;
+    if data.len() > max_bytes {
+        return Err(AppError::BadRequest(format!(
+            "{} is larger than {} MB",
+            field,
+            max_bytes / (1024 * 1024)
+        )));
+    }
+
+    match sniff_content_type(&data) {
+        Some(content_type) if allowed.contains(&content_type) => Ok((data, content_type)),
+        _ => {
+            let names: Vec<&str> = allowed.iter().map(|t| format_name(t)).collect();
+            let expected = match names.split_last() {
+                Some((last, [])) => last.to_string(),
+                Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+                None => String::new(),
+            };
+            Err(AppError::BadRequest(format!("{} must be a {} file", field, expected)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n0000";
+    const JPEG: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0, 0, 0];
+
+    #[test]
+    fn test_decode_base64_file() {
+        assert_eq!(decode_base64_file("Signature", &STANDARD.encode(PNG), 1024, IMAGE_TYPES).unwrap().1, "image/png");
+        let data_url = format!("data:image/jpeg;base64,{}", STANDARD.encode(JPEG));
+        assert_eq!(decode_base64_file("Photo 1", &data_url, 1024, IMAGE_TYPES).unwrap().1, "image/jpeg");
+        assert_eq!(decode_base64_file("File", &STANDARD.encode(b"%PDF-1.7"), 1024, DOCUMENT_TYPES).unwrap().1, "application/pdf");
+
+        assert!(matches!(decode_base64_file("Photo 1", "not base64!", 1024, IMAGE_TYPES), Err(AppError::BadRequest(_))));
+        assert!(matches!(
+            decode_base64_file("Photo 1", &STANDARD.encode(b"%PDF-1.7"), 1024, IMAGE_TYPES),
+            Err(AppError::BadRequest(msg)) if msg == "Photo 1 must be a PNG, JPEG or WebP file"
+        ));
+        assert!(matches!(
+            decode_base64_file("Photo 1", &STANDARD.encode(PNG), 4, IMAGE_TYPES),
+            Err(AppError::BadRequest(msg)) if msg.contains("larger than")
+        ));
+    }
+}
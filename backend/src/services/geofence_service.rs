@@ -0,0 +1,270 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::postgres::geofence::{Geofence, CreateGeofenceDto, GeofenceKind};
+use crate::models::postgres::maintenance::{Alert, AlertSeverity, CreateAlertDto};
+use crate::repositories::postgres::geofence_repo::GeofenceRepositoryTrait;
+use crate::repositories::postgres::maintenance_repo::AlertRepositoryTrait;
+
+pub const GEOFENCE_ENTRY_ALERT: &str = "Geofence Entry";
+pub const GEOFENCE_EXIT_ALERT: &str = "Geofence Exit";
+
+const MAX_CIRCLE_RADIUS_M: f64 = 100_000.0;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait GeofenceServiceTrait: Send + Sync {
+    async fn create_geofence(&self, dto: CreateGeofenceDto) -> Result<Geofence, AppError>;
+    async fn list_geofences(&self) -> Result<Vec<Geofence>, AppError>;
+    async fn get_geofence(&self, id: Uuid) -> Result<Geofence, AppError>;
+    async fn update_geofence(&self, id: Uuid, dto: CreateGeofenceDto) -> Result<Geofence, AppError>;
+    async fn delete_geofence(&self, id: Uuid) -> Result<(), AppError>;
+    /// Checks a vehicle's reported `(time, location)` points against its geofences and
+    /// raises an alert for every entry or exit that the geofence is configured to report.
+    async fn evaluate_positions(&self, vehicle_id: Uuid, points: Vec<(DateTime<Utc>, Value)>) -> Result<Vec<Alert>, AppError>;
+}
+
+pub struct GeofenceService {
+    geofence_repo: Arc<dyn GeofenceRepositoryTrait>,
+    alert_repo: Arc<dyn AlertRepositoryTrait>,
+}
+
+impl GeofenceService {
+    pub fn new(
+        geofence_repo: Arc<dyn GeofenceRepositoryTrait>,
+        alert_repo: Arc<dyn AlertRepositoryTrait>,
+    ) -> Self {
+        Self { geofence_repo, alert_repo }
+    }
+}
+
+fn geojson_type(value: &Option<Value>) -> Option<&str> {
+    value.as_ref()?.get("type")?.as_str()
+}
+
+/// Checks the shape matches the kind and drops fields that do not apply to it.
+fn normalize(mut dto: CreateGeofenceDto) -> Result<CreateGeofenceDto, AppError> {
+    if dto.name.trim().is_empty() {
+        return Err(AppError::BadRequest("Geofence name is required".into()));
+    }
+    if dto.vehicle_id.is_some() && dto.vehicle_type.is_some() {
+        return Err(AppError::BadRequest("Set either vehicle_id or vehicle_type, not both".into()));
+    }
+
+    match dto.kind {
+        GeofenceKind::Polygon => {
+            if geojson_type(&dto.area) != Some("Polygon") {
+                return Err(AppError::BadRequest("POLYGON geofences need a GeoJSON Polygon area".into()));
+            }
+            dto.center = None;
+            dto.radius_m = None;
+        }
+        GeofenceKind::Circle => {
+            if geojson_type(&dto.center) != Some("Point") {
+                return Err(AppError::BadRequest("CIRCLE geofences need a GeoJSON Point center".into()));
+            }
+            match dto.radius_m {
+                Some(radius) if radius > 0.0 && radius <= MAX_CIRCLE_RADIUS_M => {}
+                _ => {
+                    return Err(AppError::BadRequest(format!(
+                        "CIRCLE geofences need a radius_m between 0 and {}",
+                        MAX_CIRCLE_RADIUS_M
+                    )))
+                }
+            }
+            // The stored area is derived from center and radius
+            dto.area = None;
+        }
+    }
+
+    Ok(dto)
+}
+
+#[async_trait]
+impl GeofenceServiceTrait for GeofenceService {
+    async fn create_geofence(&self, dto: CreateGeofenceDto) -> Result<Geofence, AppError> {
+        self.geofence_repo.create(normalize(dto)?).await
+    }
+
+    async fn list_geofences(&self) -> Result<Vec<Geofence>, AppError> {
+        self.geofence_repo.find_all().await
+    }
+
+    async fn get_geofence(&self, id: Uuid) -> Result<Geofence, AppError> {
+        self.geofence_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(AppError::NotFound(format!("Geofence {} not found", id)))
+    }
+
+    async fn update_geofence(&self, id: Uuid, dto: CreateGeofenceDto) -> Result<Geofence, AppError> {
+        self.geofence_repo
+            .update(id, normalize(dto)?)
+            .await?
+            .ok_or(AppError::NotFound(format!("Geofence {} not found", id)))
+    }
+
+    async fn delete_geofence(&self, id: Uuid) -> Result<(), AppError> {
+        if !self.geofence_repo.delete(id).await? {
+            return Err(AppError::NotFound(format!("Geofence {} not found", id)));
+        }
+        Ok(())
+    }
+
+    async fn evaluate_positions(&self, vehicle_id: Uuid, points: Vec<(DateTime<Utc>, Value)>) -> Result<Vec<Alert>, AppError> {
+        if points.is_empty() {
+            return Ok(Vec::new());
+        }
+        let transitions = self.geofence_repo.evaluate_track(vehicle_id, points).await?;
+
+        let mut alerts = Vec::new();
+        for transition in transitions {
+            let (alert_type, message) = if transition.inside {
+                if !transition.alert_on_entry {
+                    continue;
+                }
+                (GEOFENCE_ENTRY_ALERT, format!("Entered geofence '{}'", transition.name))
+            } else {
+                if !transition.alert_on_exit {
+                    continue;
+                }
+                (GEOFENCE_EXIT_ALERT, format!("Left geofence '{}'", transition.name))
+            };
+
+            let alert = self.alert_repo.create(CreateAlertDto {
+                entity_id: vehicle_id,
+                r#type: alert_type.to_string(),
+                severity: AlertSeverity::Low,
+                message: Some(message),
+            }).await?;
+            alerts.push(alert);
+        }
+
+        Ok(alerts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::postgres::geofence::GeofenceTransition;
+    use crate::repositories::postgres::geofence_repo::MockGeofenceRepositoryTrait;
+    use crate::repositories::postgres::maintenance_repo::MockAlertRepositoryTrait;
+    use serde_json::json;
+
+    fn circle_dto() -> CreateGeofenceDto {
+        CreateGeofenceDto {
+            name: "North depot".into(),
+            kind: GeofenceKind::Circle,
+            area: Some(json!({"type": "Polygon", "coordinates": []})),
+            center: Some(json!({"type": "Point", "coordinates": [-74.0, 40.7]})),
+            radius_m: Some(500.0),
+            vehicle_id: None,
+            vehicle_type: None,
+            alert_on_entry: true,
+            alert_on_exit: true,
+            is_active: true,
+        }
+    }
+
+    fn transition(name: &str, inside: bool, alert_on_exit: bool) -> GeofenceTransition {
+        GeofenceTransition {
+            geofence_id: Uuid::new_v4(),
+            name: name.into(),
+            inside,
+            alert_on_entry: true,
+            alert_on_exit,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_circle_drops_client_area() {
+        let mut mock_repo = MockGeofenceRepositoryTrait::new();
+        mock_repo.expect_create()
+            .withf(|dto| dto.area.is_none() && dto.radius_m == Some(500.0))
+            .times(1)
+            .returning(|dto| Ok(Geofence {
+                id: Uuid::new_v4(),
+                name: dto.name,
+                kind: dto.kind,
+                area: json!({"type": "Polygon", "coordinates": []}),
+                center: dto.center,
+                radius_m: dto.radius_m,
+                vehicle_id: None,
+                vehicle_type: None,
+                alert_on_entry: true,
+                alert_on_exit: true,
+                is_active: true,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }));
+
+        let service = GeofenceService::new(Arc::new(mock_repo), Arc::new(MockAlertRepositoryTrait::new()));
+        assert!(service.create_geofence(circle_dto()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_invalid_shape() {
+        let service = GeofenceService::new(
+            Arc::new(MockGeofenceRepositoryTrait::new()),
+            Arc::new(MockAlertRepositoryTrait::new()),
+        );
+
+        let mut no_radius = circle_dto();
+        no_radius.radius_m = None;
+        assert!(matches!(service.create_geofence(no_radius).await, Err(AppError::BadRequest(_))));
+
+        let mut polygon_without_area = circle_dto();
+        polygon_without_area.kind = GeofenceKind::Polygon;
+        polygon_without_area.area = None;
+        assert!(matches!(service.create_geofence(polygon_without_area).await, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_positions_creates_alerts_for_enabled_transitions() {
+        let vehicle_id = Uuid::new_v4();
+
+        let mut mock_repo = MockGeofenceRepositoryTrait::new();
+        mock_repo.expect_evaluate_track()
+            .withf(|_, points| points.len() == 2)
+            .times(1)
+            .returning(|_, _| Ok(vec![
+                transition("Customer site", true, true),
+                // Exit alerts disabled for this one
+                transition("Depot", false, false),
+            ]));
+
+        let mut mock_alerts = MockAlertRepositoryTrait::new();
+        mock_alerts.expect_create()
+            .withf(move |dto| {
+                dto.entity_id == vehicle_id
+                    && dto.r#type == GEOFENCE_ENTRY_ALERT
+                    && dto.message.as_deref() == Some("Entered geofence 'Customer site'")
+            })
+            .times(1)
+            .returning(|dto| Ok(Alert {
+                id: Uuid::new_v4(),
+                entity_id: dto.entity_id,
+                r#type: dto.r#type,
+                severity: dto.severity,
+                is_resolved: false,
+                created_at: Utc::now(),
+                resolved_at: None,
+                message: dto.message,
+            }));
+
+        let service = GeofenceService::new(Arc::new(mock_repo), Arc::new(mock_alerts));
+        let alerts = service
+            .evaluate_positions(vehicle_id, vec![
+                (Utc::now(), json!({"type": "Point", "coordinates": [-74.0, 40.7]})),
+                (Utc::now(), json!({"type": "Point", "coordinates": [-74.1, 40.7]})),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(alerts.len(), 1);
+    }
+}
@@ -0,0 +1,659 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::postgres::assignment::{AssignmentStatus, VehicleAssignment};
+use crate::models::postgres::hos::{
+    ClockDto, DutyDay, DutyEvent, DutyEventKind, DutyPeriod, DutyShift, DutySource, HosLog, HosLogQuery,
+};
+use crate::models::postgres::driver::Driver;
+use crate::models::postgres::settings::AppSettings;
+use crate::models::postgres::user::UserRole;
+use crate::repositories::postgres::assignment_repo::AssignmentRepositoryTrait;
+use crate::repositories::postgres::driver_repo::DriverRepositoryTrait;
+use crate::repositories::postgres::hos_repo::HosRepositoryTrait;
+use crate::repositories::postgres::settings_repo::SettingsRepositoryTrait;
+use crate::services::auth_service::Claims;
+
+/// Length of the rolling window the weekly limit applies to.
+const WEEK: Duration = Duration::days(7);
+
+/// History loaded around the checked range, so weekly totals and shifts that started
+/// before it are complete.
+const LOOKAROUND: Duration = Duration::days(8);
+
+/// Longest log, in days, one request may ask for.
+const MAX_LOG_DAYS: i64 = 31;
+
+type Interval = (DateTime<Utc>, DateTime<Utc>);
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait HosServiceTrait: Send + Sync {
+    /// Drivers may only clock themselves in and out and read their own log; managers and
+    /// admins may do so for any driver.
+    async fn clock_in(&self, driver_id: Uuid, dto: ClockDto, caller: &Claims) -> Result<DutyEvent, AppError>;
+    async fn clock_out(&self, driver_id: Uuid, dto: ClockDto, caller: &Claims) -> Result<DutyEvent, AppError>;
+    async fn get_log(&self, driver_id: Uuid, query: HosLogQuery, caller: &Claims) -> Result<HosLog, AppError>;
+    /// Rejects an assignment that would take the driver over the daily or weekly limit,
+    /// including one that starts before the driver has had a full rest break.
+    async fn check_assignment(
+        &self,
+        driver_id: Uuid,
+        start: DateTime<Utc>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError>;
+    /// On-duty hours the driver can still work at `at` before hitting the daily or weekly
+    /// limit. `None` when no limit is configured.
+    async fn hours_remaining(&self, driver_id: Uuid, at: DateTime<Utc>) -> Result<Option<f64>, AppError>;
+}
+
+pub struct HosService {
+    hos_repo: Arc<dyn HosRepositoryTrait>,
+    assignment_repo: Arc<dyn AssignmentRepositoryTrait>,
+    driver_repo: Arc<dyn DriverRepositoryTrait>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait>,
+}
+
+impl HosService {
+    pub fn new(
+        hos_repo: Arc<dyn HosRepositoryTrait>,
+        assignment_repo: Arc<dyn AssignmentRepositoryTrait>,
+        driver_repo: Arc<dyn DriverRepositoryTrait>,
+        settings_repo: Arc<dyn SettingsRepositoryTrait>,
+    ) -> Self {
+        Self { hos_repo, assignment_repo, driver_repo, settings_repo }
+    }
+
+    async fn find_driver(&self, driver_id: Uuid, caller: &Claims) -> Result<Driver, AppError> {
+        let driver = self.driver_repo.find_by_id(driver_id).await?
+            .ok_or_else(|| AppError::NotFound("Driver not found".into()))?;
+        match caller.role {
+            UserRole::Admin | UserRole::Manager => Ok(driver),
+            _ if driver.user_id == caller.user_id => Ok(driver),
+            _ => Err(AppError::Forbidden("Drivers can only record and view their own hours of service".into())),
+        }
+    }
+
+    async fn clock(&self, driver_id: Uuid, kind: DutyEventKind, dto: ClockDto, caller: &Claims) -> Result<DutyEvent, AppError> {
+        self.find_driver(driver_id, caller).await?;
+
+        let now = Utc::now();
+        let occurred_at = dto.occurred_at.unwrap_or(now);
+        if occurred_at > now {
+            return Err(AppError::BadRequest("Duty events cannot be in the future".into()));
+        }
+
+        let last = self.hos_repo.find_last_event(driver_id).await?;
+        let clocked_in = last.as_ref().is_some_and(|event| event.kind == DutyEventKind::ClockIn);
+        match kind {
+            DutyEventKind::ClockIn if clocked_in => {
+                return Err(AppError::BadRequest("Driver is already clocked in".into()));
+            }
+            DutyEventKind::ClockOut if !clocked_in => {
+                return Err(AppError::BadRequest("Driver is not clocked in".into()));
+            }
+            _ => {}
+        }
+        if let Some(last) = last.filter(|last| occurred_at < last.occurred_at) {
+            return Err(AppError::BadRequest(format!(
+                "Duty events must come after the previous one at {}",
+                last.occurred_at
+            )));
+        }
+
+        self.hos_repo.create_event(driver_id, kind, occurred_at, dto.note).await
+    }
+
+    async fn load(
+        &self,
+        driver_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<(Vec<DutyEvent>, Vec<DutyPeriod>), AppError> {
+        let events = self.hos_repo.find_events(driver_id, from, to).await?;
+        let assignments = self.assignment_repo.find_by_driver_between(driver_id, from, to).await?;
+        let periods = duty_periods(&events, &assignments);
+        Ok((events, periods))
+    }
+}
+
+/// Pairs each clock-in with the next clock-out and adds the driver's assignments.
+/// A clock-out without a clock-in before it is ignored.
+fn duty_periods(events: &[DutyEvent], assignments: &[VehicleAssignment]) -> Vec<DutyPeriod> {
+    let mut periods = Vec::new();
+    let mut clocked_in = None;
+    for event in events {
+        match event.kind {
+            DutyEventKind::ClockIn => {
+                clocked_in.get_or_insert(event.occurred_at);
+            }
+            DutyEventKind::ClockOut => {
+                if let Some(start) = clocked_in.take() {
+                    periods.push(DutyPeriod {
+                        source: DutySource::Clock,
+                        assignment_id: None,
+                        start,
+                        end: Some(event.occurred_at),
+                    });
+                }
+            }
+        }
+    }
+    if let Some(start) = clocked_in {
+        periods.push(DutyPeriod { source: DutySource::Clock, assignment_id: None, start, end: None });
+    }
+
+    // Assignments cancelled before they started never put the driver on duty
+    for assignment in assignments
+        .iter()
+        .filter(|a| a.status != AssignmentStatus::Cancelled || a.started_at.is_some())
+    {
+        periods.push(DutyPeriod {
+            source: DutySource::Assignment,
+            assignment_id: Some(assignment.id),
+            start: assignment.effective_start(),
+            end: assignment.effective_end(),
+        });
+    }
+
+    periods.sort_by_key(|period| period.start);
+    periods
+}
+
+/// Sorted, non-overlapping on-duty intervals. Open periods run until `now`, so time a
+/// driver is double-booked or clocked in during an assignment only counts once.
+fn merge(periods: &[DutyPeriod], now: DateTime<Utc>) -> Vec<Interval> {
+    merge_intervals(periods.iter().map(|p| (p.start, p.end.unwrap_or(now).max(p.start))).collect())
+}
+
+fn merge_intervals(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.sort();
+    let mut merged: Vec<Interval> = Vec::new();
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// On-duty time within `[from, to)`.
+fn duty_between(intervals: &[Interval], from: DateTime<Utc>, to: DateTime<Utc>) -> Duration {
+    intervals
+        .iter()
+        .map(|&(start, end)| (end.min(to) - start.max(from)).max(Duration::zero()))
+        .fold(Duration::zero(), |total, d| total + d)
+}
+
+/// Groups intervals into shifts; an off-duty gap of at least `min_rest` starts a new one.
+/// Returns each shift's span and on-duty time.
+fn shifts(intervals: &[Interval], min_rest: Duration) -> Vec<(Interval, Duration)> {
+    let mut shifts: Vec<(Interval, Duration)> = Vec::new();
+    for &(start, end) in intervals {
+        match shifts.last_mut() {
+            Some(((_, shift_end), on_duty)) if start - *shift_end < min_rest => {
+                *shift_end = end;
+                *on_duty += end - start;
+            }
+            _ => shifts.push(((start, end), end - start)),
+        }
+    }
+    shifts
+}
+
+/// Most on-duty time in any 7-day window that overlaps `span`. The total only peaks
+/// when a window edge lines up with an interval edge, so those are the windows tried.
+fn max_weekly_duty(intervals: &[Interval], span: Interval) -> Duration {
+    let ending = intervals.iter().map(|&(_, end)| (end - WEEK, end));
+    let starting = intervals.iter().map(|&(start, _)| (start, start + WEEK));
+    ending
+        .chain(starting)
+        .filter(|&(from, to)| from <= span.1 && to >= span.0)
+        .map(|(from, to)| duty_between(intervals, from, to))
+        .max()
+        .unwrap_or_else(Duration::zero)
+}
+
+fn limit(hours: f64) -> Duration {
+    Duration::seconds((hours * 3600.0).round() as i64)
+}
+
+fn to_hours(duration: Duration) -> f64 {
+    (duration.num_seconds() as f64 / 36.0).round() / 100.0
+}
+
+/// Checks a new on-duty span against the limits. A zero-length span stands for an
+/// open-ended assignment, which is refused once a limit has already been reached.
+fn check_limits(settings: &AppSettings, existing: &[Interval], new: Interval) -> Result<(), String> {
+    let open_ended = new.0 == new.1;
+    let exceeds = |on_duty: Duration, max_hours: f64| {
+        max_hours > 0.0 && (on_duty > limit(max_hours) || (open_ended && on_duty >= limit(max_hours)))
+    };
+
+    let mut intervals = existing.to_vec();
+    intervals.push(new);
+    let intervals = merge_intervals(intervals);
+
+    let shift = shifts(&intervals, limit(settings.hos_min_rest_hours))
+        .into_iter()
+        .find(|((start, end), _)| *start <= new.0 && new.1 <= *end);
+    if let Some((_, on_duty)) = shift.filter(|(_, on_duty)| exceeds(*on_duty, settings.hos_max_daily_hours)) {
+        return Err(format!(
+            "Driver would be on duty for {:.1} hours without a {}-hour rest break (daily limit is {} hours)",
+            to_hours(on_duty),
+            settings.hos_min_rest_hours,
+            settings.hos_max_daily_hours
+        ));
+    }
+
+    let weekly = max_weekly_duty(&intervals, new);
+    if exceeds(weekly, settings.hos_max_weekly_hours) {
+        return Err(format!(
+            "Driver would be on duty for {:.1} hours in 7 days (weekly limit is {} hours)",
+            to_hours(weekly),
+            settings.hos_max_weekly_hours
+        ));
+    }
+    Ok(())
+}
+
+/// Time left under the limits at `at`, counting only duty up to then. The daily limit
+/// applies to the current shift unless the driver has already rested long enough.
+fn remaining_at(settings: &AppSettings, intervals: &[Interval], at: DateTime<Utc>) -> Option<Duration> {
+    let past: Vec<Interval> = intervals
+        .iter()
+        .filter(|(start, _)| *start < at)
+        .map(|&(start, end)| (start, end.min(at)))
+        .collect();
+
+    let daily = (settings.hos_max_daily_hours > 0.0).then(|| {
+        let min_rest = limit(settings.hos_min_rest_hours);
+        let on_duty = shifts(&past, min_rest)
+            .last()
+            .filter(|((_, end), _)| at - *end < min_rest)
+            .map_or_else(Duration::zero, |(_, on_duty)| *on_duty);
+        limit(settings.hos_max_daily_hours) - on_duty
+    });
+    let weekly = (settings.hos_max_weekly_hours > 0.0)
+        .then(|| limit(settings.hos_max_weekly_hours) - duty_between(&past, at - WEEK, at));
+
+    daily.into_iter().chain(weekly).min().map(|left| left.max(Duration::zero()))
+}
+
+#[async_trait]
+impl HosServiceTrait for HosService {
+    async fn clock_in(&self, driver_id: Uuid, dto: ClockDto, caller: &Claims) -> Result<DutyEvent, AppError> {
+        self.clock(driver_id, DutyEventKind::ClockIn, dto, caller).await
+    }
+
+    async fn clock_out(&self, driver_id: Uuid, dto: ClockDto, caller: &Claims) -> Result<DutyEvent, AppError> {
+        self.clock(driver_id, DutyEventKind::ClockOut, dto, caller).await
+    }
+
+    async fn get_log(&self, driver_id: Uuid, query: HosLogQuery, caller: &Claims) -> Result<HosLog, AppError> {
+        self.find_driver(driver_id, caller).await?;
+
+        let now = Utc::now();
+        let to = query.to.unwrap_or(now.date_naive());
+        let from = query.from.unwrap_or(to - Duration::days(6));
+        if from > to {
+            return Err(AppError::BadRequest("from must not be after to".into()));
+        }
+        if (to - from).num_days() >= MAX_LOG_DAYS {
+            return Err(AppError::BadRequest(format!("Log range cannot exceed {} days", MAX_LOG_DAYS)));
+        }
+        let range_start = from.and_time(NaiveTime::MIN).and_utc();
+        let range_end = (to + Duration::days(1)).and_time(NaiveTime::MIN).and_utc();
+
+        let settings = self.settings_repo.get().await?;
+        let (events, periods) = self.load(driver_id, range_start - LOOKAROUND, range_end).await?;
+        let intervals = merge(&periods, now);
+
+        let mut violations = Vec::new();
+        let mut previous_end = None;
+        let mut log_shifts = Vec::new();
+        for ((start, end), on_duty) in shifts(&intervals, limit(settings.hos_min_rest_hours)) {
+            let rest_before = previous_end.map(|previous| to_hours(start - previous));
+            previous_end = Some(end);
+            if end <= range_start || start >= range_end {
+                continue;
+            }
+
+            let weekly = duty_between(&intervals, end - WEEK, end);
+            if settings.hos_max_daily_hours > 0.0 && on_duty > limit(settings.hos_max_daily_hours) {
+                violations.push(format!(
+                    "Shift starting {}: {:.1} on-duty hours exceeds the {}-hour daily limit",
+                    start, to_hours(on_duty), settings.hos_max_daily_hours
+                ));
+            }
+            if settings.hos_max_weekly_hours > 0.0 && weekly > limit(settings.hos_max_weekly_hours) {
+                violations.push(format!(
+                    "Shift starting {}: {:.1} on-duty hours in 7 days exceeds the {}-hour weekly limit",
+                    start, to_hours(weekly), settings.hos_max_weekly_hours
+                ));
+            }
+            log_shifts.push(DutyShift {
+                start,
+                end,
+                on_duty_hours: to_hours(on_duty),
+                rest_before_hours: rest_before,
+                weekly_hours: to_hours(weekly),
+            });
+        }
+
+        let days = from
+            .iter_days()
+            .take_while(|day| *day <= to)
+            .map(|date| {
+                let day_start = date.and_time(NaiveTime::MIN).and_utc();
+                DutyDay {
+                    date,
+                    on_duty_hours: to_hours(duty_between(&intervals, day_start, day_start + Duration::days(1))),
+                }
+            })
+            .collect();
+
+        Ok(HosLog {
+            driver_id,
+            from,
+            to,
+            max_daily_hours: settings.hos_max_daily_hours,
+            max_weekly_hours: settings.hos_max_weekly_hours,
+            min_rest_hours: settings.hos_min_rest_hours,
+            events: events.into_iter().filter(|e| e.occurred_at >= range_start).collect(),
+            periods: periods
+                .into_iter()
+                .filter(|p| p.start < range_end && p.end.is_none_or(|end| end > range_start))
+                .collect(),
+            shifts: log_shifts,
+            days,
+            total_hours: to_hours(duty_between(&intervals, range_start, range_end)),
+            violations,
+        })
+    }
+
+    async fn check_assignment(
+        &self,
+        driver_id: Uuid,
+        start: DateTime<Utc>,
+        end: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError> {
+        let settings = self.settings_repo.get().await?;
+        if settings.hos_max_daily_hours <= 0.0 && settings.hos_max_weekly_hours <= 0.0 {
+            return Ok(());
+        }
+
+        let end = end.unwrap_or(start).max(start);
+        let (_, periods) = self.load(driver_id, start - LOOKAROUND, end + LOOKAROUND).await?;
+        let existing = merge(&periods, Utc::now());
+        check_limits(&settings, &existing, (start, end)).map_err(AppError::BadRequest)
+    }
+
+    async fn hours_remaining(&self, driver_id: Uuid, at: DateTime<Utc>) -> Result<Option<f64>, AppError> {
+        let settings = self.settings_repo.get().await?;
+        if settings.hos_max_daily_hours <= 0.0 && settings.hos_max_weekly_hours <= 0.0 {
+            return Ok(None);
+        }
+
+        let (_, periods) = self.load(driver_id, at - LOOKAROUND, at).await?;
+        let intervals = merge(&periods, at);
+        Ok(remaining_at(&settings, &intervals, at).map(to_hours))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+    use crate::models::postgres::driver::DriverStatus;
+    use crate::repositories::postgres::assignment_repo::MockAssignmentRepositoryTrait;
+    use crate::repositories::postgres::driver_repo::MockDriverRepositoryTrait;
+    use crate::repositories::postgres::hos_repo::MockHosRepositoryTrait;
+    use crate::repositories::postgres::settings_repo::MockSettingsRepositoryTrait;
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap()
+    }
+
+    fn assignment(driver_id: Uuid, start: DateTime<Utc>, end: DateTime<Utc>) -> VehicleAssignment {
+        VehicleAssignment {
+            id: Uuid::new_v4(),
+            vehicle_id: Uuid::new_v4(),
+            driver_id,
+            start_time: start,
+            end_time: Some(end),
+            status: AssignmentStatus::Completed,
+            created_at: start,
+            updated_at: end,
+            started_at: Some(start),
+            completed_at: Some(end),
+            cancelled_at: None,
+            cancel_reason: None,
+        }
+    }
+
+    fn event(driver_id: Uuid, kind: DutyEventKind, occurred_at: DateTime<Utc>) -> DutyEvent {
+        DutyEvent { id: Uuid::new_v4(), driver_id, kind, occurred_at, note: None, created_at: occurred_at }
+    }
+
+    fn driver(id: Uuid) -> Driver {
+        Driver {
+            id,
+            user_id: Uuid::new_v4(),
+            license_number: "D1234567".into(),
+            status: DriverStatus::Available,
+            phone: None,
+            wage_rate: None,
+            license_expiry: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn claims(user_id: Uuid, role: UserRole) -> Claims {
+        Claims { sub: user_id, user_id, role, is_active: true, exp: usize::MAX, jti: Uuid::new_v4() }
+    }
+
+    fn service(events: Vec<DutyEvent>, assignments: Vec<VehicleAssignment>) -> HosService {
+        let mut hos_repo = MockHosRepositoryTrait::new();
+        hos_repo.expect_find_events().returning(move |_, _, _| Ok(events.clone()));
+        let mut assignment_repo = MockAssignmentRepositoryTrait::new();
+        assignment_repo
+            .expect_find_by_driver_between()
+            .returning(move |_, _, _| Ok(assignments.clone()));
+        let mut settings_repo = MockSettingsRepositoryTrait::new();
+        settings_repo.expect_get().returning(|| Ok(AppSettings::default()));
+        HosService::new(
+            Arc::new(hos_repo),
+            Arc::new(assignment_repo),
+            Arc::new(MockDriverRepositoryTrait::new()),
+            Arc::new(settings_repo),
+        )
+    }
+
+    #[test]
+    fn test_duty_periods_pair_clock_events_and_skip_cancelled_assignments() {
+        let driver_id = Uuid::new_v4();
+        let events = vec![
+            event(driver_id, DutyEventKind::ClockOut, at(1, 6)),
+            event(driver_id, DutyEventKind::ClockIn, at(1, 7)),
+            event(driver_id, DutyEventKind::ClockOut, at(1, 9)),
+            event(driver_id, DutyEventKind::ClockIn, at(2, 7)),
+        ];
+        let mut cancelled = assignment(driver_id, at(1, 12), at(1, 14));
+        cancelled.status = AssignmentStatus::Cancelled;
+        (cancelled.started_at, cancelled.completed_at, cancelled.cancelled_at) = (None, None, Some(at(1, 11)));
+        let periods = duty_periods(&events, &[assignment(driver_id, at(1, 8), at(1, 10)), cancelled]);
+
+        assert_eq!(periods.len(), 3);
+        assert_eq!((periods[0].source, periods[0].start, periods[0].end), (DutySource::Clock, at(1, 7), Some(at(1, 9))));
+        assert_eq!(periods[1].source, DutySource::Assignment);
+        assert_eq!((periods[2].start, periods[2].end), (at(2, 7), None));
+
+        // The overlap between the clock-in and the assignment only counts once
+        let intervals = merge(&periods, at(2, 9));
+        assert_eq!(duty_between(&intervals, at(1, 0), at(3, 0)), Duration::hours(5));
+    }
+
+    #[tokio::test]
+    async fn test_check_assignment_requires_rest_break() {
+        let driver_id = Uuid::new_v4();
+        let service = service(Vec::new(), vec![assignment(driver_id, at(10, 6), at(10, 14))]);
+
+        // 8 hours on duty, a 2 hour break and 4 more hours breaks the 11 hour limit
+        let result = service.check_assignment(driver_id, at(10, 16), Some(at(10, 20))).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("rest break")));
+
+        // After a 10 hour rest a new duty day starts
+        let result = service.check_assignment(driver_id, at(11, 0), Some(at(11, 8))).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_check_assignment_enforces_weekly_limit() {
+        let driver_id = Uuid::new_v4();
+        let week = (1..=6).map(|day| assignment(driver_id, at(day, 6), at(day, 16))).collect();
+        let service = service(Vec::new(), week);
+
+        let result = service.check_assignment(driver_id, at(7, 6), Some(at(7, 8))).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("weekly")));
+
+        // Once the first day falls out of the window there is room again
+        let result = service.check_assignment(driver_id, at(8, 6), Some(at(8, 14))).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_hours_remaining_takes_tighter_limit() {
+        let driver_id = Uuid::new_v4();
+        let mut week: Vec<_> = (2..=6).map(|day| assignment(driver_id, at(day, 6), at(day, 16))).collect();
+        week.push(assignment(driver_id, at(7, 6), at(7, 9)));
+        let service = service(Vec::new(), week);
+
+        // 3 hours into the shift leaves 8 of the daily 11, but only 7 of the weekly 60
+        let remaining = service.hours_remaining(driver_id, at(7, 9)).await.unwrap();
+        assert_eq!(remaining, Some(7.0));
+
+        // A full rest resets the daily limit; the weekly total still counts
+        let remaining = service.hours_remaining(driver_id, at(8, 6)).await.unwrap();
+        assert_eq!(remaining, Some(7.0));
+        let remaining = service.hours_remaining(driver_id, at(9, 20)).await.unwrap();
+        assert_eq!(remaining, Some(11.0));
+    }
+
+    #[tokio::test]
+    async fn test_get_log_reports_days_shifts_and_violations() {
+        let driver_id = Uuid::new_v4();
+        let events = vec![
+            event(driver_id, DutyEventKind::ClockIn, at(5, 6)),
+            event(driver_id, DutyEventKind::ClockOut, at(5, 19)),
+        ];
+        let mut service = service(events, vec![assignment(driver_id, at(6, 8), at(6, 12))]);
+        let mut driver_repo = MockDriverRepositoryTrait::new();
+        driver_repo.expect_find_by_id().returning(|id| Ok(Some(driver(id))));
+        service.driver_repo = Arc::new(driver_repo);
+
+        let manager = claims(Uuid::new_v4(), UserRole::Manager);
+        let log = service.get_log(driver_id, HosLogQuery {
+            from: Some(at(5, 0).date_naive()),
+            to: Some(at(6, 0).date_naive()),
+        }, &manager).await.unwrap();
+
+        assert_eq!(log.days.iter().map(|d| d.on_duty_hours).collect::<Vec<_>>(), vec![13.0, 4.0]);
+        assert_eq!(log.total_hours, 17.0);
+        assert_eq!(log.shifts.len(), 2);
+        assert_eq!(log.shifts[1].rest_before_hours, Some(13.0));
+        assert_eq!(log.violations.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_clock_out_requires_clock_in() {
+        let driver_id = Uuid::new_v4();
+        let mut driver_repo = MockDriverRepositoryTrait::new();
+        driver_repo.expect_find_by_id().returning(|id| Ok(Some(driver(id))));
+        let mut hos_repo = MockHosRepositoryTrait::new();
+        hos_repo
+            .expect_find_last_event()
+            .returning(move |_| Ok(Some(event(driver_id, DutyEventKind::ClockOut, at(1, 9)))));
+
+        let service = HosService::new(
+            Arc::new(hos_repo),
+            Arc::new(MockAssignmentRepositoryTrait::new()),
+            Arc::new(driver_repo),
+            Arc::new(MockSettingsRepositoryTrait::new()),
+        );
+        let result = service.clock_out(driver_id, ClockDto::default(), &claims(Uuid::new_v4(), UserRole::Admin)).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_driver_cannot_clock_in_another_driver() {
+        let own_user = Uuid::new_v4();
+        let other_driver = Uuid::new_v4();
+        let mut driver_repo = MockDriverRepositoryTrait::new();
+        driver_repo.expect_find_by_id().returning(|id| Ok(Some(driver(id))));
+        let mut hos_repo = MockHosRepositoryTrait::new();
+        hos_repo.expect_create_event().never();
+
+        let service = HosService::new(
+            Arc::new(hos_repo),
+            Arc::new(MockAssignmentRepositoryTrait::new()),
+            Arc::new(driver_repo),
+            Arc::new(MockSettingsRepositoryTrait::new()),
+        );
+        let caller = claims(own_user, UserRole::Driver);
+        let result = service.clock_in(other_driver, ClockDto::default(), &caller).await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+
+        let result = service.get_log(other_driver, HosLogQuery::default(), &caller).await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_get_log_caps_range() {
+        let mut service = service(Vec::new(), Vec::new());
+        let mut driver_repo = MockDriverRepositoryTrait::new();
+        driver_repo.expect_find_by_id().returning(|id| Ok(Some(driver(id))));
+        service.driver_repo = Arc::new(driver_repo);
+        let manager = claims(Uuid::new_v4(), UserRole::Manager);
+
+        let query = HosLogQuery {
+            from: Some(NaiveDate::from_ymd_opt(1900, 1, 1).unwrap()),
+            to: Some(at(6, 0).date_naive()),
+        };
+        let result = service.get_log(Uuid::new_v4(), query, &manager).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("31 days")));
+
+        let query = HosLogQuery { from: Some(at(1, 0).date_naive()), to: Some(at(31, 0).date_naive()) };
+        let log = service.get_log(Uuid::new_v4(), query, &manager).await.unwrap();
+        assert_eq!(log.days.len(), 31);
+    }
+
+    #[tokio::test]
+    async fn test_driver_can_clock_in_themselves() {
+        let user_id = Uuid::new_v4();
+        let driver_id = Uuid::new_v4();
+        let mut driver_repo = MockDriverRepositoryTrait::new();
+        driver_repo.expect_find_by_id().returning(move |id| Ok(Some(Driver { user_id, ..driver(id) })));
+        let mut hos_repo = MockHosRepositoryTrait::new();
+        hos_repo.expect_find_last_event().returning(|_| Ok(None));
+        hos_repo
+            .expect_create_event()
+            .times(1)
+            .returning(|driver_id, kind, occurred_at, _| Ok(event(driver_id, kind, occurred_at)));
+
+        let service = HosService::new(
+            Arc::new(hos_repo),
+            Arc::new(MockAssignmentRepositoryTrait::new()),
+            Arc::new(driver_repo),
+            Arc::new(MockSettingsRepositoryTrait::new()),
+        );
+        let event = service.clock_in(driver_id, ClockDto::default(), &claims(user_id, UserRole::Driver)).await.unwrap();
+        assert_eq!(event.kind, DutyEventKind::ClockIn);
+    }
+}
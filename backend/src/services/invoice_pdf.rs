@@ -0,0 +1,358 @@
+use std::fmt::Write;
+use rust_decimal::Decimal;
+use crate::models::postgres::invoice::{InvoiceDetails, InvoiceStatus};
+use crate::models::postgres::settings::AppSettings;
+
+// A4 in points
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+const LINE_HEIGHT: f32 = 16.0;
+/// Room kept at the bottom of each page for the page number
+const FOOTER_HEIGHT: f32 = 40.0;
+/// Characters of a description that fit left of the amount column
+const DESCRIPTION_WIDTH: usize = 70;
+
+/// Built-in fonts, so nothing has to be embedded. Amounts use the monospaced one so they
+/// can be right-aligned without font metrics.
+#[derive(Clone, Copy)]
+enum Font {
+    Regular,
+    Bold,
+    Mono,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Mono => "F3",
+        }
+    }
+}
+
+/// Lays text out top to bottom, starting a new page when the current one is full.
+struct Layout {
+    pages: Vec<String>,
+    y: f32,
+}
+
+impl Layout {
+    fn new() -> Self {
+        Self { pages: vec![String::new()], y: PAGE_HEIGHT - MARGIN }
+    }
+
+    fn text(&mut self, x: f32, font: Font, size: f32, text: &str) {
+        let page = self.pages.last_mut().expect("layout has a page");
+        let _ = writeln!(page, "BT /{} {} Tf {:.1} {:.1} Td ({}) Tj ET", font.resource(), size, x, self.y, escape(text));
+    }
+
+    /// Courier glyphs are 0.6 em wide.
+    fn text_right(&mut self, right: f32, font: Font, size: f32, text: &str) {
+        let width = text.chars().count() as f32 * size * 0.6;
+        self.text(right - width, font, size, text);
+    }
+
+    fn rule(&mut self) {
+        let page = self.pages.last_mut().expect("layout has a page");
+        let y = self.y + LINE_HEIGHT - 4.0;
+        let _ = writeln!(page, "0.5 w {} {:.1} m {} {:.1} l S", MARGIN, y, PAGE_WIDTH - MARGIN, y);
+    }
+
+    fn advance(&mut self, lines: f32) {
+        self.y -= LINE_HEIGHT * lines;
+        if self.y < MARGIN + FOOTER_HEIGHT {
+            self.pages.push(String::new());
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+    }
+}
+
+/// Renders the invoice as a PDF document, with the company details from the settings
+/// in the header.
+pub fn render(settings: &AppSettings, details: &InvoiceDetails) -> Vec<u8> {
+    let invoice = &details.invoice;
+    let right = PAGE_WIDTH - MARGIN;
+    let mut layout = Layout::new();
+
+    layout.text(MARGIN, Font::Bold, 18.0, &settings.company_name);
+    layout.text_right(right, Font::Mono, 18.0, "INVOICE");
+    layout.advance(1.5);
+    let company_lines = settings.address.lines()
+        .chain([settings.contact_email.as_str(), settings.phone_number.as_str()])
+        .filter(|line| !line.trim().is_empty());
+    for line in company_lines {
+        layout.text(MARGIN, Font::Regular, 10.0, line.trim());
+        layout.advance(1.0);
+    }
+    layout.advance(1.0);
+
+    let facts = [
+        ("Invoice number", invoice.invoice_number.clone()),
+        ("Issue date", invoice.issue_date.format("%Y-%m-%d").to_string()),
+        ("Due date", invoice.due_date.format("%Y-%m-%d").to_string()),
+        ("Status", status_label(invoice.status).to_string()),
+    ];
+    for (label, value) in facts {
+        layout.text(MARGIN, Font::Bold, 10.0, label);
+        layout.text(MARGIN + 110.0, Font::Regular, 10.0, &value);
+        layout.advance(1.0);
+    }
+    layout.advance(1.0);
+
+    layout.text(MARGIN, Font::Bold, 11.0, "Bill to");
+    layout.advance(1.0);
+    layout.text(MARGIN, Font::Regular, 10.0, &invoice.customer_name);
+    layout.advance(1.0);
+    for line in invoice.billing_address.lines().flat_map(|line| wrap(line, DESCRIPTION_WIDTH)) {
+        layout.text(MARGIN, Font::Regular, 10.0, &line);
+        layout.advance(1.0);
+    }
+    layout.advance(1.0);
+
+    layout.text(MARGIN, Font::Bold, 10.0, "Description");
+    layout.text_right(right, Font::Mono, 10.0, &format!("Amount ({})", invoice.currency));
+    layout.advance(1.0);
+    layout.rule();
+    for line in &details.lines {
+        let wrapped = wrap(&line.description, DESCRIPTION_WIDTH);
+        for (i, part) in wrapped.iter().enumerate() {
+            layout.text(MARGIN, Font::Regular, 10.0, part);
+            if i == 0 {
+                layout.text_right(right, Font::Mono, 10.0, &money(line.amount));
+            }
+            layout.advance(1.0);
+        }
+    }
+    layout.rule();
+
+    let mut totals = vec![
+        ("Subtotal".to_string(), invoice.subtotal, Font::Regular),
+        (format!("Tax ({}%)", invoice.tax_rate.normalize()), invoice.tax_amount, Font::Regular),
+        (format!("Total ({})", invoice.currency), invoice.total, Font::Bold),
+    ];
+    if !invoice.amount_paid.is_zero() {
+        totals.push(("Paid".to_string(), invoice.amount_paid, Font::Regular));
+        totals.push(("Balance due".to_string(), invoice.balance_due, Font::Bold));
+    }
+    for (label, amount, font) in totals {
+        layout.text(right - 230.0, font, 10.0, &label);
+        layout.text_right(right, Font::Mono, 10.0, &money(amount));
+        layout.advance(1.0);
+    }
+
+    if let Some(notes) = invoice.notes.as_deref().filter(|notes| !notes.trim().is_empty()) {
+        layout.advance(1.0);
+        layout.text(MARGIN, Font::Bold, 10.0, "Notes");
+        layout.advance(1.0);
+        for line in notes.lines().flat_map(|line| wrap(line, DESCRIPTION_WIDTH)) {
+            layout.text(MARGIN, Font::Regular, 10.0, &line);
+            layout.advance(1.0);
+        }
+    }
+
+    write_document(layout.pages)
+}
+
+fn status_label(status: InvoiceStatus) -> &'static str {
+    match status {
+        InvoiceStatus::Issued => "Issued",
+        InvoiceStatus::PartiallyPaid => "Partially paid",
+        InvoiceStatus::Paid => "Paid",
+    }
+}
+
+fn money(amount: Decimal) -> String {
+    format!("{:.2}", amount)
+}
+
+/// Splits text into lines of at most `width` characters, breaking at spaces where it can.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let mut word = word.to_string();
+        while word.chars().count() > width {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
+            }
+            let split = word.char_indices().nth(width).map(|(i, _)| i).unwrap_or(word.len());
+            lines.push(word[..split].to_string());
+            word = word[split..].to_string();
+        }
+        if current.is_empty() {
+            current = word;
+        } else if current.chars().count() + 1 + word.chars().count() <= width {
+            current.push(' ');
+            current.push_str(&word);
+        } else {
+            lines.push(std::mem::replace(&mut current, word));
+        }
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Escapes a string for a PDF literal. The fonts use WinAnsiEncoding, which matches
+/// Latin-1 above 0xA0; other characters that it cannot show become `?`.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            ' '..='~' => escaped.push(c),
+            '\u{a0}'..='\u{ff}' => {
+                let _ = write!(escaped, "\\{:03o}", c as u32);
+            }
+            '\u{20ac}' => escaped.push_str("\\200"),
+            _ => escaped.push('?'),
+        }
+    }
+    escaped
+}
+
+/// Assembles the page content streams into a PDF file, numbering each page in its footer.
+fn write_document(pages: Vec<String>) -> Vec<u8> {
+    const FONTS: [(&str, &str); 3] = [("F1", "Helvetica"), ("F2", "Helvetica-Bold"), ("F3", "Courier")];
+    let page_count = pages.len();
+    // 1: catalog, 2: page tree, 3-5: fonts, then a page object and its content per page
+    let first_page = 3 + FONTS.len();
+    let mut objects: Vec<String> = Vec::new();
+
+    objects.push("<< /Type /Catalog /Pages 2 0 R >>".to_string());
+    let kids: Vec<String> = (0..page_count).map(|i| format!("{} 0 R", first_page + 2 * i)).collect();
+    objects.push(format!("<< /Type /Pages /Kids [{}] /Count {} >>", kids.join(" "), page_count));
+    for (_, base_font) in FONTS {
+        objects.push(format!(
+            "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+            base_font
+        ));
+    }
+
+    let font_resources: Vec<String> = FONTS.iter().enumerate()
+        .map(|(i, (name, _))| format!("/{} {} 0 R", name, 3 + i))
+        .collect();
+    for (i, mut content) in pages.into_iter().enumerate() {
+        let footer = format!("Page {} of {}", i + 1, page_count);
+        let width = footer.len() as f32 * 8.0 * 0.6;
+        let _ = writeln!(content, "BT /F3 8 Tf {:.1} {:.1} Td ({}) Tj ET", PAGE_WIDTH - MARGIN - width, MARGIN / 2.0, footer);
+
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << {} >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            font_resources.join(" "),
+            first_page + 2 * i + 1
+        ));
+        objects.push(format!("<< /Length {} >>\nstream\n{}endstream", content.len(), content));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        let _ = write!(pdf, "{} 0 obj\n{}\nendobj\n", i + 1, object);
+    }
+    let xref = pdf.len();
+    let _ = write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(pdf, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        pdf,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    );
+    pdf.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+    use uuid::Uuid;
+    use crate::models::postgres::invoice::{Invoice, InvoiceLine};
+
+    fn settings() -> AppSettings {
+        AppSettings {
+            contact_email: "billing@example.com".into(),
+            address: "1 Depot Rd\nSpringfield".into(),
+            currency: "EUR".into(),
+            tax_rate: Decimal::new(20, 0),
+            ..Default::default()
+        }
+    }
+
+    fn details(line_count: usize) -> InvoiceDetails {
+        let invoice_id = Uuid::new_v4();
+        InvoiceDetails {
+            invoice: Invoice {
+                id: invoice_id,
+                invoice_number: "INV-000042".into(),
+                customer_id: Uuid::new_v4(),
+                customer_name: "Müller (Logistics)".into(),
+                billing_address: "Hauptstraße 1\nBerlin".into(),
+                currency: "EUR".into(),
+                tax_rate: Decimal::new(20, 0),
+                subtotal: Decimal::new(100000, 2),
+                tax_amount: Decimal::new(20000, 2),
+                total: Decimal::new(120000, 2),
+                amount_paid: Decimal::ZERO,
+                balance_due: Decimal::new(120000, 2),
+                status: InvoiceStatus::Issued,
+                issue_date: NaiveDate::from_ymd_opt(2026, 10, 18).unwrap(),
+                due_date: NaiveDate::from_ymd_opt(2026, 11, 17).unwrap(),
+                notes: None,
+                issued_by: None,
+                created_at: Utc::now(),
+            },
+            lines: (0..line_count)
+                .map(|i| InvoiceLine {
+                    id: Uuid::new_v4(),
+                    invoice_id,
+                    job_id: Uuid::new_v4(),
+                    description: format!("Transport job {}", i + 1),
+                    amount: Decimal::new(1000, 2),
+                })
+                .collect(),
+            payments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_writes_valid_structure() {
+        let pdf = String::from_utf8(render(&settings(), &details(2))).unwrap();
+
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("(INV-000042)"));
+        assert!(pdf.contains("(M\\374ller \\(Logistics\\))"));
+        assert!(pdf.contains("(1200.00)"));
+        assert!(pdf.contains("/Count 1"));
+
+        // startxref points at the cross-reference table
+        let start: usize = pdf.lines().rev().nth(1).unwrap().parse().unwrap();
+        assert!(pdf[start..].starts_with("xref\n"));
+    }
+
+    #[test]
+    fn test_render_starts_new_pages() {
+        let pdf = String::from_utf8(render(&settings(), &details(100))).unwrap();
+        assert!(pdf.contains("/Count 3"));
+        assert!(pdf.contains("(Page 3 of 3)"));
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(wrap("one two three", 7), vec!["one two", "three"]);
+        assert_eq!(wrap("abcdefghij", 4), vec!["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("", 4), vec![""]);
+    }
+}
@@ -0,0 +1,542 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{Datelike, Duration, Utc};
+use rust_decimal::{Decimal, RoundingStrategy};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::invoice::{
+    Invoice, InvoiceDetails, InvoiceQuery, InvoiceStatus, CreateInvoiceDto, RecordPaymentDto,
+    NewInvoice, NewInvoiceLine, NewInvoicePayment,
+    AgingBuckets, AgingQuery, ArAgingReport, CustomerStatement, StatementQuery
+};
+use crate::models::postgres::logistics::{JobStatus, TransportJob};
+use crate::repositories::postgres::invoice_repo::InvoiceRepositoryTrait;
+use crate::repositories::postgres::logistics_repo::{CustomerRepositoryTrait, TransportJobRepositoryTrait};
+use crate::repositories::postgres::settings_repo::SettingsRepositoryTrait;
+use crate::services::invoice_pdf;
+use crate::services::logistics_service::JobStatusHookTrait;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait InvoiceServiceTrait: Send + Sync {
+    /// Bills delivered jobs of a customer and marks them invoiced.
+    async fn create(&self, dto: CreateInvoiceDto, issued_by: Option<Uuid>) -> Result<InvoiceDetails, AppError>;
+    async fn list(&self, query: InvoiceQuery) -> Result<Vec<Invoice>, AppError>;
+    async fn get(&self, id: Uuid) -> Result<InvoiceDetails, AppError>;
+    /// The invoice number and the invoice rendered as a PDF.
+    async fn render_pdf(&self, id: Uuid) -> Result<(String, Vec<u8>), AppError>;
+    /// Marks the invoice's jobs paid once payments cover the total.
+    async fn record_payment(&self, id: Uuid, dto: RecordPaymentDto, recorded_by: Option<Uuid>) -> Result<InvoiceDetails, AppError>;
+    /// Outstanding balances per customer, bucketed 0-30, 31-60, 61-90 and over 90 days
+    /// since the invoice date.
+    async fn aging_report(&self, query: AgingQuery) -> Result<ArAgingReport, AppError>;
+    async fn customer_statement(&self, customer_id: Uuid, query: StatementQuery) -> Result<CustomerStatement, AppError>;
+}
+
+pub struct InvoiceService {
+    invoice_repo: Arc<dyn InvoiceRepositoryTrait>,
+    customer_repo: Arc<dyn CustomerRepositoryTrait>,
+    job_repo: Arc<dyn TransportJobRepositoryTrait>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait>,
+}
+
+impl InvoiceService {
+    pub fn new(
+        invoice_repo: Arc<dyn InvoiceRepositoryTrait>,
+        customer_repo: Arc<dyn CustomerRepositoryTrait>,
+        job_repo: Arc<dyn TransportJobRepositoryTrait>,
+        settings_repo: Arc<dyn SettingsRepositoryTrait>,
+    ) -> Self {
+        Self {
+            invoice_repo,
+            customer_repo,
+            job_repo,
+            settings_repo,
+        }
+    }
+
+    /// The requested jobs, or every billable job of the customer when none are named.
+    async fn jobs_to_bill(&self, customer_id: Uuid, job_ids: Option<Vec<Uuid>>) -> Result<Vec<TransportJob>, AppError> {
+        let billable = self.invoice_repo.find_billable_jobs(customer_id).await?;
+        let Some(job_ids) = job_ids else {
+            if billable.is_empty() {
+                return Err(AppError::BadRequest("The customer has no delivered jobs to invoice".into()));
+            }
+            return Ok(billable);
+        };
+        if job_ids.is_empty() {
+            return Err(AppError::BadRequest("At least one job is required".into()));
+        }
+
+        let mut seen = HashSet::new();
+        let mut jobs = Vec::with_capacity(job_ids.len());
+        for job_id in job_ids {
+            if !seen.insert(job_id) {
+                continue;
+            }
+            if let Some(job) = billable.iter().find(|job| job.id == job_id) {
+                jobs.push(job.clone());
+                continue;
+            }
+            // Explain why the job cannot be billed
+            let job = self.job_repo.find_by_id(job_id).await?
+                .ok_or_else(|| AppError::NotFound(format!("Transport job {} not found", job_id)))?;
+            return Err(AppError::BadRequest(if job.customer_id != customer_id {
+                format!("Job {} belongs to another customer", job_id)
+            } else if job.status == JobStatus::Delivered {
+                format!("Job {} is already on an invoice", job_id)
+            } else {
+                format!("Job {} is {:?}; only delivered jobs can be invoiced", job_id, job.status)
+            }));
+        }
+        Ok(jobs)
+    }
+}
+
+/// Tax on the subtotal at `rate` percent, rounded to the cent.
+fn tax_on(subtotal: Decimal, rate: Decimal) -> Decimal {
+    (subtotal * rate / Decimal::ONE_HUNDRED).round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero)
+}
+
+#[async_trait]
+impl InvoiceServiceTrait for InvoiceService {
+    async fn create(&self, dto: CreateInvoiceDto, issued_by: Option<Uuid>) -> Result<InvoiceDetails, AppError> {
+        let customer = self.customer_repo.find_by_id(dto.customer_id).await?
+            .ok_or(AppError::NotFound("Customer not found".into()))?;
+        let jobs = self.jobs_to_bill(customer.id, dto.job_ids).await?;
+        let settings = self.settings_repo.get().await?;
+
+        let lines: Vec<NewInvoiceLine> = jobs
+            .iter()
+            .map(|job| NewInvoiceLine {
+                job_id: job.id,
+                description: format!("Transport job {} ({})", job.id, job.created_at.format("%Y-%m-%d")),
+                amount: job.agreed_price,
+            })
+            .collect();
+        let subtotal: Decimal = lines.iter().map(|line| line.amount).sum();
+        let tax_amount = tax_on(subtotal, settings.tax_rate);
+        let issue_date = dto.issue_date.unwrap_or_else(|| Utc::now().date_naive());
+
+        let invoice = NewInvoice {
+            customer_id: customer.id,
+            billing_address: customer.billing_address,
+            currency: settings.currency,
+            tax_rate: settings.tax_rate,
+            subtotal,
+            tax_amount,
+            total: subtotal + tax_amount,
+            issue_date,
+            due_date: issue_date + Duration::days(settings.payment_terms_days.into()),
+            notes: dto.notes.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+            issued_by,
+            lines,
+        };
+        self.invoice_repo.create(invoice).await?
+            .ok_or_else(|| AppError::BadRequest(
+                "One of the jobs changed status while the invoice was being issued; reload and try again".into()
+            ))
+    }
+
+    async fn list(&self, query: InvoiceQuery) -> Result<Vec<Invoice>, AppError> {
+        self.invoice_repo.find_all(query).await
+    }
+
+    async fn get(&self, id: Uuid) -> Result<InvoiceDetails, AppError> {
+        self.invoice_repo.find_by_id(id).await?
+            .ok_or(AppError::NotFound("Invoice not found".into()))
+    }
+
+    async fn render_pdf(&self, id: Uuid) -> Result<(String, Vec<u8>), AppError> {
+        let details = self.get(id).await?;
+        let settings = self.settings_repo.get().await?;
+        let pdf = invoice_pdf::render(&settings, &details);
+        Ok((details.invoice.invoice_number, pdf))
+    }
+
+    async fn record_payment(&self, id: Uuid, dto: RecordPaymentDto, recorded_by: Option<Uuid>) -> Result<InvoiceDetails, AppError> {
+        if dto.amount <= Decimal::ZERO {
+            return Err(AppError::BadRequest("Payment amount must be positive".into()));
+        }
+        if dto.amount != dto.amount.round_dp(2) {
+            return Err(AppError::BadRequest("Payment amount cannot have fractions of a cent".into()));
+        }
+        let invoice = self.get(id).await?.invoice;
+        if invoice.status == InvoiceStatus::Paid {
+            return Err(AppError::BadRequest(format!("Invoice {} is already paid", invoice.invoice_number)));
+        }
+        if dto.amount > invoice.balance_due {
+            return Err(AppError::BadRequest(format!(
+                "Payment of {} exceeds the balance due of {} {}",
+                dto.amount, invoice.balance_due, invoice.currency
+            )));
+        }
+
+        let payment = NewInvoicePayment {
+            amount: dto.amount,
+            paid_on: dto.paid_on.unwrap_or_else(|| Utc::now().date_naive()),
+            method: dto.method.map(|m| m.trim().to_string()).filter(|m| !m.is_empty()),
+            reference: dto.reference.map(|r| r.trim().to_string()).filter(|r| !r.is_empty()),
+            recorded_by,
+        };
+        self.invoice_repo.record_payment(id, payment).await?
+            .ok_or_else(|| AppError::BadRequest(
+                "Another payment was recorded meanwhile and this one exceeds the balance due; reload and try again".into()
+            ))
+    }
+
+    async fn aging_report(&self, query: AgingQuery) -> Result<ArAgingReport, AppError> {
+        let as_of = query.as_of.unwrap_or_else(|| Utc::now().date_naive());
+        let customers = self.invoice_repo.find_aging(as_of, query.customer_id).await?;
+        let settings = self.settings_repo.get().await?;
+
+        let mut totals = AgingBuckets::default();
+        for customer in &customers {
+            totals.add(&customer.balances);
+        }
+        Ok(ArAgingReport { as_of, currency: settings.currency, customers, totals })
+    }
+
+    async fn customer_statement(&self, customer_id: Uuid, query: StatementQuery) -> Result<CustomerStatement, AppError> {
+        let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+        let from = query.from.unwrap_or_else(|| to.with_day(1).unwrap_or(to));
+        if from > to {
+            return Err(AppError::BadRequest("Statement period must start on or before its end".into()));
+        }
+        let customer = self.customer_repo.find_by_id(customer_id).await?
+            .ok_or(AppError::NotFound("Customer not found".into()))?;
+        let settings = self.settings_repo.get().await?;
+
+        let opening_balance = self.invoice_repo.find_balance_before(customer.id, from).await?;
+        let mut entries = self.invoice_repo.find_statement_entries(customer.id, from, to).await?;
+        for entry in &mut entries {
+            entry.balance += opening_balance;
+        }
+        let closing_balance = entries.last().map_or(opening_balance, |entry| entry.balance);
+        let aging = self.invoice_repo.find_aging(to, Some(customer.id)).await?
+            .into_iter()
+            .next()
+            .map(|aging| aging.balances)
+            .unwrap_or_default();
+
+        Ok(CustomerStatement {
+            customer_id: customer.id,
+            customer_name: customer.name,
+            billing_address: customer.billing_address,
+            currency: settings.currency,
+            from,
+            to,
+            opening_balance,
+            entries,
+            closing_balance,
+            aging,
+        })
+    }
+}
+
+/// Invoicing and payment move jobs to `Invoiced` and `Paid` together with the invoice
+/// records, so those statuses cannot be set by hand.
+#[async_trait]
+impl JobStatusHookTrait for InvoiceService {
+    async fn before_transition(&self, _job: &TransportJob, to: JobStatus) -> Result<(), AppError> {
+        match to {
+            JobStatus::Invoiced => Err(AppError::BadRequest("Jobs are marked invoiced by issuing an invoice for them".into())),
+            JobStatus::Paid => Err(AppError::BadRequest("Jobs are marked paid by recording payment of their invoice".into())),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use serde_json::json;
+    use crate::models::postgres::invoice::{CustomerAging, InvoiceLine, StatementEntry, StatementEntryKind};
+    use crate::models::postgres::logistics::Customer;
+    use crate::models::postgres::settings::AppSettings;
+    use crate::repositories::postgres::invoice_repo::MockInvoiceRepositoryTrait;
+    use crate::repositories::postgres::logistics_repo::{MockCustomerRepositoryTrait, MockTransportJobRepositoryTrait};
+    use crate::repositories::postgres::settings_repo::MockSettingsRepositoryTrait;
+
+    fn settings() -> AppSettings {
+        AppSettings {
+            currency: "EUR".into(),
+            tax_rate: Decimal::new(19, 0),
+            payment_terms_days: 14,
+            ..Default::default()
+        }
+    }
+
+    fn customer(id: Uuid) -> Customer {
+        Customer {
+            id,
+            name: "Acme Retail".into(),
+            contact_info: json!({}),
+            billing_address: "5 Market St".into(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            credit_limit: None,
+            block_over_credit_limit: false,
+        }
+    }
+
+    fn job(customer_id: Uuid, status: JobStatus, agreed_price: Decimal) -> TransportJob {
+        TransportJob {
+            id: Uuid::new_v4(),
+            customer_id,
+            status,
+            agreed_price,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn invoice(balance_due: Decimal, status: InvoiceStatus) -> InvoiceDetails {
+        let id = Uuid::new_v4();
+        InvoiceDetails {
+            invoice: Invoice {
+                id,
+                invoice_number: "INV-000001".into(),
+                customer_id: Uuid::new_v4(),
+                customer_name: "Acme Retail".into(),
+                billing_address: "5 Market St".into(),
+                currency: "EUR".into(),
+                tax_rate: Decimal::new(19, 0),
+                subtotal: Decimal::new(10000, 2),
+                tax_amount: Decimal::new(1900, 2),
+                total: Decimal::new(11900, 2),
+                amount_paid: Decimal::new(11900, 2) - balance_due,
+                balance_due,
+                status,
+                issue_date: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+                due_date: NaiveDate::from_ymd_opt(2026, 10, 15).unwrap(),
+                notes: None,
+                issued_by: None,
+                created_at: Utc::now(),
+            },
+            lines: vec![InvoiceLine {
+                id: Uuid::new_v4(),
+                invoice_id: id,
+                job_id: Uuid::new_v4(),
+                description: "Transport job".into(),
+                amount: Decimal::new(10000, 2),
+            }],
+            payments: Vec::new(),
+        }
+    }
+
+    fn service(
+        invoice_repo: MockInvoiceRepositoryTrait,
+        customer_repo: MockCustomerRepositoryTrait,
+        job_repo: MockTransportJobRepositoryTrait,
+    ) -> InvoiceService {
+        let mut settings_repo = MockSettingsRepositoryTrait::new();
+        settings_repo.expect_get().returning(|| Ok(settings()));
+        InvoiceService::new(Arc::new(invoice_repo), Arc::new(customer_repo), Arc::new(job_repo), Arc::new(settings_repo))
+    }
+
+    #[tokio::test]
+    async fn test_create_bills_all_delivered_jobs_with_tax() {
+        let customer_id = Uuid::new_v4();
+        let jobs = vec![
+            job(customer_id, JobStatus::Delivered, Decimal::new(12050, 2)),
+            job(customer_id, JobStatus::Delivered, Decimal::new(7999, 2)),
+        ];
+        let job_ids: Vec<Uuid> = jobs.iter().map(|j| j.id).collect();
+
+        let mut customer_repo = MockCustomerRepositoryTrait::new();
+        customer_repo.expect_find_by_id().returning(|id| Ok(Some(customer(id))));
+        let mut invoice_repo = MockInvoiceRepositoryTrait::new();
+        invoice_repo.expect_find_billable_jobs().returning(move |_| Ok(jobs.clone()));
+        invoice_repo
+            .expect_create()
+            .withf(move |new: &NewInvoice| {
+                new.lines.iter().map(|l| l.job_id).collect::<Vec<_>>() == job_ids
+                    && new.subtotal == Decimal::new(20049, 2)
+                    // 19% of 200.49 is 38.0931
+                    && new.tax_amount == Decimal::new(3809, 2)
+                    && new.total == Decimal::new(23858, 2)
+                    && new.currency == "EUR"
+                    && new.billing_address == "5 Market St"
+                    && new.due_date == NaiveDate::from_ymd_opt(2026, 10, 15).unwrap()
+            })
+            .times(1)
+            .returning(|_| Ok(Some(invoice(Decimal::new(23858, 2), InvoiceStatus::Issued))));
+
+        let service = service(invoice_repo, customer_repo, MockTransportJobRepositoryTrait::new());
+        let dto = CreateInvoiceDto {
+            customer_id,
+            job_ids: None,
+            issue_date: NaiveDate::from_ymd_opt(2026, 10, 1),
+            notes: None,
+        };
+        assert!(service.create(dto, None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_job_of_another_customer() {
+        let customer_id = Uuid::new_v4();
+        let other = job(Uuid::new_v4(), JobStatus::Delivered, Decimal::ONE);
+        let other_id = other.id;
+
+        let mut customer_repo = MockCustomerRepositoryTrait::new();
+        customer_repo.expect_find_by_id().returning(|id| Ok(Some(customer(id))));
+        let mut invoice_repo = MockInvoiceRepositoryTrait::new();
+        invoice_repo.expect_find_billable_jobs().returning(|_| Ok(Vec::new()));
+        invoice_repo.expect_create().never();
+        let mut job_repo = MockTransportJobRepositoryTrait::new();
+        job_repo.expect_find_by_id().returning(move |_| Ok(Some(other.clone())));
+
+        let service = service(invoice_repo, customer_repo, job_repo);
+        let dto = CreateInvoiceDto {
+            customer_id,
+            job_ids: Some(vec![other_id]),
+            issue_date: None,
+            notes: None,
+        };
+        match service.create(dto, None).await {
+            Err(AppError::BadRequest(message)) => assert!(message.contains("another customer")),
+            other => panic!("expected BadRequest, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_payment_rejects_overpayment() {
+        let mut invoice_repo = MockInvoiceRepositoryTrait::new();
+        invoice_repo
+            .expect_find_by_id()
+            .returning(|_| Ok(Some(invoice(Decimal::new(5000, 2), InvoiceStatus::PartiallyPaid))));
+        invoice_repo.expect_record_payment().never();
+
+        let service = service(invoice_repo, MockCustomerRepositoryTrait::new(), MockTransportJobRepositoryTrait::new());
+        let dto = RecordPaymentDto {
+            amount: Decimal::new(5001, 2),
+            paid_on: None,
+            method: None,
+            reference: None,
+        };
+        let result = service.record_payment(Uuid::new_v4(), dto, None).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_record_payment_settles_balance() {
+        let mut invoice_repo = MockInvoiceRepositoryTrait::new();
+        invoice_repo
+            .expect_find_by_id()
+            .returning(|_| Ok(Some(invoice(Decimal::new(5000, 2), InvoiceStatus::PartiallyPaid))));
+        invoice_repo
+            .expect_record_payment()
+            .withf(|_, payment| payment.amount == Decimal::new(5000, 2) && payment.method.as_deref() == Some("Bank transfer"))
+            .times(1)
+            .returning(|_, _| Ok(Some(invoice(Decimal::ZERO, InvoiceStatus::Paid))));
+
+        let service = service(invoice_repo, MockCustomerRepositoryTrait::new(), MockTransportJobRepositoryTrait::new());
+        let dto = RecordPaymentDto {
+            amount: Decimal::new(5000, 2),
+            paid_on: None,
+            method: Some(" Bank transfer ".into()),
+            reference: None,
+        };
+        let paid = service.record_payment(Uuid::new_v4(), dto, None).await.unwrap();
+        assert_eq!(paid.invoice.status, InvoiceStatus::Paid);
+    }
+
+    #[tokio::test]
+    async fn test_manual_invoiced_and_paid_statuses_are_refused() {
+        let service = service(
+            MockInvoiceRepositoryTrait::new(),
+            MockCustomerRepositoryTrait::new(),
+            MockTransportJobRepositoryTrait::new(),
+        );
+        let delivered = job(Uuid::new_v4(), JobStatus::Delivered, Decimal::ONE);
+
+        assert!(service.before_transition(&delivered, JobStatus::Invoiced).await.is_err());
+        assert!(service.before_transition(&delivered, JobStatus::Paid).await.is_err());
+        assert!(service.before_transition(&delivered, JobStatus::Cancelled).await.is_ok());
+    }
+
+    fn aging(customer_id: Uuid, days_0_30: i64, days_over_90: i64) -> CustomerAging {
+        CustomerAging {
+            customer_id,
+            customer_name: "Acme Retail".into(),
+            credit_limit: None,
+            balances: AgingBuckets {
+                days_0_30: Decimal::new(days_0_30, 2),
+                days_over_90: Decimal::new(days_over_90, 2),
+                total: Decimal::new(days_0_30 + days_over_90, 2),
+                ..AgingBuckets::default()
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_aging_report_totals_buckets() {
+        let as_of = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap();
+        let mut invoice_repo = MockInvoiceRepositoryTrait::new();
+        invoice_repo
+            .expect_find_aging()
+            .withf(move |date, customer| *date == as_of && customer.is_none())
+            .times(1)
+            .returning(|_, _| Ok(vec![aging(Uuid::new_v4(), 10000, 5000), aging(Uuid::new_v4(), 2500, 0)]));
+
+        let service = service(invoice_repo, MockCustomerRepositoryTrait::new(), MockTransportJobRepositoryTrait::new());
+        let report = service.aging_report(AgingQuery { as_of: Some(as_of), customer_id: None }).await.unwrap();
+        assert_eq!(report.currency, "EUR");
+        assert_eq!(report.totals.days_0_30, Decimal::new(12500, 2));
+        assert_eq!(report.totals.days_over_90, Decimal::new(5000, 2));
+        assert_eq!(report.totals.total, Decimal::new(17500, 2));
+    }
+
+    #[tokio::test]
+    async fn test_customer_statement_carries_opening_balance() {
+        let customer_id = Uuid::new_v4();
+        let from = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 10, 31).unwrap();
+        let entry = |kind, amount, balance| StatementEntry {
+            date: from,
+            kind,
+            invoice_id: Uuid::new_v4(),
+            invoice_number: "INV-000002".into(),
+            reference: None,
+            amount: Decimal::new(amount, 2),
+            balance: Decimal::new(balance, 2),
+        };
+        let entries = vec![
+            entry(StatementEntryKind::Invoice, 11900, 11900),
+            entry(StatementEntryKind::Payment, -5000, 6900),
+        ];
+
+        let mut customer_repo = MockCustomerRepositoryTrait::new();
+        customer_repo.expect_find_by_id().returning(|id| Ok(Some(customer(id))));
+        let mut invoice_repo = MockInvoiceRepositoryTrait::new();
+        invoice_repo.expect_find_balance_before().returning(|_, _| Ok(Decimal::new(3000, 2)));
+        invoice_repo
+            .expect_find_statement_entries()
+            .withf(move |id, start, end| *id == customer_id && *start == from && *end == to)
+            .returning(move |_, _, _| Ok(entries.clone()));
+        invoice_repo
+            .expect_find_aging()
+            .withf(move |date, customer| *date == to && *customer == Some(customer_id))
+            .returning(|_, id| Ok(vec![aging(id.unwrap_or_default(), 9900, 0)]));
+
+        let service = service(invoice_repo, customer_repo, MockTransportJobRepositoryTrait::new());
+        let statement = service
+            .customer_statement(customer_id, StatementQuery { from: None, to: Some(to) })
+            .await
+            .unwrap();
+        assert_eq!(statement.from, from);
+        assert_eq!(statement.opening_balance, Decimal::new(3000, 2));
+        assert_eq!(statement.entries[0].balance, Decimal::new(14900, 2));
+        assert_eq!(statement.closing_balance, Decimal::new(9900, 2));
+        assert_eq!(statement.aging.total, statement.closing_balance);
+
+        let result = service
+            .customer_statement(customer_id, StatementQuery { from: Some(to), to: Some(from) })
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,197 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDate};
+
+use crate::error::AppError;
+use crate::models::postgres::driver::LicenseExpiry;
+use crate::models::postgres::maintenance::{Alert, AlertSeverity};
+use crate::repositories::postgres::driver_repo::DriverRepositoryTrait;
+use crate::repositories::postgres::maintenance_repo::AlertRepositoryTrait;
+use crate::repositories::postgres::settings_repo::SettingsRepositoryTrait;
+use crate::services::expiry_alert::{sync_expiry_alert, Expiry};
+
+pub const LICENSE_EXPIRING_ALERT: &str = "License Expiring";
+pub const LICENSE_EXPIRED_ALERT: &str = "License Expired";
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait LicenseServiceTrait: Send + Sync {
+    /// Raises alerts for licenses that expired or expire within the warning window, and
+    /// resolves them once a license has been renewed. Renewals are still resolved while
+    /// `notify_license_expiry` is off. Returns the alerts raised.
+    async fn raise_expiry_alerts(&self, today: NaiveDate) -> Result<Vec<Alert>, AppError>;
+}
+
+pub struct LicenseService {
+    driver_repo: Arc<dyn DriverRepositoryTrait>,
+    alert_repo: Arc<dyn AlertRepositoryTrait>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait>,
+}
+
+impl LicenseService {
+    pub fn new(
+        driver_repo: Arc<dyn DriverRepositoryTrait>,
+        alert_repo: Arc<dyn AlertRepositoryTrait>,
+        settings_repo: Arc<dyn SettingsRepositoryTrait>,
+    ) -> Self {
+        Self { driver_repo, alert_repo, settings_repo }
+    }
+}
+
+fn driver_label(license: &LicenseExpiry) -> String {
+    match &license.name {
+        Some(name) => format!("{} (license {})", name, license.license_number),
+        None => format!("License {}", license.license_number),
+    }
+}
+
+#[async_trait]
+impl LicenseServiceTrait for LicenseService {
+    async fn raise_expiry_alerts(&self, today: NaiveDate) -> Result<Vec<Alert>, AppError> {
+        let settings = self.settings_repo.get().await?;
+        let warn_until = today + Duration::days(settings.license_expiry_warning_days.max(0).into());
+
+        let mut alerts = Vec::new();
+        for license in self.driver_repo.find_license_expiries().await? {
+            let expiry = Expiry {
+                entity_id: license.driver_id,
+                label: driver_label(&license),
+                expiry_date: license.license_expiry,
+                expiring_alert: LICENSE_EXPIRING_ALERT.to_string(),
+                expired_alert: LICENSE_EXPIRED_ALERT.to_string(),
+                expired_severity: AlertSeverity::High,
+            };
+            alerts.extend(sync_expiry_alert(self.alert_repo.as_ref(), expiry, today, warn_until, settings.notify_license_expiry).await?);
+        }
+        Ok(alerts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+    use crate::models::postgres::maintenance::CreateAlertDto;
+    use crate::models::postgres::settings::AppSettings;
+    use crate::repositories::postgres::driver_repo::MockDriverRepositoryTrait;
+    use crate::repositories::postgres::maintenance_repo::MockAlertRepositoryTrait;
+    use crate::repositories::postgres::settings_repo::MockSettingsRepositoryTrait;
+
+    fn settings(notify_license_expiry: bool) -> AppSettings {
+        AppSettings {
+            notify_license_expiry,
+            ..Default::default()
+        }
+    }
+
+    fn settings_repo(notify_license_expiry: bool) -> MockSettingsRepositoryTrait {
+        let mut repo = MockSettingsRepositoryTrait::new();
+        repo.expect_get().returning(move || Ok(settings(notify_license_expiry)));
+        repo
+    }
+
+    fn license(expiry: NaiveDate) -> LicenseExpiry {
+        LicenseExpiry {
+            driver_id: Uuid::new_v4(),
+            license_number: "D1234567".into(),
+            name: Some("Sam Rivera".into()),
+            license_expiry: expiry,
+        }
+    }
+
+    fn alert(dto: &CreateAlertDto) -> Alert {
+        Alert {
+            id: Uuid::new_v4(),
+            entity_id: dto.entity_id,
+            r#type: dto.r#type.clone(),
+            severity: dto.severity,
+            is_resolved: false,
+            created_at: Utc::now(),
+            resolved_at: None,
+            message: dto.message.clone(),
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 17).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_raises_expiring_and_expired_alerts() {
+        let expiring = license(today() + Duration::days(10));
+        let expired = license(today() - Duration::days(1));
+        let expiring_id = expiring.driver_id;
+        let expired_id = expired.driver_id;
+
+        let mut driver_repo = MockDriverRepositoryTrait::new();
+        driver_repo
+            .expect_find_license_expiries()
+            .returning(move || Ok(vec![expiring.clone(), expired.clone()]));
+
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo.expect_resolve_open().times(2).returning(|_, _| Ok(0));
+        alert_repo
+            .expect_create_unless_open()
+            .withf(move |dto| {
+                (dto.entity_id == expiring_id && dto.r#type == LICENSE_EXPIRING_ALERT && dto.severity == AlertSeverity::Medium)
+                    || (dto.entity_id == expired_id && dto.r#type == LICENSE_EXPIRED_ALERT && dto.severity == AlertSeverity::High)
+            })
+            .times(2)
+            .returning(|dto| Ok(Some(alert(&dto))));
+
+        let service = LicenseService::new(Arc::new(driver_repo), Arc::new(alert_repo), Arc::new(settings_repo(true)));
+        let alerts = service.raise_expiry_alerts(today()).await.unwrap();
+        assert_eq!(alerts.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_renewed_license_resolves_alerts() {
+        let renewed = license(today() + Duration::days(365));
+        let driver_id = renewed.driver_id;
+
+        let mut driver_repo = MockDriverRepositoryTrait::new();
+        driver_repo.expect_find_license_expiries().returning(move || Ok(vec![renewed.clone()]));
+
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo
+            .expect_resolve_open()
+            .withf(move |id, types| *id == driver_id && types.len() == 2)
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        let service = LicenseService::new(Arc::new(driver_repo), Arc::new(alert_repo), Arc::new(settings_repo(true)));
+        let alerts = service.raise_expiry_alerts(today()).await.unwrap();
+        assert!(alerts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_respects_notification_setting() {
+        let expiring = license(today() + Duration::days(10));
+        let renewed = license(today() + Duration::days(365));
+        let renewed_id = renewed.driver_id;
+
+        let mut driver_repo = MockDriverRepositoryTrait::new();
+        driver_repo
+            .expect_find_license_expiries()
+            .returning(move || Ok(vec![expiring.clone(), renewed.clone()]));
+
+        // Nothing new is raised, but the renewed license's alerts are still resolved
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo
+            .expect_resolve_open()
+            .withf(move |id, types| *id == renewed_id && types.len() == 2)
+            .times(1)
+            .returning(|_, _| Ok(1));
+        alert_repo
+            .expect_resolve_open()
+            .withf(|_, types| types == &vec![LICENSE_EXPIRED_ALERT.to_string()])
+            .times(1)
+            .returning(|_, _| Ok(0));
+        alert_repo.expect_create_unless_open().never();
+
+        let service = LicenseService::new(Arc::new(driver_repo), Arc::new(alert_repo), Arc::new(settings_repo(false)));
+        let alerts = service.raise_expiry_alerts(today()).await.unwrap();
+        assert!(alerts.is_empty());
+    }
+}
@@ -1,16 +1,27 @@
 use std::sync::Arc;
+use rust_decimal::Decimal;
 use uuid::Uuid;
 use async_trait::async_trait;
-use crate::error::AppError;
+use crate::error::{AppError, ConflictKind};
 use crate::models::postgres::logistics::{
-    Customer, CreateCustomerDto,
-    TransportJob, CreateTransportJobDto, JobStatus,
+    Customer, CreateCustomerDto, UpdateCreditLimitDto,
+    TransportJob, CreateTransportJobDto, JobStatus, UpdateJobStatusDto, JobStatusHistory,
     Route, CreateRouteDto,
-    Shipment, CreateShipmentDto
+    Shipment, CreateShipmentDto,
+    JobLeg, AddJobLegDto
 };
+use crate::models::postgres::assignment::AssignmentStatus;
+use crate::models::postgres::maintenance::{AlertSeverity, CreateAlertDto};
 use crate::repositories::postgres::logistics_repo::{
-    CustomerRepositoryTrait, TransportJobRepositoryTrait, RouteRepositoryTrait, ShipmentRepositoryTrait
+    CustomerRepositoryTrait, TransportJobRepositoryTrait, RouteRepositoryTrait, ShipmentRepositoryTrait,
+    JobLegRepositoryTrait
 };
+use crate::repositories::postgres::assignment_repo::AssignmentRepositoryTrait;
+use crate::repositories::postgres::maintenance_repo::AlertRepositoryTrait;
+use crate::repositories::postgres::unit_of_work::UnitOfWorkTrait;
+
+/// Alert raised against a customer when a new job takes them over their credit limit.
+pub const CREDIT_LIMIT_ALERT: &str = "Credit Limit Exceeded";
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
@@ -20,12 +31,17 @@ pub trait LogisticsServiceTrait: Send + Sync {
     async fn get_customer(&self, id: Uuid) -> Result<Customer, AppError>;
     async fn list_customers(&self) -> Result<Vec<Customer>, AppError>;
     async fn delete_customer(&self, id: Uuid) -> Result<(), AppError>;
+    async fn update_customer_credit_limit(&self, id: Uuid, dto: UpdateCreditLimitDto) -> Result<Customer, AppError>;
 
     // Transport Job
-    async fn create_job(&self, dto: CreateTransportJobDto) -> Result<TransportJob, AppError>;
+    /// Creates the job after checking it against the customer's credit limit, refusing
+    /// it or raising an alert when the customer would owe more than the limit.
+    async fn create_job(&self, dto: CreateTransportJobDto, created_by: Option<Uuid>) -> Result<TransportJob, AppError>;
     async fn list_jobs(&self) -> Result<Vec<TransportJob>, AppError>;
     async fn get_job(&self, id: Uuid) -> Result<TransportJob, AppError>;
-    async fn update_job_status(&self, id: Uuid, status: JobStatus) -> Result<TransportJob, AppError>;
+    /// Moves the job along its workflow, running the status hooks around the change.
+    async fn update_job_status(&self, id: Uuid, dto: UpdateJobStatusDto, changed_by: Option<Uuid>) -> Result<TransportJob, AppError>;
+    async fn get_job_status_history(&self, id: Uuid) -> Result<Vec<JobStatusHistory>, AppError>;
 
     // Route
     async fn create_route(&self, dto: CreateRouteDto) -> Result<Route, AppError>;
@@ -34,6 +50,26 @@ pub trait LogisticsServiceTrait: Send + Sync {
     // Shipment
     async fn create_shipment(&self, dto: CreateShipmentDto) -> Result<Shipment, AppError>;
     async fn get_job_shipments(&self, job_id: Uuid) -> Result<Vec<Shipment>, AppError>;
+
+    // Job legs
+    async fn get_job_legs(&self, job_id: Uuid) -> Result<Vec<JobLeg>, AppError>;
+    async fn add_job_leg(&self, job_id: Uuid, dto: AddJobLegDto) -> Result<JobLeg, AppError>;
+    async fn remove_job_leg(&self, job_id: Uuid, assignment_id: Uuid) -> Result<(), AppError>;
+}
+
+/// Side effects of job status changes. `before_transition` can refuse a change, e.g.
+/// delivery without proof; `after_transition` runs once the change is saved and its
+/// failures are only logged.
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait JobStatusHookTrait: Send + Sync {
+    async fn before_transition(&self, _job: &TransportJob, _to: JobStatus) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    async fn after_transition(&self, _job: &TransportJob, _from: JobStatus) -> Result<(), AppError> {
+        Ok(())
+    }
 }
 
 pub struct LogisticsService {
@@ -41,24 +77,94 @@ pub struct LogisticsService {
     job_repo: Arc<dyn TransportJobRepositoryTrait>,
     route_repo: Arc<dyn RouteRepositoryTrait>,
     shipment_repo: Arc<dyn ShipmentRepositoryTrait>,
+    leg_repo: Arc<dyn JobLegRepositoryTrait>,
+    assignment_repo: Arc<dyn AssignmentRepositoryTrait>,
+    alert_repo: Arc<dyn AlertRepositoryTrait>,
+    unit_of_work: Arc<dyn UnitOfWorkTrait>,
+    status_hooks: Vec<Arc<dyn JobStatusHookTrait>>,
 }
 
 impl LogisticsService {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         customer_repo: Arc<dyn CustomerRepositoryTrait>,
         job_repo: Arc<dyn TransportJobRepositoryTrait>,
         route_repo: Arc<dyn RouteRepositoryTrait>,
         shipment_repo: Arc<dyn ShipmentRepositoryTrait>,
+        leg_repo: Arc<dyn JobLegRepositoryTrait>,
+        assignment_repo: Arc<dyn AssignmentRepositoryTrait>,
+        alert_repo: Arc<dyn AlertRepositoryTrait>,
+        unit_of_work: Arc<dyn UnitOfWorkTrait>,
+        status_hooks: Vec<Arc<dyn JobStatusHookTrait>>,
     ) -> Self {
         Self {
             customer_repo,
             job_repo,
             route_repo,
             shipment_repo,
+            leg_repo,
+            assignment_repo,
+            alert_repo,
+            unit_of_work,
+            status_hooks,
         }
     }
 }
 
+/// Outcome of checking a new job against the customer's credit limit.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum CreditCheck {
+    /// The customer has no limit
+    Unlimited,
+    WithinLimit,
+    /// Over the limit of a customer that is not blocked; the alert message
+    OverLimit(String),
+}
+
+/// Refuses the job if it takes a customer blocked over their limit past it. `exposure`
+/// includes the new job.
+pub(crate) fn check_credit(customer: &Customer, exposure: Decimal) -> Result<CreditCheck, AppError> {
+    let Some(limit) = customer.credit_limit else {
+        return Ok(CreditCheck::Unlimited);
+    };
+    if exposure <= limit {
+        return Ok(CreditCheck::WithinLimit);
+    }
+
+    let message = format!(
+        "{} would owe {} with this job, over their credit limit of {}",
+        customer.name, exposure, limit
+    );
+    if customer.block_over_credit_limit {
+        return Err(AppError::BadRequest(format!("Credit limit exceeded: {}", message)));
+    }
+    Ok(CreditCheck::OverLimit(message))
+}
+
+/// Raises or clears the customer's credit limit alert once the job is stored. Failures
+/// are logged; the job stands either way.
+pub(crate) async fn record_credit_check(alert_repo: &dyn AlertRepositoryTrait, customer: &Customer, check: CreditCheck) {
+    let result = match check {
+        CreditCheck::Unlimited => return,
+        CreditCheck::WithinLimit => alert_repo
+            .resolve_open(customer.id, vec![CREDIT_LIMIT_ALERT.to_string()])
+            .await
+            .map(|_| ()),
+        CreditCheck::OverLimit(message) => alert_repo
+            .create_unless_open(CreateAlertDto {
+                entity_id: customer.id,
+                r#type: CREDIT_LIMIT_ALERT.to_string(),
+                severity: AlertSeverity::High,
+                message: Some(message),
+            })
+            .await
+            .map(|_| ()),
+    };
+    if let Err(e) = result {
+        eprintln!("Failed to update credit limit alert for customer {}: {}", customer.id, e);
+    }
+}
+
 #[async_trait]
 impl LogisticsServiceTrait for LogisticsService {
     // Customer
@@ -79,9 +185,38 @@ impl LogisticsServiceTrait for LogisticsService {
         self.customer_repo.delete(id).await
     }
 
+    async fn update_customer_credit_limit(&self, id: Uuid, dto: UpdateCreditLimitDto) -> Result<Customer, AppError> {
+        if dto.credit_limit.is_some_and(|limit| limit.is_sign_negative()) {
+            return Err(AppError::BadRequest("Credit limit cannot be negative".into()));
+        }
+        self.customer_repo.update_credit_limit(id, dto).await?
+            .ok_or(AppError::NotFound("Customer not found".into()))
+    }
+
     // Transport Job
-    async fn create_job(&self, dto: CreateTransportJobDto) -> Result<TransportJob, AppError> {
-        self.job_repo.create(dto).await
+    async fn create_job(&self, dto: CreateTransportJobDto, created_by: Option<Uuid>) -> Result<TransportJob, AppError> {
+        // Delivery and billing hooks only run on status changes, so no job may skip them
+        if dto.status != JobStatus::Pending {
+            return Err(AppError::BadRequest(format!(
+                "Jobs must be created as Pending, not {:?}",
+                dto.status
+            )));
+        }
+        // The customer stays locked until the job is stored, so concurrent jobs cannot
+        // each pass the credit check against the same exposure
+        let mut tx = self.unit_of_work.begin().await?;
+        let customer = tx.lock_customer(dto.customer_id).await?
+            .ok_or(AppError::NotFound("Customer not found".into()))?;
+        let credit = match customer.credit_limit {
+            Some(_) => check_credit(&customer, tx.credit_exposure(customer.id).await? + dto.agreed_price)?,
+            None => CreditCheck::Unlimited,
+        };
+
+        let job = tx.create_job(dto, created_by).await?;
+        tx.commit().await?;
+
+        record_credit_check(self.alert_repo.as_ref(), &customer, credit).await;
+        Ok(job)
     }
 
     async fn list_jobs(&self) -> Result<Vec<TransportJob>, AppError> {
@@ -93,8 +228,43 @@ impl LogisticsServiceTrait for LogisticsService {
             .ok_or(AppError::NotFound("Transport Job not found".into()))
     }
 
-    async fn update_job_status(&self, id: Uuid, status: JobStatus) -> Result<TransportJob, AppError> {
-        self.job_repo.update_status(id, status).await
+    async fn update_job_status(&self, id: Uuid, dto: UpdateJobStatusDto, changed_by: Option<Uuid>) -> Result<TransportJob, AppError> {
+        let job = self.get_job(id).await?;
+        let to = dto.status;
+        if !job.status.can_transition_to(to) {
+            return Err(AppError::BadRequest(format!(
+                "Cannot move job from {:?} to {:?}",
+                job.status, to
+            )));
+        }
+        let reason = dto.reason.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+        if to == JobStatus::Cancelled && reason.is_none() {
+            return Err(AppError::BadRequest("A reason is required to cancel a job".into()));
+        }
+
+        for hook in &self.status_hooks {
+            hook.before_transition(&job, to).await?;
+        }
+
+        let from = job.status;
+        let updated = self.job_repo.update_status(id, from, to, changed_by, reason).await?
+            .ok_or_else(|| AppError::conflict(
+                ConflictKind::Stale,
+                format!("Job {} was changed by another request; reload and try again", id),
+            ))?;
+
+        for hook in &self.status_hooks {
+            if let Err(e) = hook.after_transition(&updated, from).await {
+                eprintln!("Job status hook failed for job {} ({:?} to {:?}): {}", id, from, to, e);
+            }
+        }
+
+        Ok(updated)
+    }
+
+    async fn get_job_status_history(&self, id: Uuid) -> Result<Vec<JobStatusHistory>, AppError> {
+        self.get_job(id).await?;
+        self.job_repo.find_status_history(id).await
     }
 
     // Route
@@ -114,4 +284,326 @@ impl LogisticsServiceTrait for LogisticsService {
     async fn get_job_shipments(&self, job_id: Uuid) -> Result<Vec<Shipment>, AppError> {
         self.shipment_repo.find_by_job_id(job_id).await
     }
+
+    // Job legs
+    async fn get_job_legs(&self, job_id: Uuid) -> Result<Vec<JobLeg>, AppError> {
+        self.get_job(job_id).await?;
+        self.leg_repo.find_by_job_id(job_id).await
+    }
+
+    async fn add_job_leg(&self, job_id: Uuid, dto: AddJobLegDto) -> Result<JobLeg, AppError> {
+        if dto.leg_number.is_some_and(|leg| leg < 1) {
+            return Err(AppError::BadRequest("Leg numbers start at 1".into()));
+        }
+        self.get_job(job_id).await?;
+
+        let assignment = self.assignment_repo.find_by_id(dto.assignment_id).await?
+            .ok_or(AppError::NotFound("Assignment not found".into()))?;
+        if assignment.status == AssignmentStatus::Cancelled {
+            return Err(AppError::BadRequest("Cancelled assignments cannot be added to a job".into()));
+        }
+
+        self.leg_repo.create(job_id, assignment.id, dto.leg_number).await
+    }
+
+    async fn remove_job_leg(&self, job_id: Uuid, assignment_id: Uuid) -> Result<(), AppError> {
+        if !self.leg_repo.delete(job_id, assignment_id).await? {
+            return Err(AppError::NotFound("Assignment is not a leg of this job".into()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::models::postgres::assignment::VehicleAssignment;
+    use crate::repositories::postgres::assignment_repo::MockAssignmentRepositoryTrait;
+    use crate::repositories::postgres::maintenance_repo::MockAlertRepositoryTrait;
+    use crate::repositories::postgres::unit_of_work::{MockRepositoryTransactionTrait, MockUnitOfWorkTrait};
+    use crate::models::postgres::maintenance::Alert;
+    use crate::repositories::postgres::logistics_repo::{
+        MockCustomerRepositoryTrait, MockTransportJobRepositoryTrait, MockRouteRepositoryTrait,
+        MockShipmentRepositoryTrait, MockJobLegRepositoryTrait,
+    };
+
+    fn job(id: Uuid) -> TransportJob {
+        TransportJob {
+            id,
+            customer_id: Uuid::new_v4(),
+            status: JobStatus::InProgress,
+            agreed_price: Decimal::new(90000, 2),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn assignment(id: Uuid, status: AssignmentStatus) -> VehicleAssignment {
+        VehicleAssignment {
+            id,
+            vehicle_id: Uuid::new_v4(),
+            driver_id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            status,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            started_at: None,
+            completed_at: None,
+            cancelled_at: None,
+            cancel_reason: None,
+        }
+    }
+
+    fn service(leg_repo: MockJobLegRepositoryTrait, statuses: Vec<(Uuid, AssignmentStatus)>) -> LogisticsService {
+        let mut job_repo = MockTransportJobRepositoryTrait::new();
+        job_repo.expect_find_by_id().returning(|id| Ok(Some(job(id))));
+        let mut assignment_repo = MockAssignmentRepositoryTrait::new();
+        assignment_repo.expect_find_by_id().returning(move |id| {
+            Ok(statuses.iter().find(|(known, _)| *known == id).map(|(_, status)| assignment(id, *status)))
+        });
+        LogisticsService::new(
+            Arc::new(MockCustomerRepositoryTrait::new()),
+            Arc::new(job_repo),
+            Arc::new(MockRouteRepositoryTrait::new()),
+            Arc::new(MockShipmentRepositoryTrait::new()),
+            Arc::new(leg_repo),
+            Arc::new(assignment_repo),
+            Arc::new(MockAlertRepositoryTrait::new()),
+            Arc::new(MockUnitOfWorkTrait::new()),
+            Vec::new(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_add_job_leg_appends_assignment() {
+        let (job_id, assignment_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut leg_repo = MockJobLegRepositoryTrait::new();
+        leg_repo
+            .expect_create()
+            .withf(move |job, assignment, leg| *job == job_id && *assignment == assignment_id && leg.is_none())
+            .times(1)
+            .returning(|job_id, assignment_id, _| Ok(JobLeg {
+                job_id,
+                leg_number: 2,
+                assignment_id,
+                vehicle_id: Uuid::new_v4(),
+                license_plate: "TRK-2".into(),
+                driver_id: Uuid::new_v4(),
+                driver_name: Some("Jo".into()),
+                start_time: Utc::now(),
+                end_time: None,
+                status: AssignmentStatus::Scheduled,
+            }));
+        let service = service(leg_repo, vec![(assignment_id, AssignmentStatus::Scheduled)]);
+
+        let leg = service.add_job_leg(job_id, AddJobLegDto { assignment_id, leg_number: None }).await.unwrap();
+        assert_eq!(leg.leg_number, 2);
+    }
+
+    #[tokio::test]
+    async fn test_add_job_leg_rejects_cancelled_and_unknown_assignments() {
+        let (job_id, cancelled_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let service = service(MockJobLegRepositoryTrait::new(), vec![(cancelled_id, AssignmentStatus::Cancelled)]);
+
+        let result = service.add_job_leg(job_id, AddJobLegDto { assignment_id: cancelled_id, leg_number: None }).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+
+        let result = service.add_job_leg(job_id, AddJobLegDto { assignment_id: Uuid::new_v4(), leg_number: None }).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let result = service.add_job_leg(job_id, AddJobLegDto { assignment_id: cancelled_id, leg_number: Some(0) }).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("start at 1")));
+    }
+
+    fn status_service(status: JobStatus, job_repo_setup: impl FnOnce(&mut MockTransportJobRepositoryTrait), hooks: Vec<Arc<dyn JobStatusHookTrait>>) -> LogisticsService {
+        let mut job_repo = MockTransportJobRepositoryTrait::new();
+        job_repo.expect_find_by_id().returning(move |id| Ok(Some(TransportJob { status, ..job(id) })));
+        job_repo_setup(&mut job_repo);
+        LogisticsService::new(
+            Arc::new(MockCustomerRepositoryTrait::new()),
+            Arc::new(job_repo),
+            Arc::new(MockRouteRepositoryTrait::new()),
+            Arc::new(MockShipmentRepositoryTrait::new()),
+            Arc::new(MockJobLegRepositoryTrait::new()),
+            Arc::new(MockAssignmentRepositoryTrait::new()),
+            Arc::new(MockAlertRepositoryTrait::new()),
+            Arc::new(MockUnitOfWorkTrait::new()),
+            hooks,
+        )
+    }
+
+    fn credit_service(
+        credit_limit: Option<Decimal>,
+        block_over_credit_limit: bool,
+        exposure: Decimal,
+        jobs_created: usize,
+        alert_repo: MockAlertRepositoryTrait,
+    ) -> LogisticsService {
+        let mut tx = MockRepositoryTransactionTrait::new();
+        tx.expect_lock_customer().returning(move |id| Ok(Some(Customer {
+            id,
+            name: "Acme Retail".into(),
+            contact_info: serde_json::json!({}),
+            billing_address: "5 Market St".into(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            credit_limit,
+            block_over_credit_limit,
+        })));
+        tx.expect_credit_exposure().returning(move |_| Ok(exposure));
+        tx.expect_create_job()
+            .times(jobs_created)
+            .returning(|dto, _| Ok(TransportJob { customer_id: dto.customer_id, agreed_price: dto.agreed_price, ..job(Uuid::new_v4()) }));
+        tx.expect_commit().times(jobs_created).returning(|| Ok(()));
+        let mut unit_of_work = MockUnitOfWorkTrait::new();
+        unit_of_work.expect_begin().return_once(move || Ok(Box::new(tx)));
+        LogisticsService::new(
+            Arc::new(MockCustomerRepositoryTrait::new()),
+            Arc::new(MockTransportJobRepositoryTrait::new()),
+            Arc::new(MockRouteRepositoryTrait::new()),
+            Arc::new(MockShipmentRepositoryTrait::new()),
+            Arc::new(MockJobLegRepositoryTrait::new()),
+            Arc::new(MockAssignmentRepositoryTrait::new()),
+            Arc::new(alert_repo),
+            Arc::new(unit_of_work),
+            Vec::new(),
+        )
+    }
+
+    fn new_job(customer_id: Uuid, agreed_price: Decimal) -> CreateTransportJobDto {
+        CreateTransportJobDto { customer_id, status: JobStatus::Pending, agreed_price }
+    }
+
+    #[tokio::test]
+    async fn test_create_job_over_credit_limit_raises_alert() {
+        let customer_id = Uuid::new_v4();
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo.expect_create_unless_open()
+            .withf(move |dto| {
+                dto.entity_id == customer_id
+                    && dto.r#type == CREDIT_LIMIT_ALERT
+                    && dto.severity == AlertSeverity::High
+                    && dto.message.as_deref().is_some_and(|m| m.contains("would owe 1100.00") && m.contains("limit of 1000"))
+            })
+            .times(1)
+            .returning(|dto| Ok(Some(Alert {
+                id: Uuid::new_v4(),
+                entity_id: dto.entity_id,
+                r#type: dto.r#type,
+                severity: dto.severity,
+                is_resolved: false,
+                created_at: Utc::now(),
+                resolved_at: None,
+                message: dto.message,
+            })));
+        let service = credit_service(Some(Decimal::new(1000, 0)), false, Decimal::new(80000, 2), 1, alert_repo);
+
+        let job = service.create_job(new_job(customer_id, Decimal::new(30000, 2)), None).await.unwrap();
+        assert_eq!(job.customer_id, customer_id);
+    }
+
+    #[tokio::test]
+    async fn test_create_job_blocked_over_credit_limit() {
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo.expect_create_unless_open().never();
+        let service = credit_service(Some(Decimal::new(1000, 0)), true, Decimal::new(80000, 2), 0, alert_repo);
+
+        let result = service.create_job(new_job(Uuid::new_v4(), Decimal::new(30000, 2)), None).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.starts_with("Credit limit exceeded")));
+    }
+
+    #[tokio::test]
+    async fn test_create_job_within_credit_limit_resolves_alert() {
+        let customer_id = Uuid::new_v4();
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo.expect_resolve_open()
+            .withf(move |id, types| *id == customer_id && types == &vec![CREDIT_LIMIT_ALERT.to_string()])
+            .times(1)
+            .returning(|_, _| Ok(1));
+        alert_repo.expect_create_unless_open().never();
+        let service = credit_service(Some(Decimal::new(1000, 0)), true, Decimal::new(70000, 2), 1, alert_repo);
+
+        service.create_job(new_job(customer_id, Decimal::new(30000, 2)), None).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_job_must_start_pending() {
+        let service = credit_service(None, false, Decimal::ZERO, 0, MockAlertRepositoryTrait::new());
+
+        for status in [JobStatus::InProgress, JobStatus::Delivered, JobStatus::Invoiced, JobStatus::Paid, JobStatus::Cancelled] {
+            let dto = CreateTransportJobDto { status, ..new_job(Uuid::new_v4(), Decimal::new(30000, 2)) };
+            let result = service.create_job(dto, None).await;
+            assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("Pending")));
+        }
+    }
+
+    fn to(status: JobStatus) -> UpdateJobStatusDto {
+        UpdateJobStatusDto { status, reason: None }
+    }
+
+    #[test]
+    fn test_job_status_transitions() {
+        use JobStatus::*;
+        assert!(Pending.can_transition_to(InProgress));
+        assert!(Delivered.can_transition_to(Invoiced));
+        assert!(InProgress.can_transition_to(Cancelled));
+        assert!(!Pending.can_transition_to(Paid));
+        assert!(!Delivered.can_transition_to(Cancelled));
+        assert!(!Paid.can_transition_to(Pending));
+        assert!(!Cancelled.can_transition_to(InProgress));
+    }
+
+    #[tokio::test]
+    async fn test_update_job_status_rejects_skipped_steps_and_unexplained_cancellation() {
+        let service = status_service(JobStatus::Pending, |_| {}, Vec::new());
+
+        let result = service.update_job_status(Uuid::new_v4(), to(JobStatus::Paid), None).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("Pending to Paid")));
+
+        let result = service.update_job_status(Uuid::new_v4(), to(JobStatus::Cancelled), None).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("reason")));
+    }
+
+    #[tokio::test]
+    async fn test_update_job_status_records_user_and_runs_hooks() {
+        let user_id = Uuid::new_v4();
+        let mut vetoing = MockJobStatusHookTrait::new();
+        vetoing
+            .expect_before_transition()
+            .returning(|_, to| match to {
+                JobStatus::Delivered => Err(AppError::BadRequest("Proof of delivery is required".into())),
+                _ => Ok(()),
+            });
+        vetoing.expect_after_transition().times(0);
+        let service = status_service(JobStatus::InProgress, |_| {}, vec![Arc::new(vetoing)]);
+        let result = service.update_job_status(Uuid::new_v4(), to(JobStatus::Delivered), Some(user_id)).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("Proof of delivery")));
+
+        let mut hook = MockJobStatusHookTrait::new();
+        hook.expect_before_transition().times(1).returning(|_, _| Ok(()));
+        // A failing side effect does not undo the saved change
+        hook.expect_after_transition()
+            .withf(|job, from| job.status == JobStatus::Cancelled && *from == JobStatus::InProgress)
+            .times(1)
+            .returning(|_, _| Err(AppError::InternalServerError("mail server down".into())));
+        let service = status_service(JobStatus::InProgress, |job_repo| {
+            job_repo
+                .expect_update_status()
+                .withf(move |_, from, to, changed_by, reason| {
+                    *from == JobStatus::InProgress
+                        && *to == JobStatus::Cancelled
+                        && *changed_by == Some(user_id)
+                        && reason.as_deref() == Some("Customer withdrew")
+                })
+                .times(1)
+                .returning(|id, _, to, _, _| Ok(Some(TransportJob { status: to, ..job(id) })));
+        }, vec![Arc::new(hook)]);
+
+        let dto = UpdateJobStatusDto { status: JobStatus::Cancelled, reason: Some(" Customer withdrew ".into()) };
+        let job = service.update_job_status(Uuid::new_v4(), dto, Some(user_id)).await.unwrap();
+        assert_eq!(job.status, JobStatus::Cancelled);
+    }
 }
@@ -1,16 +1,82 @@
 use std::sync::Arc;
 use uuid::Uuid;
 use async_trait::async_trait;
+use chrono::{DateTime, Months, Utc};
 use crate::error::AppError;
 use crate::models::postgres::maintenance::{
-    MaintenanceRecord, CreateMaintenanceRecordDto,
+    MaintenanceRecord, CreateMaintenanceRecordDto, MaintenanceType,
     MaintenanceSchedule, CreateMaintenanceScheduleDto,
-    Alert, CreateAlertDto
+    VehicleServiceBaseline, MaintenanceDue, MaintenanceDueStatus, MaintenanceDueReport,
+    Alert, AlertSeverity, CreateAlertDto
 };
 use crate::models::postgres::vehicle::VehicleType;
 use crate::repositories::postgres::maintenance_repo::{
     MaintenanceRecordRepositoryTrait, MaintenanceScheduleRepositoryTrait, AlertRepositoryTrait
 };
+use crate::repositories::postgres::settings_repo::SettingsRepositoryTrait;
+
+pub const MAINTENANCE_DUE_SOON_ALERT: &str = "Maintenance Due Soon";
+pub const MAINTENANCE_OVERDUE_ALERT: &str = "Maintenance Overdue";
+
+/// A vehicle is due soon when either limit is this close.
+const DUE_SOON_DAYS: i64 = 14;
+const DUE_SOON_KM: i32 = 1_000;
+
+/// Works out when a vehicle next needs preventive maintenance. Without a preventive record
+/// the schedule counts from when the vehicle was added and from its odometer reading then.
+pub fn maintenance_due(baseline: &VehicleServiceBaseline, now: DateTime<Utc>) -> MaintenanceDue {
+    let base_date = baseline.last_service_date.unwrap_or(baseline.vehicle_created_at);
+    let next_due_date = baseline.interval_months
+        .and_then(|months| u32::try_from(months).ok())
+        .and_then(|months| base_date.checked_add_months(Months::new(months)));
+
+    let base_km = match baseline.last_service_date {
+        Some(_) => baseline.last_service_mileage,
+        None => Some(baseline.initial_mileage),
+    };
+    let next_due_km = base_km
+        .zip(baseline.interval_km)
+        .map(|(km, interval)| km.saturating_add(interval));
+
+    let days_remaining = next_due_date.map(|due| (due - now).num_days());
+    let km_remaining = next_due_km.map(|due| due - baseline.current_mileage);
+
+    let overdue = next_due_date.is_some_and(|due| due <= now) || km_remaining.is_some_and(|km| km <= 0);
+    let due_soon = days_remaining.is_some_and(|days| days <= DUE_SOON_DAYS)
+        || km_remaining.is_some_and(|km| km <= DUE_SOON_KM);
+    let status = if overdue {
+        MaintenanceDueStatus::Overdue
+    } else if due_soon {
+        MaintenanceDueStatus::DueSoon
+    } else {
+        MaintenanceDueStatus::Ok
+    };
+
+    MaintenanceDue {
+        vehicle_id: baseline.vehicle_id,
+        license_plate: baseline.license_plate.clone(),
+        vehicle_type: baseline.vehicle_type,
+        current_mileage: baseline.current_mileage,
+        last_service_date: baseline.last_service_date,
+        last_service_mileage: baseline.last_service_mileage,
+        next_due_date,
+        next_due_km,
+        days_remaining,
+        km_remaining,
+        status,
+    }
+}
+
+fn describe_due(due: &MaintenanceDue) -> String {
+    let mut limits = Vec::new();
+    if let Some(date) = due.next_due_date {
+        limits.push(format!("due {}", date.format("%Y-%m-%d")));
+    }
+    if let Some(km) = due.next_due_km {
+        limits.push(format!("due at {} km (now {} km)", km, due.current_mileage));
+    }
+    format!("Preventive maintenance for {}: {}", due.license_plate, limits.join(", "))
+}
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
@@ -27,12 +93,18 @@ pub trait MaintenanceServiceTrait: Send + Sync {
     async fn create_alert(&self, dto: CreateAlertDto) -> Result<Alert, AppError>;
     async fn get_unresolved_alerts(&self) -> Result<Vec<Alert>, AppError>;
     async fn resolve_alert(&self, id: Uuid) -> Result<Alert, AppError>;
+
+    // Due dates
+    async fn get_due(&self, now: DateTime<Utc>) -> Result<MaintenanceDueReport, AppError>;
+    /// Raises due-soon and overdue alerts, unless maintenance alerts are turned off in settings.
+    async fn raise_due_alerts(&self, now: DateTime<Utc>) -> Result<Vec<Alert>, AppError>;
 }
 
 pub struct MaintenanceService {
     record_repo: Arc<dyn MaintenanceRecordRepositoryTrait>,
     schedule_repo: Arc<dyn MaintenanceScheduleRepositoryTrait>,
     alert_repo: Arc<dyn AlertRepositoryTrait>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait>,
 }
 
 impl MaintenanceService {
@@ -40,19 +112,42 @@ impl MaintenanceService {
         record_repo: Arc<dyn MaintenanceRecordRepositoryTrait>,
         schedule_repo: Arc<dyn MaintenanceScheduleRepositoryTrait>,
         alert_repo: Arc<dyn AlertRepositoryTrait>,
+        settings_repo: Arc<dyn SettingsRepositoryTrait>,
     ) -> Self {
         Self {
             record_repo,
             schedule_repo,
             alert_repo,
+            settings_repo,
         }
     }
+
+    async fn due_list(&self, now: DateTime<Utc>) -> Result<Vec<MaintenanceDue>, AppError> {
+        Ok(self.schedule_repo
+            .find_service_baselines()
+            .await?
+            .iter()
+            .map(|baseline| maintenance_due(baseline, now))
+            .collect())
+    }
 }
 
 #[async_trait]
 impl MaintenanceServiceTrait for MaintenanceService {
     async fn create_record(&self, dto: CreateMaintenanceRecordDto) -> Result<MaintenanceRecord, AppError> {
-        self.record_repo.create(dto).await
+        let record = self.record_repo.create(dto).await?;
+
+        // A preventive service restarts the schedule, so its reminders no longer apply
+        if record.r#type == MaintenanceType::Preventive {
+            self.alert_repo
+                .resolve_open(
+                    record.vehicle_id,
+                    vec![MAINTENANCE_DUE_SOON_ALERT.to_string(), MAINTENANCE_OVERDUE_ALERT.to_string()],
+                )
+                .await?;
+        }
+
+        Ok(record)
     }
 
     async fn get_vehicle_records(&self, vehicle_id: Uuid) -> Result<Vec<MaintenanceRecord>, AppError> {
@@ -78,4 +173,226 @@ impl MaintenanceServiceTrait for MaintenanceService {
     async fn resolve_alert(&self, id: Uuid) -> Result<Alert, AppError> {
         self.alert_repo.resolve(id).await
     }
+
+    async fn get_due(&self, now: DateTime<Utc>) -> Result<MaintenanceDueReport, AppError> {
+        let mut report = MaintenanceDueReport { overdue: Vec::new(), due_soon: Vec::new() };
+        for due in self.due_list(now).await? {
+            match due.status {
+                MaintenanceDueStatus::Overdue => report.overdue.push(due),
+                MaintenanceDueStatus::DueSoon => report.due_soon.push(due),
+                MaintenanceDueStatus::Ok => {}
+            }
+        }
+        // Most urgent first
+        report.overdue.sort_by_key(|d| (d.days_remaining, d.km_remaining));
+        report.due_soon.sort_by_key(|d| (d.days_remaining, d.km_remaining));
+        Ok(report)
+    }
+
+    async fn raise_due_alerts(&self, now: DateTime<Utc>) -> Result<Vec<Alert>, AppError> {
+        if !self.settings_repo.get().await?.notify_maintenance_alerts {
+            return Ok(Vec::new());
+        }
+
+        let mut alerts = Vec::new();
+        for due in self.due_list(now).await? {
+            let (alert_type, severity) = match due.status {
+                MaintenanceDueStatus::Overdue => (MAINTENANCE_OVERDUE_ALERT, AlertSeverity::High),
+                MaintenanceDueStatus::DueSoon => (MAINTENANCE_DUE_SOON_ALERT, AlertSeverity::Medium),
+                MaintenanceDueStatus::Ok => continue,
+            };
+            // An overdue vehicle is past the reminder stage
+            if due.status == MaintenanceDueStatus::Overdue {
+                self.alert_repo
+                    .resolve_open(due.vehicle_id, vec![MAINTENANCE_DUE_SOON_ALERT.to_string()])
+                    .await?;
+            }
+            let created = self.alert_repo.create_unless_open(CreateAlertDto {
+                entity_id: due.vehicle_id,
+                r#type: alert_type.to_string(),
+                severity,
+                message: Some(describe_due(&due)),
+            }).await?;
+            alerts.extend(created);
+        }
+        Ok(alerts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::models::postgres::settings::AppSettings;
+    use crate::repositories::postgres::maintenance_repo::{
+        MockMaintenanceRecordRepositoryTrait, MockMaintenanceScheduleRepositoryTrait, MockAlertRepositoryTrait
+    };
+    use crate::repositories::postgres::settings_repo::MockSettingsRepositoryTrait;
+
+    fn baseline(now: DateTime<Utc>) -> VehicleServiceBaseline {
+        VehicleServiceBaseline {
+            vehicle_id: Uuid::new_v4(),
+            license_plate: "ABC-123".into(),
+            vehicle_type: VehicleType::Truck,
+            current_mileage: 52_000,
+            initial_mileage: 12_000,
+            vehicle_created_at: now - Duration::days(800),
+            interval_km: Some(10_000),
+            interval_months: Some(6),
+            last_service_date: Some(now - Duration::days(30)),
+            last_service_mileage: Some(45_000),
+        }
+    }
+
+    fn settings(notify_maintenance_alerts: bool) -> AppSettings {
+        AppSettings {
+            notify_maintenance_alerts,
+            ..Default::default()
+        }
+    }
+
+    fn service(
+        schedule_repo: MockMaintenanceScheduleRepositoryTrait,
+        alert_repo: MockAlertRepositoryTrait,
+        settings_repo: MockSettingsRepositoryTrait,
+    ) -> MaintenanceService {
+        MaintenanceService::new(
+            Arc::new(MockMaintenanceRecordRepositoryTrait::new()),
+            Arc::new(schedule_repo),
+            Arc::new(alert_repo),
+            Arc::new(settings_repo),
+        )
+    }
+
+    #[test]
+    fn test_due_counts_from_last_preventive_service() {
+        let now = Utc::now();
+        let due = maintenance_due(&baseline(now), now);
+
+        assert_eq!(due.next_due_km, Some(55_000));
+        assert_eq!(due.km_remaining, Some(3_000));
+        assert!(due.days_remaining.unwrap() > 140);
+        assert_eq!(due.status, MaintenanceDueStatus::Ok);
+    }
+
+    #[test]
+    fn test_due_status_from_either_limit() {
+        let now = Utc::now();
+
+        let mut near_km = baseline(now);
+        near_km.current_mileage = 54_500;
+        assert_eq!(maintenance_due(&near_km, now).status, MaintenanceDueStatus::DueSoon);
+
+        let mut past_date = baseline(now);
+        past_date.last_service_date = Some(now - Duration::days(200));
+        assert_eq!(maintenance_due(&past_date, now).status, MaintenanceDueStatus::Overdue);
+    }
+
+    #[test]
+    fn test_due_without_history_counts_from_vehicle_creation() {
+        let now = Utc::now();
+        let mut never_serviced = baseline(now);
+        never_serviced.last_service_date = None;
+        never_serviced.last_service_mileage = None;
+        never_serviced.vehicle_created_at = now - Duration::days(10);
+        never_serviced.initial_mileage = 180_000;
+        never_serviced.current_mileage = 184_000;
+
+        let due = maintenance_due(&never_serviced, now);
+        assert_eq!(due.next_due_km, Some(190_000));
+        assert_eq!(due.status, MaintenanceDueStatus::Ok);
+    }
+
+    #[test]
+    fn test_due_time_only_schedule_has_no_km_limit() {
+        let now = Utc::now();
+        let mut time_only = baseline(now);
+        time_only.interval_km = None;
+        time_only.current_mileage = 900_000;
+
+        let due = maintenance_due(&time_only, now);
+        assert_eq!(due.next_due_km, None);
+        assert!(due.next_due_date.is_some());
+        assert_eq!(due.status, MaintenanceDueStatus::Ok);
+    }
+
+    #[test]
+    fn test_due_km_unknown_when_last_service_has_no_mileage() {
+        let now = Utc::now();
+        let mut legacy = baseline(now);
+        legacy.last_service_mileage = None;
+        legacy.current_mileage = 900_000;
+
+        let due = maintenance_due(&legacy, now);
+        assert_eq!(due.next_due_km, None);
+        assert_eq!(due.status, MaintenanceDueStatus::Ok);
+    }
+
+    #[tokio::test]
+    async fn test_get_due_splits_overdue_and_due_soon() {
+        let now = Utc::now();
+        let mut schedule_repo = MockMaintenanceScheduleRepositoryTrait::new();
+        schedule_repo.expect_find_service_baselines().returning(move || {
+            let mut overdue = baseline(now);
+            overdue.current_mileage = 56_000;
+            let mut due_soon = baseline(now);
+            due_soon.current_mileage = 54_200;
+            Ok(vec![baseline(now), overdue, due_soon])
+        });
+
+        let service = service(schedule_repo, MockAlertRepositoryTrait::new(), MockSettingsRepositoryTrait::new());
+        let report = service.get_due(now).await.unwrap();
+
+        assert_eq!(report.overdue.len(), 1);
+        assert_eq!(report.overdue[0].km_remaining, Some(-1_000));
+        assert_eq!(report.due_soon.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_raise_due_alerts_honors_settings() {
+        let mut settings_repo = MockSettingsRepositoryTrait::new();
+        settings_repo.expect_get().returning(|| Ok(settings(false)));
+        let mut schedule_repo = MockMaintenanceScheduleRepositoryTrait::new();
+        schedule_repo.expect_find_service_baselines().never();
+
+        let service = service(schedule_repo, MockAlertRepositoryTrait::new(), settings_repo);
+        assert!(service.raise_due_alerts(Utc::now()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_raise_due_alerts_creates_typed_alerts() {
+        let now = Utc::now();
+        let mut settings_repo = MockSettingsRepositoryTrait::new();
+        settings_repo.expect_get().returning(|| Ok(settings(true)));
+        let mut schedule_repo = MockMaintenanceScheduleRepositoryTrait::new();
+        schedule_repo.expect_find_service_baselines().returning(move || {
+            let mut overdue = baseline(now);
+            overdue.current_mileage = 60_000;
+            Ok(vec![baseline(now), overdue])
+        });
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo
+            .expect_resolve_open()
+            .withf(|_, types| types == &vec![MAINTENANCE_DUE_SOON_ALERT.to_string()])
+            .times(1)
+            .returning(|_, _| Ok(1));
+        alert_repo
+            .expect_create_unless_open()
+            .withf(|dto| dto.r#type == MAINTENANCE_OVERDUE_ALERT && dto.severity == AlertSeverity::High)
+            .times(1)
+            .returning(|dto| Ok(Some(Alert {
+                id: Uuid::new_v4(),
+                entity_id: dto.entity_id,
+                r#type: dto.r#type,
+                severity: dto.severity,
+                is_resolved: false,
+                created_at: Utc::now(),
+                resolved_at: None,
+                message: dto.message,
+            })));
+
+        let service = service(schedule_repo, alert_repo, settings_repo);
+        let alerts = service.raise_due_alerts(now).await.unwrap();
+        assert_eq!(alerts.len(), 1);
+    }
 }
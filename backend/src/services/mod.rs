@@ -8,5 +8,21 @@ pub mod telemetry_service;
 pub mod financial_service;
 pub mod settings_service;
 pub mod user_service;
-
-
+pub mod role_service;
+pub mod telemetry_partition_service;
+pub mod geofence_service;
+pub mod anomaly_service;
+pub mod odometer_service;
+pub mod work_order_service;
+pub mod part_service;
+pub mod license_service;
+pub mod expiry_alert;
+pub mod hos_service;
+pub mod driver_score_service;
+pub mod dispatch_service;
+pub mod file_upload;
+pub mod invoice_pdf;
+pub mod invoice_service;
+pub mod proof_of_delivery_service;
+pub mod vehicle_document_service;
+pub mod quote_service;
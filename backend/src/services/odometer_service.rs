@@ -0,0 +1,226 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::postgres::odometer::{
+    OdometerCursor, OdometerUpdate, OdometerSyncReport, DailyDistance, DailyDistanceQuery, DistanceSource
+};
+use crate::repositories::postgres::odometer_repo::OdometerRepositoryTrait;
+
+/// Days returned when a distance report has no `from`.
+const DEFAULT_REPORT_DAYS: i64 = 7;
+
+const MAX_REPORT_DAYS: i64 = 366;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait OdometerServiceTrait: Send + Sync {
+    /// Folds telemetry received up to `until` into each vehicle's current_mileage.
+    async fn sync_odometers(&self, until: DateTime<Utc>) -> Result<OdometerSyncReport, AppError>;
+    async fn get_daily_distance(&self, vehicle_id: Uuid, query: DailyDistanceQuery) -> Result<Vec<DailyDistance>, AppError>;
+}
+
+pub struct OdometerService {
+    repo: Arc<dyn OdometerRepositoryTrait>,
+}
+
+impl OdometerService {
+    pub fn new(repo: Arc<dyn OdometerRepositoryTrait>) -> Self {
+        Self { repo }
+    }
+
+    /// Returns the kilometers added, or `None` if there was nothing to apply or another
+    /// worker got there first.
+    async fn sync_vehicle(&self, cursor: OdometerCursor, until: DateTime<Utc>) -> Result<Option<i64>, AppError> {
+        let measurement = self.repo.measure(cursor.vehicle_id, cursor.synced_until, until).await?;
+        let synced_until = match measurement.last_time {
+            Some(last) if last > cursor.synced_until => last,
+            _ => return Ok(None),
+        };
+
+        // A reported odometer is authoritative, so GPS distance is only used without one
+        let update = match measurement.odometer_km {
+            Some(odometer) => OdometerUpdate {
+                vehicle_id: cursor.vehicle_id,
+                previous_until: cursor.synced_until,
+                synced_until,
+                add_km: 0,
+                remainder_m: 0.0,
+                odometer_km: Some(odometer.floor() as i32),
+            },
+            None => {
+                let total_m = cursor.remainder_m + measurement.distance_m;
+                let add_km = (total_m / 1000.0).floor();
+                OdometerUpdate {
+                    vehicle_id: cursor.vehicle_id,
+                    previous_until: cursor.synced_until,
+                    synced_until,
+                    add_km: add_km as i32,
+                    remainder_m: total_m - add_km * 1000.0,
+                    odometer_km: None,
+                }
+            }
+        };
+
+        let add_km = update.add_km.into();
+        if self.repo.apply(update).await? {
+            Ok(Some(add_km))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[async_trait]
+impl OdometerServiceTrait for OdometerService {
+    async fn sync_odometers(&self, until: DateTime<Utc>) -> Result<OdometerSyncReport, AppError> {
+        let mut report = OdometerSyncReport::default();
+        for cursor in self.repo.find_pending(until).await? {
+            let vehicle_id = cursor.vehicle_id;
+            // One vehicle's bad data must not hold up the rest of the fleet
+            match self.sync_vehicle(cursor, until).await {
+                Ok(Some(km)) => {
+                    report.vehicles_synced += 1;
+                    report.kilometers_added += km;
+                }
+                Ok(None) => {}
+                Err(e) => eprintln!("Failed to sync odometer for vehicle {}: {}", vehicle_id, e),
+            }
+        }
+        Ok(report)
+    }
+
+    async fn get_daily_distance(&self, vehicle_id: Uuid, query: DailyDistanceQuery) -> Result<Vec<DailyDistance>, AppError> {
+        let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+        let from = query.from.unwrap_or(to - Duration::days(DEFAULT_REPORT_DAYS - 1));
+        if from > to {
+            return Err(AppError::BadRequest("from must not be after to".into()));
+        }
+        if (to - from).num_days() >= MAX_REPORT_DAYS {
+            return Err(AppError::BadRequest(format!("Reports are limited to {} days", MAX_REPORT_DAYS)));
+        }
+
+        let start = from.and_time(NaiveTime::MIN).and_utc();
+        let end = (to + Duration::days(1)).and_time(NaiveTime::MIN).and_utc();
+        let rows = self.repo.find_daily_distance(vehicle_id, start, end).await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| match row.odometer_km {
+                Some(km) => DailyDistance { day: row.day, distance_km: km, source: DistanceSource::Odometer },
+                None => DailyDistance { day: row.day, distance_km: row.gps_m / 1000.0, source: DistanceSource::Gps },
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::postgres::odometer::{DistanceMeasurement, DailyDistanceRow};
+    use crate::repositories::postgres::odometer_repo::MockOdometerRepositoryTrait;
+    use chrono::NaiveDate;
+
+    fn cursor(remainder_m: f64) -> OdometerCursor {
+        OdometerCursor {
+            vehicle_id: Uuid::new_v4(),
+            synced_until: Utc::now() - Duration::hours(1),
+            remainder_m,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_carries_partial_kilometers() {
+        let pending = cursor(600.0);
+        let mut mock_repo = MockOdometerRepositoryTrait::new();
+        mock_repo.expect_find_pending().returning(move |_| Ok(vec![pending.clone()]));
+        mock_repo.expect_measure().returning(|_, _, to| Ok(DistanceMeasurement {
+            distance_m: 2_900.0,
+            odometer_km: None,
+            last_time: Some(to - Duration::minutes(1)),
+        }));
+        mock_repo
+            .expect_apply()
+            .withf(|update| update.add_km == 3 && (update.remainder_m - 500.0).abs() < 1e-6 && update.odometer_km.is_none())
+            .times(1)
+            .returning(|_| Ok(true));
+
+        let service = OdometerService::new(Arc::new(mock_repo));
+        let report = service.sync_odometers(Utc::now()).await.unwrap();
+        assert_eq!(report, OdometerSyncReport { vehicles_synced: 1, kilometers_added: 3 });
+    }
+
+    #[tokio::test]
+    async fn test_sync_prefers_reported_odometer() {
+        let pending = cursor(400.0);
+        let mut mock_repo = MockOdometerRepositoryTrait::new();
+        mock_repo.expect_find_pending().returning(move |_| Ok(vec![pending.clone()]));
+        mock_repo.expect_measure().returning(|_, _, to| Ok(DistanceMeasurement {
+            distance_m: 5_000.0,
+            odometer_km: Some(120_345.8),
+            last_time: Some(to),
+        }));
+        mock_repo
+            .expect_apply()
+            .withf(|update| update.add_km == 0 && update.remainder_m == 0.0 && update.odometer_km == Some(120_345))
+            .times(1)
+            .returning(|_| Ok(true));
+
+        let service = OdometerService::new(Arc::new(mock_repo));
+        service.sync_odometers(Utc::now()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sync_skips_cursor_claimed_elsewhere() {
+        let pending = cursor(0.0);
+        let mut mock_repo = MockOdometerRepositoryTrait::new();
+        mock_repo.expect_find_pending().returning(move |_| Ok(vec![pending.clone()]));
+        mock_repo.expect_measure().returning(|_, _, to| Ok(DistanceMeasurement {
+            distance_m: 1_500.0,
+            odometer_km: None,
+            last_time: Some(to),
+        }));
+        mock_repo.expect_apply().returning(|_| Ok(false));
+
+        let service = OdometerService::new(Arc::new(mock_repo));
+        let report = service.sync_odometers(Utc::now()).await.unwrap();
+        assert_eq!(report, OdometerSyncReport::default());
+    }
+
+    #[tokio::test]
+    async fn test_daily_distance_picks_source_per_day() {
+        let mut mock_repo = MockOdometerRepositoryTrait::new();
+        mock_repo
+            .expect_find_daily_distance()
+            .withf(|_, from, to| {
+                from.to_rfc3339() == "2026-10-01T00:00:00+00:00" && to.to_rfc3339() == "2026-10-03T00:00:00+00:00"
+            })
+            .returning(|_, _, _| Ok(vec![
+                DailyDistanceRow { day: NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(), gps_m: 12_500.0, odometer_km: None },
+                DailyDistanceRow { day: NaiveDate::from_ymd_opt(2026, 10, 2).unwrap(), gps_m: 9_000.0, odometer_km: Some(9.4) },
+            ]));
+
+        let service = OdometerService::new(Arc::new(mock_repo));
+        let days = service.get_daily_distance(Uuid::new_v4(), DailyDistanceQuery {
+            from: NaiveDate::from_ymd_opt(2026, 10, 1),
+            to: NaiveDate::from_ymd_opt(2026, 10, 2),
+        }).await.unwrap();
+
+        assert_eq!(days[0].distance_km, 12.5);
+        assert_eq!(days[0].source, DistanceSource::Gps);
+        assert_eq!(days[1].distance_km, 9.4);
+        assert_eq!(days[1].source, DistanceSource::Odometer);
+    }
+
+    #[tokio::test]
+    async fn test_daily_distance_rejects_inverted_range() {
+        let service = OdometerService::new(Arc::new(MockOdometerRepositoryTrait::new()));
+        let result = service.get_daily_distance(Uuid::new_v4(), DailyDistanceQuery {
+            from: NaiveDate::from_ymd_opt(2026, 10, 5),
+            to: NaiveDate::from_ymd_opt(2026, 10, 1),
+        }).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,371 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::error::AppError;
+use crate::models::postgres::maintenance::{AlertSeverity, CreateAlertDto};
+use crate::models::postgres::part::{
+    Part, CreatePartDto, UpdatePartDto, PartQuery, StockMovement, StockMovementKind, CreateStockMovementDto, NewStockMovement
+};
+use crate::repositories::postgres::maintenance_repo::AlertRepositoryTrait;
+use crate::repositories::postgres::part_repo::PartRepositoryTrait;
+use crate::repositories::postgres::vehicle_repo::VehicleRepositoryTrait;
+
+pub const LOW_STOCK_ALERT: &str = "Low Stock";
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait PartServiceTrait: Send + Sync {
+    async fn create_part(&self, dto: CreatePartDto) -> Result<Part, AppError>;
+    async fn list_parts(&self, query: PartQuery) -> Result<Vec<Part>, AppError>;
+    async fn get_part(&self, id: Uuid) -> Result<Part, AppError>;
+    async fn update_part(&self, id: Uuid, dto: UpdatePartDto) -> Result<Part, AppError>;
+    /// Receives, issues or corrects stock and returns the part with its new level.
+    async fn record_movement(&self, part_id: Uuid, dto: CreateStockMovementDto) -> Result<Part, AppError>;
+    async fn get_movements(&self, part_id: Uuid) -> Result<Vec<StockMovement>, AppError>;
+    /// Raises or resolves the part's low-stock alert after its level changed elsewhere.
+    async fn refresh_stock_alert(&self, part_id: Uuid) -> Result<(), AppError>;
+}
+
+pub struct PartService {
+    part_repo: Arc<dyn PartRepositoryTrait>,
+    vehicle_repo: Arc<dyn VehicleRepositoryTrait>,
+    alert_repo: Arc<dyn AlertRepositoryTrait>,
+}
+
+impl PartService {
+    pub fn new(
+        part_repo: Arc<dyn PartRepositoryTrait>,
+        vehicle_repo: Arc<dyn VehicleRepositoryTrait>,
+        alert_repo: Arc<dyn AlertRepositoryTrait>,
+    ) -> Self {
+        Self { part_repo, vehicle_repo, alert_repo }
+    }
+
+    async fn check_stock_level(&self, part: &Part) -> Result<(), AppError> {
+        if part.is_low_stock() {
+            // One open alert per part until it is restocked
+            self.alert_repo
+                .create_unless_open(CreateAlertDto {
+                    entity_id: part.id,
+                    r#type: LOW_STOCK_ALERT.to_string(),
+                    severity: AlertSeverity::Medium,
+                    message: Some(format!(
+                        "{} ({}): {} on hand, reorder threshold {}",
+                        part.sku, part.description, part.quantity_on_hand, part.reorder_threshold
+                    )),
+                })
+                .await?;
+        } else {
+            self.alert_repo.resolve_open(part.id, vec![LOW_STOCK_ALERT.to_string()]).await?;
+        }
+        Ok(())
+    }
+
+    /// Checks the movement makes sense for its kind and gives its quantity a sign.
+    async fn validate_movement(&self, part: &Part, dto: CreateStockMovementDto) -> Result<NewStockMovement, AppError> {
+        if dto.quantity.is_zero() {
+            return Err(AppError::BadRequest("Quantity must not be zero".into()));
+        }
+        if dto.kind != StockMovementKind::Adjustment && dto.quantity < Decimal::ZERO {
+            return Err(AppError::BadRequest("Only adjustments may have a negative quantity".into()));
+        }
+        if dto.kind != StockMovementKind::Receipt && dto.unit_cost.is_some() {
+            return Err(AppError::BadRequest("unit_cost can only be set on receipts".into()));
+        }
+        if dto.unit_cost.is_some_and(|cost| cost < Decimal::ZERO) {
+            return Err(AppError::BadRequest("Unit cost cannot be negative".into()));
+        }
+
+        let issued_to_vehicle = matches!(dto.kind, StockMovementKind::Consumption | StockMovementKind::Return);
+        match dto.vehicle_id {
+            Some(vehicle_id) => {
+                let vehicle = self
+                    .vehicle_repo
+                    .find_by_id(vehicle_id)
+                    .await?
+                    .ok_or(AppError::NotFound(format!("Vehicle {} not found", vehicle_id)))?;
+                if dto.kind == StockMovementKind::Consumption && !part.fits(vehicle.r#type) {
+                    return Err(AppError::BadRequest(format!(
+                        "Part {} does not fit {:?} vehicles",
+                        part.sku, vehicle.r#type
+                    )));
+                }
+            }
+            None if issued_to_vehicle => {
+                return Err(AppError::BadRequest("vehicle_id is required for consumptions and returns".into()));
+            }
+            None => {}
+        }
+
+        let quantity = match dto.kind {
+            StockMovementKind::Consumption => -dto.quantity,
+            _ => dto.quantity,
+        };
+
+        Ok(NewStockMovement {
+            part_id: part.id,
+            kind: dto.kind,
+            quantity,
+            unit_cost: dto.unit_cost,
+            vehicle_id: dto.vehicle_id,
+            work_order_id: None,
+            maintenance_record_id: dto.maintenance_record_id,
+            note: dto.note,
+        })
+    }
+}
+
+fn validate_catalogue(description: &str, unit_cost: Decimal, reorder_threshold: Decimal) -> Result<(), AppError> {
+    if description.trim().is_empty() {
+        return Err(AppError::BadRequest("Part description is required".into()));
+    }
+    if unit_cost < Decimal::ZERO {
+        return Err(AppError::BadRequest("Unit cost cannot be negative".into()));
+    }
+    if reorder_threshold < Decimal::ZERO {
+        return Err(AppError::BadRequest("Reorder threshold cannot be negative".into()));
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl PartServiceTrait for PartService {
+    async fn create_part(&self, mut dto: CreatePartDto) -> Result<Part, AppError> {
+        dto.sku = dto.sku.trim().to_string();
+        if dto.sku.is_empty() {
+            return Err(AppError::BadRequest("SKU is required".into()));
+        }
+        validate_catalogue(&dto.description, dto.unit_cost, dto.reorder_threshold)?;
+        self.part_repo.create(dto).await
+    }
+
+    async fn list_parts(&self, query: PartQuery) -> Result<Vec<Part>, AppError> {
+        self.part_repo.find_all(query).await
+    }
+
+    async fn get_part(&self, id: Uuid) -> Result<Part, AppError> {
+        self.part_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(AppError::NotFound(format!("Part {} not found", id)))
+    }
+
+    async fn update_part(&self, id: Uuid, dto: UpdatePartDto) -> Result<Part, AppError> {
+        validate_catalogue(&dto.description, dto.unit_cost, dto.reorder_threshold)?;
+        let part = self
+            .part_repo
+            .update(id, dto)
+            .await?
+            .ok_or(AppError::NotFound(format!("Part {} not found", id)))?;
+        // A new threshold can put the part above or below it without any stock moving
+        self.check_stock_level(&part).await?;
+        Ok(part)
+    }
+
+    async fn record_movement(&self, part_id: Uuid, dto: CreateStockMovementDto) -> Result<Part, AppError> {
+        let part = self.get_part(part_id).await?;
+        let movement = self.validate_movement(&part, dto).await?;
+        let updated = self.part_repo.record_movement(movement).await?.ok_or(AppError::BadRequest(format!(
+            "Not enough stock of {}: {} on hand",
+            part.sku, part.quantity_on_hand
+        )))?;
+        self.check_stock_level(&updated).await?;
+        Ok(updated)
+    }
+
+    async fn get_movements(&self, part_id: Uuid) -> Result<Vec<StockMovement>, AppError> {
+        self.get_part(part_id).await?;
+        self.part_repo.find_movements(part_id).await
+    }
+
+    async fn refresh_stock_alert(&self, part_id: Uuid) -> Result<(), AppError> {
+        let part = self.get_part(part_id).await?;
+        self.check_stock_level(&part).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::postgres::maintenance::Alert;
+    use crate::models::postgres::vehicle::{Vehicle, VehicleType, VehicleStatus, FuelType};
+    use crate::repositories::postgres::maintenance_repo::MockAlertRepositoryTrait;
+    use crate::repositories::postgres::part_repo::MockPartRepositoryTrait;
+    use crate::repositories::postgres::vehicle_repo::MockVehicleRepositoryTrait;
+    use chrono::Utc;
+
+    fn oil_filter(quantity_on_hand: i64) -> Part {
+        Part {
+            id: Uuid::new_v4(),
+            sku: "OF-100".into(),
+            description: "Oil filter".into(),
+            unit_cost: Decimal::new(1250, 2),
+            quantity_on_hand: Decimal::new(quantity_on_hand, 0),
+            reorder_threshold: Decimal::new(5, 0),
+            compatible_vehicle_types: vec![VehicleType::Truck],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn vehicle(id: Uuid, r#type: VehicleType) -> Vehicle {
+        Vehicle {
+            id,
+            make: "Volvo".into(),
+            model: "FH".into(),
+            year: 2022,
+            vin: "VIN789".into(),
+            license_plate: "TRK-001".into(),
+            r#type,
+            status: VehicleStatus::Maintenance,
+            current_mileage: 80_000,
+            fuel_type: FuelType::Diesel,
+            specs: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn movement(kind: StockMovementKind, quantity: i64, vehicle_id: Option<Uuid>) -> CreateStockMovementDto {
+        CreateStockMovementDto {
+            kind,
+            quantity: Decimal::new(quantity, 0),
+            unit_cost: None,
+            vehicle_id,
+            maintenance_record_id: None,
+            note: None,
+        }
+    }
+
+    fn alert(dto: &CreateAlertDto) -> Alert {
+        Alert {
+            id: Uuid::new_v4(),
+            entity_id: dto.entity_id,
+            r#type: dto.r#type.clone(),
+            severity: dto.severity,
+            is_resolved: false,
+            created_at: Utc::now(),
+            resolved_at: None,
+            message: dto.message.clone(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consumption_below_threshold_raises_alert() {
+        let part = oil_filter(6);
+        let part_id = part.id;
+
+        let mut part_repo = MockPartRepositoryTrait::new();
+        let found = part.clone();
+        part_repo.expect_find_by_id().returning(move |_| Ok(Some(found.clone())));
+        part_repo
+            .expect_record_movement()
+            .withf(|movement| movement.kind == StockMovementKind::Consumption && movement.quantity == Decimal::new(-2, 0))
+            .times(1)
+            .returning(move |movement| {
+                let mut updated = part.clone();
+                updated.quantity_on_hand += movement.quantity;
+                Ok(Some(updated))
+            });
+
+        let mut vehicle_repo = MockVehicleRepositoryTrait::new();
+        vehicle_repo.expect_find_by_id().returning(|id| Ok(Some(vehicle(id, VehicleType::Truck))));
+
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo
+            .expect_create_unless_open()
+            .withf(move |dto| dto.entity_id == part_id && dto.r#type == LOW_STOCK_ALERT)
+            .times(1)
+            .returning(|dto| Ok(Some(alert(&dto))));
+
+        let service = PartService::new(Arc::new(part_repo), Arc::new(vehicle_repo), Arc::new(alert_repo));
+        let updated = service
+            .record_movement(part_id, movement(StockMovementKind::Consumption, 2, Some(Uuid::new_v4())))
+            .await
+            .unwrap();
+        assert_eq!(updated.quantity_on_hand, Decimal::new(4, 0));
+    }
+
+    #[tokio::test]
+    async fn test_receipt_resolves_alert_and_reprices() {
+        let part = oil_filter(1);
+        let part_id = part.id;
+
+        let mut part_repo = MockPartRepositoryTrait::new();
+        let found = part.clone();
+        part_repo.expect_find_by_id().returning(move |_| Ok(Some(found.clone())));
+        part_repo
+            .expect_record_movement()
+            .withf(|movement| movement.quantity == Decimal::new(20, 0) && movement.unit_cost == Some(Decimal::new(11, 0)))
+            .returning(move |movement| {
+                let mut updated = part.clone();
+                updated.quantity_on_hand += movement.quantity;
+                updated.unit_cost = movement.unit_cost.unwrap();
+                Ok(Some(updated))
+            });
+
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo
+            .expect_resolve_open()
+            .withf(move |id, types| *id == part_id && types == &vec![LOW_STOCK_ALERT.to_string()])
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        let service = PartService::new(Arc::new(part_repo), Arc::new(MockVehicleRepositoryTrait::new()), Arc::new(alert_repo));
+        let mut dto = movement(StockMovementKind::Receipt, 20, None);
+        dto.unit_cost = Some(Decimal::new(11, 0));
+        let updated = service.record_movement(part_id, dto).await.unwrap();
+        assert_eq!(updated.quantity_on_hand, Decimal::new(21, 0));
+    }
+
+    #[tokio::test]
+    async fn test_consumption_rejects_incompatible_vehicle() {
+        let part = oil_filter(10);
+        let mut part_repo = MockPartRepositoryTrait::new();
+        part_repo.expect_find_by_id().returning(move |_| Ok(Some(part.clone())));
+
+        let mut vehicle_repo = MockVehicleRepositoryTrait::new();
+        vehicle_repo.expect_find_by_id().returning(|id| Ok(Some(vehicle(id, VehicleType::Sedan))));
+
+        let service = PartService::new(Arc::new(part_repo), Arc::new(vehicle_repo), Arc::new(MockAlertRepositoryTrait::new()));
+        let result = service
+            .record_movement(Uuid::new_v4(), movement(StockMovementKind::Consumption, 1, Some(Uuid::new_v4())))
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_consumption_without_stock_is_rejected() {
+        let part = oil_filter(1);
+        let mut part_repo = MockPartRepositoryTrait::new();
+        part_repo.expect_find_by_id().returning(move |_| Ok(Some(part.clone())));
+        part_repo.expect_record_movement().returning(|_| Ok(None));
+
+        let mut vehicle_repo = MockVehicleRepositoryTrait::new();
+        vehicle_repo.expect_find_by_id().returning(|id| Ok(Some(vehicle(id, VehicleType::Truck))));
+
+        let service = PartService::new(Arc::new(part_repo), Arc::new(vehicle_repo), Arc::new(MockAlertRepositoryTrait::new()));
+        let result = service
+            .record_movement(Uuid::new_v4(), movement(StockMovementKind::Consumption, 3, Some(Uuid::new_v4())))
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_consumption_requires_vehicle() {
+        let part = oil_filter(10);
+        let mut part_repo = MockPartRepositoryTrait::new();
+        part_repo.expect_find_by_id().returning(move |_| Ok(Some(part.clone())));
+
+        let service = PartService::new(
+            Arc::new(part_repo),
+            Arc::new(MockVehicleRepositoryTrait::new()),
+            Arc::new(MockAlertRepositoryTrait::new()),
+        );
+        let result = service.record_movement(Uuid::new_v4(), movement(StockMovementKind::Consumption, 1, None)).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,295 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::logistics::{JobStatus, TransportJob};
+use crate::models::postgres::proof_of_delivery::{
+    ProofOfDeliveryDetails, CreateProofOfDeliveryDto, NewProofOfDeliveryFile, PodFileKind
+};
+use crate::repositories::postgres::logistics_repo::TransportJobRepositoryTrait;
+use crate::repositories::postgres::proof_of_delivery_repo::ProofOfDeliveryRepositoryTrait;
+use crate::repositories::storage::StorageTrait;
+use crate::services::logistics_service::JobStatusHookTrait;
+use crate::services::file_upload::{decode_base64_file, IMAGE_TYPES};
+
+pub const MAX_PHOTOS: usize = 10;
+pub const MAX_IMAGE_BYTES: usize = 4 * 1024 * 1024;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ProofOfDeliveryServiceTrait: Send + Sync {
+    async fn capture(&self, job_id: Uuid, dto: CreateProofOfDeliveryDto, captured_by: Option<Uuid>) -> Result<ProofOfDeliveryDetails, AppError>;
+    async fn get(&self, job_id: Uuid) -> Result<ProofOfDeliveryDetails, AppError>;
+    /// Content type and bytes of a signature or photo.
+    async fn get_file(&self, job_id: Uuid, file_id: Uuid) -> Result<(String, Vec<u8>), AppError>;
+}
+
+pub struct ProofOfDeliveryService {
+    job_repo: Arc<dyn TransportJobRepositoryTrait>,
+    pod_repo: Arc<dyn ProofOfDeliveryRepositoryTrait>,
+    storage: Arc<dyn StorageTrait>,
+}
+
+impl ProofOfDeliveryService {
+    pub fn new(
+        job_repo: Arc<dyn TransportJobRepositoryTrait>,
+        pod_repo: Arc<dyn ProofOfDeliveryRepositoryTrait>,
+        storage: Arc<dyn StorageTrait>,
+    ) -> Self {
+        Self {
+            job_repo,
+            pod_repo,
+            storage,
+        }
+    }
+
+    /// Removes files whose record could not be saved. Failures only leave orphaned
+    /// objects behind, so they are logged rather than returned.
+    async fn discard(&self, files: &[NewProofOfDeliveryFile]) {
+        for file in files {
+            if let Err(e) = self.storage.delete(&file.storage_key).await {
+                eprintln!("Failed to remove orphaned file {}: {}", file.storage_key, e);
+            }
+        }
+    }
+}
+
+fn decode_image(field: &str, encoded: &str) -> Result<(Vec<u8>, &'static str), AppError> {
+    decode_base64_file(field, encoded, MAX_IMAGE_BYTES, IMAGE_TYPES)
+}
+
+#[async_trait]
+impl ProofOfDeliveryServiceTrait for ProofOfDeliveryService {
+    async fn capture(&self, job_id: Uuid, mut dto: CreateProofOfDeliveryDto, captured_by: Option<Uuid>) -> Result<ProofOfDeliveryDetails, AppError> {
+        let job = self.job_repo.find_by_id(job_id).await?
+            .ok_or(AppError::NotFound("Transport Job not found".into()))?;
+        if job.status != JobStatus::InProgress {
+            return Err(AppError::BadRequest(format!(
+                "Proof of delivery can only be captured for jobs in progress, not {:?}",
+                job.status
+            )));
+        }
+
+        dto.recipient_name = dto.recipient_name.trim().to_string();
+        if dto.recipient_name.is_empty() {
+            return Err(AppError::BadRequest("Recipient name is required".into()));
+        }
+        if !(-90.0..=90.0).contains(&dto.latitude) || !(-180.0..=180.0).contains(&dto.longitude) {
+            return Err(AppError::BadRequest("Latitude must be within ±90 and longitude within ±180".into()));
+        }
+        if dto.photos.len() > MAX_PHOTOS {
+            return Err(AppError::BadRequest(format!("At most {} photos can be attached", MAX_PHOTOS)));
+        }
+
+        // Decode everything before writing anything
+        let mut images = vec![(PodFileKind::Signature, decode_image("Signature", &dto.signature)?)];
+        for (i, photo) in dto.photos.iter().enumerate() {
+            images.push((PodFileKind::Photo, decode_image(&format!("Photo {}", i + 1), photo)?));
+        }
+        dto.signature.clear();
+        dto.photos.clear();
+
+        let mut files = Vec::with_capacity(images.len());
+        for (kind, (data, content_type)) in images {
+            let file = NewProofOfDeliveryFile {
+                kind,
+                storage_key: format!("pod/{}/{}", job_id, Uuid::new_v4()),
+                content_type: content_type.to_string(),
+                size_bytes: data.len() as i64,
+            };
+            if let Err(e) = self.storage.put(&file.storage_key, content_type, data).await {
+                self.discard(&files).await;
+                return Err(e);
+            }
+            files.push(file);
+        }
+
+        match self.pod_repo.create(job_id, &dto, captured_by, files.clone()).await {
+            Ok(details) => Ok(details),
+            Err(e) => {
+                self.discard(&files).await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn get(&self, job_id: Uuid) -> Result<ProofOfDeliveryDetails, AppError> {
+        self.pod_repo.find_by_job_id(job_id).await?
+            .ok_or(AppError::NotFound("Proof of delivery not found".into()))
+    }
+
+    async fn get_file(&self, job_id: Uuid, file_id: Uuid) -> Result<(String, Vec<u8>), AppError> {
+        let file = self.pod_repo.find_file(job_id, file_id).await?
+            .ok_or(AppError::NotFound("File not found".into()))?;
+        let data = self.storage.get(&file.storage_key).await?
+            .ok_or_else(|| AppError::StorageError(format!("Stored file {} is missing", file.storage_key)))?;
+        Ok((file.content_type, data))
+    }
+}
+
+/// Jobs can only be marked delivered once proof of delivery is on record.
+#[async_trait]
+impl JobStatusHookTrait for ProofOfDeliveryService {
+    async fn before_transition(&self, job: &TransportJob, to: JobStatus) -> Result<(), AppError> {
+        if to == JobStatus::Delivered && !self.pod_repo.exists_for_job(job.id).await? {
+            return Err(AppError::BadRequest("Capture proof of delivery before marking the job delivered".into()));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use crate::models::postgres::proof_of_delivery::{ProofOfDelivery, ProofOfDeliveryFile};
+    use crate::repositories::postgres::logistics_repo::MockTransportJobRepositoryTrait;
+    use crate::repositories::postgres::proof_of_delivery_repo::MockProofOfDeliveryRepositoryTrait;
+    use crate::repositories::storage::MockStorageTrait;
+
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n0000";
+    const JPEG: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0, 0, 0];
+
+    fn job(id: Uuid, status: JobStatus) -> TransportJob {
+        TransportJob {
+            id,
+            customer_id: Uuid::new_v4(),
+            status,
+            agreed_price: Decimal::new(90000, 2),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn dto(photos: Vec<String>) -> CreateProofOfDeliveryDto {
+        CreateProofOfDeliveryDto {
+            recipient_name: " Ana Ruiz ".into(),
+            latitude: 40.4168,
+            longitude: -3.7038,
+            notes: None,
+            signature: format!("data:image/png;base64,{}", STANDARD.encode(PNG)),
+            photos,
+        }
+    }
+
+    fn service(status: JobStatus, pod_repo: MockProofOfDeliveryRepositoryTrait, storage: MockStorageTrait) -> ProofOfDeliveryService {
+        let mut job_repo = MockTransportJobRepositoryTrait::new();
+        job_repo.expect_find_by_id().returning(move |id| Ok(Some(job(id, status))));
+        ProofOfDeliveryService::new(Arc::new(job_repo), Arc::new(pod_repo), Arc::new(storage))
+    }
+
+    fn details(job_id: Uuid, files: Vec<NewProofOfDeliveryFile>) -> ProofOfDeliveryDetails {
+        let pod_id = Uuid::new_v4();
+        ProofOfDeliveryDetails {
+            proof: ProofOfDelivery {
+                id: pod_id,
+                job_id,
+                recipient_name: "Ana Ruiz".into(),
+                latitude: 40.4168,
+                longitude: -3.7038,
+                notes: None,
+                captured_by: None,
+                captured_by_name: None,
+                captured_at: Utc::now(),
+            },
+            files: files.into_iter().map(|f| ProofOfDeliveryFile {
+                id: Uuid::new_v4(),
+                pod_id,
+                kind: f.kind,
+                storage_key: f.storage_key,
+                content_type: f.content_type,
+                size_bytes: f.size_bytes,
+                created_at: Utc::now(),
+            }).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_capture_stores_files_and_records_proof() {
+        let job_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let mut storage = MockStorageTrait::new();
+        let prefix = format!("pod/{}/", job_id);
+        storage.expect_put()
+            .withf(move |key, _, _| key.starts_with(&prefix))
+            .times(2)
+            .returning(|_, _, _| Ok(()));
+        let mut pod_repo = MockProofOfDeliveryRepositoryTrait::new();
+        pod_repo.expect_create()
+            .withf(move |_, dto, captured_by, files| {
+                dto.recipient_name == "Ana Ruiz"
+                    && dto.signature.is_empty()
+                    && *captured_by == Some(user_id)
+                    && files.len() == 2
+                    && files[0].kind == PodFileKind::Signature
+                    && files[0].content_type == "image/png"
+                    && files[1].kind == PodFileKind::Photo
+                    && files[1].content_type == "image/jpeg"
+                    && files[1].size_bytes == JPEG.len() as i64
+            })
+            .times(1)
+            .returning(|job_id, _, _, files| Ok(details(job_id, files)));
+
+        let service = service(JobStatus::InProgress, pod_repo, storage);
+        let result = service.capture(job_id, dto(vec![STANDARD.encode(JPEG)]), Some(user_id)).await.unwrap();
+        assert_eq!(result.files.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_capture_validates_before_storing() {
+        let storage = || {
+            let mut storage = MockStorageTrait::new();
+            storage.expect_put().times(0);
+            storage
+        };
+
+        let pending = service(JobStatus::Pending, MockProofOfDeliveryRepositoryTrait::new(), storage());
+        let result = pending.capture(Uuid::new_v4(), dto(vec![]), None).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("in progress")));
+
+        let service = service(JobStatus::InProgress, MockProofOfDeliveryRepositoryTrait::new(), storage());
+        let result = service.capture(Uuid::new_v4(), CreateProofOfDeliveryDto { recipient_name: "  ".into(), ..dto(vec![]) }, None).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("Recipient")));
+
+        let result = service.capture(Uuid::new_v4(), CreateProofOfDeliveryDto { latitude: 91.0, ..dto(vec![]) }, None).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("Latitude")));
+
+        let result = service.capture(Uuid::new_v4(), dto(vec![STANDARD.encode(PNG); MAX_PHOTOS + 1]), None).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("At most")));
+
+        // The second photo is bad, so not even the signature gets uploaded
+        let result = service.capture(Uuid::new_v4(), dto(vec![STANDARD.encode(PNG), STANDARD.encode(b"%PDF-1.7")]), None).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.starts_with("Photo 2")));
+    }
+
+    #[tokio::test]
+    async fn test_capture_removes_uploaded_files_when_record_fails() {
+        let mut storage = MockStorageTrait::new();
+        storage.expect_put().times(2).returning(|_, _, _| Ok(()));
+        storage.expect_delete().times(2).returning(|_| Ok(()));
+        let mut pod_repo = MockProofOfDeliveryRepositoryTrait::new();
+        pod_repo.expect_create().returning(|_, _, _, _| {
+            Err(AppError::BadRequest("Proof of delivery was already captured for this job".into()))
+        });
+
+        let service = service(JobStatus::InProgress, pod_repo, storage);
+        let result = service.capture(Uuid::new_v4(), dto(vec![STANDARD.encode(JPEG)]), None).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_delivery_requires_proof() {
+        let with_proof = Uuid::new_v4();
+        let mut pod_repo = MockProofOfDeliveryRepositoryTrait::new();
+        pod_repo.expect_exists_for_job().returning(move |job_id| Ok(job_id == with_proof));
+        let service = service(JobStatus::InProgress, pod_repo, MockStorageTrait::new());
+
+        let result = service.before_transition(&job(Uuid::new_v4(), JobStatus::InProgress), JobStatus::Delivered).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("proof of delivery")));
+        assert!(service.before_transition(&job(with_proof, JobStatus::InProgress), JobStatus::Delivered).await.is_ok());
+        assert!(service.before_transition(&job(Uuid::new_v4(), JobStatus::InProgress), JobStatus::Cancelled).await.is_ok());
+    }
+}
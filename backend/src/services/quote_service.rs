@@ -0,0 +1,567 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use rust_decimal::prelude::FromPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
+use uuid::Uuid;
+use crate::error::{AppError, ConflictKind};
+use crate::models::postgres::logistics::{CreateTransportJobDto, JobStatus, TransportJob};
+use crate::models::postgres::quote::{
+    RateCard, RateCardDto, Quote, QuoteQuery, CreateQuoteDto, NewQuote
+};
+use crate::repositories::postgres::logistics_repo::CustomerRepositoryTrait;
+use crate::repositories::postgres::maintenance_repo::AlertRepositoryTrait;
+use crate::repositories::postgres::quote_repo::{QuoteRepositoryTrait, RateCardRepositoryTrait};
+use crate::repositories::postgres::settings_repo::SettingsRepositoryTrait;
+use crate::repositories::postgres::unit_of_work::UnitOfWorkTrait;
+use crate::services::logistics_service::{check_credit, record_credit_check, CreditCheck};
+
+/// Days a quote can be converted when no `valid_until` is given.
+const QUOTE_VALIDITY_DAYS: i64 = 30;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait QuoteServiceTrait: Send + Sync {
+    // Rate cards
+    async fn create_rate_card(&self, dto: RateCardDto) -> Result<RateCard, AppError>;
+    async fn list_rate_cards(&self) -> Result<Vec<RateCard>, AppError>;
+    async fn get_rate_card(&self, id: Uuid) -> Result<RateCard, AppError>;
+    async fn update_rate_card(&self, id: Uuid, dto: RateCardDto) -> Result<RateCard, AppError>;
+    async fn delete_rate_card(&self, id: Uuid) -> Result<(), AppError>;
+
+    // Quotes
+    /// Prices the route and shipments with the rate card for the vehicle type.
+    async fn create_quote(&self, dto: CreateQuoteDto, created_by: Option<Uuid>) -> Result<Quote, AppError>;
+    async fn list_quotes(&self, query: QuoteQuery) -> Result<Vec<Quote>, AppError>;
+    async fn get_quote(&self, id: Uuid) -> Result<Quote, AppError>;
+    /// Creates a pending job at the quoted price, with the quote's route and shipments.
+    async fn convert_quote(&self, id: Uuid, created_by: Option<Uuid>) -> Result<TransportJob, AppError>;
+}
+
+pub struct QuoteService {
+    rate_card_repo: Arc<dyn RateCardRepositoryTrait>,
+    quote_repo: Arc<dyn QuoteRepositoryTrait>,
+    customer_repo: Arc<dyn CustomerRepositoryTrait>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait>,
+    alert_repo: Arc<dyn AlertRepositoryTrait>,
+    unit_of_work: Arc<dyn UnitOfWorkTrait>,
+}
+
+impl QuoteService {
+    pub fn new(
+        rate_card_repo: Arc<dyn RateCardRepositoryTrait>,
+        quote_repo: Arc<dyn QuoteRepositoryTrait>,
+        customer_repo: Arc<dyn CustomerRepositoryTrait>,
+        settings_repo: Arc<dyn SettingsRepositoryTrait>,
+        alert_repo: Arc<dyn AlertRepositoryTrait>,
+        unit_of_work: Arc<dyn UnitOfWorkTrait>,
+    ) -> Self {
+        Self {
+            rate_card_repo,
+            quote_repo,
+            customer_repo,
+            settings_repo,
+            alert_repo,
+            unit_of_work,
+        }
+    }
+}
+
+/// The charges making up a quoted price.
+#[derive(Debug, Clone, PartialEq)]
+struct Price {
+    distance_charge: Decimal,
+    weight_charge: Decimal,
+    surcharge: Decimal,
+    total: Decimal,
+}
+
+fn round_cents(amount: Decimal) -> Decimal {
+    amount.round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero)
+}
+
+/// Distance and weight charges, plus the surcharge of every shipment type carried, and
+/// no less than the card's minimum charge.
+fn price(card: &RateCard, distance_km: Decimal, weight_kg: Decimal, shipment_types: &[&str]) -> Price {
+    let distance_charge = round_cents(distance_km * card.per_km);
+    let weight_charge = round_cents(weight_kg * card.per_kg);
+    let percent: Decimal = card
+        .surcharges
+        .iter()
+        .filter(|s| shipment_types.iter().any(|t| t.trim().eq_ignore_ascii_case(&s.shipment_type)))
+        .map(|s| s.percent)
+        .sum();
+    let surcharge = round_cents((distance_charge + weight_charge) * percent / Decimal::ONE_HUNDRED);
+    let total = (distance_charge + weight_charge + surcharge).max(card.minimum_charge);
+    Price { distance_charge, weight_charge, surcharge, total }
+}
+
+fn validate_rate_card(dto: &mut RateCardDto) -> Result<(), AppError> {
+    dto.name = dto.name.trim().to_string();
+    if dto.name.is_empty() {
+        return Err(AppError::BadRequest("Rate card name is required".into()));
+    }
+    if dto.per_km.is_sign_negative() || dto.per_kg.is_sign_negative() || dto.minimum_charge.is_sign_negative() {
+        return Err(AppError::BadRequest("Rates and minimum charge cannot be negative".into()));
+    }
+
+    let mut seen = HashSet::new();
+    for surcharge in &mut dto.surcharges {
+        surcharge.shipment_type = surcharge.shipment_type.trim().to_string();
+        if surcharge.shipment_type.is_empty() {
+            return Err(AppError::BadRequest("Surcharges need a shipment type".into()));
+        }
+        if !seen.insert(surcharge.shipment_type.to_lowercase()) {
+            return Err(AppError::BadRequest(format!("Shipment type {} has more than one surcharge", surcharge.shipment_type)));
+        }
+        if surcharge.percent < Decimal::ZERO || surcharge.percent > Decimal::ONE_HUNDRED {
+            return Err(AppError::BadRequest("Surcharges must be between 0 and 100 percent".into()));
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl QuoteServiceTrait for QuoteService {
+    // Rate cards
+    async fn create_rate_card(&self, mut dto: RateCardDto) -> Result<RateCard, AppError> {
+        validate_rate_card(&mut dto)?;
+        self.rate_card_repo.create(dto).await
+    }
+
+    async fn list_rate_cards(&self) -> Result<Vec<RateCard>, AppError> {
+        self.rate_card_repo.find_all().await
+    }
+
+    async fn get_rate_card(&self, id: Uuid) -> Result<RateCard, AppError> {
+        self.rate_card_repo.find_by_id(id).await?
+            .ok_or(AppError::NotFound("Rate card not found".into()))
+    }
+
+    async fn update_rate_card(&self, id: Uuid, mut dto: RateCardDto) -> Result<RateCard, AppError> {
+        validate_rate_card(&mut dto)?;
+        self.rate_card_repo.update(id, dto).await?
+            .ok_or(AppError::NotFound("Rate card not found".into()))
+    }
+
+    async fn delete_rate_card(&self, id: Uuid) -> Result<(), AppError> {
+        if !self.rate_card_repo.delete(id).await? {
+            return Err(AppError::NotFound("Rate card not found".into()));
+        }
+        Ok(())
+    }
+
+    // Quotes
+    async fn create_quote(&self, dto: CreateQuoteDto, created_by: Option<Uuid>) -> Result<Quote, AppError> {
+        let today = Utc::now().date_naive();
+        let valid_until = dto.valid_until.unwrap_or(today + Duration::days(QUOTE_VALIDITY_DAYS));
+        if valid_until < today {
+            return Err(AppError::BadRequest("Quotes cannot be valid only in the past".into()));
+        }
+        if dto.shipments.iter().any(|s| !s.weight.is_finite() || s.weight < 0.0) {
+            return Err(AppError::BadRequest("Shipment weights cannot be negative".into()));
+        }
+        if dto.shipments.iter().any(|s| s.r#type.trim().is_empty()) {
+            return Err(AppError::BadRequest("Shipments need a type".into()));
+        }
+
+        let customer = self.customer_repo.find_by_id(dto.customer_id).await?
+            .ok_or(AppError::NotFound("Customer not found".into()))?;
+        let card = self.rate_card_repo.find_for_vehicle_type(dto.vehicle_type).await?
+            .ok_or_else(|| AppError::BadRequest(match dto.vehicle_type {
+                Some(vehicle_type) => format!("No active rate card for {:?} or for any vehicle type", vehicle_type),
+                None => "No active rate card for any vehicle type".to_string(),
+            }))?;
+        let settings = self.settings_repo.get().await?;
+
+        let distance_km = self.quote_repo
+            .route_distance_km(dto.origin.clone(), dto.destination.clone(), dto.waypoints.clone())
+            .await?;
+        let distance_km = Decimal::from_f64(distance_km).map(round_cents).unwrap_or_default();
+        let weight_kg = Decimal::from_f64(dto.shipments.iter().map(|s| s.weight).sum())
+            .map(round_cents)
+            .unwrap_or_default();
+        let shipment_types: Vec<&str> = dto.shipments.iter().map(|s| s.r#type.as_str()).collect();
+        let price = price(&card, distance_km, weight_kg, &shipment_types);
+
+        let quote = NewQuote {
+            customer_id: customer.id,
+            rate_card_id: card.id,
+            vehicle_type: dto.vehicle_type,
+            origin: dto.origin,
+            destination: dto.destination,
+            waypoints: dto.waypoints,
+            shipments: serde_json::to_value(&dto.shipments)
+                .map_err(|e| AppError::SerializationError(e.to_string()))?,
+            distance_km,
+            weight_kg,
+            distance_charge: price.distance_charge,
+            weight_charge: price.weight_charge,
+            surcharge: price.surcharge,
+            price: price.total,
+            currency: settings.currency,
+            valid_until,
+            created_by,
+        };
+        self.quote_repo.create(quote).await
+    }
+
+    async fn list_quotes(&self, query: QuoteQuery) -> Result<Vec<Quote>, AppError> {
+        self.quote_repo.find_all(query).await
+    }
+
+    async fn get_quote(&self, id: Uuid) -> Result<Quote, AppError> {
+        self.quote_repo.find_by_id(id).await?
+            .ok_or(AppError::NotFound("Quote not found".into()))
+    }
+
+    async fn convert_quote(&self, id: Uuid, created_by: Option<Uuid>) -> Result<TransportJob, AppError> {
+        // The quote and customer stay locked until the job, its route and shipments are
+        // stored, so a quote is converted once and credit checks do not race
+        let mut tx = self.unit_of_work.begin().await?;
+        let quote = tx.lock_quote(id).await?
+            .ok_or(AppError::NotFound("Quote not found".into()))?;
+        if let Some(job_id) = quote.job_id {
+            return Err(AppError::conflict(
+                ConflictKind::Duplicate,
+                format!("Quote was already converted into job {}", job_id),
+            ));
+        }
+        if quote.valid_until < Utc::now().date_naive() {
+            return Err(AppError::BadRequest(format!(
+                "Quote expired on {}; request a new one",
+                quote.valid_until
+            )));
+        }
+
+        let customer = tx.lock_customer(quote.customer_id).await?
+            .ok_or(AppError::NotFound("Customer not found".into()))?;
+        let credit = match customer.credit_limit {
+            Some(_) => check_credit(&customer, tx.credit_exposure(customer.id).await? + quote.price)?,
+            None => CreditCheck::Unlimited,
+        };
+
+        let job = tx.create_job(CreateTransportJobDto {
+            customer_id: quote.customer_id,
+            status: JobStatus::Pending,
+            agreed_price: quote.price,
+        }, created_by).await?;
+        tx.attach_quote_job(id, job.id).await?;
+        tx.commit().await?;
+
+        record_credit_check(self.alert_repo.as_ref(), &customer, credit).await;
+        Ok(job)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use serde_json::json;
+    use crate::models::postgres::logistics::Customer;
+    use crate::models::postgres::quote::{QuoteShipmentDto, RateCardSurcharge};
+    use crate::models::postgres::settings::AppSettings;
+    use crate::models::postgres::vehicle::VehicleType;
+    use crate::repositories::postgres::logistics_repo::MockCustomerRepositoryTrait;
+    use crate::repositories::postgres::maintenance_repo::MockAlertRepositoryTrait;
+    use crate::repositories::postgres::quote_repo::{MockQuoteRepositoryTrait, MockRateCardRepositoryTrait};
+    use crate::repositories::postgres::settings_repo::MockSettingsRepositoryTrait;
+    use crate::repositories::postgres::unit_of_work::{MockRepositoryTransactionTrait, MockUnitOfWorkTrait};
+    use crate::services::logistics_service::CREDIT_LIMIT_ALERT;
+
+    fn settings() -> AppSettings {
+        AppSettings {
+            currency: "EUR".into(),
+            ..Default::default()
+        }
+    }
+
+    fn card() -> RateCard {
+        RateCard {
+            id: Uuid::new_v4(),
+            name: "Trucks".into(),
+            vehicle_type: Some(VehicleType::Truck),
+            per_km: Decimal::new(150, 2),
+            per_kg: Decimal::new(5, 2),
+            minimum_charge: Decimal::new(100, 0),
+            is_active: true,
+            surcharges: vec![RateCardSurcharge { shipment_type: "hazmat".into(), percent: Decimal::new(25, 0) }],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn quote(valid_until: NaiveDate, job_id: Option<Uuid>) -> Quote {
+        Quote {
+            id: Uuid::new_v4(),
+            customer_id: Uuid::new_v4(),
+            rate_card_id: None,
+            vehicle_type: None,
+            origin: json!({"type": "Point", "coordinates": [13.4, 52.5]}),
+            destination: json!({"type": "Point", "coordinates": [11.6, 48.1]}),
+            waypoints: None,
+            shipments: json!([]),
+            distance_km: Decimal::new(50400, 2),
+            weight_kg: Decimal::ZERO,
+            distance_charge: Decimal::new(75600, 2),
+            weight_charge: Decimal::ZERO,
+            surcharge: Decimal::ZERO,
+            price: Decimal::new(75600, 2),
+            currency: "EUR".into(),
+            valid_until,
+            job_id,
+            converted_at: None,
+            created_by: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn customer(id: Uuid, credit_limit: Option<Decimal>) -> Customer {
+        Customer {
+            id,
+            name: "Acme Retail".into(),
+            contact_info: json!({}),
+            billing_address: "5 Market St".into(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            credit_limit,
+            block_over_credit_limit: false,
+        }
+    }
+
+    fn service(rate_card_repo: MockRateCardRepositoryTrait, quote_repo: MockQuoteRepositoryTrait) -> QuoteService {
+        conversion_service(rate_card_repo, quote_repo, MockAlertRepositoryTrait::new(), MockUnitOfWorkTrait::new())
+    }
+
+    fn conversion_service(
+        rate_card_repo: MockRateCardRepositoryTrait,
+        quote_repo: MockQuoteRepositoryTrait,
+        alert_repo: MockAlertRepositoryTrait,
+        unit_of_work: MockUnitOfWorkTrait,
+    ) -> QuoteService {
+        let mut customer_repo = MockCustomerRepositoryTrait::new();
+        customer_repo.expect_find_by_id().returning(|id| Ok(Some(customer(id, None))));
+        let mut settings_repo = MockSettingsRepositoryTrait::new();
+        settings_repo.expect_get().returning(|| Ok(settings()));
+        QuoteService::new(
+            Arc::new(rate_card_repo),
+            Arc::new(quote_repo),
+            Arc::new(customer_repo),
+            Arc::new(settings_repo),
+            Arc::new(alert_repo),
+            Arc::new(unit_of_work),
+        )
+    }
+
+    fn unit_of_work(tx: MockRepositoryTransactionTrait) -> MockUnitOfWorkTrait {
+        let mut unit_of_work = MockUnitOfWorkTrait::new();
+        unit_of_work.expect_begin().times(1).return_once(move || Ok(Box::new(tx)));
+        unit_of_work
+    }
+
+    #[test]
+    fn test_price_adds_surcharges_and_keeps_minimum() {
+        let card = card();
+
+        // 120 km at 1.50 and 800 kg at 0.05, plus 25% for hazmat
+        let quoted = price(&card, Decimal::new(120, 0), Decimal::new(800, 0), &["pallet", " HAZMAT "]);
+        assert_eq!(quoted.distance_charge, Decimal::new(18000, 2));
+        assert_eq!(quoted.weight_charge, Decimal::new(4000, 2));
+        assert_eq!(quoted.surcharge, Decimal::new(5500, 2));
+        assert_eq!(quoted.total, Decimal::new(27500, 2));
+
+        let short = price(&card, Decimal::new(10, 0), Decimal::ZERO, &["pallet"]);
+        assert_eq!(short.surcharge, Decimal::ZERO);
+        assert_eq!(short.total, Decimal::new(100, 0));
+    }
+
+    #[test]
+    fn test_validate_rate_card() {
+        let dto = |surcharges: Vec<RateCardSurcharge>| RateCardDto {
+            name: " Vans ".into(),
+            vehicle_type: Some(VehicleType::Van),
+            per_km: Decimal::ONE,
+            per_kg: Decimal::ZERO,
+            minimum_charge: Decimal::ZERO,
+            is_active: true,
+            surcharges,
+        };
+        let surcharge = |shipment_type: &str, percent| RateCardSurcharge { shipment_type: shipment_type.into(), percent };
+
+        let mut valid = dto(vec![surcharge(" hazmat ", Decimal::new(25, 0))]);
+        assert!(validate_rate_card(&mut valid).is_ok());
+        assert_eq!(valid.name, "Vans");
+        assert_eq!(valid.surcharges[0].shipment_type, "hazmat");
+
+        let mut duplicate = dto(vec![surcharge("hazmat", Decimal::ONE), surcharge("HAZMAT", Decimal::TWO)]);
+        assert!(matches!(validate_rate_card(&mut duplicate), Err(AppError::BadRequest(_))));
+        let mut too_high = dto(vec![surcharge("hazmat", Decimal::new(101, 0))]);
+        assert!(matches!(validate_rate_card(&mut too_high), Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_quote_prices_route_and_shipments() {
+        let card = card();
+        let card_id = card.id;
+        let mut rate_card_repo = MockRateCardRepositoryTrait::new();
+        rate_card_repo
+            .expect_find_for_vehicle_type()
+            .withf(|vehicle_type| *vehicle_type == Some(VehicleType::Truck))
+            .returning(move |_| Ok(Some(card.clone())));
+        let mut quote_repo = MockQuoteRepositoryTrait::new();
+        quote_repo.expect_route_distance_km().times(1).returning(|_, _, _| Ok(120.004));
+        quote_repo
+            .expect_create()
+            .withf(move |new: &NewQuote| {
+                new.rate_card_id == card_id
+                    && new.distance_km == Decimal::new(12000, 2)
+                    && new.weight_kg == Decimal::new(80000, 2)
+                    && new.price == Decimal::new(27500, 2)
+                    && new.currency == "EUR"
+                    && new.shipments[1]["type"] == "hazmat"
+            })
+            .times(1)
+            .returning(|new| Ok(Quote { price: new.price, ..quote(new.valid_until, None) }));
+
+        let service = service(rate_card_repo, quote_repo);
+        let dto = CreateQuoteDto {
+            customer_id: Uuid::new_v4(),
+            vehicle_type: Some(VehicleType::Truck),
+            origin: json!({"type": "Point", "coordinates": [13.4, 52.5]}),
+            destination: json!({"type": "Point", "coordinates": [13.9, 51.9]}),
+            waypoints: None,
+            shipments: vec![
+                QuoteShipmentDto { weight: 500.0, dimensions: json!({"l": 120, "w": 80, "h": 100}), r#type: "pallet".into() },
+                QuoteShipmentDto { weight: 300.0, dimensions: json!({"l": 60, "w": 40, "h": 40}), r#type: "hazmat".into() },
+            ],
+            valid_until: None,
+        };
+        let quote = service.create_quote(dto, None).await.unwrap();
+        assert_eq!(quote.price, Decimal::new(27500, 2));
+    }
+
+    #[tokio::test]
+    async fn test_create_quote_without_rate_card() {
+        let mut rate_card_repo = MockRateCardRepositoryTrait::new();
+        rate_card_repo.expect_find_for_vehicle_type().returning(|_| Ok(None));
+        let mut quote_repo = MockQuoteRepositoryTrait::new();
+        quote_repo.expect_create().never();
+
+        let service = service(rate_card_repo, quote_repo);
+        let dto = CreateQuoteDto {
+            customer_id: Uuid::new_v4(),
+            vehicle_type: Some(VehicleType::Van),
+            origin: json!({"type": "Point", "coordinates": [13.4, 52.5]}),
+            destination: json!({"type": "Point", "coordinates": [13.9, 51.9]}),
+            waypoints: None,
+            shipments: Vec::new(),
+            valid_until: None,
+        };
+        let result = service.create_quote(dto, None).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("No active rate card for Van")));
+    }
+
+    #[tokio::test]
+    async fn test_convert_quote_locks_in_price() {
+        let open = quote(Utc::now().date_naive(), None);
+        let (quote_id, customer_id) = (open.id, open.customer_id);
+        let job_id = Uuid::new_v4();
+
+        let mut tx = MockRepositoryTransactionTrait::new();
+        tx.expect_lock_quote().times(1).returning(move |_| Ok(Some(open.clone())));
+        tx.expect_lock_customer()
+            .times(1)
+            .returning(|id| Ok(Some(customer(id, Some(Decimal::new(1000, 0))))));
+        tx.expect_credit_exposure().times(1).returning(|_| Ok(Decimal::new(10000, 2)));
+        tx.expect_create_job()
+            .withf(move |dto, _| {
+                dto.customer_id == customer_id
+                    && dto.status == JobStatus::Pending
+                    && dto.agreed_price == Decimal::new(75600, 2)
+            })
+            .times(1)
+            .returning(move |dto, _| Ok(TransportJob {
+                id: job_id,
+                customer_id: dto.customer_id,
+                status: dto.status,
+                agreed_price: dto.agreed_price,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }));
+        tx.expect_attach_quote_job()
+            .withf(move |id, job| *id == quote_id && *job == job_id)
+            .times(1)
+            .returning(|_, _| Ok(()));
+        tx.expect_commit().times(1).returning(|| Ok(()));
+
+        // 100 owed plus the 756 quoted stays within the 1000 limit
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo.expect_resolve_open()
+            .withf(move |id, types| *id == customer_id && types == &vec![CREDIT_LIMIT_ALERT.to_string()])
+            .times(1)
+            .returning(|_, _| Ok(0));
+
+        let service = conversion_service(MockRateCardRepositoryTrait::new(), MockQuoteRepositoryTrait::new(), alert_repo, unit_of_work(tx));
+        let job = service.convert_quote(quote_id, None).await.unwrap();
+        assert_eq!(job.id, job_id);
+        assert_eq!(job.agreed_price, Decimal::new(75600, 2));
+    }
+
+    #[tokio::test]
+    async fn test_convert_quote_rejects_converted_and_expired() {
+        let converted = quote(Utc::now().date_naive(), Some(Uuid::new_v4()));
+        let mut tx = MockRepositoryTransactionTrait::new();
+        tx.expect_lock_quote().returning(move |_| Ok(Some(converted.clone())));
+        tx.expect_create_job().never();
+        tx.expect_commit().never();
+        let service = conversion_service(
+            MockRateCardRepositoryTrait::new(),
+            MockQuoteRepositoryTrait::new(),
+            MockAlertRepositoryTrait::new(),
+            unit_of_work(tx),
+        );
+        let result = service.convert_quote(Uuid::new_v4(), None).await;
+        assert!(matches!(result, Err(AppError::Conflict(details)) if details.message.contains("already converted")));
+
+        let expired = quote(Utc::now().date_naive() - Duration::days(1), None);
+        let mut tx = MockRepositoryTransactionTrait::new();
+        tx.expect_lock_quote().returning(move |_| Ok(Some(expired.clone())));
+        tx.expect_create_job().never();
+        tx.expect_commit().never();
+        let service = conversion_service(
+            MockRateCardRepositoryTrait::new(),
+            MockQuoteRepositoryTrait::new(),
+            MockAlertRepositoryTrait::new(),
+            unit_of_work(tx),
+        );
+        let result = service.convert_quote(Uuid::new_v4(), None).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("expired")));
+    }
+
+    #[tokio::test]
+    async fn test_convert_quote_blocked_over_credit_limit_creates_nothing() {
+        let open = quote(Utc::now().date_naive(), None);
+        let mut tx = MockRepositoryTransactionTrait::new();
+        tx.expect_lock_quote().returning(move |_| Ok(Some(open.clone())));
+        tx.expect_lock_customer().returning(|id| Ok(Some(Customer {
+            block_over_credit_limit: true,
+            ..customer(id, Some(Decimal::new(500, 0)))
+        })));
+        tx.expect_credit_exposure().returning(|_| Ok(Decimal::ZERO));
+        tx.expect_create_job().never();
+        tx.expect_attach_quote_job().never();
+        tx.expect_commit().never();
+
+        let service = conversion_service(
+            MockRateCardRepositoryTrait::new(),
+            MockQuoteRepositoryTrait::new(),
+            MockAlertRepositoryTrait::new(),
+            unit_of_work(tx),
+        );
+        let result = service.convert_quote(Uuid::new_v4(), None).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.starts_with("Credit limit exceeded")));
+    }
+}
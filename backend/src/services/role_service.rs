@@ -0,0 +1,199 @@
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use moka::future::Cache;
+
+use crate::error::AppError;
+use crate::models::postgres::user::{Role, UserRole};
+use crate::repositories::postgres::role_repo::RoleRepositoryTrait;
+
+/// How long a role's permission list is cached before it is re-read from the `roles` table.
+const PERMISSION_CACHE_TTL: Duration = Duration::from_secs(60);
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait RoleServiceTrait: Send + Sync {
+    async fn list_roles(&self) -> Result<Vec<Role>, AppError>;
+    async fn get_role(&self, role: UserRole) -> Result<Role, AppError>;
+    async fn update_permissions(&self, role: UserRole, permissions: Vec<String>) -> Result<Role, AppError>;
+    async fn has_permission(&self, role: UserRole, permission: &str) -> Result<bool, AppError>;
+}
+
+pub struct RoleService {
+    repo: Arc<dyn RoleRepositoryTrait>,
+    cache: Cache<UserRole, Arc<Vec<String>>>,
+}
+
+impl RoleService {
+    pub fn new(repo: Arc<dyn RoleRepositoryTrait>) -> Self {
+        Self {
+            repo,
+            cache: Cache::builder().time_to_live(PERMISSION_CACHE_TTL).build(),
+        }
+    }
+
+    async fn permissions_for(&self, role: UserRole) -> Result<Arc<Vec<String>>, AppError> {
+        if let Some(permissions) = self.cache.get(&role).await {
+            return Ok(permissions);
+        }
+
+        // A role without a row in `roles` is granted nothing.
+        let permissions = match self.repo.find_by_role(role).await? {
+            Some(row) => serde_json::from_value::<Vec<String>>(row.permissions)
+                .map_err(|e| AppError::SerializationError(e.to_string()))?,
+            None => Vec::new(),
+        };
+
+        let permissions = Arc::new(permissions);
+        self.cache.insert(role, permissions.clone()).await;
+        Ok(permissions)
+    }
+}
+
+/// Builds the permission string required for an action on a resource,
+/// e.g. `("vehicles", false)` -> `"vehicles:read"`.
+pub fn permission_for(resource: &str, write: bool) -> String {
+    format!("{}:{}", resource, if write { "write" } else { "read" })
+}
+
+/// Returns true when any granted permission covers `required`.
+/// Supports the `*` wildcard and `resource:*` for every action on a resource.
+pub fn grants(granted: &[String], required: &str) -> bool {
+    let resource = required.split(':').next().unwrap_or_default();
+    granted.iter().any(|p| {
+        p == "*" || p == required || p.strip_suffix(":*") == Some(resource)
+    })
+}
+
+fn is_valid_permission(permission: &str) -> bool {
+    if permission == "*" {
+        return true;
+    }
+    match permission.split_once(':') {
+        Some((resource, action)) => {
+            !resource.is_empty()
+                && resource.chars().all(|c| c.is_ascii_lowercase() || c == '_' || c == '-')
+                && matches!(action, "read" | "write" | "*")
+        }
+        None => false,
+    }
+}
+
+#[async_trait]
+impl RoleServiceTrait for RoleService {
+    async fn list_roles(&self) -> Result<Vec<Role>, AppError> {
+        self.repo.find_all().await
+    }
+
+    async fn get_role(&self, role: UserRole) -> Result<Role, AppError> {
+        self.repo
+            .find_by_role(role)
+            .await?
+            .ok_or(AppError::NotFound(format!("Role {} not found", role.as_str())))
+    }
+
+    async fn update_permissions(&self, role: UserRole, permissions: Vec<String>) -> Result<Role, AppError> {
+        if let Some(invalid) = permissions.iter().find(|p| !is_valid_permission(p)) {
+            return Err(AppError::BadRequest(format!("Invalid permission: {}", invalid)));
+        }
+
+        let updated = self.repo.update_permissions(role, serde_json::json!(permissions)).await?;
+        self.cache.invalidate(&role).await;
+        Ok(updated)
+    }
+
+    async fn has_permission(&self, role: UserRole, permission: &str) -> Result<bool, AppError> {
+        let permissions = self.permissions_for(role).await?;
+        Ok(grants(&permissions, permission))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::postgres::role_repo::MockRoleRepositoryTrait;
+    use mockall::predicate;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn sample_role(role: UserRole, permissions: serde_json::Value) -> Role {
+        Role {
+            id: Uuid::new_v4(),
+            name: role.as_str().into(),
+            permissions,
+        }
+    }
+
+    #[test]
+    fn test_grants_wildcards() {
+        let granted = vec!["vehicles:*".to_string(), "financial:read".to_string()];
+        assert!(grants(&granted, "vehicles:write"));
+        assert!(grants(&granted, "financial:read"));
+        assert!(!grants(&granted, "financial:write"));
+        assert!(!grants(&granted, "users:read"));
+        assert!(grants(&["*".to_string()], "users:write"));
+    }
+
+    #[tokio::test]
+    async fn test_has_permission_reads_role_once() {
+        let mut mock_repo = MockRoleRepositoryTrait::new();
+        mock_repo
+            .expect_find_by_role()
+            .with(predicate::eq(UserRole::Driver))
+            .times(1)
+            .returning(|role| Ok(Some(sample_role(role, json!(["vehicles:read"])))));
+
+        let service = RoleService::new(Arc::new(mock_repo));
+        assert!(service.has_permission(UserRole::Driver, "vehicles:read").await.unwrap());
+        assert!(!service.has_permission(UserRole::Driver, "vehicles:write").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_missing_role_has_no_permissions() {
+        let mut mock_repo = MockRoleRepositoryTrait::new();
+        mock_repo.expect_find_by_role().returning(|_| Ok(None));
+
+        let service = RoleService::new(Arc::new(mock_repo));
+        assert!(!service.has_permission(UserRole::Mechanic, "vehicles:read").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_update_permissions_rejects_invalid_entries() {
+        let mock_repo = MockRoleRepositoryTrait::new();
+        let service = RoleService::new(Arc::new(mock_repo));
+
+        let result = service
+            .update_permissions(UserRole::Manager, vec!["vehicles:delete".into()])
+            .await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_update_permissions_invalidates_cache() {
+        let mut mock_repo = MockRoleRepositoryTrait::new();
+        let mut seq = mockall::Sequence::new();
+        mock_repo
+            .expect_find_by_role()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|role| Ok(Some(sample_role(role, json!([])))));
+        mock_repo
+            .expect_update_permissions()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|role, permissions| Ok(sample_role(role, permissions)));
+        mock_repo
+            .expect_find_by_role()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|role| Ok(Some(sample_role(role, json!(["logistics:read"])))));
+
+        let service = RoleService::new(Arc::new(mock_repo));
+        assert!(!service.has_permission(UserRole::Driver, "logistics:read").await.unwrap());
+        service
+            .update_permissions(UserRole::Driver, vec!["logistics:read".into()])
+            .await
+            .unwrap();
+        assert!(service.has_permission(UserRole::Driver, "logistics:read").await.unwrap());
+    }
+}
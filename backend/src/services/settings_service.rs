@@ -1,5 +1,6 @@
 use std::sync::Arc;
 use async_trait::async_trait;
+use rust_decimal::Decimal;
 
 use crate::error::AppError;
 use crate::models::postgres::settings::{AppSettings, UpdateAppSettingsDto};
@@ -29,6 +30,34 @@ impl SettingsServiceTrait for SettingsService {
     }
 
     async fn update_settings(&self, dto: UpdateAppSettingsDto) -> Result<AppSettings, AppError> {
+        if dto.telemetry_retention_months.is_some_and(|months| months < 0) {
+            return Err(AppError::BadRequest("telemetry_retention_months cannot be negative".into()));
+        }
+        let negative_threshold = dto.speed_limit_kmh.is_some_and(|v| v < 0.0)
+            || dto.fuel_drop_threshold.is_some_and(|v| v < 0.0)
+            || dto.idle_alert_minutes.is_some_and(|v| v < 0)
+            || dto.tracker_stale_minutes.is_some_and(|v| v < 0);
+        if negative_threshold {
+            return Err(AppError::BadRequest("Anomaly thresholds cannot be negative".into()));
+        }
+        if dto.license_expiry_warning_days.is_some_and(|days| days < 0) {
+            return Err(AppError::BadRequest("license_expiry_warning_days cannot be negative".into()));
+        }
+        if dto.document_expiry_warning_days.is_some_and(|days| days < 0) {
+            return Err(AppError::BadRequest("document_expiry_warning_days cannot be negative".into()));
+        }
+        if dto.tax_rate.is_some_and(|rate| rate.is_sign_negative() || rate > Decimal::ONE_HUNDRED) {
+            return Err(AppError::BadRequest("tax_rate must be a percentage between 0 and 100".into()));
+        }
+        if dto.payment_terms_days.is_some_and(|days| days < 0) {
+            return Err(AppError::BadRequest("payment_terms_days cannot be negative".into()));
+        }
+        let negative_hos_limit = dto.hos_max_daily_hours.is_some_and(|v| v < 0.0)
+            || dto.hos_max_weekly_hours.is_some_and(|v| v < 0.0)
+            || dto.hos_min_rest_hours.is_some_and(|v| v < 0.0);
+        if negative_hos_limit {
+            return Err(AppError::BadRequest("Hours-of-service limits cannot be negative".into()));
+        }
         self.repo.update(dto).await
     }
 }
@@ -37,11 +66,27 @@ impl SettingsServiceTrait for SettingsService {
 mod tests {
     use super::*;
     use crate::repositories::postgres::settings_repo::MockSettingsRepositoryTrait;
-    use chrono::Utc;
 
-    fn sample_settings() -> AppSettings {
-        AppSettings {
-            id: 1,
+    #[tokio::test]
+    async fn test_get_settings() {
+        let mut mock_repo = MockSettingsRepositoryTrait::new();
+        mock_repo.expect_get().returning(|| Ok(AppSettings::default()));
+
+        let service = SettingsService::new(Arc::new(mock_repo));
+        let result = service.get_settings().await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().id, 1);
+    }
+
+    #[tokio::test]
+    async fn test_update_settings() {
+        let mut mock_repo = MockSettingsRepositoryTrait::new();
+        mock_repo
+            .expect_update()
+            .returning(|_| Ok(AppSettings::default()));
+
+        let service = SettingsService::new(Arc::new(mock_repo));
+        let dto = UpdateAppSettingsDto {
             company_name: "Acme".into(),
             contact_email: "admin@acme.com".into(),
             phone_number: "123".into(),
@@ -57,30 +102,28 @@ mod tests {
             notify_sms: false,
             notify_desktop: false,
             notify_weekly_summary: true,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
-        }
-    }
-
-    #[tokio::test]
-    async fn test_get_settings() {
-        let mut mock_repo = MockSettingsRepositoryTrait::new();
-        mock_repo.expect_get().returning(|| Ok(sample_settings()));
+            telemetry_retention_months: None,
+            telemetry_archive_expired: None,
+            speed_limit_kmh: None,
+            fuel_drop_threshold: None,
+            idle_alert_minutes: None,
+            tracker_stale_minutes: None,
+            license_expiry_warning_days: None,
+            hos_max_daily_hours: None,
+            hos_max_weekly_hours: None,
+            hos_min_rest_hours: None,
+            document_expiry_warning_days: None,
+            tax_rate: None,
+            payment_terms_days: None,
+        };
 
-        let service = SettingsService::new(Arc::new(mock_repo));
-        let result = service.get_settings().await;
+        let result = service.update_settings(dto).await;
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().id, 1);
     }
 
     #[tokio::test]
-    async fn test_update_settings() {
-        let mut mock_repo = MockSettingsRepositoryTrait::new();
-        mock_repo
-            .expect_update()
-            .returning(|_| Ok(sample_settings()));
-
-        let service = SettingsService::new(Arc::new(mock_repo));
+    async fn test_update_settings_rejects_negative_retention() {
+        let service = SettingsService::new(Arc::new(MockSettingsRepositoryTrait::new()));
         let dto = UpdateAppSettingsDto {
             company_name: "Acme".into(),
             contact_email: "admin@acme.com".into(),
@@ -97,9 +140,22 @@ mod tests {
             notify_sms: false,
             notify_desktop: false,
             notify_weekly_summary: true,
+            telemetry_retention_months: Some(-1),
+            telemetry_archive_expired: None,
+            speed_limit_kmh: None,
+            fuel_drop_threshold: None,
+            idle_alert_minutes: None,
+            tracker_stale_minutes: None,
+            license_expiry_warning_days: None,
+            hos_max_daily_hours: None,
+            hos_max_weekly_hours: None,
+            hos_min_rest_hours: None,
+            document_expiry_warning_days: None,
+            tax_rate: None,
+            payment_terms_days: None,
         };
 
         let result = service.update_settings(dto).await;
-        assert!(result.is_ok());
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
     }
 }
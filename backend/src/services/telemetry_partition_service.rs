@@ -0,0 +1,202 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{Datelike, Months, NaiveDate};
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::repositories::postgres::partition_repo::TelemetryPartitionRepositoryTrait;
+use crate::repositories::postgres::settings_repo::SettingsRepositoryTrait;
+
+/// Partitions are created for the current month and this many months ahead.
+const MONTHS_AHEAD: u32 = 2;
+
+#[derive(Debug, Default, Serialize)]
+pub struct PartitionMaintenanceReport {
+    pub created: Vec<NaiveDate>,
+    /// Rows moved out of the default partition into monthly partitions
+    pub moved_rows: u64,
+    /// Rows in the default partition older than the retention window, deleted or
+    /// moved to archive tables depending on `telemetry_archive_expired`
+    pub expired_rows: u64,
+    pub dropped: Vec<NaiveDate>,
+    pub archived: Vec<NaiveDate>,
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait TelemetryPartitionServiceTrait: Send + Sync {
+    async fn run_maintenance(&self, today: NaiveDate) -> Result<PartitionMaintenanceReport, AppError>;
+}
+
+pub struct TelemetryPartitionService {
+    partition_repo: Arc<dyn TelemetryPartitionRepositoryTrait>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait>,
+}
+
+impl TelemetryPartitionService {
+    pub fn new(
+        partition_repo: Arc<dyn TelemetryPartitionRepositoryTrait>,
+        settings_repo: Arc<dyn SettingsRepositoryTrait>,
+    ) -> Self {
+        Self { partition_repo, settings_repo }
+    }
+}
+
+#[async_trait]
+impl TelemetryPartitionServiceTrait for TelemetryPartitionService {
+    async fn run_maintenance(&self, today: NaiveDate) -> Result<PartitionMaintenanceReport, AppError> {
+        let settings = self.settings_repo.get().await?;
+        let current = today.with_day(1).unwrap_or(today);
+
+        // Months before the cutoff are outside retention; 0 keeps everything.
+        let cutoff = match settings.telemetry_retention_months {
+            months if months > 0 => current.checked_sub_months(Months::new(months as u32)),
+            _ => None,
+        };
+
+        let mut report = PartitionMaintenanceReport::default();
+
+        let mut months = self.partition_repo.find_default_partition_months().await?;
+
+        if let Some(cutoff) = cutoff {
+            if settings.telemetry_archive_expired {
+                for month in months.iter().filter(|m| **m < cutoff) {
+                    report.expired_rows += self.partition_repo.archive_default_rows(*month).await?;
+                }
+            } else {
+                let cutoff_time = cutoff.and_hms_opt(0, 0, 0).map(|t| t.and_utc())
+                    .ok_or_else(|| AppError::InternalServerError("Invalid retention cutoff".into()))?;
+                report.expired_rows = self.partition_repo.delete_default_rows_before(cutoff_time).await?;
+            }
+        }
+
+        months.extend((0..=MONTHS_AHEAD).filter_map(|n| current.checked_add_months(Months::new(n))));
+        months.sort();
+        months.dedup();
+
+        let existing = self.partition_repo.find_partition_months().await?;
+        for month in months {
+            if cutoff.is_some_and(|cutoff| month < cutoff) || existing.contains(&month) {
+                continue;
+            }
+            report.moved_rows += self.partition_repo.create_partition(month).await?;
+            report.created.push(month);
+        }
+
+        if let Some(cutoff) = cutoff {
+            for month in existing.into_iter().filter(|m| *m < cutoff) {
+                if settings.telemetry_archive_expired {
+                    self.partition_repo.archive_partition(month).await?;
+                    report.archived.push(month);
+                } else {
+                    self.partition_repo.drop_partition(month).await?;
+                    report.dropped.push(month);
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::postgres::settings::AppSettings;
+    use crate::repositories::postgres::partition_repo::MockTelemetryPartitionRepositoryTrait;
+    use crate::repositories::postgres::settings_repo::MockSettingsRepositoryTrait;
+    use mockall::predicate::*;
+
+    fn month(year: i32, month: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, 1).unwrap()
+    }
+
+    fn settings_repo(retention_months: i32, archive: bool) -> MockSettingsRepositoryTrait {
+        let mut mock_repo = MockSettingsRepositoryTrait::new();
+        mock_repo.expect_get().returning(move || Ok(AppSettings {
+            telemetry_retention_months: retention_months,
+            telemetry_archive_expired: archive,
+            ..Default::default()
+        }));
+        mock_repo
+    }
+
+    #[tokio::test]
+    async fn test_creates_upcoming_and_backfills_default_months() {
+        let mut mock_partitions = MockTelemetryPartitionRepositoryTrait::new();
+        mock_partitions.expect_delete_default_rows_before().returning(|_| Ok(0));
+        mock_partitions.expect_find_default_partition_months()
+            .returning(|| Ok(vec![month(2026, 8), month(2026, 10)]));
+        mock_partitions.expect_find_partition_months()
+            .returning(|| Ok(vec![month(2026, 11)]));
+        mock_partitions.expect_create_partition()
+            .with(eq(month(2026, 8))).times(1).returning(|_| Ok(40));
+        mock_partitions.expect_create_partition()
+            .with(eq(month(2026, 10))).times(1).returning(|_| Ok(2));
+        mock_partitions.expect_create_partition()
+            .with(eq(month(2026, 12))).times(1).returning(|_| Ok(0));
+
+        let service = TelemetryPartitionService::new(
+            Arc::new(mock_partitions),
+            Arc::new(settings_repo(12, false)),
+        );
+
+        let report = service.run_maintenance(NaiveDate::from_ymd_opt(2026, 10, 17).unwrap()).await.unwrap();
+        assert_eq!(report.created, vec![month(2026, 8), month(2026, 10), month(2026, 12)]);
+        assert_eq!(report.moved_rows, 42);
+    }
+
+    #[tokio::test]
+    async fn test_expires_partitions_outside_retention() {
+        let mut mock_partitions = MockTelemetryPartitionRepositoryTrait::new();
+        mock_partitions.expect_delete_default_rows_before()
+            .withf(|cutoff| cutoff.date_naive() == NaiveDate::from_ymd_opt(2026, 7, 1).unwrap())
+            .times(1)
+            .returning(|_| Ok(5));
+        mock_partitions.expect_find_default_partition_months().returning(|| Ok(vec![month(2026, 5)]));
+        mock_partitions.expect_find_partition_months().returning(|| Ok(vec![
+            month(2026, 6), month(2026, 7), month(2026, 10), month(2026, 11), month(2026, 12),
+        ]));
+        mock_partitions.expect_drop_partition()
+            .with(eq(month(2026, 6))).times(1).returning(|_| Ok(()));
+
+        let service = TelemetryPartitionService::new(
+            Arc::new(mock_partitions),
+            Arc::new(settings_repo(3, false)),
+        );
+
+        let report = service.run_maintenance(NaiveDate::from_ymd_opt(2026, 10, 17).unwrap()).await.unwrap();
+        assert_eq!(report.dropped, vec![month(2026, 6)]);
+        assert!(report.archived.is_empty());
+        assert!(report.created.is_empty());
+        assert_eq!(report.expired_rows, 5);
+    }
+
+    #[tokio::test]
+    async fn test_archives_expired_default_rows_instead_of_deleting() {
+        let mut mock_partitions = MockTelemetryPartitionRepositoryTrait::new();
+        mock_partitions.expect_delete_default_rows_before().never();
+        mock_partitions.expect_find_default_partition_months()
+            .returning(|| Ok(vec![month(2026, 4), month(2026, 5), month(2026, 10)]));
+        mock_partitions.expect_archive_default_rows()
+            .with(eq(month(2026, 4))).times(1).returning(|_| Ok(3));
+        mock_partitions.expect_archive_default_rows()
+            .with(eq(month(2026, 5))).times(1).returning(|_| Ok(2));
+        mock_partitions.expect_find_partition_months().returning(|| Ok(vec![
+            month(2026, 6), month(2026, 7), month(2026, 10), month(2026, 11), month(2026, 12),
+        ]));
+        mock_partitions.expect_archive_partition()
+            .with(eq(month(2026, 6))).times(1).returning(|_| Ok(()));
+
+        let service = TelemetryPartitionService::new(
+            Arc::new(mock_partitions),
+            Arc::new(settings_repo(3, true)),
+        );
+
+        let report = service.run_maintenance(NaiveDate::from_ymd_opt(2026, 10, 17).unwrap()).await.unwrap();
+        assert_eq!(report.archived, vec![month(2026, 6)]);
+        assert!(report.dropped.is_empty());
+        assert!(report.created.is_empty());
+        assert_eq!(report.expired_rows, 5);
+    }
+}
@@ -1,34 +1,662 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{json, Value};
 use uuid::Uuid;
 use async_trait::async_trait;
 use crate::error::AppError;
-use crate::models::postgres::telemetry::{VehicleTelemetry, CreateVehicleTelemetryDto};
+use crate::models::postgres::telemetry::{
+    VehicleTelemetry, CreateVehicleTelemetryDto, BulkTelemetryResult, TelemetryRejection,
+    TelemetryFormat, TelemetryHistory, TelemetryHistoryQuery
+};
+use crate::models::redis::vehicle_status::VehicleStatusCache;
 use crate::repositories::postgres::telemetry_repo::TelemetryRepositoryTrait;
+use crate::repositories::postgres::assignment_repo::AssignmentRepositoryTrait;
+use crate::repositories::postgres::vehicle_repo::VehicleRepositoryTrait;
+use crate::repositories::redis::vehicle_status_repo::VehicleStatusCacheRepositoryTrait;
+use crate::services::geofence_service::GeofenceServiceTrait;
+use crate::services::anomaly_service::AnomalyServiceTrait;
 
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait TelemetryServiceTrait: Send + Sync {
     async fn create_telemetry(&self, dto: CreateVehicleTelemetryDto) -> Result<VehicleTelemetry, AppError>;
     async fn get_latest_telemetry(&self, vehicle_id: Uuid) -> Result<Option<VehicleTelemetry>, AppError>;
+    async fn get_live_positions(&self) -> Result<Vec<VehicleStatusCache>, AppError>;
+    /// Stores a batch of readings. `entries` holds one item per request element, either
+    /// parsed or with the reason it could not be parsed, so rejections keep their index.
+    async fn create_telemetry_batch(
+        &self,
+        entries: Vec<Result<CreateVehicleTelemetryDto, String>>,
+    ) -> Result<BulkTelemetryResult, AppError>;
+    async fn get_history(&self, vehicle_id: Uuid, query: TelemetryHistoryQuery) -> Result<TelemetryHistory, AppError>;
+}
+
+/// Largest number of readings accepted in one batch request.
+pub const MAX_BATCH_SIZE: usize = 5000;
+
+/// Readings stamped further ahead than this are treated as clock errors.
+const MAX_CLOCK_SKEW_MINUTES: i64 = 5;
+
+/// Window used when a history request has no `from`.
+const DEFAULT_HISTORY_HOURS: i64 = 24;
+
+const MAX_HISTORY_DAYS: i64 = 31;
+
+/// Upper bound on rows or buckets returned by one history request.
+pub const MAX_HISTORY_POINTS: i64 = 10_000;
+
+/// Parses a bucket width such as `30s`, `5m`, `1h` or `1d` into seconds.
+pub fn parse_bucket(bucket: &str) -> Result<i64, String> {
+    let bucket = bucket.trim();
+    let split = bucket.find(|c: char| !c.is_ascii_digit()).unwrap_or(bucket.len());
+    let (amount, unit) = bucket.split_at(split);
+
+    let amount: i64 = amount.parse().map_err(|_| format!("Invalid bucket: {}", bucket))?;
+    let multiplier = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return Err(format!("Invalid bucket unit in {}; use s, m, h or d", bucket)),
+    };
+    if amount <= 0 {
+        return Err("Bucket must be positive".into());
+    }
+    amount
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("Bucket too large: {}", bucket))
+}
+
+/// Extracts `(longitude, latitude)` from a GeoJSON point.
+fn point_coordinates(location: &serde_json::Value) -> Option<(f64, f64)> {
+    let coordinates = location.get("coordinates")?.as_array()?;
+    Some((coordinates.first()?.as_f64()?, coordinates.get(1)?.as_f64()?))
+}
+
+pub fn validate_telemetry(dto: &CreateVehicleTelemetryDto) -> Result<(), String> {
+    if dto.location.get("type").and_then(|t| t.as_str()) != Some("Point") {
+        return Err("location must be a GeoJSON Point".into());
+    }
+    match point_coordinates(&dto.location) {
+        Some((lon, lat)) if (-180.0..=180.0).contains(&lon) && (-90.0..=90.0).contains(&lat) => {}
+        _ => return Err("location coordinates must be [longitude, latitude] within range".into()),
+    }
+    if !dto.speed.is_finite() || dto.speed < 0.0 {
+        return Err("speed must be a non-negative number".into());
+    }
+    if !(0.0..=100.0).contains(&dto.fuel_level) {
+        return Err("fuel_level must be between 0 and 100".into());
+    }
+    if dto.time > Utc::now() + Duration::minutes(MAX_CLOCK_SKEW_MINUTES) {
+        return Err("time is in the future".into());
+    }
+    Ok(())
 }
 
 pub struct TelemetryService {
     telemetry_repo: Arc<dyn TelemetryRepositoryTrait>,
+    assignment_repo: Arc<dyn AssignmentRepositoryTrait>,
+    vehicle_repo: Arc<dyn VehicleRepositoryTrait>,
+    status_cache: Arc<dyn VehicleStatusCacheRepositoryTrait>,
+    geofence_service: Arc<dyn GeofenceServiceTrait>,
+    anomaly_service: Arc<dyn AnomalyServiceTrait>,
 }
 
 impl TelemetryService {
-    pub fn new(telemetry_repo: Arc<dyn TelemetryRepositoryTrait>) -> Self {
-        Self { telemetry_repo }
+    pub fn new(
+        telemetry_repo: Arc<dyn TelemetryRepositoryTrait>,
+        assignment_repo: Arc<dyn AssignmentRepositoryTrait>,
+        vehicle_repo: Arc<dyn VehicleRepositoryTrait>,
+        status_cache: Arc<dyn VehicleStatusCacheRepositoryTrait>,
+        geofence_service: Arc<dyn GeofenceServiceTrait>,
+        anomaly_service: Arc<dyn AnomalyServiceTrait>,
+    ) -> Self {
+        Self {
+            telemetry_repo,
+            assignment_repo,
+            vehicle_repo,
+            status_cache,
+            geofence_service,
+            anomaly_service,
+        }
+    }
+
+    /// Side effects of newly stored readings. They run after the insert has committed,
+    /// so failures are logged rather than failing ingestion.
+    async fn process_readings(&self, readings: &[VehicleTelemetry]) {
+        // Geofence transitions depend on order, so each vehicle's points go in chronologically,
+        // one query per vehicle rather than per reading
+        let mut ordered: Vec<&VehicleTelemetry> = readings.iter().collect();
+        ordered.sort_by_key(|t| t.time);
+        let mut tracks: HashMap<Uuid, Vec<(DateTime<Utc>, Value)>> = HashMap::new();
+        for telemetry in &ordered {
+            tracks.entry(telemetry.vehicle_id).or_default().push((telemetry.time, telemetry.location.clone()));
+        }
+        for (vehicle_id, points) in tracks {
+            if let Err(e) = self.geofence_service.evaluate_positions(vehicle_id, points).await {
+                eprintln!("Failed to evaluate geofences for vehicle {}: {}", vehicle_id, e);
+            }
+        }
+
+        if let Err(e) = self.anomaly_service.check_readings(readings).await {
+            eprintln!("Failed to check telemetry for anomalies: {}", e);
+        }
+
+        // Only the newest reading per vehicle matters for the live view
+        let mut latest: HashMap<Uuid, &VehicleTelemetry> = HashMap::new();
+        for telemetry in ordered {
+            latest.insert(telemetry.vehicle_id, telemetry);
+        }
+        for telemetry in latest.into_values() {
+            if let Err(e) = self.update_live_status(telemetry).await {
+                eprintln!("Failed to update live status for vehicle {}: {}", telemetry.vehicle_id, e);
+            }
+        }
+    }
+
+    async fn update_live_status(&self, telemetry: &VehicleTelemetry) -> Result<(), AppError> {
+        let (longitude, latitude) = match point_coordinates(&telemetry.location) {
+            Some(point) => point,
+            None => return Ok(()),
+        };
+
+        // Late-arriving readings must not move the vehicle back in time
+        if let Some(current) = self.status_cache.get(telemetry.vehicle_id).await? {
+            if current.last_updated > telemetry.time.timestamp() {
+                return Ok(());
+            }
+        }
+
+        let driver_id = self.assignment_repo
+            .find_active_by_vehicle_id(telemetry.vehicle_id)
+            .await?
+            .map(|a| a.driver_id);
+
+        self.status_cache.set(&VehicleStatusCache {
+            vehicle_id: telemetry.vehicle_id,
+            speed: telemetry.speed,
+            latitude,
+            longitude,
+            driver_id,
+            last_updated: telemetry.time.timestamp(),
+        }).await
     }
 }
 
 #[async_trait]
 impl TelemetryServiceTrait for TelemetryService {
     async fn create_telemetry(&self, dto: CreateVehicleTelemetryDto) -> Result<VehicleTelemetry, AppError> {
-        self.telemetry_repo.create(dto).await
+        validate_telemetry(&dto).map_err(AppError::BadRequest)?;
+
+        let telemetry = self.telemetry_repo.create(dto).await?;
+        self.process_readings(std::slice::from_ref(&telemetry)).await;
+
+        Ok(telemetry)
     }
 
     async fn get_latest_telemetry(&self, vehicle_id: Uuid) -> Result<Option<VehicleTelemetry>, AppError> {
         self.telemetry_repo.find_latest_by_vehicle_id(vehicle_id).await
     }
+
+    async fn get_live_positions(&self) -> Result<Vec<VehicleStatusCache>, AppError> {
+        self.status_cache.get_all().await
+    }
+
+    async fn create_telemetry_batch(
+        &self,
+        entries: Vec<Result<CreateVehicleTelemetryDto, String>>,
+    ) -> Result<BulkTelemetryResult, AppError> {
+        let received = entries.len();
+        if received > MAX_BATCH_SIZE {
+            return Err(AppError::BadRequest(format!(
+                "Batch contains {} readings; the maximum is {}",
+                received, MAX_BATCH_SIZE
+            )));
+        }
+
+        let mut rejected = Vec::new();
+        let mut candidates = Vec::with_capacity(received);
+        for (index, entry) in entries.into_iter().enumerate() {
+            match entry.and_then(|dto| validate_telemetry(&dto).map(|_| dto)) {
+                Ok(dto) => candidates.push((index, dto)),
+                Err(error) => rejected.push(TelemetryRejection { index, error }),
+            }
+        }
+
+        // Check vehicles up front so one unknown id does not fail the whole insert
+        let requested: HashSet<Uuid> = candidates.iter().map(|(_, dto)| dto.vehicle_id).collect();
+        let known: HashSet<Uuid> = if requested.is_empty() {
+            HashSet::new()
+        } else {
+            self.vehicle_repo
+                .find_existing_ids(requested.into_iter().collect())
+                .await?
+                .into_iter()
+                .collect()
+        };
+
+        let mut valid = Vec::with_capacity(candidates.len());
+        for (index, dto) in candidates {
+            if known.contains(&dto.vehicle_id) {
+                valid.push(dto);
+            } else {
+                rejected.push(TelemetryRejection {
+                    index,
+                    error: format!("Vehicle {} not found", dto.vehicle_id),
+                });
+            }
+        }
+        rejected.sort_by_key(|r| r.index);
+
+        let valid_count = valid.len();
+        let inserted = self.telemetry_repo.create_many(valid).await?;
+
+        self.process_readings(&inserted).await;
+
+        Ok(BulkTelemetryResult {
+            received,
+            inserted: inserted.len(),
+            duplicates: valid_count - inserted.len(),
+            rejected,
+        })
+    }
+
+    async fn get_history(&self, vehicle_id: Uuid, query: TelemetryHistoryQuery) -> Result<TelemetryHistory, AppError> {
+        let to = query.to.unwrap_or_else(Utc::now);
+        let from = query.from.unwrap_or(to - Duration::hours(DEFAULT_HISTORY_HOURS));
+        if from >= to {
+            return Err(AppError::BadRequest("from must be before to".into()));
+        }
+        if to - from > Duration::days(MAX_HISTORY_DAYS) {
+            return Err(AppError::BadRequest(format!(
+                "Time range cannot exceed {} days",
+                MAX_HISTORY_DAYS
+            )));
+        }
+
+        let bucket_secs = query.bucket.as_deref().map(parse_bucket).transpose().map_err(AppError::BadRequest)?;
+        if let Some(secs) = bucket_secs {
+            if (to - from).num_seconds() / secs > MAX_HISTORY_POINTS {
+                return Err(AppError::BadRequest(format!(
+                    "Bucket too small for this range; at most {} buckets are returned",
+                    MAX_HISTORY_POINTS
+                )));
+            }
+        }
+
+        match (query.format.unwrap_or(TelemetryFormat::Json), bucket_secs) {
+            (TelemetryFormat::Geojson, bucket_secs) => {
+                let track = self.telemetry_repo.find_track(vehicle_id, from, to, bucket_secs).await?;
+                let features = match track.geometry {
+                    Some(geometry) => vec![json!({
+                        "type": "Feature",
+                        "geometry": geometry,
+                        "properties": {
+                            "vehicle_id": vehicle_id,
+                            "started_at": track.started_at,
+                            "ended_at": track.ended_at,
+                            "point_count": track.point_count,
+                        }
+                    })],
+                    None => Vec::new(),
+                };
+                Ok(TelemetryHistory::Track(json!({
+                    "type": "FeatureCollection",
+                    "features": features,
+                })))
+            }
+            (TelemetryFormat::Json, Some(secs)) => {
+                let buckets = self.telemetry_repo.find_bucketed_by_vehicle(vehicle_id, from, to, secs).await?;
+                Ok(TelemetryHistory::Buckets(buckets))
+            }
+            (TelemetryFormat::Json, None) => {
+                // Fetch one extra row to tell a full window from a truncated one
+                let points = self.telemetry_repo
+                    .find_by_vehicle_in_range(vehicle_id, from, to, MAX_HISTORY_POINTS + 1)
+                    .await?;
+                if points.len() as i64 > MAX_HISTORY_POINTS {
+                    return Err(AppError::BadRequest(format!(
+                        "More than {} readings in range; narrow the range or set a bucket",
+                        MAX_HISTORY_POINTS
+                    )));
+                }
+                Ok(TelemetryHistory::Points(points))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::postgres::assignment::{AssignmentStatus, VehicleAssignment};
+    use crate::models::postgres::telemetry::TelemetryTrack;
+    use crate::repositories::postgres::assignment_repo::MockAssignmentRepositoryTrait;
+    use crate::repositories::postgres::telemetry_repo::MockTelemetryRepositoryTrait;
+    use crate::repositories::postgres::vehicle_repo::MockVehicleRepositoryTrait;
+    use crate::repositories::redis::vehicle_status_repo::MockVehicleStatusCacheRepositoryTrait;
+    use crate::services::geofence_service::MockGeofenceServiceTrait;
+    use crate::services::anomaly_service::MockAnomalyServiceTrait;
+    use chrono::Utc;
+    use mockall::predicate::*;
+
+    fn telemetry_repo_echo() -> MockTelemetryRepositoryTrait {
+        let mut mock_repo = MockTelemetryRepositoryTrait::new();
+        mock_repo.expect_create()
+            .returning(|dto| Ok(VehicleTelemetry {
+                time: dto.time,
+                vehicle_id: dto.vehicle_id,
+                location: dto.location,
+                speed: dto.speed,
+                fuel_level: dto.fuel_level,
+                engine_status: dto.engine_status,
+            }));
+        mock_repo
+    }
+
+    fn geofences_without_transitions() -> MockGeofenceServiceTrait {
+        let mut mock_geofences = MockGeofenceServiceTrait::new();
+        mock_geofences.expect_evaluate_positions().returning(|_, _| Ok(vec![]));
+        mock_geofences
+    }
+
+    fn no_anomalies() -> MockAnomalyServiceTrait {
+        let mut mock_anomalies = MockAnomalyServiceTrait::new();
+        mock_anomalies.expect_check_readings().returning(|_| Ok(vec![]));
+        mock_anomalies
+    }
+
+    fn sample_dto(vehicle_id: Uuid) -> CreateVehicleTelemetryDto {
+        CreateVehicleTelemetryDto {
+            time: Utc::now(),
+            vehicle_id,
+            location: json!({"type": "Point", "coordinates": [-74.006, 40.7128]}),
+            speed: 42.0,
+            fuel_level: 80.0,
+            engine_status: json!({}),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_telemetry_updates_live_status() {
+        let vehicle_id = Uuid::new_v4();
+        let driver_id = Uuid::new_v4();
+
+        let mut mock_assignments = MockAssignmentRepositoryTrait::new();
+        mock_assignments.expect_find_active_by_vehicle_id()
+            .with(eq(vehicle_id))
+            .returning(move |vehicle_id| Ok(Some(VehicleAssignment {
+                id: Uuid::new_v4(),
+                vehicle_id,
+                driver_id,
+                start_time: Utc::now(),
+                end_time: None,
+                status: AssignmentStatus::Active,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                started_at: Some(Utc::now()),
+                completed_at: None,
+                cancelled_at: None,
+                cancel_reason: None,
+            })));
+
+        let mut mock_cache = MockVehicleStatusCacheRepositoryTrait::new();
+        mock_cache.expect_get().returning(|_| Ok(None));
+        mock_cache.expect_set()
+            .withf(move |status| {
+                status.vehicle_id == vehicle_id
+                    && status.driver_id == Some(driver_id)
+                    && status.latitude == 40.7128
+                    && status.longitude == -74.006
+                    && status.speed == 42.0
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = TelemetryService::new(
+            Arc::new(telemetry_repo_echo()),
+            Arc::new(mock_assignments),
+            Arc::new(MockVehicleRepositoryTrait::new()),
+            Arc::new(mock_cache),
+            Arc::new(geofences_without_transitions()),
+            Arc::new(no_anomalies()),
+        );
+
+        assert!(service.create_telemetry(sample_dto(vehicle_id)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_telemetry_survives_cache_failure() {
+        let mut mock_cache = MockVehicleStatusCacheRepositoryTrait::new();
+        mock_cache.expect_get()
+            .returning(|_| Err(AppError::RedisError("connection refused".into())));
+
+        let service = TelemetryService::new(
+            Arc::new(telemetry_repo_echo()),
+            Arc::new(MockAssignmentRepositoryTrait::new()),
+            Arc::new(MockVehicleRepositoryTrait::new()),
+            Arc::new(mock_cache),
+            Arc::new(geofences_without_transitions()),
+            Arc::new(no_anomalies()),
+        );
+
+        assert!(service.create_telemetry(sample_dto(Uuid::new_v4())).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_create_telemetry_rejects_invalid_location() {
+        let service = TelemetryService::new(
+            Arc::new(MockTelemetryRepositoryTrait::new()),
+            Arc::new(MockAssignmentRepositoryTrait::new()),
+            Arc::new(MockVehicleRepositoryTrait::new()),
+            Arc::new(MockVehicleStatusCacheRepositoryTrait::new()),
+            Arc::new(MockGeofenceServiceTrait::new()),
+            Arc::new(MockAnomalyServiceTrait::new()),
+        );
+
+        let mut dto = sample_dto(Uuid::new_v4());
+        dto.location = json!({"lat": 40.7128, "lng": -74.006});
+
+        let result = service.create_telemetry(dto).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_telemetry_batch_reports_rejections_and_duplicates() {
+        let known_vehicle = Uuid::new_v4();
+        let unknown_vehicle = Uuid::new_v4();
+
+        let mut mock_vehicles = MockVehicleRepositoryTrait::new();
+        mock_vehicles.expect_find_existing_ids()
+            .times(1)
+            .returning(move |_| Ok(vec![known_vehicle]));
+
+        // Pretend the second valid reading was already stored
+        let mut mock_repo = MockTelemetryRepositoryTrait::new();
+        mock_repo.expect_create_many()
+            .withf(|dtos| dtos.len() == 2)
+            .times(1)
+            .returning(|dtos| Ok(dtos.into_iter().take(1).map(|dto| VehicleTelemetry {
+                time: dto.time,
+                vehicle_id: dto.vehicle_id,
+                location: dto.location,
+                speed: dto.speed,
+                fuel_level: dto.fuel_level,
+                engine_status: dto.engine_status,
+            }).collect()));
+
+        let mut mock_assignments = MockAssignmentRepositoryTrait::new();
+        mock_assignments.expect_find_active_by_vehicle_id().returning(|_| Ok(None));
+        let mut mock_cache = MockVehicleStatusCacheRepositoryTrait::new();
+        mock_cache.expect_get().returning(|_| Ok(None));
+        mock_cache.expect_set().times(1).returning(|_| Ok(()));
+
+        let service = TelemetryService::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_assignments),
+            Arc::new(mock_vehicles),
+            Arc::new(mock_cache),
+            Arc::new(geofences_without_transitions()),
+            Arc::new(no_anomalies()),
+        );
+
+        let mut negative_speed = sample_dto(known_vehicle);
+        negative_speed.speed = -1.0;
+        let entries = vec![
+            Ok(sample_dto(known_vehicle)),
+            Err("missing field `time`".to_string()),
+            Ok(negative_speed),
+            Ok(sample_dto(unknown_vehicle)),
+            Ok(sample_dto(known_vehicle)),
+        ];
+
+        let result = service.create_telemetry_batch(entries).await.unwrap();
+        assert_eq!(result.received, 5);
+        assert_eq!(result.inserted, 1);
+        assert_eq!(result.duplicates, 1);
+        let rejected: Vec<usize> = result.rejected.iter().map(|r| r.index).collect();
+        assert_eq!(rejected, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_create_telemetry_batch_evaluates_geofences_once_per_vehicle() {
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        let mut mock_vehicles = MockVehicleRepositoryTrait::new();
+        mock_vehicles.expect_find_existing_ids().returning(move |_| Ok(vec![first, second]));
+        let mut mock_repo = MockTelemetryRepositoryTrait::new();
+        mock_repo.expect_create_many()
+            .returning(|dtos| Ok(dtos.into_iter().map(|dto| VehicleTelemetry {
+                time: dto.time,
+                vehicle_id: dto.vehicle_id,
+                location: dto.location,
+                speed: dto.speed,
+                fuel_level: dto.fuel_level,
+                engine_status: dto.engine_status,
+            }).collect()));
+        let mut mock_assignments = MockAssignmentRepositoryTrait::new();
+        mock_assignments.expect_find_active_by_vehicle_id().returning(|_| Ok(None));
+        let mut mock_cache = MockVehicleStatusCacheRepositoryTrait::new();
+        mock_cache.expect_get().returning(|_| Ok(None));
+        mock_cache.expect_set().returning(|_| Ok(()));
+
+        let mut mock_geofences = MockGeofenceServiceTrait::new();
+        mock_geofences.expect_evaluate_positions()
+            .withf(move |id, points| {
+                let count = if *id == first { 3 } else { 1 };
+                points.len() == count && points.windows(2).all(|pair| pair[0].0 <= pair[1].0)
+            })
+            .times(2)
+            .returning(|_, _| Ok(vec![]));
+
+        let service = TelemetryService::new(
+            Arc::new(mock_repo),
+            Arc::new(mock_assignments),
+            Arc::new(mock_vehicles),
+            Arc::new(mock_cache),
+            Arc::new(mock_geofences),
+            Arc::new(no_anomalies()),
+        );
+
+        let now = Utc::now();
+        let reading = |vehicle_id, minutes_ago| Ok(CreateVehicleTelemetryDto {
+            time: now - Duration::minutes(minutes_ago),
+            ..sample_dto(vehicle_id)
+        });
+        let entries = vec![reading(first, 1), reading(second, 2), reading(first, 3), reading(first, 2)];
+
+        let result = service.create_telemetry_batch(entries).await.unwrap();
+        assert_eq!(result.inserted, 4);
+    }
+
+    #[tokio::test]
+    async fn test_create_telemetry_batch_enforces_size_limit() {
+        let service = TelemetryService::new(
+            Arc::new(MockTelemetryRepositoryTrait::new()),
+            Arc::new(MockAssignmentRepositoryTrait::new()),
+            Arc::new(MockVehicleRepositoryTrait::new()),
+            Arc::new(MockVehicleStatusCacheRepositoryTrait::new()),
+            Arc::new(MockGeofenceServiceTrait::new()),
+            Arc::new(MockAnomalyServiceTrait::new()),
+        );
+
+        let entries = (0..=MAX_BATCH_SIZE).map(|_| Err("skipped".to_string())).collect();
+        let result = service.create_telemetry_batch(entries).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[test]
+    fn test_parse_bucket() {
+        assert_eq!(parse_bucket("30s"), Ok(30));
+        assert_eq!(parse_bucket("5m"), Ok(300));
+        assert_eq!(parse_bucket("1h"), Ok(3600));
+        assert_eq!(parse_bucket("90"), Ok(90));
+        assert!(parse_bucket("0m").is_err());
+        assert!(parse_bucket("5w").is_err());
+        assert!(parse_bucket("m").is_err());
+    }
+
+    fn history_service(mock_repo: MockTelemetryRepositoryTrait) -> TelemetryService {
+        TelemetryService::new(
+            Arc::new(mock_repo),
+            Arc::new(MockAssignmentRepositoryTrait::new()),
+            Arc::new(MockVehicleRepositoryTrait::new()),
+            Arc::new(MockVehicleStatusCacheRepositoryTrait::new()),
+            Arc::new(MockGeofenceServiceTrait::new()),
+            Arc::new(MockAnomalyServiceTrait::new()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_get_history_uses_buckets_when_requested() {
+        let mut mock_repo = MockTelemetryRepositoryTrait::new();
+        mock_repo.expect_find_bucketed_by_vehicle()
+            .withf(|_, from, to, secs| *secs == 300 && *to - *from == Duration::hours(24))
+            .times(1)
+            .returning(|_, _, _, _| Ok(vec![]));
+
+        let query = TelemetryHistoryQuery { bucket: Some("5m".into()), ..Default::default() };
+        let result = history_service(mock_repo).get_history(Uuid::new_v4(), query).await.unwrap();
+        assert!(matches!(result, TelemetryHistory::Buckets(_)));
+    }
+
+    #[tokio::test]
+    async fn test_get_history_geojson_wraps_track() {
+        let mut mock_repo = MockTelemetryRepositoryTrait::new();
+        mock_repo.expect_find_track()
+            .withf(|_, _, _, bucket| bucket.is_none())
+            .times(1)
+            .returning(|_, from, to, _| Ok(TelemetryTrack {
+                geometry: Some(json!({"type": "LineString", "coordinates": [[-74.0, 40.7], [-74.1, 40.8]]})),
+                point_count: 2,
+                started_at: Some(from),
+                ended_at: Some(to),
+            }));
+
+        let query = TelemetryHistoryQuery { format: Some(TelemetryFormat::Geojson), ..Default::default() };
+        let result = history_service(mock_repo).get_history(Uuid::new_v4(), query).await.unwrap();
+        match result {
+            TelemetryHistory::Track(collection) => {
+                assert_eq!(collection["type"], "FeatureCollection");
+                assert_eq!(collection["features"][0]["geometry"]["type"], "LineString");
+                assert_eq!(collection["features"][0]["properties"]["point_count"], 2);
+            }
+            other => panic!("expected a track, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_history_rejects_inverted_range() {
+        let now = Utc::now();
+        let query = TelemetryHistoryQuery {
+            from: Some(now),
+            to: Some(now - Duration::hours(1)),
+            ..Default::default()
+        };
+
+        let result = history_service(MockTelemetryRepositoryTrait::new()).get_history(Uuid::new_v4(), query).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
 }
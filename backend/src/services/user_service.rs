@@ -76,6 +76,7 @@ mod tests {
             email: email.into(),
             password_hash: "hash".into(),
             role: UserRole::Admin,
+            name: None,
             is_active: true,
             created_at: Utc::now(),
             updated_at: Utc::now(),
@@ -136,6 +137,7 @@ mod tests {
             email: "new@example.com".into(),
             password_hash: "plain_password".into(),
             role: UserRole::Driver,
+            name: None,
             is_active: true,
         };
 
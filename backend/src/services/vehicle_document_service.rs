@@ -0,0 +1,434 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDate};
+use uuid::Uuid;
+use crate::error::AppError;
+use crate::models::postgres::maintenance::{Alert, AlertSeverity};
+use crate::models::postgres::vehicle_document::{
+    VehicleDocument, VehicleDocumentType, CreateVehicleDocumentDto, NewVehicleDocument, DocumentExpiry
+};
+use crate::repositories::postgres::maintenance_repo::AlertRepositoryTrait;
+use crate::repositories::postgres::settings_repo::SettingsRepositoryTrait;
+use crate::repositories::postgres::vehicle_document_repo::VehicleDocumentRepositoryTrait;
+use crate::repositories::postgres::vehicle_repo::VehicleRepositoryTrait;
+use crate::repositories::storage::StorageTrait;
+use crate::services::expiry_alert::{sync_expiry_alert, Expiry};
+use crate::services::file_upload::{decode_base64_file, DOCUMENT_TYPES};
+
+pub const MAX_DOCUMENT_BYTES: usize = 10 * 1024 * 1024;
+
+/// Alert types are per document type, e.g. "Insurance Expiring", so each can be tracked
+/// and resolved on its own.
+pub fn expiring_alert(r#type: VehicleDocumentType) -> String {
+    format!("{} Expiring", r#type.label())
+}
+
+pub fn expired_alert(r#type: VehicleDocumentType) -> String {
+    format!("{} Expired", r#type.label())
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait VehicleDocumentServiceTrait: Send + Sync {
+    async fn list(&self, vehicle_id: Uuid) -> Result<Vec<VehicleDocument>, AppError>;
+    async fn get(&self, vehicle_id: Uuid, id: Uuid) -> Result<VehicleDocument, AppError>;
+    async fn create(&self, vehicle_id: Uuid, dto: CreateVehicleDocumentDto, uploaded_by: Option<Uuid>) -> Result<VehicleDocument, AppError>;
+    async fn delete(&self, vehicle_id: Uuid, id: Uuid) -> Result<(), AppError>;
+    /// The document with the bytes of its uploaded copy.
+    async fn get_file(&self, vehicle_id: Uuid, id: Uuid) -> Result<(VehicleDocument, Vec<u8>), AppError>;
+    /// Rejects assignments running past the expiry of a mandatory document in force.
+    /// Vehicles without a document of a mandatory type are not held back.
+    async fn check_assignment(&self, vehicle_id: Uuid, today: NaiveDate, last_day: NaiveDate) -> Result<(), AppError>;
+    /// Raises alerts for documents in force that expired or expire within the warning
+    /// window, and resolves them once renewed. Returns the alerts raised.
+    async fn raise_expiry_alerts(&self, today: NaiveDate) -> Result<Vec<Alert>, AppError>;
+}
+
+pub struct VehicleDocumentService {
+    document_repo: Arc<dyn VehicleDocumentRepositoryTrait>,
+    vehicle_repo: Arc<dyn VehicleRepositoryTrait>,
+    alert_repo: Arc<dyn AlertRepositoryTrait>,
+    settings_repo: Arc<dyn SettingsRepositoryTrait>,
+    storage: Arc<dyn StorageTrait>,
+}
+
+impl VehicleDocumentService {
+    pub fn new(
+        document_repo: Arc<dyn VehicleDocumentRepositoryTrait>,
+        vehicle_repo: Arc<dyn VehicleRepositoryTrait>,
+        alert_repo: Arc<dyn AlertRepositoryTrait>,
+        settings_repo: Arc<dyn SettingsRepositoryTrait>,
+        storage: Arc<dyn StorageTrait>,
+    ) -> Self {
+        Self {
+            document_repo,
+            vehicle_repo,
+            alert_repo,
+            settings_repo,
+            storage,
+        }
+    }
+
+    async fn ensure_vehicle(&self, vehicle_id: Uuid) -> Result<(), AppError> {
+        self.vehicle_repo.find_by_id(vehicle_id).await?
+            .ok_or(AppError::NotFound("Vehicle not found".into()))?;
+        Ok(())
+    }
+
+    /// Files are only ever removed alongside their record, so a failure just leaves an
+    /// orphaned object behind and is logged.
+    async fn discard(&self, storage_key: &str) {
+        if let Err(e) = self.storage.delete(storage_key).await {
+            eprintln!("Failed to remove orphaned file {}: {}", storage_key, e);
+        }
+    }
+}
+
+fn document_label(document: &DocumentExpiry) -> String {
+    match &document.document_number {
+        Some(number) => format!("{} {} of vehicle {}", document.r#type.label(), number, document.license_plate),
+        None => format!("{} of vehicle {}", document.r#type.label(), document.license_plate),
+    }
+}
+
+/// Keeps the last path segment and drops characters that would break a
+/// `Content-Disposition` header.
+fn clean_file_name(name: &str) -> String {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    base.chars().filter(|c| !c.is_control() && *c != '"').take(255).collect::<String>().trim().to_string()
+}
+
+fn extension(content_type: &str) -> &'static str {
+    match content_type {
+        "application/pdf" => "pdf",
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        _ => "webp",
+    }
+}
+
+#[async_trait]
+impl VehicleDocumentServiceTrait for VehicleDocumentService {
+    async fn list(&self, vehicle_id: Uuid) -> Result<Vec<VehicleDocument>, AppError> {
+        self.ensure_vehicle(vehicle_id).await?;
+        self.document_repo.find_by_vehicle(vehicle_id).await
+    }
+
+    async fn get(&self, vehicle_id: Uuid, id: Uuid) -> Result<VehicleDocument, AppError> {
+        self.document_repo.find_by_id(vehicle_id, id).await?
+            .ok_or(AppError::NotFound("Document not found".into()))
+    }
+
+    async fn create(&self, vehicle_id: Uuid, dto: CreateVehicleDocumentDto, uploaded_by: Option<Uuid>) -> Result<VehicleDocument, AppError> {
+        self.ensure_vehicle(vehicle_id).await?;
+        if let (Some(issue), Some(expiry)) = (dto.issue_date, dto.expiry_date) {
+            if expiry < issue {
+                return Err(AppError::BadRequest("expiry_date cannot be before issue_date".into()));
+            }
+        }
+
+        let mut document = NewVehicleDocument {
+            vehicle_id,
+            r#type: dto.r#type,
+            document_number: dto.document_number.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+            issue_date: dto.issue_date,
+            expiry_date: dto.expiry_date,
+            notes: dto.notes,
+            file_name: None,
+            content_type: None,
+            size_bytes: None,
+            storage_key: None,
+            uploaded_by,
+        };
+
+        if let Some(file) = dto.file {
+            let (data, content_type) = decode_base64_file("File", &file, MAX_DOCUMENT_BYTES, DOCUMENT_TYPES)?;
+            let file_name = dto.file_name.as_deref().map(clean_file_name).filter(|n| !n.is_empty())
+                .unwrap_or_else(|| format!("{}.{}", dto.r#type.label().to_lowercase(), extension(content_type)));
+            let storage_key = format!("vehicles/{}/documents/{}", vehicle_id, Uuid::new_v4());
+
+            document.size_bytes = Some(data.len() as i64);
+            self.storage.put(&storage_key, content_type, data).await?;
+            document.file_name = Some(file_name);
+            document.content_type = Some(content_type.to_string());
+            document.storage_key = Some(storage_key);
+        }
+
+        let storage_key = document.storage_key.clone();
+        match self.document_repo.create(document).await {
+            Ok(created) => Ok(created),
+            Err(e) => {
+                if let Some(key) = storage_key {
+                    self.discard(&key).await;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn delete(&self, vehicle_id: Uuid, id: Uuid) -> Result<(), AppError> {
+        let removed = self.document_repo.delete(vehicle_id, id).await?
+            .ok_or(AppError::NotFound("Document not found".into()))?;
+        if let Some(key) = &removed.storage_key {
+            self.discard(key).await;
+        }
+        // Alerts about it go too; the next run re-raises them if another document of
+        // the type is also due
+        self.alert_repo
+            .resolve_open(vehicle_id, vec![expiring_alert(removed.r#type), expired_alert(removed.r#type)])
+            .await?;
+        Ok(())
+    }
+
+    async fn get_file(&self, vehicle_id: Uuid, id: Uuid) -> Result<(VehicleDocument, Vec<u8>), AppError> {
+        let document = self.get(vehicle_id, id).await?;
+        let Some(key) = document.storage_key.clone() else {
+            return Err(AppError::NotFound("No file was uploaded for this document".into()));
+        };
+        let data = self.storage.get(&key).await?
+            .ok_or_else(|| AppError::StorageError(format!("Stored file {} is missing", key)))?;
+        Ok((document, data))
+    }
+
+    async fn check_assignment(&self, vehicle_id: Uuid, today: NaiveDate, last_day: NaiveDate) -> Result<(), AppError> {
+        let lapsed = self.document_repo.find_current(vehicle_id).await?
+            .into_iter()
+            .find(|document| document.r#type.is_mandatory() && !document.valid_on(last_day));
+        let Some(document) = lapsed else {
+            return Ok(());
+        };
+
+        let label = document.r#type.label().to_lowercase();
+        let expiry = document.expiry_date.unwrap_or(today);
+        Err(AppError::BadRequest(if expiry < today {
+            format!("Vehicle's {} expired on {}", label, expiry)
+        } else {
+            format!("Vehicle's {} expires on {}, before the assignment ends", label, expiry)
+        }))
+    }
+
+    async fn raise_expiry_alerts(&self, today: NaiveDate) -> Result<Vec<Alert>, AppError> {
+        let settings = self.settings_repo.get().await?;
+        let warn_until = today + Duration::days(settings.document_expiry_warning_days.max(0).into());
+
+        let mut alerts = Vec::new();
+        for document in self.document_repo.find_current_expiries().await? {
+            let expiry = Expiry {
+                entity_id: document.vehicle_id,
+                label: document_label(&document),
+                expiry_date: document.expiry_date,
+                expiring_alert: expiring_alert(document.r#type),
+                expired_alert: expired_alert(document.r#type),
+                // A lapsed mandatory document keeps the vehicle off the road, others are a reminder
+                expired_severity: if document.r#type.is_mandatory() { AlertSeverity::High } else { AlertSeverity::Medium },
+            };
+            alerts.extend(sync_expiry_alert(self.alert_repo.as_ref(), expiry, today, warn_until, true).await?);
+        }
+        Ok(alerts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use base64::engine::general_purpose::STANDARD;
+    use chrono::Utc;
+    use crate::models::postgres::settings::AppSettings;
+    use crate::models::postgres::vehicle::{Vehicle, VehicleStatus, VehicleType, FuelType};
+    use crate::repositories::postgres::maintenance_repo::MockAlertRepositoryTrait;
+    use crate::repositories::postgres::settings_repo::MockSettingsRepositoryTrait;
+    use crate::repositories::postgres::vehicle_document_repo::MockVehicleDocumentRepositoryTrait;
+    use crate::repositories::postgres::vehicle_repo::MockVehicleRepositoryTrait;
+    use crate::repositories::storage::MockStorageTrait;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 17).unwrap()
+    }
+
+    fn vehicle_repo() -> MockVehicleRepositoryTrait {
+        let mut repo = MockVehicleRepositoryTrait::new();
+        repo.expect_find_by_id().returning(|id| Ok(Some(Vehicle {
+            id,
+            make: "Volvo".into(),
+            model: "FH16".into(),
+            year: 2022,
+            vin: "YV2RT40A8NB123456".into(),
+            license_plate: "TRK-042".into(),
+            r#type: VehicleType::Truck,
+            status: VehicleStatus::Available,
+            current_mileage: 120000,
+            fuel_type: FuelType::Diesel,
+            specs: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        })));
+        repo
+    }
+
+    fn settings_repo() -> MockSettingsRepositoryTrait {
+        let mut repo = MockSettingsRepositoryTrait::new();
+        repo.expect_get().returning(|| Ok(AppSettings::default()));
+        repo
+    }
+
+    fn document(vehicle_id: Uuid, r#type: VehicleDocumentType, expiry_date: Option<NaiveDate>) -> VehicleDocument {
+        VehicleDocument {
+            id: Uuid::new_v4(),
+            vehicle_id,
+            r#type,
+            document_number: Some("POL-1".into()),
+            issue_date: None,
+            expiry_date,
+            notes: None,
+            file_name: None,
+            content_type: None,
+            size_bytes: None,
+            storage_key: None,
+            uploaded_by: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn service(
+        document_repo: MockVehicleDocumentRepositoryTrait,
+        alert_repo: MockAlertRepositoryTrait,
+        storage: MockStorageTrait,
+    ) -> VehicleDocumentService {
+        VehicleDocumentService::new(
+            Arc::new(document_repo),
+            Arc::new(vehicle_repo()),
+            Arc::new(alert_repo),
+            Arc::new(settings_repo()),
+            Arc::new(storage),
+        )
+    }
+
+    fn create_dto(file: Option<String>) -> CreateVehicleDocumentDto {
+        CreateVehicleDocumentDto {
+            r#type: VehicleDocumentType::Insurance,
+            document_number: Some(" POL-1 ".into()),
+            issue_date: Some(today()),
+            expiry_date: Some(today() + Duration::days(365)),
+            notes: None,
+            file_name: Some("C:\\scans\\policy \"2026\".pdf".into()),
+            file,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_stores_file_and_records_document() {
+        let vehicle_id = Uuid::new_v4();
+        let mut storage = MockStorageTrait::new();
+        let prefix = format!("vehicles/{}/documents/", vehicle_id);
+        storage.expect_put()
+            .withf(move |key, content_type, _| key.starts_with(&prefix) && content_type == "application/pdf")
+            .times(1)
+            .returning(|_, _, _| Ok(()));
+        let mut document_repo = MockVehicleDocumentRepositoryTrait::new();
+        document_repo.expect_create()
+            .withf(|doc| {
+                doc.document_number.as_deref() == Some("POL-1")
+                    && doc.file_name.as_deref() == Some("policy 2026.pdf")
+                    && doc.content_type.as_deref() == Some("application/pdf")
+                    && doc.size_bytes == Some(8)
+                    && doc.storage_key.is_some()
+            })
+            .times(1)
+            .returning(|doc| Ok(VehicleDocument {
+                file_name: doc.file_name,
+                storage_key: doc.storage_key,
+                ..document(doc.vehicle_id, doc.r#type, doc.expiry_date)
+            }));
+
+        let service = service(document_repo, MockAlertRepositoryTrait::new(), storage);
+        let created = service.create(vehicle_id, create_dto(Some(STANDARD.encode(b"%PDF-1.7"))), None).await.unwrap();
+        assert_eq!(created.file_name.as_deref(), Some("policy 2026.pdf"));
+    }
+
+    #[tokio::test]
+    async fn test_create_validates_dates_and_removes_file_when_record_fails() {
+        let no_writes = service(MockVehicleDocumentRepositoryTrait::new(), MockAlertRepositoryTrait::new(), MockStorageTrait::new());
+        let dto = CreateVehicleDocumentDto { expiry_date: Some(today() - Duration::days(1)), ..create_dto(None) };
+        let result = no_writes.create(Uuid::new_v4(), dto, None).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("before issue_date")));
+
+        let mut storage = MockStorageTrait::new();
+        storage.expect_put().times(1).returning(|_, _, _| Ok(()));
+        storage.expect_delete().times(1).returning(|_| Ok(()));
+        let mut document_repo = MockVehicleDocumentRepositoryTrait::new();
+        document_repo.expect_create().returning(|_| Err(AppError::InternalServerError("db down".into())));
+        let failing = service(document_repo, MockAlertRepositoryTrait::new(), storage);
+        let result = failing.create(Uuid::new_v4(), create_dto(Some(STANDARD.encode(b"%PDF-1.7"))), None).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_check_assignment_rejects_lapsed_mandatory_documents() {
+        let vehicle_id = Uuid::new_v4();
+        let mut document_repo = MockVehicleDocumentRepositoryTrait::new();
+        document_repo.expect_find_current().returning(move |id| Ok(vec![
+            document(id, VehicleDocumentType::Insurance, Some(today() + Duration::days(5))),
+            // Optional documents never block an assignment
+            document(id, VehicleDocumentType::Permit, Some(today() - Duration::days(30))),
+            document(id, VehicleDocumentType::Registration, None),
+        ]));
+        let service = service(document_repo, MockAlertRepositoryTrait::new(), MockStorageTrait::new());
+
+        assert!(service.check_assignment(vehicle_id, today(), today() + Duration::days(5)).await.is_ok());
+        let result = service.check_assignment(vehicle_id, today(), today() + Duration::days(6)).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("insurance expires on 2026-10-22")));
+        let result = service.check_assignment(vehicle_id, today() + Duration::days(10), today() + Duration::days(10)).await;
+        assert!(matches!(result, Err(AppError::BadRequest(msg)) if msg.contains("insurance expired on 2026-10-22")));
+    }
+
+    #[tokio::test]
+    async fn test_raises_and_resolves_expiry_alerts() {
+        let expiry = |vehicle_id, r#type, days| DocumentExpiry {
+            document_id: Uuid::new_v4(),
+            vehicle_id,
+            license_plate: "TRK-042".into(),
+            r#type,
+            document_number: None,
+            expiry_date: today() + Duration::days(days),
+        };
+        let (expiring_id, expired_id, renewed_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut document_repo = MockVehicleDocumentRepositoryTrait::new();
+        document_repo.expect_find_current_expiries().returning(move || Ok(vec![
+            expiry(expiring_id, VehicleDocumentType::Inspection, 10),
+            expiry(expired_id, VehicleDocumentType::Insurance, -1),
+            expiry(renewed_id, VehicleDocumentType::Registration, 365),
+        ]));
+
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo.expect_resolve_open()
+            .withf(move |id, types| {
+                (*id == renewed_id && types == &["Registration Expiring".to_string(), "Registration Expired".to_string()])
+                    || (*id == expiring_id && types == &["Inspection Expired".to_string()])
+                    || (*id == expired_id && types == &["Insurance Expiring".to_string()])
+            })
+            .times(3)
+            .returning(|_, _| Ok(0));
+        alert_repo.expect_create_unless_open()
+            .withf(move |dto| {
+                (dto.entity_id == expiring_id && dto.r#type == "Inspection Expiring" && dto.severity == AlertSeverity::Medium)
+                    || (dto.entity_id == expired_id && dto.r#type == "Insurance Expired" && dto.severity == AlertSeverity::High)
+            })
+            .times(2)
+            .returning(|dto| Ok(Some(Alert {
+                id: Uuid::new_v4(),
+                entity_id: dto.entity_id,
+                r#type: dto.r#type,
+                severity: dto.severity,
+                is_resolved: false,
+                created_at: Utc::now(),
+                resolved_at: None,
+                message: dto.message,
+            })));
+
+        let service = service(document_repo, alert_repo, MockStorageTrait::new());
+        let alerts = service.raise_expiry_alerts(today()).await.unwrap();
+        assert_eq!(alerts.len(), 2);
+    }
+}
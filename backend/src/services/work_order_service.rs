@@ -0,0 +1,680 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::error::{AppError, ConflictKind};
+use crate::models::postgres::maintenance::MaintenanceType;
+use crate::models::postgres::user::UserRole;
+use crate::models::postgres::vehicle::{Vehicle, VehicleStatus};
+use crate::models::postgres::work_order::{
+    WorkOrder, WorkOrderItem, WorkOrderItemKind, WorkOrderDetails, WorkOrderStatus, CreateWorkOrderDto, CreateWorkOrderItemDto, WorkOrderQuery
+};
+use crate::repositories::postgres::maintenance_repo::AlertRepositoryTrait;
+use crate::repositories::postgres::unit_of_work::{RepositoryTransactionTrait, UnitOfWorkTrait};
+use crate::repositories::postgres::user_repo::UserRepositoryTrait;
+use crate::repositories::postgres::vehicle_repo::VehicleRepositoryTrait;
+use crate::repositories::postgres::work_order_repo::WorkOrderRepositoryTrait;
+use crate::services::maintenance_service::{MAINTENANCE_DUE_SOON_ALERT, MAINTENANCE_OVERDUE_ALERT};
+use crate::services::part_service::PartServiceTrait;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait WorkOrderServiceTrait: Send + Sync {
+    /// Opens a work order and takes the vehicle out of service while it is worked on.
+    async fn create_work_order(&self, dto: CreateWorkOrderDto) -> Result<WorkOrder, AppError>;
+    async fn list_work_orders(&self, query: WorkOrderQuery) -> Result<Vec<WorkOrder>, AppError>;
+    async fn get_work_order(&self, id: Uuid) -> Result<WorkOrderDetails, AppError>;
+    /// Completing an order logs a maintenance record; closing the vehicle's last open
+    /// order makes it available again.
+    async fn update_status(&self, id: Uuid, status: WorkOrderStatus) -> Result<WorkOrder, AppError>;
+    async fn assign_mechanic(&self, id: Uuid, mechanic_id: Uuid) -> Result<WorkOrder, AppError>;
+    /// Lines with a `part_id` are taken out of inventory at the part's unit cost.
+    async fn add_item(&self, id: Uuid, dto: CreateWorkOrderItemDto) -> Result<WorkOrderItem, AppError>;
+    async fn remove_item(&self, id: Uuid, item_id: Uuid) -> Result<(), AppError>;
+}
+
+pub struct WorkOrderService {
+    work_order_repo: Arc<dyn WorkOrderRepositoryTrait>,
+    vehicle_repo: Arc<dyn VehicleRepositoryTrait>,
+    user_repo: Arc<dyn UserRepositoryTrait>,
+    alert_repo: Arc<dyn AlertRepositoryTrait>,
+    part_service: Arc<dyn PartServiceTrait>,
+    unit_of_work: Arc<dyn UnitOfWorkTrait>,
+}
+
+impl WorkOrderService {
+    pub fn new(
+        work_order_repo: Arc<dyn WorkOrderRepositoryTrait>,
+        vehicle_repo: Arc<dyn VehicleRepositoryTrait>,
+        user_repo: Arc<dyn UserRepositoryTrait>,
+        alert_repo: Arc<dyn AlertRepositoryTrait>,
+        part_service: Arc<dyn PartServiceTrait>,
+        unit_of_work: Arc<dyn UnitOfWorkTrait>,
+    ) -> Self {
+        Self { work_order_repo, vehicle_repo, user_repo, alert_repo, part_service, unit_of_work }
+    }
+
+    async fn find_work_order(&self, id: Uuid) -> Result<WorkOrder, AppError> {
+        self.work_order_repo
+            .find_by_id(id)
+            .await?
+            .ok_or(AppError::NotFound(format!("Work order {} not found", id)))
+    }
+
+    async fn find_open_work_order(&self, id: Uuid) -> Result<WorkOrder, AppError> {
+        let work_order = self.find_work_order(id).await?;
+        if work_order.status.is_closed() {
+            return Err(AppError::BadRequest(format!("Work order {} is already closed", id)));
+        }
+        Ok(work_order)
+    }
+
+    async fn ensure_mechanic(&self, user_id: Uuid) -> Result<(), AppError> {
+        let user = self
+            .user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or(AppError::NotFound(format!("User {} not found", user_id)))?;
+        if user.role != UserRole::Mechanic || !user.is_active {
+            return Err(AppError::BadRequest(format!("User {} is not an active mechanic", user_id)));
+        }
+        Ok(())
+    }
+
+}
+
+/// Puts the vehicle back into service once none of its work orders are open. Vehicles that
+/// were set to another status by hand in the meantime are left alone.
+async fn release_vehicle(tx: &mut dyn RepositoryTransactionTrait, vehicle: &Vehicle) -> Result<(), AppError> {
+    if vehicle.status != VehicleStatus::Maintenance || tx.count_open_work_orders(vehicle.id).await? > 0 {
+        return Ok(());
+    }
+    tx.update_vehicle_status(vehicle.id, VehicleStatus::Available).await?;
+    Ok(())
+}
+
+#[async_trait]
+impl WorkOrderServiceTrait for WorkOrderService {
+    async fn create_work_order(&self, mut dto: CreateWorkOrderDto) -> Result<WorkOrder, AppError> {
+        if dto.description.trim().is_empty() {
+            return Err(AppError::BadRequest("Work order description is required".into()));
+        }
+        dto.provider = dto.provider.map(|p| p.trim().to_string()).filter(|p| !p.is_empty());
+
+        if let Some(mechanic_id) = dto.mechanic_id {
+            self.ensure_mechanic(mechanic_id).await?;
+        }
+
+        let mut tx = self.unit_of_work.begin().await?;
+        let vehicle = tx
+            .lock_vehicle(dto.vehicle_id)
+            .await?
+            .ok_or(AppError::NotFound(format!("Vehicle {} not found", dto.vehicle_id)))?;
+        if vehicle.status == VehicleStatus::Assigned {
+            return Err(AppError::BadRequest(format!(
+                "Vehicle {} is assigned to a driver; end the assignment before opening a work order",
+                vehicle.id
+            )));
+        }
+
+        let work_order = tx.create_work_order(dto).await?;
+
+        // Out-of-service vehicles stay that way; the order does not make them serviceable
+        if vehicle.status == VehicleStatus::Available {
+            tx.update_vehicle_status(vehicle.id, VehicleStatus::Maintenance).await?;
+        }
+        tx.commit().await?;
+
+        Ok(work_order)
+    }
+
+    async fn list_work_orders(&self, query: WorkOrderQuery) -> Result<Vec<WorkOrder>, AppError> {
+        self.work_order_repo.find_all(query).await
+    }
+
+    async fn get_work_order(&self, id: Uuid) -> Result<WorkOrderDetails, AppError> {
+        let work_order = self.find_work_order(id).await?;
+        let items = self.work_order_repo.find_items(id).await?;
+        let total_cost = items.iter().map(|item| item.quantity * item.unit_cost).sum::<Decimal>().round_dp(2);
+        Ok(WorkOrderDetails { work_order, items, total_cost })
+    }
+
+    async fn update_status(&self, id: Uuid, status: WorkOrderStatus) -> Result<WorkOrder, AppError> {
+        let current = self.find_work_order(id).await?;
+        if !current.status.can_transition_to(status) {
+            return Err(AppError::BadRequest(format!(
+                "Cannot move work order from {:?} to {:?}",
+                current.status, status
+            )));
+        }
+        if status == WorkOrderStatus::InProgress && current.mechanic_id.is_none() && current.provider.is_none() {
+            return Err(AppError::BadRequest("Assign a mechanic before starting work".into()));
+        }
+
+        // The vehicle is locked before the order, the same order as when one is opened
+        let mut tx = self.unit_of_work.begin().await?;
+        let vehicle = tx.lock_vehicle(current.vehicle_id).await?;
+        let updated = if status == WorkOrderStatus::Completed {
+            tx.complete_work_order(id).await?
+        } else {
+            tx.update_work_order_status(id, current.status, status).await?
+        };
+        let work_order = updated.ok_or(AppError::conflict(
+            ConflictKind::Stale,
+            format!("Work order {} was changed by another request; reload and try again", id),
+        ))?;
+
+        if let Some(vehicle) = vehicle.filter(|_| work_order.status.is_closed()) {
+            release_vehicle(tx.as_mut(), &vehicle).await?;
+        }
+        tx.commit().await?;
+
+        // Same as logging a preventive record by hand: the schedule starts over
+        if work_order.status == WorkOrderStatus::Completed && work_order.r#type == MaintenanceType::Preventive {
+            self.alert_repo
+                .resolve_open(
+                    work_order.vehicle_id,
+                    vec![MAINTENANCE_DUE_SOON_ALERT.to_string(), MAINTENANCE_OVERDUE_ALERT.to_string()],
+                )
+                .await?;
+        }
+
+        Ok(work_order)
+    }
+
+    async fn assign_mechanic(&self, id: Uuid, mechanic_id: Uuid) -> Result<WorkOrder, AppError> {
+        self.find_open_work_order(id).await?;
+        self.ensure_mechanic(mechanic_id).await?;
+        self.work_order_repo
+            .assign_mechanic(id, mechanic_id)
+            .await?
+            .ok_or(AppError::NotFound(format!("Work order {} not found", id)))
+    }
+
+    async fn add_item(&self, id: Uuid, mut dto: CreateWorkOrderItemDto) -> Result<WorkOrderItem, AppError> {
+        if dto.quantity <= Decimal::ZERO {
+            return Err(AppError::BadRequest("Quantity must be greater than zero".into()));
+        }
+
+        let work_order = self.find_open_work_order(id).await?;
+
+        let part = match dto.part_id {
+            Some(part_id) => {
+                if dto.kind != WorkOrderItemKind::Part {
+                    return Err(AppError::BadRequest("Only PART lines can draw from inventory".into()));
+                }
+                let part = self.part_service.get_part(part_id).await?;
+                let vehicle = self
+                    .vehicle_repo
+                    .find_by_id(work_order.vehicle_id)
+                    .await?
+                    .ok_or(AppError::NotFound(format!("Vehicle {} not found", work_order.vehicle_id)))?;
+                if !part.fits(vehicle.r#type) {
+                    return Err(AppError::BadRequest(format!(
+                        "Part {} does not fit {:?} vehicles",
+                        part.sku, vehicle.r#type
+                    )));
+                }
+                if dto.description.trim().is_empty() {
+                    dto.description = part.description.clone();
+                }
+                // Inventory lines are always costed at the part's price
+                dto.unit_cost = None;
+                Some(part)
+            }
+            None => {
+                match dto.unit_cost {
+                    Some(cost) if cost >= Decimal::ZERO => {}
+                    Some(_) => return Err(AppError::BadRequest("Unit cost cannot be negative".into())),
+                    None => return Err(AppError::BadRequest("unit_cost is required for lines without a part".into())),
+                }
+                None
+            }
+        };
+        if dto.description.trim().is_empty() {
+            return Err(AppError::BadRequest("Line item description is required".into()));
+        }
+
+        // Only inventory lines can come back empty, when the shelf ran out in the meantime
+        let item = self.work_order_repo.add_item(id, dto).await?.ok_or_else(|| {
+            let (sku, on_hand) = part
+                .as_ref()
+                .map(|part| (part.sku.clone(), part.quantity_on_hand))
+                .unwrap_or_default();
+            AppError::BadRequest(format!("Not enough stock of {}: {} on hand", sku, on_hand))
+        })?;
+        if let Some(part) = part {
+            self.part_service.refresh_stock_alert(part.id).await?;
+        }
+        Ok(item)
+    }
+
+    async fn remove_item(&self, id: Uuid, item_id: Uuid) -> Result<(), AppError> {
+        self.find_open_work_order(id).await?;
+        let item = self
+            .work_order_repo
+            .delete_item(id, item_id)
+            .await?
+            .ok_or(AppError::NotFound(format!("Line item {} not found", item_id)))?;
+        if let Some(part_id) = item.part_id {
+            self.part_service.refresh_stock_alert(part_id).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::postgres::user::User;
+    use crate::models::postgres::vehicle::{Vehicle, VehicleType, FuelType};
+    use crate::models::postgres::part::Part;
+    use crate::repositories::postgres::maintenance_repo::MockAlertRepositoryTrait;
+    use crate::repositories::postgres::unit_of_work::{MockRepositoryTransactionTrait, MockUnitOfWorkTrait};
+    use crate::repositories::postgres::user_repo::MockUserRepositoryTrait;
+    use crate::repositories::postgres::vehicle_repo::MockVehicleRepositoryTrait;
+    use crate::repositories::postgres::work_order_repo::MockWorkOrderRepositoryTrait;
+    use crate::services::part_service::MockPartServiceTrait;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+
+    fn vehicle(id: Uuid, status: VehicleStatus) -> Vehicle {
+        Vehicle {
+            id,
+            make: "Ford".into(),
+            model: "Transit".into(),
+            year: 2021,
+            vin: "VIN456".into(),
+            license_plate: "WO-001".into(),
+            r#type: VehicleType::Van,
+            status,
+            current_mileage: 42_000,
+            fuel_type: FuelType::Diesel,
+            specs: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn user(id: Uuid, role: UserRole) -> User {
+        User {
+            id,
+            email: "mechanic@example.com".into(),
+            password_hash: "hash".into(),
+            role,
+            name: None,
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn work_order(vehicle_id: Uuid, status: WorkOrderStatus) -> WorkOrder {
+        WorkOrder {
+            id: Uuid::new_v4(),
+            vehicle_id,
+            mechanic_id: Some(Uuid::new_v4()),
+            r#type: MaintenanceType::Preventive,
+            description: "Oil change".into(),
+            provider: None,
+            status,
+            maintenance_record_id: None,
+            started_at: None,
+            completed_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn create_dto(vehicle_id: Uuid, mechanic_id: Option<Uuid>) -> CreateWorkOrderDto {
+        CreateWorkOrderDto {
+            vehicle_id,
+            r#type: MaintenanceType::Repair,
+            description: "Replace brake pads".into(),
+            mechanic_id,
+            provider: None,
+        }
+    }
+
+    fn service(
+        work_order_repo: MockWorkOrderRepositoryTrait,
+        vehicle_repo: MockVehicleRepositoryTrait,
+        user_repo: MockUserRepositoryTrait,
+        alert_repo: MockAlertRepositoryTrait,
+    ) -> WorkOrderService {
+        WorkOrderService::new(
+            Arc::new(work_order_repo),
+            Arc::new(vehicle_repo),
+            Arc::new(user_repo),
+            Arc::new(alert_repo),
+            Arc::new(MockPartServiceTrait::new()),
+            Arc::new(MockUnitOfWorkTrait::new()),
+        )
+    }
+
+    fn transactional_service(
+        work_order_repo: MockWorkOrderRepositoryTrait,
+        user_repo: MockUserRepositoryTrait,
+        alert_repo: MockAlertRepositoryTrait,
+        tx: MockRepositoryTransactionTrait,
+    ) -> WorkOrderService {
+        let mut unit_of_work = MockUnitOfWorkTrait::new();
+        unit_of_work.expect_begin().times(1).return_once(move || Ok(Box::new(tx)));
+        WorkOrderService::new(
+            Arc::new(work_order_repo),
+            Arc::new(MockVehicleRepositoryTrait::new()),
+            Arc::new(user_repo),
+            Arc::new(alert_repo),
+            Arc::new(MockPartServiceTrait::new()),
+            Arc::new(unit_of_work),
+        )
+    }
+
+    #[test]
+    fn test_status_transitions() {
+        use WorkOrderStatus::*;
+        assert!(Open.can_transition_to(InProgress));
+        assert!(AwaitingParts.can_transition_to(InProgress));
+        assert!(AwaitingParts.can_transition_to(Cancelled));
+        assert!(!Open.can_transition_to(Completed));
+        assert!(!AwaitingParts.can_transition_to(Completed));
+        assert!(!Completed.can_transition_to(Cancelled));
+        assert!(!Cancelled.can_transition_to(Open));
+    }
+
+    #[tokio::test]
+    async fn test_create_puts_vehicle_into_maintenance() {
+        let vehicle_id = Uuid::new_v4();
+        let mechanic_id = Uuid::new_v4();
+
+        let mut tx = MockRepositoryTransactionTrait::new();
+        tx.expect_lock_vehicle()
+            .with(eq(vehicle_id))
+            .returning(|id| Ok(Some(vehicle(id, VehicleStatus::Available))));
+        tx.expect_create_work_order()
+            .times(1)
+            .returning(|dto| Ok(work_order(dto.vehicle_id, WorkOrderStatus::Open)));
+        tx.expect_update_vehicle_status()
+            .with(eq(vehicle_id), eq(VehicleStatus::Maintenance))
+            .times(1)
+            .returning(|id, status| Ok(vehicle(id, status)));
+        tx.expect_commit().times(1).returning(|| Ok(()));
+
+        let mut user_repo = MockUserRepositoryTrait::new();
+        user_repo.expect_find_by_id().returning(|id| Ok(Some(user(id, UserRole::Mechanic))));
+
+        let service = transactional_service(MockWorkOrderRepositoryTrait::new(), user_repo, MockAlertRepositoryTrait::new(), tx);
+        let created = service.create_work_order(create_dto(vehicle_id, Some(mechanic_id))).await.unwrap();
+        assert_eq!(created.status, WorkOrderStatus::Open);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_assigned_vehicle() {
+        let mut tx = MockRepositoryTransactionTrait::new();
+        tx.expect_lock_vehicle()
+            .returning(|id| Ok(Some(vehicle(id, VehicleStatus::Assigned))));
+        tx.expect_create_work_order().never();
+        tx.expect_commit().never();
+
+        let service = transactional_service(
+            MockWorkOrderRepositoryTrait::new(),
+            MockUserRepositoryTrait::new(),
+            MockAlertRepositoryTrait::new(),
+            tx,
+        );
+        let result = service.create_work_order(create_dto(Uuid::new_v4(), None)).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_non_mechanic() {
+        let mut user_repo = MockUserRepositoryTrait::new();
+        user_repo.expect_find_by_id().returning(|id| Ok(Some(user(id, UserRole::Driver))));
+
+        // Rejected before a transaction is started
+        let service = service(
+            MockWorkOrderRepositoryTrait::new(),
+            MockVehicleRepositoryTrait::new(),
+            user_repo,
+            MockAlertRepositoryTrait::new(),
+        );
+        let result = service.create_work_order(create_dto(Uuid::new_v4(), Some(Uuid::new_v4()))).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_complete_releases_vehicle_and_resolves_due_alerts() {
+        let vehicle_id = Uuid::new_v4();
+        let current = work_order(vehicle_id, WorkOrderStatus::InProgress);
+        let id = current.id;
+
+        let mut work_order_repo = MockWorkOrderRepositoryTrait::new();
+        let found = current.clone();
+        work_order_repo.expect_find_by_id().returning(move |_| Ok(Some(found.clone())));
+
+        let mut tx = MockRepositoryTransactionTrait::new();
+        tx.expect_lock_vehicle()
+            .with(eq(vehicle_id))
+            .returning(|id| Ok(Some(vehicle(id, VehicleStatus::Maintenance))));
+        tx.expect_complete_work_order().with(eq(id)).times(1).returning(move |_| {
+            let mut completed = current.clone();
+            completed.status = WorkOrderStatus::Completed;
+            completed.maintenance_record_id = Some(Uuid::new_v4());
+            Ok(Some(completed))
+        });
+        tx.expect_count_open_work_orders().returning(|_| Ok(0));
+        tx.expect_update_vehicle_status()
+            .with(eq(vehicle_id), eq(VehicleStatus::Available))
+            .times(1)
+            .returning(|id, status| Ok(vehicle(id, status)));
+        tx.expect_commit().times(1).returning(|| Ok(()));
+
+        let mut alert_repo = MockAlertRepositoryTrait::new();
+        alert_repo
+            .expect_resolve_open()
+            .withf(move |id, types| *id == vehicle_id && types.contains(&MAINTENANCE_OVERDUE_ALERT.to_string()))
+            .times(1)
+            .returning(|_, _| Ok(1));
+
+        let service = transactional_service(work_order_repo, MockUserRepositoryTrait::new(), alert_repo, tx);
+        let completed = service.update_status(id, WorkOrderStatus::Completed).await.unwrap();
+        assert!(completed.maintenance_record_id.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_close_keeps_vehicle_with_other_open_orders() {
+        let vehicle_id = Uuid::new_v4();
+        let current = work_order(vehicle_id, WorkOrderStatus::Open);
+        let id = current.id;
+
+        let mut work_order_repo = MockWorkOrderRepositoryTrait::new();
+        let found = current.clone();
+        work_order_repo.expect_find_by_id().returning(move |_| Ok(Some(found.clone())));
+
+        let mut tx = MockRepositoryTransactionTrait::new();
+        tx.expect_lock_vehicle()
+            .returning(|id| Ok(Some(vehicle(id, VehicleStatus::Maintenance))));
+        tx.expect_update_work_order_status()
+            .with(eq(id), eq(WorkOrderStatus::Open), eq(WorkOrderStatus::Cancelled))
+            .returning(move |_, _, to| {
+                let mut cancelled = current.clone();
+                cancelled.status = to;
+                Ok(Some(cancelled))
+            });
+        tx.expect_count_open_work_orders().returning(|_| Ok(1));
+        tx.expect_update_vehicle_status().never();
+        tx.expect_commit().times(1).returning(|| Ok(()));
+
+        // No alert changes expected
+        let service = transactional_service(work_order_repo, MockUserRepositoryTrait::new(), MockAlertRepositoryTrait::new(), tx);
+        let cancelled = service.update_status(id, WorkOrderStatus::Cancelled).await.unwrap();
+        assert_eq!(cancelled.status, WorkOrderStatus::Cancelled);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_skipping_to_completed() {
+        let current = work_order(Uuid::new_v4(), WorkOrderStatus::Open);
+        let mut work_order_repo = MockWorkOrderRepositoryTrait::new();
+        work_order_repo.expect_find_by_id().returning(move |_| Ok(Some(current.clone())));
+
+        let service = service(
+            work_order_repo,
+            MockVehicleRepositoryTrait::new(),
+            MockUserRepositoryTrait::new(),
+            MockAlertRepositoryTrait::new(),
+        );
+        let result = service.update_status(Uuid::new_v4(), WorkOrderStatus::Completed).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_details_total_cost_and_closed_items_are_locked() {
+        let current = work_order(Uuid::new_v4(), WorkOrderStatus::Completed);
+        let id = current.id;
+        let mut work_order_repo = MockWorkOrderRepositoryTrait::new();
+        work_order_repo.expect_find_by_id().returning(move |_| Ok(Some(current.clone())));
+        work_order_repo.expect_find_items().returning(|work_order_id| Ok(vec![
+            WorkOrderItem {
+                id: Uuid::new_v4(),
+                work_order_id,
+                kind: WorkOrderItemKind::Part,
+                part_id: None,
+                description: "Oil filter".into(),
+                quantity: Decimal::new(2, 0),
+                unit_cost: Decimal::new(1250, 2),
+                created_at: Utc::now(),
+            },
+            WorkOrderItem {
+                id: Uuid::new_v4(),
+                work_order_id,
+                kind: WorkOrderItemKind::Labor,
+                part_id: None,
+                description: "Labor".into(),
+                quantity: Decimal::new(15, 1),
+                unit_cost: Decimal::new(80, 0),
+                created_at: Utc::now(),
+            },
+        ]));
+
+        let service = service(
+            work_order_repo,
+            MockVehicleRepositoryTrait::new(),
+            MockUserRepositoryTrait::new(),
+            MockAlertRepositoryTrait::new(),
+        );
+        let details = service.get_work_order(id).await.unwrap();
+        assert_eq!(details.total_cost, Decimal::new(14500, 2));
+
+        let result = service.add_item(id, CreateWorkOrderItemDto {
+            kind: WorkOrderItemKind::Part,
+            part_id: None,
+            description: "Air filter".into(),
+            quantity: Decimal::ONE,
+            unit_cost: Some(Decimal::new(30, 0)),
+        }).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    fn brake_pads(quantity_on_hand: i64) -> Part {
+        Part {
+            id: Uuid::new_v4(),
+            sku: "BP-200".into(),
+            description: "Brake pad set".into(),
+            unit_cost: Decimal::new(4500, 2),
+            quantity_on_hand: Decimal::new(quantity_on_hand, 0),
+            reorder_threshold: Decimal::new(2, 0),
+            compatible_vehicle_types: vec![VehicleType::Van],
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn part_line(part_id: Uuid) -> CreateWorkOrderItemDto {
+        CreateWorkOrderItemDto {
+            kind: WorkOrderItemKind::Part,
+            part_id: Some(part_id),
+            description: String::new(),
+            quantity: Decimal::ONE,
+            unit_cost: Some(Decimal::new(1, 0)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_part_line_draws_from_inventory() {
+        let current = work_order(Uuid::new_v4(), WorkOrderStatus::InProgress);
+        let id = current.id;
+        let part = brake_pads(3);
+        let part_id = part.id;
+
+        let mut work_order_repo = MockWorkOrderRepositoryTrait::new();
+        work_order_repo.expect_find_by_id().returning(move |_| Ok(Some(current.clone())));
+        work_order_repo
+            .expect_add_item()
+            .withf(move |_, dto| dto.part_id == Some(part_id) && dto.unit_cost.is_none() && dto.description == "Brake pad set")
+            .times(1)
+            .returning(|work_order_id, dto| Ok(Some(WorkOrderItem {
+                id: Uuid::new_v4(),
+                work_order_id,
+                kind: dto.kind,
+                part_id: dto.part_id,
+                description: dto.description,
+                quantity: dto.quantity,
+                unit_cost: Decimal::new(4500, 2),
+                created_at: Utc::now(),
+            })));
+
+        let mut vehicle_repo = MockVehicleRepositoryTrait::new();
+        vehicle_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(vehicle(id, VehicleStatus::Maintenance))));
+
+        let mut part_service = MockPartServiceTrait::new();
+        part_service.expect_get_part().returning(move |_| Ok(part.clone()));
+        part_service.expect_refresh_stock_alert().with(eq(part_id)).times(1).returning(|_| Ok(()));
+
+        let service = WorkOrderService::new(
+            Arc::new(work_order_repo),
+            Arc::new(vehicle_repo),
+            Arc::new(MockUserRepositoryTrait::new()),
+            Arc::new(MockAlertRepositoryTrait::new()),
+            Arc::new(part_service),
+            Arc::new(MockUnitOfWorkTrait::new()),
+        );
+        let item = service.add_item(id, part_line(part_id)).await.unwrap();
+        assert_eq!(item.unit_cost, Decimal::new(4500, 2));
+    }
+
+    #[tokio::test]
+    async fn test_part_line_out_of_stock() {
+        let current = work_order(Uuid::new_v4(), WorkOrderStatus::InProgress);
+        let id = current.id;
+        let part = brake_pads(0);
+        let part_id = part.id;
+
+        let mut work_order_repo = MockWorkOrderRepositoryTrait::new();
+        work_order_repo.expect_find_by_id().returning(move |_| Ok(Some(current.clone())));
+        work_order_repo.expect_add_item().returning(|_, _| Ok(None));
+
+        let mut vehicle_repo = MockVehicleRepositoryTrait::new();
+        vehicle_repo
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(vehicle(id, VehicleStatus::Maintenance))));
+
+        let mut part_service = MockPartServiceTrait::new();
+        part_service.expect_get_part().returning(move |_| Ok(part.clone()));
+
+        let service = WorkOrderService::new(
+            Arc::new(work_order_repo),
+            Arc::new(vehicle_repo),
+            Arc::new(MockUserRepositoryTrait::new()),
+            Arc::new(MockAlertRepositoryTrait::new()),
+            Arc::new(part_service),
+            Arc::new(MockUnitOfWorkTrait::new()),
+        );
+        let result = service.add_item(id, part_line(part_id)).await;
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+}
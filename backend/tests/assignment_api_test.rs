@@ -1,10 +1,12 @@
 use actix_web::{test, web, App};
 use fleet_management_backend::routes::assignment;
-use fleet_management_backend::models::postgres::assignment::{VehicleAssignment, CreateAssignmentDto, AssignmentStatus};
+use fleet_management_backend::models::postgres::assignment::{
+    VehicleAssignment, CreateAssignmentDto, AssignmentStatus, CancelAssignmentDto, UpdateAssignmentDto, AssignmentQuery,
+};
 use fleet_management_backend::services::assignment_service::AssignmentServiceTrait;
-use fleet_management_backend::error::AppError;
+use fleet_management_backend::error::{AppError, ConflictDetails, ConflictKind};
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use mockall::mock;
 use async_trait::async_trait;
@@ -15,9 +17,13 @@ mock! {
     #[async_trait]
     impl AssignmentServiceTrait for AssignmentService {
         async fn create_assignment(&self, dto: CreateAssignmentDto) -> Result<VehicleAssignment, AppError>;
+        async fn start_assignment(&self, id: Uuid) -> Result<VehicleAssignment, AppError>;
         async fn complete_assignment(&self, id: Uuid) -> Result<VehicleAssignment, AppError>;
+        async fn cancel_assignment(&self, id: Uuid, dto: CancelAssignmentDto) -> Result<VehicleAssignment, AppError>;
         async fn get_assignment(&self, id: Uuid) -> Result<VehicleAssignment, AppError>;
-        async fn list_assignments(&self) -> Result<Vec<VehicleAssignment>, AppError>;
+        async fn list_assignments(&self, query: AssignmentQuery) -> Result<Vec<VehicleAssignment>, AppError>;
+        async fn get_assignments_by_driver(&self, driver_id: Uuid) -> Result<Vec<VehicleAssignment>, AppError>;
+        async fn activate_due_assignments(&self, now: DateTime<Utc>) -> Result<Vec<VehicleAssignment>, AppError>;
     }
 }
 
@@ -38,6 +44,10 @@ async fn test_create_assignment_success() {
         status: AssignmentStatus::Active,
         created_at: now,
         updated_at: now,
+        started_at: Some(now),
+        completed_at: None,
+        cancelled_at: None,
+        cancel_reason: None,
     };
 
     let return_assignment = expected_assignment.clone();
@@ -61,6 +71,7 @@ async fn test_create_assignment_success() {
             start_time: now,
             end_time: None,
             status: AssignmentStatus::Active,
+            job_id: None,
         })
         .to_request();
 
@@ -76,10 +87,12 @@ async fn test_create_assignment_success() {
 async fn test_list_assignments() {
     let mut mock_service = MockAssignmentService::new();
     
+    let vehicle_id = Uuid::new_v4();
     mock_service
         .expect_list_assignments()
+        .withf(move |q| q.vehicle_id == Some(vehicle_id) && q.from.is_some() && q.to.is_some() && q.driver_id.is_none())
         .times(1)
-        .returning(|| Ok(vec![]));
+        .returning(|_| Ok(vec![]));
 
     let app = test::init_service(
         App::new()
@@ -88,9 +101,98 @@ async fn test_list_assignments() {
     ).await;
 
     let req = test::TestRequest::get()
-        .uri("/assignments")
+        .uri(&format!(
+            "/assignments?from=2026-10-19T00:00:00Z&to=2026-10-26T00:00:00Z&vehicle_id={}",
+            vehicle_id
+        ))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn test_update_assignment_cancels_with_reason() {
+    let mut mock_service = MockAssignmentService::new();
+    let assignment_id = Uuid::new_v4();
+    let now = Utc::now();
+
+    mock_service
+        .expect_cancel_assignment()
+        .withf(move |id, dto| *id == assignment_id && dto.reason == "Customer postponed")
+        .times(1)
+        .returning(move |id, dto| Ok(VehicleAssignment {
+            id,
+            vehicle_id: Uuid::new_v4(),
+            driver_id: Uuid::new_v4(),
+            start_time: now,
+            end_time: None,
+            status: AssignmentStatus::Cancelled,
+            created_at: now,
+            updated_at: now,
+            started_at: None,
+            completed_at: None,
+            cancelled_at: Some(now),
+            cancel_reason: Some(dto.reason),
+        }));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn AssignmentServiceTrait>))
+            .configure(assignment::config)
+    ).await;
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/assignments/{}", assignment_id))
+        .set_json(UpdateAssignmentDto {
+            status: Some(AssignmentStatus::Cancelled),
+            reason: Some("Customer postponed".into()),
+        })
         .to_request();
 
     let resp = test::call_service(&app, req).await;
     assert!(resp.status().is_success());
+
+    let body: VehicleAssignment = test::read_body_json(resp).await;
+    assert_eq!(body.status, AssignmentStatus::Cancelled);
+    assert_eq!(body.cancel_reason.as_deref(), Some("Customer postponed"));
+}
+
+#[actix_web::test]
+async fn test_create_assignment_overlap_returns_conflict() {
+    let mut mock_service = MockAssignmentService::new();
+
+    mock_service
+        .expect_create_assignment()
+        .times(1)
+        .returning(|_| Err(AppError::Conflict(ConflictDetails {
+            kind: ConflictKind::Overlap,
+            message: "Vehicle already has an assignment in this period".into(),
+            constraint: Some("vehicle_assignments_vehicle_id_tstzrange_excl".into()),
+        })));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn AssignmentServiceTrait>))
+            .configure(assignment::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri("/assignments")
+        .set_json(CreateAssignmentDto {
+            vehicle_id: Uuid::new_v4(),
+            driver_id: Uuid::new_v4(),
+            start_time: Utc::now(),
+            end_time: None,
+            status: AssignmentStatus::Scheduled,
+            job_id: None,
+        })
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["kind"], "OVERLAP");
+    assert_eq!(body["constraint"], "vehicle_assignments_vehicle_id_tstzrange_excl");
 }
@@ -21,6 +21,7 @@ async fn test_create_and_find_assignment(pool: PgPool) -> sqlx::Result<()> {
         start_time: Utc::now(),
         end_time: Some(Utc::now() + Duration::hours(8)),
         status: AssignmentStatus::Scheduled,
+        job_id: None,
     };
 
     // 3. Act
@@ -1,6 +1,6 @@
 use actix_web::{test, web, App};
 use fleet_management_backend::routes::auth;
-use fleet_management_backend::services::auth_service::{AuthServiceTrait, AuthResponse, LoginDto, Claims};
+use fleet_management_backend::services::auth_service::{AuthServiceTrait, AuthResponse, LoginDto, Claims, RefreshTokenDto, LogoutDto};
 use fleet_management_backend::models::postgres::user::{User, CreateUserDto, UserRole};
 use fleet_management_backend::error::AppError;
 use uuid::Uuid;
@@ -16,6 +16,8 @@ mock! {
     impl AuthServiceTrait for AuthService {
         async fn register(&self, dto: CreateUserDto) -> Result<AuthResponse, AppError>;
         async fn login(&self, dto: LoginDto) -> Result<AuthResponse, AppError>;
+        async fn refresh(&self, dto: RefreshTokenDto) -> Result<AuthResponse, AppError>;
+        async fn logout(&self, claims: Claims, dto: LogoutDto) -> Result<(), AppError>;
     }
 }
 
@@ -30,6 +32,7 @@ async fn test_register() {
         email: "test@example.com".to_string(),
         password_hash: "hashed".to_string(),
         role: UserRole::Driver,
+        name: None,
         is_active: true,
         created_at: now,
         updated_at: now,
@@ -38,17 +41,20 @@ async fn test_register() {
 
     let auth_response = AuthResponse {
         token: "token".to_string(),
+        refresh_token: "refresh".to_string(),
         user: expected_user,
     };
 
     // Clone for closure
     let return_response = AuthResponse {
         token: auth_response.token.clone(),
+        refresh_token: auth_response.refresh_token.clone(),
         user: User {
             id: auth_response.user.id,
             email: auth_response.user.email.clone(),
             password_hash: auth_response.user.password_hash.clone(),
             role: auth_response.user.role,
+            name: auth_response.user.name.clone(),
             is_active: auth_response.user.is_active,
             created_at: auth_response.user.created_at,
             updated_at: auth_response.user.updated_at,
@@ -61,11 +67,13 @@ async fn test_register() {
         .times(1)
         .returning(move |_| Ok(AuthResponse {
             token: return_response.token.clone(),
+            refresh_token: return_response.refresh_token.clone(),
             user: User {
                 id: return_response.user.id,
                 email: return_response.user.email.clone(),
                 password_hash: return_response.user.password_hash.clone(),
                 role: return_response.user.role,
+                name: return_response.user.name.clone(),
                 is_active: return_response.user.is_active,
                 created_at: return_response.user.created_at,
                 updated_at: return_response.user.updated_at,
@@ -85,6 +93,7 @@ async fn test_register() {
             email: "test@example.com".to_string(),
             password_hash: "password".to_string(),
             role: UserRole::Driver,
+            name: None,
             is_active: true,
         })
         .to_request();
@@ -104,6 +113,7 @@ async fn test_login() {
         email: "test@example.com".to_string(),
         password_hash: "hashed".to_string(),
         role: UserRole::Driver,
+        name: None,
         is_active: true,
         created_at: now,
         updated_at: now,
@@ -112,17 +122,20 @@ async fn test_login() {
 
     let auth_response = AuthResponse {
         token: "token".to_string(),
+        refresh_token: "refresh".to_string(),
         user: expected_user,
     };
 
     // Clone for closure
     let return_response = AuthResponse {
         token: auth_response.token.clone(),
+        refresh_token: auth_response.refresh_token.clone(),
         user: User {
             id: auth_response.user.id,
             email: auth_response.user.email.clone(),
             password_hash: auth_response.user.password_hash.clone(),
             role: auth_response.user.role,
+            name: auth_response.user.name.clone(),
             is_active: auth_response.user.is_active,
             created_at: auth_response.user.created_at,
             updated_at: auth_response.user.updated_at,
@@ -135,11 +148,13 @@ async fn test_login() {
         .times(1)
         .returning(move |_| Ok(AuthResponse {
             token: return_response.token.clone(),
+            refresh_token: return_response.refresh_token.clone(),
             user: User {
                 id: return_response.user.id,
                 email: return_response.user.email.clone(),
                 password_hash: return_response.user.password_hash.clone(),
                 role: return_response.user.role,
+                name: return_response.user.name.clone(),
                 is_active: return_response.user.is_active,
                 created_at: return_response.user.created_at,
                 updated_at: return_response.user.updated_at,
@@ -164,3 +179,33 @@ async fn test_login() {
     let resp = test::call_service(&app, req).await;
     assert!(resp.status().is_success());
 }
+
+#[actix_web::test]
+async fn test_refresh_separates_rejected_tokens_from_server_errors() {
+    let mut mock_service = MockAuthService::new();
+    mock_service
+        .expect_refresh()
+        .returning(|dto| match dto.refresh_token.as_str() {
+            "reused" => Err(AppError::AuthError("Refresh token has been revoked".into())),
+            _ => Err(AppError::DatabaseError(sqlx::Error::PoolTimedOut)),
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn AuthServiceTrait>))
+            .configure(auth::config_public)
+    ).await;
+
+    for (token, status) in [
+        ("reused", actix_web::http::StatusCode::UNAUTHORIZED),
+        ("valid", actix_web::http::StatusCode::INTERNAL_SERVER_ERROR),
+    ] {
+        let req = test::TestRequest::post()
+            .uri("/auth/refresh")
+            .set_json(RefreshTokenDto { refresh_token: token.to_string() })
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), status);
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use fleet_management_backend::repositories::postgres::vehicle_repo::{VehicleRepository, VehicleRepositoryTrait};
 use fleet_management_backend::repositories::postgres::user_repo::{UserRepository, UserRepositoryTrait};
 use fleet_management_backend::repositories::postgres::driver_repo::{DriverRepository, DriverRepositoryTrait};
@@ -38,6 +40,7 @@ pub async fn create_test_user(pool: &PgPool, role: UserRole) -> User {
         email: format!("user_{}@example.com", unique_id),
         password_hash: "hashed_password".to_string(),
         role,
+        name: None,
         is_active: true,
     };
     
@@ -67,6 +70,8 @@ pub async fn create_test_customer(pool: &PgPool) -> Customer {
         name: format!("Customer {}", unique_id),
         contact_info: json!({"email": "test@example.com"}),
         billing_address: "123 Test St".to_string(),
+        credit_limit: None,
+        block_over_credit_limit: false,
     };
     
     repo.create(dto).await.expect("Failed to create test customer")
@@ -81,5 +86,5 @@ pub async fn create_test_job(pool: &PgPool, customer_id: Uuid) -> TransportJob {
         agreed_price: Decimal::new(10000, 2),
     };
     
-    repo.create(dto).await.expect("Failed to create test job")
+    repo.create(dto, None).await.expect("Failed to create test job")
 }
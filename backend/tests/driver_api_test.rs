@@ -1,7 +1,7 @@
 use actix_web::{test, web, App};
 use fleet_management_backend::api::v1::drivers;
 use fleet_management_backend::api::dto::driver_dto::{CreateDriverRequest, DriverResponse, UpdateDriverStatusRequest};
-use fleet_management_backend::models::postgres::driver::{Driver, CreateDriverDto, DriverStatus};
+use fleet_management_backend::models::postgres::driver::{Driver, DriverWithUser, CreateDriverDto, DriverStatus};
 use fleet_management_backend::services::driver_service::DriverServiceTrait;
 use fleet_management_backend::error::AppError;
 use uuid::Uuid;
@@ -16,8 +16,8 @@ mock! {
     #[async_trait]
     impl DriverServiceTrait for DriverService {
         async fn create_driver(&self, dto: CreateDriverDto) -> Result<Driver, AppError>;
-        async fn get_driver(&self, id: Uuid) -> Result<Driver, AppError>;
-        async fn list_drivers(&self) -> Result<Vec<Driver>, AppError>;
+        async fn get_driver(&self, id: Uuid) -> Result<DriverWithUser, AppError>;
+        async fn list_drivers(&self) -> Result<Vec<DriverWithUser>, AppError>;
         async fn update_driver_status(&self, id: Uuid, status: DriverStatus) -> Result<Driver, AppError>;
         async fn delete_driver(&self, id: Uuid) -> Result<(), AppError>;
     }
@@ -36,6 +36,9 @@ async fn test_create_driver_success() {
         user_id,
         license_number: "LIC-12345".to_string(),
         status: DriverStatus::Available,
+        phone: None,
+        wage_rate: None,
+        license_expiry: None,
         created_at: now,
         updated_at: now,
         deleted_at: None,
@@ -78,14 +81,18 @@ async fn test_get_driver_found() {
     let user_id = Uuid::new_v4();
     let now = Utc::now();
 
-    let expected_driver = Driver {
+    let expected_driver = DriverWithUser {
         id: driver_id,
         user_id,
         license_number: "LIC-12345".to_string(),
         status: DriverStatus::Available,
+        email: "driver@example.com".to_string(),
+        name: Some("Test Driver".to_string()),
+        phone: None,
+        wage_rate: None,
+        license_expiry: None,
         created_at: now,
         updated_at: now,
-        deleted_at: None,
     };
 
     mock_service
@@ -148,6 +155,9 @@ async fn test_update_driver_status() {
         user_id,
         license_number: "LIC-12345".to_string(),
         status: DriverStatus::OnDuty,
+        phone: None,
+        wage_rate: None,
+        license_expiry: None,
         created_at: now,
         updated_at: now,
         deleted_at: None,
@@ -29,6 +29,7 @@ async fn test_get_monthly_summary() {
             revenue: Decimal::new(10000, 0),
             cost: Decimal::new(2000, 0),
             profit: Decimal::new(8000, 0),
+            collected: Decimal::new(6000, 0),
         }
     ];
 
@@ -38,6 +39,7 @@ async fn test_get_monthly_summary() {
         revenue: s.revenue,
         cost: s.cost,
         profit: s.profit,
+        collected: s.collected,
     }).collect::<Vec<_>>();
 
     mock_service
@@ -48,6 +50,7 @@ async fn test_get_monthly_summary() {
             revenue: s.revenue,
             cost: s.cost,
             profit: s.profit,
+            collected: s.collected,
         }).collect()));
 
     let app = test::init_service(
@@ -0,0 +1,147 @@
+use actix_web::{test, web, App};
+use fleet_management_backend::routes::geofences;
+use fleet_management_backend::models::postgres::geofence::{Geofence, CreateGeofenceDto, GeofenceKind};
+use fleet_management_backend::models::postgres::maintenance::Alert;
+use fleet_management_backend::services::geofence_service::GeofenceServiceTrait;
+use fleet_management_backend::error::AppError;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use mockall::mock;
+use async_trait::async_trait;
+
+mock! {
+    pub GeofenceService {}
+
+    #[async_trait]
+    impl GeofenceServiceTrait for GeofenceService {
+        async fn create_geofence(&self, dto: CreateGeofenceDto) -> Result<Geofence, AppError>;
+        async fn list_geofences(&self) -> Result<Vec<Geofence>, AppError>;
+        async fn get_geofence(&self, id: Uuid) -> Result<Geofence, AppError>;
+        async fn update_geofence(&self, id: Uuid, dto: CreateGeofenceDto) -> Result<Geofence, AppError>;
+        async fn delete_geofence(&self, id: Uuid) -> Result<(), AppError>;
+        async fn evaluate_positions(&self, vehicle_id: Uuid, points: Vec<(DateTime<Utc>, Value)>) -> Result<Vec<Alert>, AppError>;
+    }
+}
+
+fn depot(id: Uuid) -> Geofence {
+    Geofence {
+        id,
+        name: "Depot".to_string(),
+        kind: GeofenceKind::Circle,
+        area: json!({"type": "Polygon", "coordinates": []}),
+        center: Some(json!({"type": "Point", "coordinates": [-74.0060, 40.7128]})),
+        radius_m: Some(500.0),
+        vehicle_id: None,
+        vehicle_type: None,
+        alert_on_entry: true,
+        alert_on_exit: true,
+        is_active: true,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[actix_web::test]
+async fn test_create_geofence() {
+    let mut mock_service = MockGeofenceService::new();
+    let id = Uuid::new_v4();
+
+    mock_service
+        .expect_create_geofence()
+        .withf(|dto| dto.kind == GeofenceKind::Circle && dto.alert_on_entry && dto.is_active)
+        .times(1)
+        .returning(move |_| Ok(depot(id)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn GeofenceServiceTrait>))
+            .configure(geofences::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri("/geofences")
+        .set_json(json!({
+            "name": "Depot",
+            "kind": "Circle",
+            "center": {"type": "Point", "coordinates": [-74.0060, 40.7128]},
+            "radius_m": 500.0
+        }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+}
+
+#[actix_web::test]
+async fn test_create_geofence_invalid_shape() {
+    let mut mock_service = MockGeofenceService::new();
+
+    mock_service
+        .expect_create_geofence()
+        .times(1)
+        .returning(|_| Err(AppError::BadRequest("radius_m must be positive".to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn GeofenceServiceTrait>))
+            .configure(geofences::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri("/geofences")
+        .set_json(json!({"name": "Depot", "kind": "Circle", "radius_m": -1.0}))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+}
+
+#[actix_web::test]
+async fn test_get_geofence_not_found() {
+    let mut mock_service = MockGeofenceService::new();
+
+    mock_service
+        .expect_get_geofence()
+        .times(1)
+        .returning(|_| Err(AppError::NotFound("Geofence not found".to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn GeofenceServiceTrait>))
+            .configure(geofences::config)
+    ).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/geofences/{}", Uuid::new_v4()))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+}
+
+#[actix_web::test]
+async fn test_delete_geofence() {
+    let mut mock_service = MockGeofenceService::new();
+    let id = Uuid::new_v4();
+
+    mock_service
+        .expect_delete_geofence()
+        .with(mockall::predicate::eq(id))
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn GeofenceServiceTrait>))
+            .configure(geofences::config)
+    ).await;
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/geofences/{}", id))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 204);
+}
@@ -0,0 +1,180 @@
+use actix_web::{test, web, App, HttpMessage};
+use fleet_management_backend::routes::hos;
+use fleet_management_backend::models::postgres::hos::{
+    ClockDto, DutyDay, DutyEvent, DutyEventKind, HosLog, HosLogQuery
+};
+use fleet_management_backend::models::postgres::user::UserRole;
+use fleet_management_backend::services::auth_service::Claims;
+use fleet_management_backend::services::hos_service::HosServiceTrait;
+use fleet_management_backend::error::AppError;
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use mockall::mock;
+use async_trait::async_trait;
+
+mock! {
+    pub HosService {}
+
+    #[async_trait]
+    impl HosServiceTrait for HosService {
+        async fn clock_in(&self, driver_id: Uuid, dto: ClockDto, caller: &Claims) -> Result<DutyEvent, AppError>;
+        async fn clock_out(&self, driver_id: Uuid, dto: ClockDto, caller: &Claims) -> Result<DutyEvent, AppError>;
+        async fn get_log(&self, driver_id: Uuid, query: HosLogQuery, caller: &Claims) -> Result<HosLog, AppError>;
+        async fn check_assignment(
+            &self,
+            driver_id: Uuid,
+            start: DateTime<Utc>,
+            end: Option<DateTime<Utc>>,
+        ) -> Result<(), AppError>;
+        async fn hours_remaining(&self, driver_id: Uuid, at: DateTime<Utc>) -> Result<Option<f64>, AppError>;
+    }
+}
+
+fn app_data(mock_service: MockHosService) -> web::Data<dyn HosServiceTrait> {
+    web::Data::from(Arc::new(mock_service) as Arc<dyn HosServiceTrait>)
+}
+
+/// Claims the `Auth` middleware would have added for the caller.
+fn claims(user_id: Uuid, role: UserRole) -> Claims {
+    Claims { sub: user_id, user_id, role, is_active: true, exp: usize::MAX, jti: Uuid::new_v4() }
+}
+
+#[actix_web::test]
+async fn test_clock_in() {
+    let mut mock_service = MockHosService::new();
+    let driver_id = Uuid::new_v4();
+
+    mock_service
+        .expect_clock_in()
+        .withf(move |id, dto, caller| {
+            *id == driver_id && dto.occurred_at.is_none() && dto.note.as_deref() == Some("Yard check") && caller.role == UserRole::Driver
+        })
+        .times(1)
+        .returning(|driver_id, dto, _| Ok(DutyEvent {
+            id: Uuid::new_v4(),
+            driver_id,
+            kind: DutyEventKind::ClockIn,
+            occurred_at: Utc::now(),
+            note: dto.note,
+            created_at: Utc::now(),
+        }));
+
+    let app = test::init_service(
+        App::new().app_data(app_data(mock_service)).configure(hos::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/hos/drivers/{}/clock-in", driver_id))
+        .set_json(json!({ "note": "Yard check" }))
+        .to_request();
+    req.extensions_mut().insert(claims(Uuid::new_v4(), UserRole::Driver));
+
+    let resp: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(resp["kind"], "ClockIn");
+}
+
+#[actix_web::test]
+async fn test_clock_out_when_not_clocked_in() {
+    let mut mock_service = MockHosService::new();
+
+    mock_service
+        .expect_clock_out()
+        .returning(|_, _, _| Err(AppError::BadRequest("Driver is not clocked in".into())));
+
+    let app = test::init_service(
+        App::new().app_data(app_data(mock_service)).configure(hos::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/hos/drivers/{}/clock-out", Uuid::new_v4()))
+        .set_json(json!({}))
+        .to_request();
+    req.extensions_mut().insert(claims(Uuid::new_v4(), UserRole::Manager));
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+}
+
+#[actix_web::test]
+async fn test_get_log() {
+    let mut mock_service = MockHosService::new();
+    let driver_id = Uuid::new_v4();
+    let day = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+
+    mock_service
+        .expect_get_log()
+        .withf(move |id, query, _| *id == driver_id && query.from == Some(day) && query.to == Some(day))
+        .times(1)
+        .returning(move |driver_id, _, _| Ok(HosLog {
+            driver_id,
+            from: day,
+            to: day,
+            max_daily_hours: 11.0,
+            max_weekly_hours: 60.0,
+            min_rest_hours: 10.0,
+            events: Vec::new(),
+            periods: Vec::new(),
+            shifts: Vec::new(),
+            days: vec![DutyDay { date: day, on_duty_hours: 0.0 }],
+            total_hours: 0.0,
+            violations: Vec::new(),
+        }));
+
+    let app = test::init_service(
+        App::new().app_data(app_data(mock_service)).configure(hos::config)
+    ).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/hos/drivers/{}/log?from=2026-10-12&to=2026-10-12", driver_id))
+        .to_request();
+    req.extensions_mut().insert(claims(Uuid::new_v4(), UserRole::Manager));
+
+    let resp: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(resp["days"].as_array().unwrap().len(), 1);
+    assert_eq!(resp["max_daily_hours"], 11.0);
+}
+
+#[actix_web::test]
+async fn test_driver_cannot_clock_in_another_driver() {
+    let mut mock_service = MockHosService::new();
+    let caller_id = Uuid::new_v4();
+
+    mock_service
+        .expect_clock_in()
+        .withf(move |_, _, caller| caller.user_id == caller_id && caller.role == UserRole::Driver)
+        .times(1)
+        .returning(|_, _, _| Err(AppError::Forbidden("Drivers can only record and view their own hours of service".into())));
+
+    let app = test::init_service(
+        App::new().app_data(app_data(mock_service)).configure(hos::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/hos/drivers/{}/clock-in", Uuid::new_v4()))
+        .set_json(json!({}))
+        .to_request();
+    req.extensions_mut().insert(claims(caller_id, UserRole::Driver));
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 403);
+}
+
+#[actix_web::test]
+async fn test_clock_in_without_credentials() {
+    let mut mock_service = MockHosService::new();
+    mock_service.expect_clock_in().never();
+
+    let app = test::init_service(
+        App::new().app_data(app_data(mock_service)).configure(hos::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/hos/drivers/{}/clock-in", Uuid::new_v4()))
+        .set_json(json!({}))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 401);
+}
@@ -0,0 +1,185 @@
+use actix_web::{test, web, App};
+use fleet_management_backend::routes::invoices;
+use fleet_management_backend::models::postgres::invoice::{
+    Invoice, InvoiceDetails, InvoiceQuery, InvoiceStatus, CreateInvoiceDto, RecordPaymentDto,
+    AgingBuckets, AgingQuery, ArAgingReport, CustomerAging, CustomerStatement, StatementQuery
+};
+use fleet_management_backend::services::invoice_service::InvoiceServiceTrait;
+use fleet_management_backend::error::AppError;
+use uuid::Uuid;
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use mockall::mock;
+use async_trait::async_trait;
+
+mock! {
+    pub InvoiceService {}
+
+    #[async_trait]
+    impl InvoiceServiceTrait for InvoiceService {
+        async fn create(&self, dto: CreateInvoiceDto, issued_by: Option<Uuid>) -> Result<InvoiceDetails, AppError>;
+        async fn list(&self, query: InvoiceQuery) -> Result<Vec<Invoice>, AppError>;
+        async fn get(&self, id: Uuid) -> Result<InvoiceDetails, AppError>;
+        async fn render_pdf(&self, id: Uuid) -> Result<(String, Vec<u8>), AppError>;
+        async fn record_payment(&self, id: Uuid, dto: RecordPaymentDto, recorded_by: Option<Uuid>) -> Result<InvoiceDetails, AppError>;
+        async fn aging_report(&self, query: AgingQuery) -> Result<ArAgingReport, AppError>;
+        async fn customer_statement(&self, customer_id: Uuid, query: StatementQuery) -> Result<CustomerStatement, AppError>;
+    }
+}
+
+fn invoice(customer_id: Uuid, status: InvoiceStatus) -> InvoiceDetails {
+    InvoiceDetails {
+        invoice: Invoice {
+            id: Uuid::new_v4(),
+            invoice_number: "INV-000007".to_string(),
+            customer_id,
+            customer_name: "Acme Retail".to_string(),
+            billing_address: "5 Market St".to_string(),
+            currency: "EUR".to_string(),
+            tax_rate: Decimal::new(20, 0),
+            subtotal: Decimal::new(10000, 2),
+            tax_amount: Decimal::new(2000, 2),
+            total: Decimal::new(12000, 2),
+            amount_paid: Decimal::ZERO,
+            balance_due: Decimal::new(12000, 2),
+            status,
+            issue_date: NaiveDate::from_ymd_opt(2026, 10, 18).unwrap(),
+            due_date: NaiveDate::from_ymd_opt(2026, 11, 17).unwrap(),
+            notes: None,
+            issued_by: None,
+            created_at: Utc::now(),
+        },
+        lines: Vec::new(),
+        payments: Vec::new(),
+    }
+}
+
+#[actix_web::test]
+async fn test_create_invoice() {
+    let mut mock_service = MockInvoiceService::new();
+    let customer_id = Uuid::new_v4();
+
+    mock_service
+        .expect_create()
+        .withf(move |dto, _| dto.customer_id == customer_id && dto.job_ids.is_none())
+        .times(1)
+        .returning(|dto, _| Ok(invoice(dto.customer_id, InvoiceStatus::Issued)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn InvoiceServiceTrait>))
+            .configure(invoices::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri("/invoices")
+        .set_json(serde_json::json!({ "customer_id": customer_id }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::CREATED);
+    let body: InvoiceDetails = test::read_body_json(resp).await;
+    assert_eq!(body.invoice.invoice_number, "INV-000007");
+}
+
+#[actix_web::test]
+async fn test_get_invoice_pdf() {
+    let mut mock_service = MockInvoiceService::new();
+    let invoice_id = Uuid::new_v4();
+
+    mock_service
+        .expect_render_pdf()
+        .with(mockall::predicate::eq(invoice_id))
+        .times(1)
+        .returning(|_| Ok(("INV-000007".to_string(), b"%PDF-1.4".to_vec())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn InvoiceServiceTrait>))
+            .configure(invoices::config)
+    ).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/invoices/{}/pdf", invoice_id))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    assert_eq!(resp.headers().get("content-type").unwrap(), "application/pdf");
+    assert_eq!(
+        resp.headers().get("content-disposition").unwrap(),
+        "inline; filename=\"INV-000007.pdf\""
+    );
+}
+
+#[actix_web::test]
+async fn test_record_payment_rejected() {
+    let mut mock_service = MockInvoiceService::new();
+
+    mock_service
+        .expect_record_payment()
+        .withf(|_, dto, _| dto.amount == Decimal::new(50000, 2))
+        .times(1)
+        .returning(|_, _, _| Err(AppError::BadRequest("Payment exceeds the balance due".to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn InvoiceServiceTrait>))
+            .configure(invoices::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/invoices/{}/payments", Uuid::new_v4()))
+        .set_json(serde_json::json!({ "amount": "500.00", "method": "Bank transfer" }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_get_aging_report() {
+    let mut mock_service = MockInvoiceService::new();
+    let as_of = NaiveDate::from_ymd_opt(2026, 9, 30).unwrap();
+
+    mock_service
+        .expect_aging_report()
+        .withf(move |query| query.as_of == Some(as_of) && query.customer_id.is_none())
+        .times(1)
+        .returning(|query| {
+            let balances = AgingBuckets {
+                days_61_90: Decimal::new(12000, 2),
+                total: Decimal::new(12000, 2),
+                ..AgingBuckets::default()
+            };
+            Ok(ArAgingReport {
+                as_of: query.as_of.unwrap(),
+                currency: "EUR".to_string(),
+                customers: vec![CustomerAging {
+                    customer_id: Uuid::new_v4(),
+                    customer_name: "Acme Retail".to_string(),
+                    credit_limit: None,
+                    balances: balances.clone(),
+                }],
+                totals: balances,
+            })
+        });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn InvoiceServiceTrait>))
+            .configure(invoices::config)
+    ).await;
+
+    // Not taken for an invoice id
+    let req = test::TestRequest::get()
+        .uri("/invoices/aging?as_of=2026-09-30")
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["customers"][0]["days_61_90"], "120.00");
+    assert_eq!(body["totals"]["total"], "120.00");
+}
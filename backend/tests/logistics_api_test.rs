@@ -1,12 +1,18 @@
 use actix_web::{test, web, App};
 use fleet_management_backend::routes::logistics;
 use fleet_management_backend::models::postgres::logistics::{
-    Customer, CreateCustomerDto,
-    TransportJob, CreateTransportJobDto, JobStatus,
+    Customer, CreateCustomerDto, UpdateCreditLimitDto,
+    TransportJob, CreateTransportJobDto, JobStatus, UpdateJobStatusDto, JobStatusHistory,
     Route, CreateRouteDto,
-    Shipment, CreateShipmentDto
+    Shipment, CreateShipmentDto,
+    JobLeg, AddJobLegDto
+};
+use fleet_management_backend::models::postgres::assignment::AssignmentStatus;
+use fleet_management_backend::models::postgres::proof_of_delivery::{
+    ProofOfDelivery, ProofOfDeliveryDetails, CreateProofOfDeliveryDto
 };
 use fleet_management_backend::services::logistics_service::LogisticsServiceTrait;
+use fleet_management_backend::services::proof_of_delivery_service::ProofOfDeliveryServiceTrait;
 use fleet_management_backend::error::AppError;
 use uuid::Uuid;
 use chrono::Utc;
@@ -23,14 +29,30 @@ mock! {
         async fn get_customer(&self, id: Uuid) -> Result<Customer, AppError>;
         async fn list_customers(&self) -> Result<Vec<Customer>, AppError>;
         async fn delete_customer(&self, id: Uuid) -> Result<(), AppError>;
-        async fn create_job(&self, dto: CreateTransportJobDto) -> Result<TransportJob, AppError>;
+        async fn update_customer_credit_limit(&self, id: Uuid, dto: UpdateCreditLimitDto) -> Result<Customer, AppError>;
+        async fn create_job(&self, dto: CreateTransportJobDto, created_by: Option<Uuid>) -> Result<TransportJob, AppError>;
         async fn list_jobs(&self) -> Result<Vec<TransportJob>, AppError>;
         async fn get_job(&self, id: Uuid) -> Result<TransportJob, AppError>;
-        async fn update_job_status(&self, id: Uuid, status: JobStatus) -> Result<TransportJob, AppError>;
+        async fn update_job_status(&self, id: Uuid, dto: UpdateJobStatusDto, changed_by: Option<Uuid>) -> Result<TransportJob, AppError>;
+        async fn get_job_status_history(&self, id: Uuid) -> Result<Vec<JobStatusHistory>, AppError>;
         async fn create_route(&self, dto: CreateRouteDto) -> Result<Route, AppError>;
         async fn get_job_route(&self, job_id: Uuid) -> Result<Option<Route>, AppError>;
         async fn create_shipment(&self, dto: CreateShipmentDto) -> Result<Shipment, AppError>;
         async fn get_job_shipments(&self, job_id: Uuid) -> Result<Vec<Shipment>, AppError>;
+        async fn get_job_legs(&self, job_id: Uuid) -> Result<Vec<JobLeg>, AppError>;
+        async fn add_job_leg(&self, job_id: Uuid, dto: AddJobLegDto) -> Result<JobLeg, AppError>;
+        async fn remove_job_leg(&self, job_id: Uuid, assignment_id: Uuid) -> Result<(), AppError>;
+    }
+}
+
+mock! {
+    pub ProofOfDeliveryService {}
+
+    #[async_trait]
+    impl ProofOfDeliveryServiceTrait for ProofOfDeliveryService {
+        async fn capture(&self, job_id: Uuid, dto: CreateProofOfDeliveryDto, captured_by: Option<Uuid>) -> Result<ProofOfDeliveryDetails, AppError>;
+        async fn get(&self, job_id: Uuid) -> Result<ProofOfDeliveryDetails, AppError>;
+        async fn get_file(&self, job_id: Uuid, file_id: Uuid) -> Result<(String, Vec<u8>), AppError>;
     }
 }
 
@@ -48,6 +70,8 @@ async fn test_create_customer() {
         created_at: now,
         updated_at: now,
         deleted_at: None,
+        credit_limit: None,
+        block_over_credit_limit: false,
     };
 
     let return_customer = expected_customer.clone();
@@ -69,6 +93,8 @@ async fn test_create_customer() {
             name: "Acme Corp".to_string(),
             contact_info: serde_json::json!({"email": "contact@acme.com"}),
             billing_address: "123 Main St".to_string(),
+            credit_limit: None,
+            block_over_credit_limit: false,
         })
         .to_request();
 
@@ -97,7 +123,7 @@ async fn test_create_job() {
     mock_service
         .expect_create_job()
         .times(1)
-        .returning(move |_| Ok(return_job.clone()));
+        .returning(move |_, _| Ok(return_job.clone()));
 
     let app = test::init_service(
         App::new()
@@ -117,3 +143,145 @@ async fn test_create_job() {
     let resp = test::call_service(&app, req).await;
     assert!(resp.status().is_success());
 }
+
+#[actix_web::test]
+async fn test_add_job_leg() {
+    let mut mock_service = MockLogisticsService::new();
+    let job_id = Uuid::new_v4();
+    let assignment_id = Uuid::new_v4();
+
+    mock_service
+        .expect_add_job_leg()
+        .times(1)
+        .returning(|job_id, dto| Ok(JobLeg {
+            job_id,
+            leg_number: dto.leg_number.unwrap_or(1),
+            assignment_id: dto.assignment_id,
+            vehicle_id: Uuid::new_v4(),
+            license_plate: "TRK-001".to_string(),
+            driver_id: Uuid::new_v4(),
+            driver_name: Some("Sam Driver".to_string()),
+            start_time: Utc::now(),
+            end_time: None,
+            status: AssignmentStatus::Scheduled,
+        }));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn LogisticsServiceTrait>))
+            .configure(logistics::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/logistics/jobs/{}/legs", job_id))
+        .set_json(AddJobLegDto { assignment_id, leg_number: Some(2) })
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::CREATED);
+    let leg: JobLeg = test::read_body_json(resp).await;
+    assert_eq!((leg.job_id, leg.leg_number), (job_id, 2));
+}
+
+#[actix_web::test]
+async fn test_update_job_status_rejects_invalid_transition() {
+    let mut mock_service = MockLogisticsService::new();
+    let job_id = Uuid::new_v4();
+
+    mock_service
+        .expect_update_job_status()
+        .times(1)
+        .returning(|_, _, _| Err(AppError::BadRequest("Cannot move job from Pending to Paid".to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn LogisticsServiceTrait>))
+            .configure(logistics::config)
+    ).await;
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/logistics/jobs/{}/status", job_id))
+        .set_json(UpdateJobStatusDto { status: JobStatus::Paid, reason: None })
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_capture_proof_of_delivery_accepts_large_photos() {
+    let mut mock_service = MockProofOfDeliveryService::new();
+    let job_id = Uuid::new_v4();
+
+    mock_service
+        .expect_capture()
+        .withf(move |id, dto, _| *id == job_id && dto.photos.len() == 1)
+        .times(1)
+        .returning(|job_id, dto, _| Ok(ProofOfDeliveryDetails {
+            proof: ProofOfDelivery {
+                id: Uuid::new_v4(),
+                job_id,
+                recipient_name: dto.recipient_name,
+                latitude: dto.latitude,
+                longitude: dto.longitude,
+                notes: None,
+                captured_by: None,
+                captured_by_name: None,
+                captured_at: Utc::now(),
+            },
+            files: Vec::new(),
+        }));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn ProofOfDeliveryServiceTrait>))
+            .configure(logistics::config)
+    ).await;
+
+    // Larger than actix's default 2 MB JSON limit
+    let req = test::TestRequest::post()
+        .uri(&format!("/logistics/jobs/{}/proof-of-delivery", job_id))
+        .set_json(CreateProofOfDeliveryDto {
+            recipient_name: "Ana Ruiz".to_string(),
+            latitude: 40.4168,
+            longitude: -3.7038,
+            notes: None,
+            signature: "iVBORw0KGgo=".to_string(),
+            photos: vec!["A".repeat(3 * 1024 * 1024)],
+        })
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::CREATED);
+    let proof: ProofOfDeliveryDetails = test::read_body_json(resp).await;
+    assert_eq!(proof.proof.job_id, job_id);
+}
+
+#[actix_web::test]
+async fn test_get_proof_of_delivery_file() {
+    let mut mock_service = MockProofOfDeliveryService::new();
+    let job_id = Uuid::new_v4();
+    let file_id = Uuid::new_v4();
+
+    mock_service
+        .expect_get_file()
+        .withf(move |job, file| (*job, *file) == (job_id, file_id))
+        .times(1)
+        .returning(|_, _| Ok(("image/png".to_string(), vec![0x89, b'P', b'N', b'G'])));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn ProofOfDeliveryServiceTrait>))
+            .configure(logistics::config)
+    ).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/logistics/jobs/{}/proof-of-delivery/files/{}", job_id, file_id))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+    assert_eq!(resp.headers().get("content-type").unwrap(), "image/png");
+    let body = test::read_body(resp).await;
+    assert_eq!(&body[..], &[0x89, b'P', b'N', b'G']);
+}
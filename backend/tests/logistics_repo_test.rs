@@ -35,7 +35,7 @@ async fn test_logistics_flow(pool: PgPool) -> sqlx::Result<()> {
         destination: destination.clone(),
         waypoints: None,
     };
-    let route = route_repo.create(route_dto).await.expect("Failed to create route");
+    route_repo.create(route_dto).await.expect("Failed to create route");
     
     // Verify Route
     let found_route = route_repo.find_by_job_id(job.id).await.expect("Failed to find route");
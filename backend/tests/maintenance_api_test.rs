@@ -3,13 +3,13 @@ use fleet_management_backend::routes::maintenance;
 use fleet_management_backend::models::postgres::maintenance::{
     MaintenanceRecord, CreateMaintenanceRecordDto,
     MaintenanceSchedule, CreateMaintenanceScheduleDto,
-    Alert, CreateAlertDto, MaintenanceType, AlertSeverity
+    Alert, CreateAlertDto, MaintenanceType, MaintenanceDueReport, MaintenanceDue, MaintenanceDueStatus
 };
 use fleet_management_backend::models::postgres::vehicle::VehicleType;
 use fleet_management_backend::services::maintenance_service::MaintenanceServiceTrait;
 use fleet_management_backend::error::AppError;
 use uuid::Uuid;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use mockall::mock;
 use async_trait::async_trait;
@@ -26,6 +26,8 @@ mock! {
         async fn create_alert(&self, dto: CreateAlertDto) -> Result<Alert, AppError>;
         async fn get_unresolved_alerts(&self) -> Result<Vec<Alert>, AppError>;
         async fn resolve_alert(&self, id: Uuid) -> Result<Alert, AppError>;
+        async fn get_due(&self, now: DateTime<Utc>) -> Result<MaintenanceDueReport, AppError>;
+        async fn raise_due_alerts(&self, now: DateTime<Utc>) -> Result<Vec<Alert>, AppError>;
     }
 }
 
@@ -45,6 +47,7 @@ async fn test_create_maintenance_record() {
         provider: "Service Center".to_string(),
         description: Some("Oil change".to_string()),
         created_at: now,
+        mileage: Some(45_000),
     };
 
     let return_record = expected_record.clone();
@@ -69,6 +72,7 @@ async fn test_create_maintenance_record() {
             date: now,
             provider: "Service Center".to_string(),
             description: Some("Oil change".to_string()),
+            mileage: None,
         })
         .to_request();
 
@@ -98,3 +102,46 @@ async fn test_get_unresolved_alerts() {
     let resp = test::call_service(&app, req).await;
     assert!(resp.status().is_success());
 }
+
+#[actix_web::test]
+async fn test_get_maintenance_due() {
+    let mut mock_service = MockMaintenanceService::new();
+    let vehicle_id = Uuid::new_v4();
+
+    mock_service
+        .expect_get_due()
+        .times(1)
+        .returning(move |_| Ok(MaintenanceDueReport {
+            overdue: vec![MaintenanceDue {
+                vehicle_id,
+                license_plate: "ABC-123".to_string(),
+                vehicle_type: VehicleType::Truck,
+                current_mileage: 56_000,
+                last_service_date: None,
+                last_service_mileage: None,
+                next_due_date: None,
+                next_due_km: Some(55_000),
+                days_remaining: None,
+                km_remaining: Some(-1_000),
+                status: MaintenanceDueStatus::Overdue,
+            }],
+            due_soon: vec![],
+        }));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn MaintenanceServiceTrait>))
+            .configure(maintenance::config)
+    ).await;
+
+    let req = test::TestRequest::get()
+        .uri("/maintenance/due")
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["overdue"][0]["status"], "overdue");
+    assert_eq!(body["overdue"][0]["km_remaining"], -1000);
+}
@@ -32,6 +32,7 @@ async fn test_maintenance_flow(pool: PgPool) -> sqlx::Result<()> {
         date: Utc::now(),
         provider: "QuickFix".to_string(),
         description: Some("Oil change".to_string()),
+        mileage: None,
     };
     let record = record_repo.create(record_dto).await.expect("Failed to create record");
     
@@ -56,6 +57,7 @@ async fn test_maintenance_flow(pool: PgPool) -> sqlx::Result<()> {
         entity_id: vehicle.id,
         r#type: "EngineCheck".to_string(),
         severity: AlertSeverity::High,
+        message: None,
     };
     let alert = alert_repo.create(alert_dto).await.expect("Failed to create alert");
     
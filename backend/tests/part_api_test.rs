@@ -0,0 +1,142 @@
+use actix_web::{test, web, App};
+use fleet_management_backend::routes::parts;
+use fleet_management_backend::models::postgres::part::{
+    Part, CreatePartDto, UpdatePartDto, PartQuery, StockMovement, StockMovementKind, CreateStockMovementDto
+};
+use fleet_management_backend::models::postgres::vehicle::VehicleType;
+use fleet_management_backend::services::part_service::PartServiceTrait;
+use fleet_management_backend::error::AppError;
+use uuid::Uuid;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use mockall::mock;
+use async_trait::async_trait;
+
+mock! {
+    pub PartService {}
+
+    #[async_trait]
+    impl PartServiceTrait for PartService {
+        async fn create_part(&self, dto: CreatePartDto) -> Result<Part, AppError>;
+        async fn list_parts(&self, query: PartQuery) -> Result<Vec<Part>, AppError>;
+        async fn get_part(&self, id: Uuid) -> Result<Part, AppError>;
+        async fn update_part(&self, id: Uuid, dto: UpdatePartDto) -> Result<Part, AppError>;
+        async fn record_movement(&self, part_id: Uuid, dto: CreateStockMovementDto) -> Result<Part, AppError>;
+        async fn get_movements(&self, part_id: Uuid) -> Result<Vec<StockMovement>, AppError>;
+        async fn refresh_stock_alert(&self, part_id: Uuid) -> Result<(), AppError>;
+    }
+}
+
+fn air_filter(id: Uuid, quantity_on_hand: i64) -> Part {
+    Part {
+        id,
+        sku: "AF-300".to_string(),
+        description: "Air filter".to_string(),
+        unit_cost: Decimal::new(1899, 2),
+        quantity_on_hand: Decimal::new(quantity_on_hand, 0),
+        reorder_threshold: Decimal::new(4, 0),
+        compatible_vehicle_types: vec![VehicleType::Truck, VehicleType::Van],
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn app_data(mock_service: MockPartService) -> web::Data<dyn PartServiceTrait> {
+    web::Data::from(Arc::new(mock_service) as Arc<dyn PartServiceTrait>)
+}
+
+#[actix_web::test]
+async fn test_create_part() {
+    let mut mock_service = MockPartService::new();
+    let id = Uuid::new_v4();
+
+    mock_service
+        .expect_create_part()
+        .withf(|dto| dto.sku == "AF-300" && dto.compatible_vehicle_types.len() == 2 && dto.reorder_threshold == Decimal::new(4, 0))
+        .times(1)
+        .returning(move |_| Ok(air_filter(id, 0)));
+
+    let app = test::init_service(
+        App::new().app_data(app_data(mock_service)).configure(parts::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri("/parts")
+        .set_json(json!({
+            "sku": "AF-300",
+            "description": "Air filter",
+            "unit_cost": "18.99",
+            "reorder_threshold": "4",
+            "compatible_vehicle_types": ["Truck", "Van"]
+        }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+}
+
+#[actix_web::test]
+async fn test_list_low_stock_parts() {
+    let mut mock_service = MockPartService::new();
+
+    mock_service
+        .expect_list_parts()
+        .withf(|query| query.low_stock && query.vehicle_type == Some(VehicleType::Van))
+        .times(1)
+        .returning(|_| Ok(vec![air_filter(Uuid::new_v4(), 2)]));
+
+    let app = test::init_service(
+        App::new().app_data(app_data(mock_service)).configure(parts::config)
+    ).await;
+
+    let req = test::TestRequest::get().uri("/parts?low_stock=true&vehicle_type=Van").to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body[0]["quantity_on_hand"], "2");
+}
+
+#[actix_web::test]
+async fn test_record_receipt() {
+    let mut mock_service = MockPartService::new();
+    let id = Uuid::new_v4();
+
+    mock_service
+        .expect_record_movement()
+        .withf(move |part_id, dto| *part_id == id && dto.kind == StockMovementKind::Receipt && dto.unit_cost == Some(Decimal::new(1750, 2)))
+        .times(1)
+        .returning(|part_id, _| Ok(air_filter(part_id, 12)));
+
+    let app = test::init_service(
+        App::new().app_data(app_data(mock_service)).configure(parts::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/parts/{}/movements", id))
+        .set_json(json!({"kind": "Receipt", "quantity": "10", "unit_cost": "17.50"}))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+}
+
+#[actix_web::test]
+async fn test_consumption_without_stock() {
+    let mut mock_service = MockPartService::new();
+
+    mock_service
+        .expect_record_movement()
+        .returning(|_, _| Err(AppError::BadRequest("Not enough stock of AF-300: 0 on hand".to_string())));
+
+    let app = test::init_service(
+        App::new().app_data(app_data(mock_service)).configure(parts::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/parts/{}/movements", Uuid::new_v4()))
+        .set_json(json!({"kind": "Consumption", "quantity": "1", "vehicle_id": Uuid::new_v4()}))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+}
@@ -0,0 +1,161 @@
+use actix_web::{test, web, App};
+use fleet_management_backend::routes::{quotes, rate_cards};
+use fleet_management_backend::models::postgres::quote::{
+    RateCard, RateCardDto, Quote, QuoteQuery, CreateQuoteDto
+};
+use fleet_management_backend::models::postgres::logistics::{JobStatus, TransportJob};
+use fleet_management_backend::models::postgres::vehicle::VehicleType;
+use fleet_management_backend::services::quote_service::QuoteServiceTrait;
+use fleet_management_backend::error::AppError;
+use uuid::Uuid;
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use mockall::mock;
+use async_trait::async_trait;
+
+mock! {
+    pub QuoteService {}
+
+    #[async_trait]
+    impl QuoteServiceTrait for QuoteService {
+        async fn create_rate_card(&self, dto: RateCardDto) -> Result<RateCard, AppError>;
+        async fn list_rate_cards(&self) -> Result<Vec<RateCard>, AppError>;
+        async fn get_rate_card(&self, id: Uuid) -> Result<RateCard, AppError>;
+        async fn update_rate_card(&self, id: Uuid, dto: RateCardDto) -> Result<RateCard, AppError>;
+        async fn delete_rate_card(&self, id: Uuid) -> Result<(), AppError>;
+        async fn create_quote(&self, dto: CreateQuoteDto, created_by: Option<Uuid>) -> Result<Quote, AppError>;
+        async fn list_quotes(&self, query: QuoteQuery) -> Result<Vec<Quote>, AppError>;
+        async fn get_quote(&self, id: Uuid) -> Result<Quote, AppError>;
+        async fn convert_quote(&self, id: Uuid, created_by: Option<Uuid>) -> Result<TransportJob, AppError>;
+    }
+}
+
+fn quote(customer_id: Uuid) -> Quote {
+    Quote {
+        id: Uuid::new_v4(),
+        customer_id,
+        rate_card_id: Some(Uuid::new_v4()),
+        vehicle_type: Some(VehicleType::Truck),
+        origin: serde_json::json!({"type": "Point", "coordinates": [13.4, 52.5]}),
+        destination: serde_json::json!({"type": "Point", "coordinates": [11.6, 48.1]}),
+        waypoints: None,
+        shipments: serde_json::json!([{"weight": 800.0, "dimensions": {"l": 120}, "type": "hazmat"}]),
+        distance_km: Decimal::new(50400, 2),
+        weight_kg: Decimal::new(80000, 2),
+        distance_charge: Decimal::new(75600, 2),
+        weight_charge: Decimal::new(4000, 2),
+        surcharge: Decimal::new(19900, 2),
+        price: Decimal::new(99500, 2),
+        currency: "EUR".to_string(),
+        valid_until: NaiveDate::from_ymd_opt(2026, 11, 17).unwrap(),
+        job_id: None,
+        converted_at: None,
+        created_by: None,
+        created_at: Utc::now(),
+    }
+}
+
+#[actix_web::test]
+async fn test_create_quote() {
+    let mut mock_service = MockQuoteService::new();
+    let customer_id = Uuid::new_v4();
+
+    mock_service
+        .expect_create_quote()
+        .withf(move |dto, _| {
+            dto.customer_id == customer_id
+                && dto.vehicle_type == Some(VehicleType::Truck)
+                && dto.shipments.len() == 1
+                && dto.shipments[0].r#type == "hazmat"
+        })
+        .times(1)
+        .returning(|dto, _| Ok(quote(dto.customer_id)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn QuoteServiceTrait>))
+            .configure(quotes::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri("/quotes")
+        .set_json(serde_json::json!({
+            "customer_id": customer_id,
+            "vehicle_type": "Truck",
+            "origin": {"type": "Point", "coordinates": [13.4, 52.5]},
+            "destination": {"type": "Point", "coordinates": [11.6, 48.1]},
+            "shipments": [{"weight": 800.0, "dimensions": {"l": 120}, "type": "hazmat"}]
+        }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::CREATED);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["price"], "995.00");
+}
+
+#[actix_web::test]
+async fn test_convert_quote() {
+    let mut mock_service = MockQuoteService::new();
+    let quote_id = Uuid::new_v4();
+
+    mock_service
+        .expect_convert_quote()
+        .withf(move |id, _| *id == quote_id)
+        .times(1)
+        .returning(|_, _| Ok(TransportJob {
+            id: Uuid::new_v4(),
+            customer_id: Uuid::new_v4(),
+            status: JobStatus::Pending,
+            agreed_price: Decimal::new(99500, 2),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn QuoteServiceTrait>))
+            .configure(quotes::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/quotes/{}/convert", quote_id))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::CREATED);
+    let job: TransportJob = test::read_body_json(resp).await;
+    assert_eq!(job.agreed_price, Decimal::new(99500, 2));
+}
+
+#[actix_web::test]
+async fn test_create_rate_card_rejected() {
+    let mut mock_service = MockQuoteService::new();
+
+    mock_service
+        .expect_create_rate_card()
+        .withf(|dto| dto.is_active && dto.surcharges.len() == 1 && dto.minimum_charge == Decimal::ZERO)
+        .times(1)
+        .returning(|_| Err(AppError::BadRequest("Surcharges must be between 0 and 100 percent".to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn QuoteServiceTrait>))
+            .configure(rate_cards::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri("/rate-cards")
+        .set_json(serde_json::json!({
+            "name": "Trucks",
+            "vehicle_type": "Truck",
+            "per_km": "1.50",
+            "per_kg": "0.05",
+            "surcharges": [{"shipment_type": "hazmat", "percent": "150"}]
+        }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+}
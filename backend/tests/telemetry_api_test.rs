@@ -1,7 +1,10 @@
 use actix_web::{test, web, App};
 use fleet_management_backend::routes::telemetry;
-use fleet_management_backend::models::postgres::telemetry::{VehicleTelemetry, CreateVehicleTelemetryDto};
+use fleet_management_backend::models::postgres::telemetry::{VehicleTelemetry, CreateVehicleTelemetryDto, BulkTelemetryResult, TelemetryRejection, TelemetryHistory, TelemetryHistoryQuery, TelemetryFormat};
+use fleet_management_backend::models::redis::vehicle_status::VehicleStatusCache;
+use fleet_management_backend::models::postgres::odometer::{DailyDistance, DailyDistanceQuery, DistanceSource, OdometerSyncReport};
 use fleet_management_backend::services::telemetry_service::TelemetryServiceTrait;
+use fleet_management_backend::services::odometer_service::OdometerServiceTrait;
 use fleet_management_backend::error::AppError;
 use uuid::Uuid;
 use chrono::Utc;
@@ -16,6 +19,22 @@ mock! {
     impl TelemetryServiceTrait for TelemetryService {
         async fn create_telemetry(&self, dto: CreateVehicleTelemetryDto) -> Result<VehicleTelemetry, AppError>;
         async fn get_latest_telemetry(&self, vehicle_id: Uuid) -> Result<Option<VehicleTelemetry>, AppError>;
+        async fn get_live_positions(&self) -> Result<Vec<VehicleStatusCache>, AppError>;
+        async fn create_telemetry_batch(
+            &self,
+            entries: Vec<Result<CreateVehicleTelemetryDto, String>>,
+        ) -> Result<BulkTelemetryResult, AppError>;
+        async fn get_history(&self, vehicle_id: Uuid, query: TelemetryHistoryQuery) -> Result<TelemetryHistory, AppError>;
+    }
+}
+
+mock! {
+    pub OdometerService {}
+
+    #[async_trait]
+    impl OdometerServiceTrait for OdometerService {
+        async fn sync_odometers(&self, until: chrono::DateTime<Utc>) -> Result<OdometerSyncReport, AppError>;
+        async fn get_daily_distance(&self, vehicle_id: Uuid, query: DailyDistanceQuery) -> Result<Vec<DailyDistance>, AppError>;
     }
 }
 
@@ -98,3 +117,215 @@ async fn test_get_latest_telemetry() {
     let resp = test::call_service(&app, req).await;
     assert!(resp.status().is_success());
 }
+
+#[actix_web::test]
+async fn test_get_live_positions() {
+    let mut mock_service = MockTelemetryService::new();
+    let vehicle_id = Uuid::new_v4();
+
+    mock_service
+        .expect_get_live_positions()
+        .times(1)
+        .returning(move || Ok(vec![VehicleStatusCache {
+            vehicle_id,
+            speed: 55.0,
+            latitude: 40.7128,
+            longitude: -74.0060,
+            driver_id: None,
+            last_updated: Utc::now().timestamp(),
+        }]));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn TelemetryServiceTrait>))
+            .configure(telemetry::config)
+    ).await;
+
+    let req = test::TestRequest::get()
+        .uri("/telemetry/live")
+        .to_request();
+
+    let positions: Vec<VehicleStatusCache> = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions[0].vehicle_id, vehicle_id);
+}
+
+fn batch_echo(mock_service: &mut MockTelemetryService) {
+    // Reports every unparsable entry as rejected and everything else as inserted
+    mock_service
+        .expect_create_telemetry_batch()
+        .times(1)
+        .returning(|entries| {
+            let received = entries.len();
+            let rejected: Vec<TelemetryRejection> = entries
+                .into_iter()
+                .enumerate()
+                .filter_map(|(index, entry)| entry.err().map(|error| TelemetryRejection { index, error }))
+                .collect();
+            Ok(BulkTelemetryResult {
+                received,
+                inserted: received - rejected.len(),
+                duplicates: 0,
+                rejected,
+            })
+        });
+}
+
+#[actix_web::test]
+async fn test_create_telemetry_batch_json() {
+    let mut mock_service = MockTelemetryService::new();
+    batch_echo(&mut mock_service);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn TelemetryServiceTrait>))
+            .configure(telemetry::config)
+    ).await;
+
+    let reading = serde_json::json!({
+        "time": Utc::now(),
+        "vehicle_id": Uuid::new_v4(),
+        "location": {"type": "Point", "coordinates": [-74.0060, 40.7128]},
+        "speed": 60.0,
+        "fuel_level": 80.0,
+        "engine_status": {}
+    });
+
+    let req = test::TestRequest::post()
+        .uri("/telemetry/batch")
+        .set_json(serde_json::json!([reading, {"vehicle_id": "not-a-uuid"}, reading]))
+        .to_request();
+
+    let result: BulkTelemetryResult = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(result.received, 3);
+    assert_eq!(result.inserted, 2);
+    assert_eq!(result.rejected.len(), 1);
+    assert_eq!(result.rejected[0].index, 1);
+}
+
+#[actix_web::test]
+async fn test_create_telemetry_batch_ndjson() {
+    let mut mock_service = MockTelemetryService::new();
+    batch_echo(&mut mock_service);
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn TelemetryServiceTrait>))
+            .configure(telemetry::config)
+    ).await;
+
+    let reading = serde_json::json!({
+        "time": Utc::now(),
+        "vehicle_id": Uuid::new_v4(),
+        "location": {"type": "Point", "coordinates": [-74.0060, 40.7128]},
+        "speed": 60.0,
+        "fuel_level": 80.0,
+        "engine_status": {}
+    });
+    let body = format!("{}\n{{broken\n\n{}\n", reading, reading);
+
+    let req = test::TestRequest::post()
+        .uri("/telemetry/batch")
+        .insert_header(("Content-Type", "application/x-ndjson"))
+        .set_payload(body)
+        .to_request();
+
+    let result: BulkTelemetryResult = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(result.received, 3);
+    assert_eq!(result.inserted, 2);
+    assert_eq!(result.rejected[0].index, 1);
+}
+
+#[actix_web::test]
+async fn test_create_telemetry_batch_rejects_non_array() {
+    let mock_service = MockTelemetryService::new();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn TelemetryServiceTrait>))
+            .configure(telemetry::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri("/telemetry/batch")
+        .set_json(serde_json::json!({"vehicle_id": Uuid::new_v4()}))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::BAD_REQUEST);
+}
+
+#[actix_web::test]
+async fn test_get_telemetry_history_parses_query() {
+    let mut mock_service = MockTelemetryService::new();
+    let vehicle_id = Uuid::new_v4();
+
+    mock_service
+        .expect_get_history()
+        .withf(move |id, query| {
+            *id == vehicle_id
+                && query.bucket.as_deref() == Some("5m")
+                && query.format == Some(TelemetryFormat::Geojson)
+                && query.from.is_some()
+                && query.to.is_none()
+        })
+        .times(1)
+        .returning(|_, _| Ok(TelemetryHistory::Track(serde_json::json!({
+            "type": "FeatureCollection",
+            "features": []
+        }))));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn TelemetryServiceTrait>))
+            .configure(telemetry::config)
+    ).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/telemetry/vehicle/{}?from=2026-01-01T00:00:00Z&bucket=5m&format=geojson",
+            vehicle_id
+        ))
+        .to_request();
+
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["type"], "FeatureCollection");
+}
+
+#[actix_web::test]
+async fn test_get_daily_distance() {
+    let mut mock_odometer = MockOdometerService::new();
+    let vehicle_id = Uuid::new_v4();
+
+    mock_odometer
+        .expect_get_daily_distance()
+        .withf(move |id, query| {
+            *id == vehicle_id
+                && query.from == chrono::NaiveDate::from_ymd_opt(2026, 10, 1)
+                && query.to.is_none()
+        })
+        .times(1)
+        .returning(|_, _| Ok(vec![DailyDistance {
+            day: chrono::NaiveDate::from_ymd_opt(2026, 10, 1).unwrap(),
+            distance_km: 84.2,
+            source: DistanceSource::Gps,
+        }]));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(MockTelemetryService::new()) as Arc<dyn TelemetryServiceTrait>))
+            .app_data(web::Data::from(Arc::new(mock_odometer) as Arc<dyn OdometerServiceTrait>))
+            .configure(telemetry::config)
+    ).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/telemetry/vehicle/{}/distance?from=2026-10-01", vehicle_id))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body[0]["distance_km"], 84.2);
+    assert_eq!(body[0]["source"], "gps");
+}
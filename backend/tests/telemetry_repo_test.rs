@@ -29,7 +29,7 @@ async fn test_telemetry_flow(pool: PgPool) -> sqlx::Result<()> {
         engine_status: json!({"temp": 90, "rpm": 0}), // Electric car RPM? :)
     };
     
-    let telemetry = telemetry_repo.create(telemetry_dto).await.expect("Failed to create telemetry");
+    telemetry_repo.create(telemetry_dto).await.expect("Failed to create telemetry");
     
     // 3. Find Latest
     let latest = telemetry_repo.find_latest_by_vehicle_id(vehicle.id).await.expect("Failed to find latest telemetry");
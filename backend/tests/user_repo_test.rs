@@ -11,6 +11,7 @@ async fn test_create_and_find_user(pool: PgPool) -> sqlx::Result<()> {
         email: "test@example.com".to_string(),
         password_hash: "hashed_password".to_string(),
         role: UserRole::Driver,
+        name: None,
         is_active: true,
     };
 
@@ -36,6 +37,7 @@ async fn test_soft_delete_user(pool: PgPool) -> sqlx::Result<()> {
         email: "delete@example.com".to_string(),
         password_hash: "hashed_password".to_string(),
         role: UserRole::Manager,
+        name: None,
         is_active: true,
     };
     let created = repo.create(dto).await.expect("Failed to create user");
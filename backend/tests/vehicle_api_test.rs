@@ -3,7 +3,7 @@ use fleet_management_backend::api::v1::vehicles;
 use fleet_management_backend::api::dto::vehicle_dto::{CreateVehicleRequest, VehicleResponse, UpdateVehicleStatusRequest};
 use fleet_management_backend::models::postgres::vehicle::{Vehicle, CreateVehicleDto, VehicleStatus, VehicleType, FuelType};
 use fleet_management_backend::services::vehicle_service::VehicleServiceTrait;
-use fleet_management_backend::error::AppError;
+use fleet_management_backend::error::{AppError, ConflictDetails, ConflictKind};
 use uuid::Uuid;
 use chrono::Utc;
 use std::sync::Arc;
@@ -83,6 +83,48 @@ async fn test_create_vehicle_success() {
     assert_eq!(body.make, "Toyota");
 }
 
+#[actix_web::test]
+async fn test_create_vehicle_duplicate_vin_returns_conflict() {
+    let mut mock_service = MockVehicleService::new();
+
+    mock_service
+        .expect_create_vehicle()
+        .times(1)
+        .returning(|_| Err(AppError::Conflict(ConflictDetails {
+            kind: ConflictKind::Duplicate,
+            message: "A vehicle with this VIN already exists".into(),
+            constraint: Some("vehicles_vin_key".into()),
+        })));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn VehicleServiceTrait>))
+            .configure(vehicles::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri("/vehicles")
+        .set_json(CreateVehicleRequest {
+            make: "Toyota".to_string(),
+            model: "Camry".to_string(),
+            year: 2024,
+            vin: "VIN123".to_string(),
+            license_plate: "ABC-123".to_string(),
+            r#type: VehicleType::Sedan,
+            current_mileage: 0,
+            fuel_type: FuelType::Gasoline,
+            specs: None,
+        })
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::CONFLICT);
+
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["kind"], "DUPLICATE");
+    assert_eq!(body["constraint"], "vehicles_vin_key");
+}
+
 #[actix_web::test]
 async fn test_get_vehicle_found() {
     let mut mock_service = MockVehicleService::new();
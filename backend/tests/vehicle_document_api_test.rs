@@ -0,0 +1,139 @@
+use actix_web::{test, web, App};
+use fleet_management_backend::routes::vehicle;
+use fleet_management_backend::models::postgres::maintenance::Alert;
+use fleet_management_backend::models::postgres::vehicle_document::{
+    VehicleDocument, VehicleDocumentType, CreateVehicleDocumentDto
+};
+use fleet_management_backend::services::vehicle_document_service::VehicleDocumentServiceTrait;
+use fleet_management_backend::error::AppError;
+use uuid::Uuid;
+use chrono::{NaiveDate, Utc};
+use std::sync::Arc;
+use mockall::mock;
+use async_trait::async_trait;
+
+mock! {
+    pub VehicleDocumentService {}
+
+    #[async_trait]
+    impl VehicleDocumentServiceTrait for VehicleDocumentService {
+        async fn list(&self, vehicle_id: Uuid) -> Result<Vec<VehicleDocument>, AppError>;
+        async fn get(&self, vehicle_id: Uuid, id: Uuid) -> Result<VehicleDocument, AppError>;
+        async fn create(&self, vehicle_id: Uuid, dto: CreateVehicleDocumentDto, uploaded_by: Option<Uuid>) -> Result<VehicleDocument, AppError>;
+        async fn delete(&self, vehicle_id: Uuid, id: Uuid) -> Result<(), AppError>;
+        async fn get_file(&self, vehicle_id: Uuid, id: Uuid) -> Result<(VehicleDocument, Vec<u8>), AppError>;
+        async fn check_assignment(&self, vehicle_id: Uuid, today: NaiveDate, last_day: NaiveDate) -> Result<(), AppError>;
+        async fn raise_expiry_alerts(&self, today: NaiveDate) -> Result<Vec<Alert>, AppError>;
+    }
+}
+
+fn document(vehicle_id: Uuid) -> VehicleDocument {
+    VehicleDocument {
+        id: Uuid::new_v4(),
+        vehicle_id,
+        r#type: VehicleDocumentType::Insurance,
+        document_number: Some("POL-2026-118".to_string()),
+        issue_date: NaiveDate::from_ymd_opt(2026, 1, 1),
+        expiry_date: NaiveDate::from_ymd_opt(2026, 12, 31),
+        notes: None,
+        file_name: Some("insurance.pdf".to_string()),
+        content_type: Some("application/pdf".to_string()),
+        size_bytes: Some(8),
+        storage_key: None,
+        uploaded_by: None,
+        created_at: Utc::now(),
+    }
+}
+
+#[actix_web::test]
+async fn test_create_vehicle_document() {
+    let mut mock_service = MockVehicleDocumentService::new();
+    let vehicle_id = Uuid::new_v4();
+
+    mock_service
+        .expect_create()
+        .withf(move |id, dto, _| *id == vehicle_id && dto.r#type == VehicleDocumentType::Insurance)
+        .times(1)
+        .returning(|id, _, _| Ok(document(id)));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn VehicleDocumentServiceTrait>))
+            .configure(vehicle::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/vehicles/{}/documents", vehicle_id))
+        .set_json(CreateVehicleDocumentDto {
+            r#type: VehicleDocumentType::Insurance,
+            document_number: Some("POL-2026-118".to_string()),
+            issue_date: NaiveDate::from_ymd_opt(2026, 1, 1),
+            expiry_date: NaiveDate::from_ymd_opt(2026, 12, 31),
+            notes: None,
+            file_name: Some("insurance.pdf".to_string()),
+            // Larger than the default JSON limit
+            file: Some("A".repeat(4 * 1024 * 1024)),
+        })
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::CREATED);
+    let body: VehicleDocument = test::read_body_json(resp).await;
+    assert_eq!(body.vehicle_id, vehicle_id);
+}
+
+#[actix_web::test]
+async fn test_get_vehicle_document_file() {
+    let mut mock_service = MockVehicleDocumentService::new();
+    let vehicle_id = Uuid::new_v4();
+    let document_id = Uuid::new_v4();
+
+    mock_service
+        .expect_get_file()
+        .withf(move |vehicle, id| (*vehicle, *id) == (vehicle_id, document_id))
+        .times(1)
+        .returning(|vehicle, _| Ok((document(vehicle), b"%PDF-1.7".to_vec())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn VehicleDocumentServiceTrait>))
+            .configure(vehicle::config)
+    ).await;
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/vehicles/{}/documents/{}/file", vehicle_id, document_id))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    assert_eq!(resp.headers().get("content-type").unwrap(), "application/pdf");
+    assert_eq!(
+        resp.headers().get("content-disposition").unwrap(),
+        "inline; filename=\"insurance.pdf\""
+    );
+    let body = test::read_body(resp).await;
+    assert_eq!(&body[..], b"%PDF-1.7");
+}
+
+#[actix_web::test]
+async fn test_delete_vehicle_document_not_found() {
+    let mut mock_service = MockVehicleDocumentService::new();
+
+    mock_service
+        .expect_delete()
+        .times(1)
+        .returning(|_, _| Err(AppError::NotFound("Document not found".to_string())));
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::from(Arc::new(mock_service) as Arc<dyn VehicleDocumentServiceTrait>))
+            .configure(vehicle::config)
+    ).await;
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/vehicles/{}/documents/{}", Uuid::new_v4(), Uuid::new_v4()))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), actix_web::http::StatusCode::NOT_FOUND);
+}
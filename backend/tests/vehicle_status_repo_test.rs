@@ -1,4 +1,4 @@
-use fleet_management_backend::repositories::redis::vehicle_status_repo::VehicleStatusCacheRepository;
+use fleet_management_backend::repositories::redis::vehicle_status_repo::{VehicleStatusCacheRepository, VehicleStatusCacheRepositoryTrait};
 use fleet_management_backend::models::redis::vehicle_status::VehicleStatusCache;
 use uuid::Uuid;
 use chrono::Utc;
@@ -10,7 +10,7 @@ async fn test_vehicle_status_cache() {
     let client = redis::Client::open("redis://127.0.0.1/").expect("Failed to create Redis client");
     
     // Check connection
-    let mut conn = match client.get_async_connection().await {
+    let _conn = match client.get_async_connection().await {
         Ok(c) => c,
         Err(_) => {
             println!("Skipping Redis test: Connection failed");
@@ -40,4 +40,8 @@ async fn test_vehicle_status_cache() {
     
     assert_eq!(retrieved_status.vehicle_id, vehicle_id);
     assert_eq!(retrieved_status.speed, 50.0);
+
+    // 3. Get all
+    let all = repo.get_all().await.expect("Failed to get all statuses");
+    assert!(all.iter().any(|s| s.vehicle_id == vehicle_id));
 }
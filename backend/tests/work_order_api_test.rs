@@ -0,0 +1,179 @@
+use actix_web::{test, web, App};
+use fleet_management_backend::routes::work_orders;
+use fleet_management_backend::models::postgres::maintenance::MaintenanceType;
+use fleet_management_backend::models::postgres::work_order::{
+    WorkOrder, WorkOrderItem, WorkOrderDetails, WorkOrderStatus, WorkOrderItemKind, CreateWorkOrderDto, CreateWorkOrderItemDto, WorkOrderQuery
+};
+use fleet_management_backend::services::work_order_service::WorkOrderServiceTrait;
+use fleet_management_backend::error::AppError;
+use uuid::Uuid;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use mockall::mock;
+use async_trait::async_trait;
+
+mock! {
+    pub WorkOrderService {}
+
+    #[async_trait]
+    impl WorkOrderServiceTrait for WorkOrderService {
+        async fn create_work_order(&self, dto: CreateWorkOrderDto) -> Result<WorkOrder, AppError>;
+        async fn list_work_orders(&self, query: WorkOrderQuery) -> Result<Vec<WorkOrder>, AppError>;
+        async fn get_work_order(&self, id: Uuid) -> Result<WorkOrderDetails, AppError>;
+        async fn update_status(&self, id: Uuid, status: WorkOrderStatus) -> Result<WorkOrder, AppError>;
+        async fn assign_mechanic(&self, id: Uuid, mechanic_id: Uuid) -> Result<WorkOrder, AppError>;
+        async fn add_item(&self, id: Uuid, dto: CreateWorkOrderItemDto) -> Result<WorkOrderItem, AppError>;
+        async fn remove_item(&self, id: Uuid, item_id: Uuid) -> Result<(), AppError>;
+    }
+}
+
+fn brake_job(id: Uuid, status: WorkOrderStatus) -> WorkOrder {
+    WorkOrder {
+        id,
+        vehicle_id: Uuid::new_v4(),
+        mechanic_id: Some(Uuid::new_v4()),
+        r#type: MaintenanceType::Repair,
+        description: "Replace brake pads".to_string(),
+        provider: None,
+        status,
+        maintenance_record_id: None,
+        started_at: None,
+        completed_at: None,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+fn app_data(mock_service: MockWorkOrderService) -> web::Data<dyn WorkOrderServiceTrait> {
+    web::Data::from(Arc::new(mock_service) as Arc<dyn WorkOrderServiceTrait>)
+}
+
+#[actix_web::test]
+async fn test_create_work_order() {
+    let mut mock_service = MockWorkOrderService::new();
+    let id = Uuid::new_v4();
+
+    mock_service
+        .expect_create_work_order()
+        .withf(|dto| dto.r#type == MaintenanceType::Repair && dto.mechanic_id.is_none())
+        .times(1)
+        .returning(move |_| Ok(brake_job(id, WorkOrderStatus::Open)));
+
+    let app = test::init_service(
+        App::new().app_data(app_data(mock_service)).configure(work_orders::config)
+    ).await;
+
+    let req = test::TestRequest::post()
+        .uri("/work-orders")
+        .set_json(json!({
+            "vehicle_id": Uuid::new_v4(),
+            "type": "Repair",
+            "description": "Replace brake pads"
+        }))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+}
+
+#[actix_web::test]
+async fn test_list_work_orders_by_status() {
+    let mut mock_service = MockWorkOrderService::new();
+
+    mock_service
+        .expect_list_work_orders()
+        .withf(|query| query.status == Some(WorkOrderStatus::AwaitingParts) && query.vehicle_id.is_none())
+        .times(1)
+        .returning(|_| Ok(vec![brake_job(Uuid::new_v4(), WorkOrderStatus::AwaitingParts)]));
+
+    let app = test::init_service(
+        App::new().app_data(app_data(mock_service)).configure(work_orders::config)
+    ).await;
+
+    let req = test::TestRequest::get().uri("/work-orders?status=AwaitingParts").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+}
+
+#[actix_web::test]
+async fn test_get_work_order_details() {
+    let mut mock_service = MockWorkOrderService::new();
+    let id = Uuid::new_v4();
+
+    mock_service
+        .expect_get_work_order()
+        .returning(move |id| Ok(WorkOrderDetails {
+            work_order: brake_job(id, WorkOrderStatus::InProgress),
+            items: vec![WorkOrderItem {
+                id: Uuid::new_v4(),
+                work_order_id: id,
+                kind: WorkOrderItemKind::Part,
+                part_id: Some(Uuid::new_v4()),
+                description: "Brake pads".to_string(),
+                quantity: Decimal::new(4, 0),
+                unit_cost: Decimal::new(2500, 2),
+                created_at: Utc::now(),
+            }],
+            total_cost: Decimal::new(10000, 2),
+        }));
+
+    let app = test::init_service(
+        App::new().app_data(app_data(mock_service)).configure(work_orders::config)
+    ).await;
+
+    let req = test::TestRequest::get().uri(&format!("/work-orders/{}", id)).to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["id"], json!(id));
+    assert_eq!(body["status"], "InProgress");
+    assert_eq!(body["items"].as_array().unwrap().len(), 1);
+    assert_eq!(body["total_cost"], "100.00");
+}
+
+#[actix_web::test]
+async fn test_invalid_status_transition() {
+    let mut mock_service = MockWorkOrderService::new();
+
+    mock_service
+        .expect_update_status()
+        .withf(|_, status| *status == WorkOrderStatus::Completed)
+        .times(1)
+        .returning(|_, _| Err(AppError::BadRequest("Cannot move work order from Open to Completed".to_string())));
+
+    let app = test::init_service(
+        App::new().app_data(app_data(mock_service)).configure(work_orders::config)
+    ).await;
+
+    let req = test::TestRequest::patch()
+        .uri(&format!("/work-orders/{}/status", Uuid::new_v4()))
+        .set_json(json!({"status": "Completed"}))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+}
+
+#[actix_web::test]
+async fn test_remove_item() {
+    let mut mock_service = MockWorkOrderService::new();
+    let id = Uuid::new_v4();
+    let item_id = Uuid::new_v4();
+
+    mock_service
+        .expect_remove_item()
+        .withf(move |wo, item| *wo == id && *item == item_id)
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let app = test::init_service(
+        App::new().app_data(app_data(mock_service)).configure(work_orders::config)
+    ).await;
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/work-orders/{}/items/{}", id, item_id))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 204);
+}
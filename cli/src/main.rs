@@ -129,7 +129,7 @@ fn get_docker_status() -> Vec<DockerContainer> {
     let compose_file = "backend/docker-compose.yml";
     // Use sudo for docker compose ps
     let output = Command::new("sudo")
-        .args(&["docker", "compose", "-f", compose_file, "ps", "--format", "json"])
+        .args(["docker", "compose", "-f", compose_file, "ps", "--format", "json"])
         .output()
         .expect("Failed to execute docker compose ps");
 
@@ -286,7 +286,7 @@ async fn main() {
             // Interactive shell requires inheriting stdio
             let compose_file = "backend/docker-compose.yml";
             let mut cmd = Command::new("sudo");
-            cmd.args(&["docker", "compose", "-f", compose_file, "exec", &service, "/bin/sh", "-c", "bash || sh"]);
+            cmd.args(["docker", "compose", "-f", compose_file, "exec", service, "/bin/sh", "-c", "bash || sh"]);
             cmd.stdin(Stdio::inherit())
                 .stdout(Stdio::inherit())
                 .stderr(Stdio::inherit());